@@ -0,0 +1,111 @@
+//! # Batched Sampling Benchmark
+//!
+//! Compares drawing samples one at a time against drawing them in batches, first for the sampling
+//! distribution alone and then for whole planner iterations in a 3-dimensional space without obstacles.
+//!
+//! ## Usage
+//! Run the benchmark with:
+//! ```bash
+//! cargo bench --bench batched_sampling
+//! ```
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rrt::collision::EuclideanSphericalObstacleSet;
+use rrt::{
+    EuclideanSteering, GoalBiasedUniformDistribution, KdTreeNearestNeighbors, Point,
+    SamplingDistribution, UniformDistribution, RRT,
+};
+use std::time::Instant;
+
+const DIMENSIONS: usize = 3;
+const ITERATIONS: u32 = 200000;
+const BATCH_SIZE: u32 = 1024;
+const SAMPLES: usize = 10_000_000;
+
+type Planner = RRT<
+    f64,
+    DIMENSIONS,
+    EuclideanSphericalObstacleSet<f64, DIMENSIONS>,
+    UniformDistribution<f64, DIMENSIONS, StdRng>,
+    EuclideanSteering<f64, DIMENSIONS>,
+    KdTreeNearestNeighbors<f64, DIMENSIONS>,
+>;
+
+fn make_planner() -> Planner {
+    // The goal is out of reach, so both runs perform every iteration.
+    RRT::new(
+        Point::new([0.0; DIMENSIONS]),
+        Point::new([2.0; DIMENSIONS]),
+        0.01,
+        EuclideanSphericalObstacleSet::new(Vec::new()),
+        UniformDistribution::new_with_rng([(0.0, 1.0); DIMENSIONS], StdRng::seed_from_u64(1))
+            .unwrap(),
+        EuclideanSteering::new(0.05),
+    )
+}
+
+fn make_distribution() -> GoalBiasedUniformDistribution<f64, DIMENSIONS, StdRng> {
+    GoalBiasedUniformDistribution::new_with_rng(
+        [(0.0, 1.0); DIMENSIONS],
+        Point::new([1.0; DIMENSIONS]),
+        0.05,
+        StdRng::seed_from_u64(1),
+    )
+    .unwrap()
+}
+
+fn main() {
+    let mut distribution = make_distribution();
+    let mut checksum = 0.0;
+    let start_time = Instant::now();
+    for _ in 0..SAMPLES {
+        checksum += distribution.sample()[0];
+    }
+    println!(
+        "Sampling one at a time: {:?} for {} samples (checksum {:.0})",
+        start_time.elapsed(),
+        SAMPLES,
+        checksum
+    );
+
+    let mut distribution = make_distribution();
+    let mut checksum = 0.0;
+    let mut samples = Vec::with_capacity(BATCH_SIZE as usize);
+    let start_time = Instant::now();
+    for _ in 0..SAMPLES / BATCH_SIZE as usize {
+        samples.clear();
+        distribution.sample_batch(BATCH_SIZE as usize, &mut samples);
+        checksum += samples.iter().map(|sample| sample[0]).sum::<f64>();
+    }
+    println!(
+        "Sampling in batches:    {:?} for {} samples (checksum {:.0})",
+        start_time.elapsed(),
+        SAMPLES / BATCH_SIZE as usize * BATCH_SIZE as usize,
+        checksum
+    );
+
+    let mut single = make_planner();
+    let start_time = Instant::now();
+    let summary = single.run_iterations(ITERATIONS);
+    println!(
+        "Iterations one at a time: {:?} for {} iterations, {} nodes added",
+        start_time.elapsed(),
+        summary.iterations,
+        summary.nodes_added
+    );
+
+    let mut batched = make_planner();
+    let start_time = Instant::now();
+    let mut nodes_added = 0;
+    for _ in 0..ITERATIONS / BATCH_SIZE {
+        nodes_added += batched.run_iterations_batched(BATCH_SIZE).nodes_added;
+    }
+    println!(
+        "Iterations in batches:    {:?} for {} iterations in batches of {}, {} nodes added",
+        start_time.elapsed(),
+        ITERATIONS / BATCH_SIZE * BATCH_SIZE,
+        BATCH_SIZE,
+        nodes_added
+    );
+}
@@ -0,0 +1,84 @@
+//! # Edge Cache Benchmark
+//!
+//! Plans a long path through a 2D field of spherical obstacles and smooths it many times, as an animation
+//! that smooths the path again every frame would. Smoothing through an `EdgeCache` answers the repeated
+//! edge checks from the cache, which the benchmark shows by counting the checks that reach the obstacles.
+//!
+//! ## Usage
+//! Run the benchmark with:
+//! ```bash
+//! cargo bench --bench edge_cache
+//! ```
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rrt::collision::{EdgeCache, EuclideanSphericalObstacleSet, ProfiledChecker, Sphere};
+use rrt::{
+    EuclideanSteering, KdTreeNearestNeighbors, Point, UniformDistribution, ValidityChecker, RRT,
+};
+use std::time::Instant;
+
+const SMOOTHING_ROUNDS: usize = 1000;
+
+fn smooth_repeatedly<VC: ValidityChecker<f64, 2>>(path: &[Point<f64, 2>], checker: &VC) -> usize {
+    let mut length = 0;
+    for _ in 0..SMOOTHING_ROUNDS {
+        length = rrt::smoothing::fast_shortcutting(path.to_vec(), checker).len();
+    }
+    length
+}
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let spheres: Vec<Sphere<f64, 2>> = (0..2000)
+        .map(|_| Sphere {
+            center: Point::new([rng.gen_range(5.0..95.0), rng.gen_range(5.0..95.0)]),
+            radius: rng.gen_range(0.2..1.0),
+        })
+        .collect();
+    let obstacles = EuclideanSphericalObstacleSet::new(spheres);
+
+    // Plan without goal bias and with short steps, so the path has many nodes.
+    let mut planner: RRT<f64, 2, _, _, EuclideanSteering<f64, 2>, KdTreeNearestNeighbors<f64, 2>> =
+        RRT::new(
+            Point::new([1.0, 1.0]),
+            Point::new([99.0, 99.0]),
+            1.0,
+            &obstacles,
+            UniformDistribution::new_with_rng([(0.0, 100.0); 2], StdRng::seed_from_u64(2)).unwrap(),
+            EuclideanSteering::new(0.5),
+        );
+    planner.run_iterations(200_000);
+    let path = match planner.get_path() {
+        Some(path) => path,
+        None => {
+            println!("No path found");
+            return;
+        }
+    };
+    println!("Planned a path with {} nodes", path.len());
+
+    let profiled = ProfiledChecker::new(&obstacles);
+    let start_time = Instant::now();
+    let length = smooth_repeatedly(&path, &profiled);
+    println!(
+        "Without cache: {:?} for {} rounds, {} edge checks, {} nodes after smoothing",
+        start_time.elapsed(),
+        SMOOTHING_ROUNDS,
+        profiled.stats().edge_queries,
+        length
+    );
+
+    let profiled = ProfiledChecker::new(&obstacles);
+    let cache = EdgeCache::new(&profiled, 1e-6, 100_000).unwrap();
+    let start_time = Instant::now();
+    let length = smooth_repeatedly(&path, &cache);
+    println!(
+        "With cache:    {:?} for {} rounds, {} edge checks ({} cache hits), {} nodes after smoothing",
+        start_time.elapsed(),
+        SMOOTHING_ROUNDS,
+        profiled.stats().edge_queries,
+        cache.hits(),
+        length
+    );
+}
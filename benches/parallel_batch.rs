@@ -0,0 +1,154 @@
+//! # Parallel Batch Extension Benchmark
+//!
+//! Measures how the collision checks of a batch scale with the number of threads, in a 6-dimensional
+//! space with many spherical obstacles. The collision phase alone is timed by checking the points and
+//! edges of many short extensions, then whole batches of iterations are compared against serial iterations.
+//! Each measurement is repeated with thread pools of 1, 2, 4, ... threads, up to the available parallelism.
+//!
+//! ## Usage
+//! Run the benchmark with:
+//! ```bash
+//! cargo bench --features parallel --bench parallel_batch
+//! ```
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use rrt::collision::{EuclideanSphericalObstacleSet, Sphere};
+use rrt::{
+    EuclideanSteering, KdTreeNearestNeighbors, Point, Steering, UniformDistribution,
+    ValidityChecker, RRT,
+};
+use std::time::{Duration, Instant};
+
+const DIMENSIONS: usize = 6;
+const OBSTACLES: usize = 5000;
+const ITERATIONS: u32 = 20000;
+const BATCH_SIZE: u32 = 256;
+const EDGES: usize = 20000;
+const STEERING_RANGE: f64 = 0.05;
+
+type Planner = RRT<
+    f64,
+    DIMENSIONS,
+    EuclideanSphericalObstacleSet<f64, DIMENSIONS>,
+    UniformDistribution<f64, DIMENSIONS, StdRng>,
+    EuclideanSteering<f64, DIMENSIONS>,
+    KdTreeNearestNeighbors<f64, DIMENSIONS>,
+>;
+
+type Edge = (Point<f64, DIMENSIONS>, Point<f64, DIMENSIONS>);
+
+fn make_obstacles() -> EuclideanSphericalObstacleSet<f64, DIMENSIONS> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let spheres = (0..OBSTACLES)
+        .map(|_| Sphere {
+            center: Point::new(std::array::from_fn(|_| rng.gen_range(0.1..1.0))),
+            radius: 0.12,
+        })
+        .collect();
+    EuclideanSphericalObstacleSet::new(spheres)
+}
+
+fn make_planner() -> Planner {
+    // The goal is out of reach, so every run performs every iteration.
+    RRT::new(
+        Point::new([0.0; DIMENSIONS]),
+        Point::new([2.0; DIMENSIONS]),
+        0.01,
+        make_obstacles(),
+        UniformDistribution::new_with_rng([(0.0, 1.0); DIMENSIONS], StdRng::seed_from_u64(1))
+            .unwrap(),
+        EuclideanSteering::new(STEERING_RANGE),
+    )
+}
+
+/// Random extensions of the steering range, like the ones checked in a batch.
+fn make_edges() -> Vec<Edge> {
+    let mut rng = StdRng::seed_from_u64(2);
+    let steering = EuclideanSteering::new(STEERING_RANGE);
+    (0..EDGES)
+        .map(|_| {
+            let from = Point::new(std::array::from_fn(|_| rng.gen_range(0.0..1.0)));
+            let towards = Point::new(std::array::from_fn(|_| rng.gen_range(0.0..1.0)));
+            (from, steering.steer(&from, &towards))
+        })
+        .collect()
+}
+
+/// Checks the end point and the edge of each extension, as the collision phase of a batch does.
+fn check_edges(
+    obstacles: &EuclideanSphericalObstacleSet<f64, DIMENSIONS>,
+    edges: &[Edge],
+) -> usize {
+    edges
+        .par_iter()
+        .filter(|(from, to)| obstacles.is_point_valid(to) && obstacles.is_edge_valid(from, to))
+        .count()
+}
+
+fn thread_counts() -> Vec<usize> {
+    let available = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+    let mut counts: Vec<usize> = std::iter::successors(Some(1), |&threads| Some(threads * 2))
+        .take_while(|&threads| threads < available)
+        .collect();
+    counts.push(available);
+    counts
+}
+
+fn print_speedup(label: &str, threads: usize, elapsed: Duration, baseline: Duration) {
+    println!(
+        "{label} with {threads:>2} threads: {elapsed:?} ({:.2}x)",
+        baseline.as_secs_f64() / elapsed.as_secs_f64()
+    );
+}
+
+fn main() {
+    let obstacles = make_obstacles();
+    let edges = make_edges();
+    let start_time = Instant::now();
+    let valid = edges
+        .iter()
+        .filter(|(from, to)| obstacles.is_point_valid(to) && obstacles.is_edge_valid(from, to))
+        .count();
+    let serial = start_time.elapsed();
+    println!("Collision phase, serial:     {serial:?} for {EDGES} extensions, {valid} valid");
+    for threads in thread_counts() {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        let start_time = Instant::now();
+        assert_eq!(pool.install(|| check_edges(&obstacles, &edges)), valid);
+        print_speedup("Collision phase", threads, start_time.elapsed(), serial);
+    }
+
+    let mut planner = make_planner();
+    let start_time = Instant::now();
+    let summary = planner.run_iterations(ITERATIONS);
+    let serial = start_time.elapsed();
+    println!(
+        "Iterations, serial:          {serial:?} for {} iterations, {} nodes added, {} samples rejected",
+        summary.iterations, summary.nodes_added, summary.samples_rejected
+    );
+    for threads in thread_counts() {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        let mut planner = make_planner();
+        let start_time = Instant::now();
+        pool.install(|| {
+            for _ in 0..ITERATIONS / BATCH_SIZE {
+                planner.run_iterations_parallel(BATCH_SIZE);
+            }
+        });
+        print_speedup(
+            "Batches of iterations",
+            threads,
+            start_time.elapsed(),
+            serial,
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! # Sphere Broad Phase Benchmark
+//!
+//! Compares checking points and edges against 50,000 small spheres, such as obstacles built from a point cloud,
+//! with a linear scan over every sphere (`EuclideanSphericalObstacleSet`) and with a kd-tree over the sphere
+//! centers (`IndexedSphericalObstacleSet`). Both give the same answers, which the benchmark checks.
+//!
+//! ## Usage
+//! Run the benchmark with:
+//! ```bash
+//! cargo bench --bench sphere_broad_phase
+//! ```
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rrt::collision::{EuclideanSphericalObstacleSet, IndexedSphericalObstacleSet, Sphere};
+use rrt::{Point, ValidityChecker};
+use std::time::Instant;
+
+const DIMENSIONS: usize = 3;
+const SPHERES: usize = 50_000;
+const QUERIES: usize = 5_000;
+const EDGE_LENGTH: f64 = 0.05;
+
+fn random_point(rng: &mut StdRng) -> Point<f64, DIMENSIONS> {
+    Point::new([rng.gen(), rng.gen(), rng.gen()])
+}
+
+/// Counts the valid points and edges, and returns the counts with the time taken.
+fn run_queries<VC: ValidityChecker<f64, DIMENSIONS>>(
+    checker: &VC,
+    queries: &[(Point<f64, DIMENSIONS>, Point<f64, DIMENSIONS>)],
+) -> (usize, usize, std::time::Duration) {
+    let start_time = Instant::now();
+    let valid_points = queries
+        .iter()
+        .filter(|(a, _)| checker.is_point_valid(a))
+        .count();
+    let valid_edges = queries
+        .iter()
+        .filter(|(a, b)| checker.is_edge_valid(a, b))
+        .count();
+    (valid_points, valid_edges, start_time.elapsed())
+}
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let spheres: Vec<Sphere<f64, DIMENSIONS>> = (0..SPHERES)
+        .map(|_| Sphere {
+            center: random_point(&mut rng),
+            radius: rng.gen_range(0.002..0.01),
+        })
+        .collect();
+
+    // Edges of the length of a typical planner step, in random directions.
+    let queries: Vec<_> = (0..QUERIES)
+        .map(|_| {
+            let a = random_point(&mut rng);
+            let direction = random_point(&mut rng) - Point::new([0.5; DIMENSIONS]);
+            let b = a + direction * (EDGE_LENGTH / direction.norm());
+            (a, b)
+        })
+        .collect();
+
+    let linear = EuclideanSphericalObstacleSet::with_inflation(spheres.clone(), 0.005).unwrap();
+    let start_time = Instant::now();
+    let indexed = IndexedSphericalObstacleSet::with_inflation(spheres, 0.005).unwrap();
+    println!("Built the index in {:?}", start_time.elapsed());
+
+    let (linear_points, linear_edges, linear_time) = run_queries(&linear, &queries);
+    println!(
+        "Linear scan: {:?} for {} point and {} edge checks ({} valid points, {} valid edges)",
+        linear_time, QUERIES, QUERIES, linear_points, linear_edges
+    );
+
+    let (indexed_points, indexed_edges, indexed_time) = run_queries(&indexed, &queries);
+    println!(
+        "Kd-tree:     {:?} for {} point and {} edge checks ({} valid points, {} valid edges)",
+        indexed_time, QUERIES, QUERIES, indexed_points, indexed_edges
+    );
+
+    assert_eq!(linear_points, indexed_points);
+    assert_eq!(linear_edges, indexed_edges);
+    println!(
+        "Speedup: {:.0}x",
+        linear_time.as_secs_f64() / indexed_time.as_secs_f64()
+    );
+}
@@ -52,7 +52,11 @@ async fn main() {
         },
     ];
     // We clone the spheres so that we have still have an unmoved copy that we can use for drawing.
-    let validity_checker = rrt::collision::EuclideanSphericalObstacleSet::new(spheres.clone());
+    // The recording checker keeps the rejected points, so we can draw where the tree ran into obstacles.
+    let validity_checker = rrt::collision::RecordingChecker::new(
+        rrt::collision::EuclideanSphericalObstacleSet::new(spheres.clone()),
+        10000,
+    );
 
     // Define the start and goal points.
     let start = rrt::Point::new([100.0, 100.0]);
@@ -65,25 +69,30 @@ async fn main() {
     // Use a uniform sampling distribution with 5% goal bias.
     let ranges = [(0.0, SCREEN_WIDTH as f32), (0.0, SCREEN_HEIGHT as f32)];
     let goal_bias = 0.05;
-    let result = rrt::GoalBiasedUniformDistribution::new(ranges, goal, goal_bias);
-    if result.is_err() {
-        println!(
-            "Error creating sampling distribution: {}",
-            result.err().unwrap()
-        );
-        return;
-    }
-    let sampling_distribution = result.unwrap();
+    let sampling_distribution =
+        match rrt::GoalBiasedUniformDistribution::new(ranges, goal, goal_bias) {
+            Ok(distribution) => distribution,
+            Err(error) => {
+                println!("Error creating sampling distribution: {}", error);
+                return;
+            }
+        };
 
     // Create the RRT planner.
-    let mut rrt = rrt::RRT::<f32, 2, _, _, _, rrt::KdTreeNearestNeighbors<_, 2>>::new(
-        start,
-        goal,
-        goal_tolerance,
-        validity_checker,
-        sampling_distribution,
-        steering,
-    );
+    let result = rrt::RRTBuilder::new()
+        .start(start)
+        .goal(goal)
+        .goal_tolerance(goal_tolerance)
+        .validity_checker(validity_checker)
+        .sampling_distribution(sampling_distribution)
+        .steering(steering)
+        .build();
+    if let Err(error) = result {
+        println!("Error creating RRT planner: {}", error);
+        return;
+    }
+    let mut rrt = result.unwrap();
+    let mut last_event = None;
 
     loop {
         // Clear the screen
@@ -98,38 +107,50 @@ async fn main() {
         draw_circle(start[0], start[1], 5.0, BLUE);
         draw_circle(goal[0], goal[1], goal_tolerance, GREEN);
 
+        // Run one iteration per frame, keeping its event to show the latest sample.
         if !rrt.solved() {
-            rrt.run_iterations(1);
+            rrt.solve_with_callback(1, |_, event| last_event = Some(event));
+        }
+
+        // Draw the rejected points as faint red dots.
+        for point in rrt.get_validity_checker().rejected_points() {
+            draw_circle(point[0], point[1], 2.0, Color::new(1.0, 0.0, 0.0, 0.3));
         }
 
-        // Draw each node and the edge to its parent.
-        let nodes: &Vec<rrt::rrt::Node<f32, 2>> = rrt.get_tree();
-        for node in nodes {
+        // Draw the edges of the tree.
+        for (parent, child) in rrt.edges() {
+            draw_line(parent[0], parent[1], child[0], child[1], 1.0, LIGHTGRAY);
+        }
+
+        // Draw each node, fading older nodes to gray.
+        let iterations = rrt.iterations_run().max(1) as f32;
+        for node in rrt.get_tree() {
+            let age = 1.0 - node.created_at() as f32 / iterations;
+            let color = Color::new(0.7 * age, 0.7 * age, 0.7 * age, 1.0);
             let point = node.point();
-            if let Some(parent_index) = node.parent() {
-                let parent = &nodes[parent_index];
-                let parent_point = parent.point();
-                draw_line(
-                    point[0],
-                    point[1],
-                    parent_point[0],
-                    parent_point[1],
-                    1.0,
-                    BLACK,
-                );
+            draw_circle(point[0], point[1], 2.0, color);
+        }
+
+        // Draw the latest sample, with the extension towards it if a node was added.
+        match last_event {
+            Some(rrt::IterationEvent::SampleRejected { sample }) => {
+                draw_circle(sample[0], sample[1], 3.0, ORANGE);
             }
-            draw_circle(point[0], point[1], 2.0, BLACK);
+            Some(
+                rrt::IterationEvent::NodeAdded { sample, point, .. }
+                | rrt::IterationEvent::SolutionFound { sample, point, .. },
+            ) => {
+                draw_line(point[0], point[1], sample[0], sample[1], 1.0, SKYBLUE);
+                draw_circle(sample[0], sample[1], 3.0, BLUE);
+            }
+            Some(rrt::IterationEvent::NodeLimitReached) | None => {}
         }
 
         // Draw the path if a solution was found.
+        for (a, b) in rrt.solution_edges() {
+            draw_line(a[0], a[1], b[0], b[1], 2.0, RED);
+        }
         if let Some(path) = rrt.get_path() {
-            // Raw path
-            for i in 0..path.len() - 1 {
-                let a = path[i];
-                let b = path[i + 1];
-                draw_line(a[0], a[1], b[0], b[1], 2.0, RED);
-            }
-
             // Smooth the path using shortcutting
             let shortened_path =
                 rrt::smoothing::fast_shortcutting(path, rrt.get_validity_checker());
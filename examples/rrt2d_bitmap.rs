@@ -0,0 +1,173 @@
+//! # Rapidly-exploring Random Tree (RRT) Example on a 2D Map Image
+//!
+//! Plans across a floor plan loaded from a PNG image, where dark pixels are walls,
+//! and draws the tree and the path over the image.
+//!
+//! ## Usage
+//! Run the program with the included floor plan:
+//! ```bash
+//! cargo run --example rrt2d_bitmap --features image
+//! ```
+//! Or with your own map, with the start in the top left and the goal in the bottom right:
+//! ```bash
+//! cargo run --example rrt2d_bitmap --features image -- path/to/map.png
+//! ```
+
+use macroquad::prelude::*;
+
+const SCREEN_HEIGHT: i32 = 600;
+const SCREEN_WIDTH: i32 = 600;
+
+// The size of each pixel of the map in meters.
+const METERS_PER_PIXEL: f32 = 0.05;
+
+fn window_conf() -> Conf {
+    Conf {
+        window_title: "RRT on a 2D Map Image".to_string(),
+        window_width: SCREEN_WIDTH,
+        window_height: SCREEN_HEIGHT,
+        window_resizable: false,
+        fullscreen: false,
+        ..Default::default()
+    }
+}
+
+#[macroquad::main(window_conf)]
+async fn main() {
+    // Load the map.
+    let path = std::env::args().nth(1).unwrap_or_else(|| {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/examples/floor_plan.png").to_string()
+    });
+    let map = match image::open(&path) {
+        Ok(map) => map.to_luma8(),
+        Err(error) => {
+            println!("Error loading map {}: {}", path, error);
+            return;
+        }
+    };
+    let validity_checker = match rrt::collision::BitmapChecker::from_image(
+        &map,
+        128,
+        METERS_PER_PIXEL,
+        rrt::Point::new([0.0, 0.0]),
+    ) {
+        Ok(checker) => checker,
+        Err(error) => {
+            println!("Error creating validity checker: {}", error);
+            return;
+        }
+    };
+
+    // The map is stretched to fill the window.
+    let width = map.width() as f32 * METERS_PER_PIXEL;
+    let height = map.height() as f32 * METERS_PER_PIXEL;
+    let to_screen = |point: &rrt::Point<f32, 2>| {
+        (
+            point[0] / width * SCREEN_WIDTH as f32,
+            point[1] / height * SCREEN_HEIGHT as f32,
+        )
+    };
+    let pixels_to_screen = SCREEN_WIDTH as f32 / map.width() as f32;
+
+    // Define the start and goal points near opposite corners of the map.
+    let start = rrt::Point::new([0.1 * width, 0.1 * height]);
+    let goal = rrt::Point::new([0.9 * width, 0.9 * height]);
+    let goal_tolerance = 0.25;
+
+    // Steps of half a meter, which is ten pixels of the included floor plan.
+    let steering = rrt::EuclideanSteering::new(0.5);
+
+    // Use a uniform sampling distribution over the map with 5% goal bias.
+    let ranges = [(0.0, width), (0.0, height)];
+    let goal_bias = 0.05;
+    let sampling_distribution =
+        match rrt::GoalBiasedUniformDistribution::new(ranges, goal, goal_bias) {
+            Ok(distribution) => distribution,
+            Err(error) => {
+                println!("Error creating sampling distribution: {}", error);
+                return;
+            }
+        };
+
+    // Create the RRT planner.
+    let result = rrt::RRTBuilder::new()
+        .start(start)
+        .goal(goal)
+        .goal_tolerance(goal_tolerance)
+        .validity_checker(validity_checker)
+        .sampling_distribution(sampling_distribution)
+        .steering(steering)
+        .build();
+    let mut rrt = match result {
+        Ok(rrt) => rrt,
+        Err(error) => {
+            println!("Error creating RRT planner: {}", error);
+            return;
+        }
+    };
+
+    // Upload the map to draw it as the background.
+    let rgba: Vec<u8> = map
+        .pixels()
+        .flat_map(|pixel| [pixel.0[0], pixel.0[0], pixel.0[0], 255])
+        .collect();
+    let texture = Texture2D::from_rgba8(map.width() as u16, map.height() as u16, &rgba);
+    texture.set_filter(FilterMode::Nearest);
+
+    loop {
+        // Draw the map.
+        clear_background(WHITE);
+        draw_texture_ex(
+            texture,
+            0.0,
+            0.0,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(vec2(SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32)),
+                ..Default::default()
+            },
+        );
+
+        // Draw the start and goal points.
+        let (x, y) = to_screen(&start);
+        draw_circle(x, y, 5.0, BLUE);
+        let (x, y) = to_screen(&goal);
+        draw_circle(
+            x,
+            y,
+            goal_tolerance / METERS_PER_PIXEL * pixels_to_screen,
+            GREEN,
+        );
+
+        if !rrt.solved() {
+            rrt.run_iterations(10);
+        }
+
+        // Draw the edges of the tree.
+        for (parent, child) in rrt.edges() {
+            let (x1, y1) = to_screen(parent);
+            let (x2, y2) = to_screen(child);
+            draw_line(x1, y1, x2, y2, 1.0, LIGHTGRAY);
+        }
+
+        // Draw the path if a solution was found.
+        if let Some(path) = rrt.get_path() {
+            for i in 0..path.len() - 1 {
+                let (x1, y1) = to_screen(&path[i]);
+                let (x2, y2) = to_screen(&path[i + 1]);
+                draw_line(x1, y1, x2, y2, 2.0, RED);
+            }
+
+            // Smooth the path using shortcutting
+            let shortened_path =
+                rrt::smoothing::fast_shortcutting(path, rrt.get_validity_checker());
+            for i in 0..shortened_path.len() - 1 {
+                let (x1, y1) = to_screen(&shortened_path[i]);
+                let (x2, y2) = to_screen(&shortened_path[i + 1]);
+                draw_line(x1, y1, x2, y2, 2.0, GREEN);
+            }
+        }
+
+        next_frame().await;
+    }
+}
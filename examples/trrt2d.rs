@@ -0,0 +1,93 @@
+//! # Transition-based RRT (T-RRT) Example in 2 Dimensions
+//!
+//! Plans across a cost field with a hill in the middle of the space, once with the RRT and once
+//! with the T-RRT, and prints the average integrated cost of the resulting paths.
+//! The T-RRT paths go around the hill, so their integrated cost is lower.
+//!
+//! ## Usage
+//! Run the program with:
+//! ```bash
+//! cargo run --example trrt2d
+//! ```
+
+use rrt::collision::EuclideanSphericalObstacleSet;
+use rrt::{
+    EuclideanSteering, GoalBiasedUniformDistribution, KdTreeNearestNeighbors, Planner, Point,
+    PointGoal, StateCost, TRRTConfig, RRT, TRRT,
+};
+
+const TRIALS: u32 = 20;
+const MAX_ITERATIONS: u32 = 100000;
+
+/// A hill centered in the middle of the space.
+struct Hill;
+
+impl StateCost<f64, 2> for Hill {
+    fn cost(&self, point: &Point<f64, 2>) -> f64 {
+        let dx = point[0] - 50.0;
+        let dy = point[1] - 50.0;
+        10.0 * (-(dx * dx + dy * dy) / 200.0).exp()
+    }
+}
+
+/// Integrates the state cost along a path with the trapezoidal rule, subdividing each edge.
+fn integrated_cost(path: &[Point<f64, 2>], state_cost: &impl StateCost<f64, 2>) -> f64 {
+    const SUBDIVISIONS: usize = 10;
+    let mut total = 0.0;
+    for edge in path.windows(2) {
+        let length = rrt::distance::euclidean_distance(&edge[0], &edge[1]);
+        for i in 0..SUBDIVISIONS {
+            let a = edge[0] + (edge[1] - edge[0]) * (i as f64 / SUBDIVISIONS as f64);
+            let b = edge[0] + (edge[1] - edge[0]) * ((i + 1) as f64 / SUBDIVISIONS as f64);
+            total +=
+                0.5 * (state_cost.cost(&a) + state_cost.cost(&b)) * length / SUBDIVISIONS as f64;
+        }
+    }
+    total
+}
+
+/// Solves with any planner and returns the integrated cost of its path.
+fn solve_and_measure(planner: &mut dyn Planner<f64, 2>) -> f64 {
+    assert!(planner.solve(MAX_ITERATIONS), "planner found no path");
+    integrated_cost(&planner.get_path().unwrap(), &Hill)
+}
+
+fn main() {
+    let start = Point::new([10.0, 50.0]);
+    let goal = Point::new([90.0, 50.0]);
+    let goal_tolerance = 2.0;
+    let ranges = [(0.0, 100.0), (0.0, 100.0)];
+
+    let mut rrt_total = 0.0;
+    let mut trrt_total = 0.0;
+    for _ in 0..TRIALS {
+        let mut rrt: RRT<f64, 2, _, _, _, KdTreeNearestNeighbors<f64, 2>> = RRT::new(
+            start,
+            goal,
+            goal_tolerance,
+            EuclideanSphericalObstacleSet::new(Vec::new()),
+            GoalBiasedUniformDistribution::new(ranges, goal, 0.05).unwrap(),
+            EuclideanSteering::new(2.0),
+        );
+        rrt_total += solve_and_measure(&mut rrt);
+
+        let mut trrt: TRRT<f64, 2, _, _, _, KdTreeNearestNeighbors<f64, 2>, _> = TRRT::new(
+            start,
+            PointGoal::new(goal, goal_tolerance),
+            EuclideanSphericalObstacleSet::new(Vec::new()),
+            GoalBiasedUniformDistribution::new(ranges, goal, 0.05).unwrap(),
+            EuclideanSteering::new(2.0),
+            Hill,
+            TRRTConfig::default(),
+        )
+        .unwrap();
+        trrt_total += solve_and_measure(&mut trrt);
+    }
+
+    println!(
+        "Average integrated cost over {} trials: RRT {:.2}, T-RRT {:.2}",
+        TRIALS,
+        rrt_total / TRIALS as f64,
+        trrt_total / TRIALS as f64
+    );
+}
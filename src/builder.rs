@@ -0,0 +1,350 @@
+use crate::collision::ValidityChecker;
+use crate::neighbors::{KdTreeNearestNeighbors, NearestNeighbors};
+use crate::point::Point;
+use crate::rrt::{ExtendMode, PlanningError, RRT};
+use crate::sampling::SamplingDistribution;
+use crate::steering::Steering;
+use kiddo::float::kdtree::Axis;
+use num_traits::Float;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Errors that can occur when building an RRT planner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    MissingStart,
+    MissingGoal,
+    MissingGoalTolerance,
+    MissingValidityChecker,
+    MissingSamplingDistribution,
+    MissingSteering,
+    /// The start point is not valid according to the validity checker.
+    StartInCollision,
+    /// The goal point is not valid according to the validity checker.
+    GoalInCollision,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            BuildError::MissingStart => "start point was not set",
+            BuildError::MissingGoal => "goal point was not set",
+            BuildError::MissingGoalTolerance => "goal tolerance was not set",
+            BuildError::MissingValidityChecker => "validity checker was not set",
+            BuildError::MissingSamplingDistribution => "sampling distribution was not set",
+            BuildError::MissingSteering => "steering function was not set",
+            BuildError::StartInCollision => "start point is not valid",
+            BuildError::GoalInCollision => "goal point is not valid",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// A builder for the RRT planner.
+///
+/// The nearest neighbors data structure defaults to `KdTreeNearestNeighbors`
+/// and can be changed with `with_nearest_neighbors`.
+pub struct RRTBuilder<F: Float, const N: usize, VC, SD, ST, NN> {
+    start: Option<Point<F, N>>,
+    goal: Option<Point<F, N>>,
+    goal_tolerance: Option<F>,
+    validity_checker: Option<VC>,
+    sampling_distribution: Option<SD>,
+    steering: Option<ST>,
+    max_nodes: Option<usize>,
+    connect_to_goal: bool,
+    direct_connection: bool,
+    lazy_edge_checking: bool,
+    extend_mode: ExtendMode,
+    min_node_separation: Option<F>,
+    waypoint_resolution: Option<F>,
+    nearest_neighbors: PhantomData<NN>,
+}
+
+impl<F: Float + Axis, const N: usize, VC, SD, ST>
+    RRTBuilder<F, N, VC, SD, ST, KdTreeNearestNeighbors<F, N>>
+{
+    /// Constructs a new builder with no fields set.
+    pub fn new() -> Self {
+        Self {
+            start: None,
+            goal: None,
+            goal_tolerance: None,
+            validity_checker: None,
+            sampling_distribution: None,
+            steering: None,
+            max_nodes: None,
+            connect_to_goal: false,
+            direct_connection: false,
+            lazy_edge_checking: false,
+            extend_mode: ExtendMode::Single,
+            min_node_separation: None,
+            waypoint_resolution: None,
+            nearest_neighbors: PhantomData,
+        }
+    }
+}
+
+impl<F: Float + Axis, const N: usize, VC, SD, ST> Default
+    for RRTBuilder<F, N, VC, SD, ST, KdTreeNearestNeighbors<F, N>>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Float, const N: usize, VC, SD, ST, NN> RRTBuilder<F, N, VC, SD, ST, NN> {
+    /// Sets the start point.
+    pub fn start(mut self, start: Point<F, N>) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Sets the goal point.
+    pub fn goal(mut self, goal: Point<F, N>) -> Self {
+        self.goal = Some(goal);
+        self
+    }
+
+    /// Sets the tolerance for reaching the goal.
+    pub fn goal_tolerance(mut self, goal_tolerance: F) -> Self {
+        self.goal_tolerance = Some(goal_tolerance);
+        self
+    }
+
+    /// Sets the validity checker.
+    pub fn validity_checker(mut self, validity_checker: VC) -> Self {
+        self.validity_checker = Some(validity_checker);
+        self
+    }
+
+    /// Sets the sampling distribution.
+    pub fn sampling_distribution(mut self, sampling_distribution: SD) -> Self {
+        self.sampling_distribution = Some(sampling_distribution);
+        self
+    }
+
+    /// Sets the steering function.
+    pub fn steering(mut self, steering: ST) -> Self {
+        self.steering = Some(steering);
+        self
+    }
+
+    /// Sets the maximum number of nodes in the tree. By default, there is no limit.
+    pub fn max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Sets whether to attempt a direct connection to the goal after adding each node. Disabled by default.
+    pub fn connect_to_goal(mut self, connect_to_goal: bool) -> Self {
+        self.connect_to_goal = connect_to_goal;
+        self
+    }
+
+    /// Sets whether to check for a direct connection from the start to the goal before solving. Disabled by default.
+    pub fn direct_connection(mut self, direct_connection: bool) -> Self {
+        self.direct_connection = direct_connection;
+        self
+    }
+
+    /// Sets whether edges are only checked once they are on the path to the goal. Disabled by default.
+    pub fn lazy_edge_checking(mut self, lazy_edge_checking: bool) -> Self {
+        self.lazy_edge_checking = lazy_edge_checking;
+        self
+    }
+
+    /// Sets how far the tree is extended towards each sample. Defaults to `ExtendMode::Single`.
+    pub fn extend_mode(mut self, extend_mode: ExtendMode) -> Self {
+        self.extend_mode = extend_mode;
+        self
+    }
+
+    /// Sets the minimum distance between a new node and the existing nodes of the tree. Disabled by default.
+    pub fn min_node_separation(mut self, min_node_separation: F) -> Self {
+        self.min_node_separation = Some(min_node_separation);
+        self
+    }
+
+    /// Sets the spacing of the waypoints checked along each steered edge. By default, the straight edge is checked.
+    pub fn waypoint_resolution(mut self, waypoint_resolution: F) -> Self {
+        self.waypoint_resolution = Some(waypoint_resolution);
+        self
+    }
+
+    /// Changes the nearest neighbors data structure used by the planner.
+    pub fn with_nearest_neighbors<NN2: NearestNeighbors<F, N>>(
+        self,
+    ) -> RRTBuilder<F, N, VC, SD, ST, NN2> {
+        RRTBuilder {
+            start: self.start,
+            goal: self.goal,
+            goal_tolerance: self.goal_tolerance,
+            validity_checker: self.validity_checker,
+            sampling_distribution: self.sampling_distribution,
+            steering: self.steering,
+            max_nodes: self.max_nodes,
+            connect_to_goal: self.connect_to_goal,
+            direct_connection: self.direct_connection,
+            lazy_edge_checking: self.lazy_edge_checking,
+            extend_mode: self.extend_mode,
+            min_node_separation: self.min_node_separation,
+            waypoint_resolution: self.waypoint_resolution,
+            nearest_neighbors: PhantomData,
+        }
+    }
+}
+
+impl<F: Float, const N: usize, VC, SD, ST, NN> RRTBuilder<F, N, VC, SD, ST, NN>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    ST: Steering<F, N>,
+    NN: NearestNeighbors<F, N>,
+{
+    /// Builds the RRT planner.
+    ///
+    /// Returns:
+    /// The RRT planner, or an error if a field is missing or the start or goal point is not valid.
+    pub fn build(self) -> Result<RRT<F, N, VC, SD, ST, NN>, BuildError> {
+        let start = self.start.ok_or(BuildError::MissingStart)?;
+        let goal = self.goal.ok_or(BuildError::MissingGoal)?;
+        let goal_tolerance = self
+            .goal_tolerance
+            .ok_or(BuildError::MissingGoalTolerance)?;
+        let validity_checker = self
+            .validity_checker
+            .ok_or(BuildError::MissingValidityChecker)?;
+        let sampling_distribution = self
+            .sampling_distribution
+            .ok_or(BuildError::MissingSamplingDistribution)?;
+        let steering = self.steering.ok_or(BuildError::MissingSteering)?;
+
+        let mut rrt = RRT::try_new(
+            start,
+            goal,
+            goal_tolerance,
+            validity_checker,
+            sampling_distribution,
+            steering,
+        )
+        .map_err(|error| match error {
+            PlanningError::InvalidStart => BuildError::StartInCollision,
+            PlanningError::InvalidGoal => BuildError::GoalInCollision,
+        })?;
+        rrt.set_max_nodes(self.max_nodes);
+        rrt.set_connect_to_goal(self.connect_to_goal);
+        rrt.set_direct_connection(self.direct_connection);
+        rrt.set_lazy_edge_checking(self.lazy_edge_checking);
+        rrt.set_extend_mode(self.extend_mode);
+        if let Some(min_node_separation) = self.min_node_separation {
+            rrt.set_min_node_separation(min_node_separation);
+        }
+        rrt.set_waypoint_resolution(self.waypoint_resolution);
+        Ok(rrt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::{EuclideanSphericalObstacleSet, Sphere};
+    use crate::sampling::UniformDistribution;
+    use crate::steering::EuclideanSteering;
+
+    type TestBuilder = RRTBuilder<
+        f64,
+        2,
+        EuclideanSphericalObstacleSet<f64, 2>,
+        UniformDistribution<f64, 2>,
+        EuclideanSteering<f64, 2>,
+        KdTreeNearestNeighbors<f64, 2>,
+    >;
+
+    /// A builder from `start` to `goal` around a sphere at (5, 5), with every required field set
+    /// except the one reported by `missing`.
+    fn builder(start: [f64; 2], goal: [f64; 2], missing: Option<BuildError>) -> TestBuilder {
+        let mut builder = TestBuilder::new();
+        if missing != Some(BuildError::MissingStart) {
+            builder = builder.start(Point::new(start));
+        }
+        if missing != Some(BuildError::MissingGoal) {
+            builder = builder.goal(Point::new(goal));
+        }
+        if missing != Some(BuildError::MissingGoalTolerance) {
+            builder = builder.goal_tolerance(0.5);
+        }
+        if missing != Some(BuildError::MissingValidityChecker) {
+            builder = builder.validity_checker(EuclideanSphericalObstacleSet::new(vec![Sphere {
+                center: Point::new([5.0, 5.0]),
+                radius: 2.0,
+            }]));
+        }
+        if missing != Some(BuildError::MissingSamplingDistribution) {
+            builder =
+                builder.sampling_distribution(UniformDistribution::new([(0.0, 10.0); 2]).unwrap());
+        }
+        if missing != Some(BuildError::MissingSteering) {
+            builder = builder.steering(EuclideanSteering::new(0.5));
+        }
+        builder
+    }
+
+    #[test]
+    fn each_missing_field_is_reported() {
+        for missing in [
+            BuildError::MissingStart,
+            BuildError::MissingGoal,
+            BuildError::MissingGoalTolerance,
+            BuildError::MissingValidityChecker,
+            BuildError::MissingSamplingDistribution,
+            BuildError::MissingSteering,
+        ] {
+            let build = builder([1.0, 1.0], [9.0, 9.0], Some(missing)).build();
+            assert_eq!(build.err(), Some(missing));
+        }
+    }
+
+    #[test]
+    fn start_and_goal_in_collision_are_reported() {
+        let build = builder([5.0, 6.0], [9.0, 9.0], None).build();
+        assert_eq!(build.err(), Some(BuildError::StartInCollision));
+        let build = builder([1.0, 1.0], [4.0, 5.0], None).build();
+        assert_eq!(build.err(), Some(BuildError::GoalInCollision));
+    }
+
+    #[test]
+    fn build_applies_the_settings() {
+        let mut rrt = builder([1.0, 1.0], [9.0, 9.0], None)
+            .max_nodes(5000)
+            .connect_to_goal(true)
+            .direct_connection(true)
+            .lazy_edge_checking(true)
+            .extend_mode(ExtendMode::Greedy)
+            .min_node_separation(0.1)
+            .waypoint_resolution(0.25)
+            .build()
+            .unwrap();
+        assert_eq!(rrt.max_nodes(), Some(5000));
+        assert!(rrt.connect_to_goal());
+        assert!(rrt.direct_connection());
+        assert!(rrt.lazy_edge_checking());
+        assert_eq!(rrt.extend_mode(), ExtendMode::Greedy);
+        assert_eq!(rrt.min_node_separation(), 0.1);
+        assert_eq!(rrt.waypoint_resolution(), Some(0.25));
+        assert_eq!(rrt.get_tree().len(), 1);
+
+        assert!(rrt.solve(10_000).is_solved());
+        let path = rrt.get_path().unwrap();
+        assert_eq!(path[0].coords(), &[1.0, 1.0]);
+        assert!(rrt.get_tree().len() <= 5000);
+
+        let rrt = builder([1.0, 1.0], [9.0, 9.0], None).build().unwrap();
+        assert_eq!(rrt.max_nodes(), None);
+        assert!(!rrt.connect_to_goal());
+        assert_eq!(rrt.extend_mode(), ExtendMode::Single);
+        assert_eq!(rrt.waypoint_resolution(), None);
+    }
+}
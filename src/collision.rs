@@ -1,6 +1,10 @@
 use crate::distance::euclidean_distance_squared;
 use crate::point::Point;
+use kiddo::float::{distance::SquaredEuclidean, kdtree::Axis, kdtree::KdTree};
 use num_traits::Float;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 /// Checks if a point or edge is valid (i.e., not in collision).
 pub trait ValidityChecker<F: Float, const N: usize> {
@@ -22,6 +26,237 @@ pub trait ValidityChecker<F: Float, const N: usize> {
     /// Returns:
     /// Whether the edge is valid.
     fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool;
+
+    /// Finds where an edge first becomes invalid, for example to add a node just before an obstacle.
+    ///
+    /// The default implementation bisects on the validity of the part of the edge before `t` with `is_edge_valid`,
+    /// until the precision of `F` is reached. Checkers that can find the collision directly should override it.
+    ///
+    /// Parameters:
+    /// - `a`: The start point of the edge.
+    /// - `b`: The end point of the edge.
+    ///
+    /// Returns:
+    /// The parameter `t` in [0, 1] of the first invalid point `a + t * (b - a)`, or None if the edge is valid.
+    fn first_collision(&self, a: &Point<F, N>, b: &Point<F, N>) -> Option<F> {
+        if !self.is_point_valid(a) {
+            return Some(F::zero());
+        }
+        if self.is_edge_valid(a, b) {
+            return None;
+        }
+
+        // The part of the edge before `low` is valid, and the part before `high` is not.
+        let ab = b - a;
+        let two = F::one() + F::one();
+        let (mut low, mut high) = (F::zero(), F::one());
+        loop {
+            let middle = (low + high) / two;
+            if middle <= low || middle >= high {
+                return Some(high);
+            }
+            if self.is_edge_valid(a, &(a + &(ab * middle))) {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+    }
+}
+
+/// A reference to a validity checker is also a validity checker.
+/// This lets many planners share one environment without cloning it.
+impl<F: Float, const N: usize, VC: ValidityChecker<F, N> + ?Sized> ValidityChecker<F, N> for &VC {
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        (**self).is_point_valid(point)
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        (**self).is_edge_valid(a, b)
+    }
+
+    fn first_collision(&self, a: &Point<F, N>, b: &Point<F, N>) -> Option<F> {
+        (**self).first_collision(a, b)
+    }
+}
+
+/// A shared validity checker, for planners that must own their checker, for example on other threads.
+impl<F: Float, const N: usize, VC: ValidityChecker<F, N> + ?Sized> ValidityChecker<F, N>
+    for Arc<VC>
+{
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        (**self).is_point_valid(point)
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        (**self).is_edge_valid(a, b)
+    }
+
+    fn first_collision(&self, a: &Point<F, N>, b: &Point<F, N>) -> Option<F> {
+        (**self).first_collision(a, b)
+    }
+}
+
+/// Finds the closest point on the surface of the obstacles, for samplers that place points near obstacles.
+pub trait ObstacleSurface<F: Float, const N: usize> {
+    /// Finds the nearest obstacle to a point and the closest point on its surface.
+    ///
+    /// Parameters:
+    /// - `point`: The point to check. It may be inside an obstacle.
+    ///
+    /// Returns:
+    /// The closest point on the surface and the unit normal pointing out of the obstacle there,
+    /// or None if there are no obstacles.
+    fn nearest_obstacle_surface(&self, point: &Point<F, N>) -> Option<(Point<F, N>, Point<F, N>)>;
+}
+
+impl<F: Float, const N: usize, O: ObstacleSurface<F, N> + ?Sized> ObstacleSurface<F, N> for &O {
+    fn nearest_obstacle_surface(&self, point: &Point<F, N>) -> Option<(Point<F, N>, Point<F, N>)> {
+        (**self).nearest_obstacle_surface(point)
+    }
+}
+
+impl<F: Float, const N: usize, O: ObstacleSurface<F, N> + ?Sized> ObstacleSurface<F, N> for Arc<O> {
+    fn nearest_obstacle_surface(&self, point: &Point<F, N>) -> Option<(Point<F, N>, Point<F, N>)> {
+        (**self).nearest_obstacle_surface(point)
+    }
+}
+
+/// Measures how far points and edges are from the obstacles, for features that need more than
+/// a valid or invalid answer, such as clearance-aware smoothing.
+pub trait DistanceQuery<F: Float, const N: usize> {
+    /// Computes the signed distance from a point to the nearest obstacle.
+    ///
+    /// Parameters:
+    /// - `point`: The point to measure.
+    ///
+    /// Returns:
+    /// The distance to the nearest obstacle surface, which is negative inside an obstacle,
+    /// or infinity if there are no obstacles.
+    fn clearance(&self, point: &Point<F, N>) -> F;
+
+    /// Computes the smallest clearance of any point on an edge.
+    ///
+    /// Parameters:
+    /// - `a`: The start point of the edge.
+    /// - `b`: The end point of the edge.
+    ///
+    /// Returns:
+    /// The smallest signed distance from a point of the edge to the nearest obstacle,
+    /// or infinity if there are no obstacles.
+    fn edge_clearance(&self, a: &Point<F, N>, b: &Point<F, N>) -> F;
+}
+
+impl<F: Float, const N: usize, D: DistanceQuery<F, N> + ?Sized> DistanceQuery<F, N> for &D {
+    fn clearance(&self, point: &Point<F, N>) -> F {
+        (**self).clearance(point)
+    }
+
+    fn edge_clearance(&self, a: &Point<F, N>, b: &Point<F, N>) -> F {
+        (**self).edge_clearance(a, b)
+    }
+}
+
+impl<F: Float, const N: usize, D: DistanceQuery<F, N> + ?Sized> DistanceQuery<F, N> for Arc<D> {
+    fn clearance(&self, point: &Point<F, N>) -> F {
+        (**self).clearance(point)
+    }
+
+    fn edge_clearance(&self, a: &Point<F, N>, b: &Point<F, N>) -> F {
+        (**self).edge_clearance(a, b)
+    }
+}
+
+/// Casts rays through the obstacles, for example to simulate a range sensor.
+pub trait Raycast<F: Float, const N: usize> {
+    /// Finds the distance along a ray to the first obstacle.
+    ///
+    /// Parameters:
+    /// - `origin`: The start of the ray.
+    /// - `direction`: The direction of the ray, which does not need to be a unit vector.
+    /// - `max_range`: The length of the ray.
+    ///
+    /// Returns:
+    /// The distance from the origin to the first obstacle, which is zero if the origin is inside an obstacle,
+    /// or None if the ray hits nothing within the range, the direction has zero length or the range is negative.
+    fn raycast(&self, origin: &Point<F, N>, direction: &Point<F, N>, max_range: F) -> Option<F>;
+}
+
+impl<F: Float, const N: usize, R: Raycast<F, N> + ?Sized> Raycast<F, N> for &R {
+    fn raycast(&self, origin: &Point<F, N>, direction: &Point<F, N>, max_range: F) -> Option<F> {
+        (**self).raycast(origin, direction, max_range)
+    }
+}
+
+impl<F: Float, const N: usize, R: Raycast<F, N> + ?Sized> Raycast<F, N> for Arc<R> {
+    fn raycast(&self, origin: &Point<F, N>, direction: &Point<F, N>, max_range: F) -> Option<F> {
+        (**self).raycast(origin, direction, max_range)
+    }
+}
+
+/// Finds the end of a ray of length `max_range`, or None if the direction has zero length or the range is negative.
+fn ray_end<F: Float, const N: usize>(
+    origin: &Point<F, N>,
+    direction: &Point<F, N>,
+    max_range: F,
+) -> Option<Point<F, N>> {
+    let length = direction.norm();
+    if !(length > F::zero() && length.is_finite() && max_range >= F::zero()) {
+        return None;
+    }
+    Some(origin + &(*direction * (max_range / length)))
+}
+
+/// Casts rays through any validity checker by checking evenly spaced points along them,
+/// for checkers without an exact `Raycast` implementation.
+///
+/// The distance found is that of the first invalid point checked, so it can be up to the resolution
+/// too long, and obstacles thinner than the resolution can be missed.
+pub struct RayMarcher<F: Float, VC> {
+    checker: VC,
+    resolution: F, // The distance between checked points along a ray.
+}
+
+impl<F: Float, VC> RayMarcher<F, VC> {
+    /// Constructs a new ray marcher.
+    ///
+    /// Parameters:
+    /// - `checker`: The validity checker whose invalid points are the obstacles.
+    /// - `resolution`: The distance between checked points along a ray.
+    ///
+    /// Returns:
+    /// The ray marcher, or an error if the resolution is not positive and finite.
+    pub fn new(checker: VC, resolution: F) -> Result<Self, &'static str> {
+        if !(resolution > F::zero() && resolution.is_finite()) {
+            return Err("resolution must be positive and finite");
+        }
+        Ok(Self {
+            checker,
+            resolution,
+        })
+    }
+
+    pub fn resolution(&self) -> F {
+        self.resolution
+    }
+
+    pub fn inner(&self) -> &VC {
+        &self.checker
+    }
+}
+
+impl<F: Float, const N: usize, VC: ValidityChecker<F, N>> Raycast<F, N> for RayMarcher<F, VC> {
+    fn raycast(&self, origin: &Point<F, N>, direction: &Point<F, N>, max_range: F) -> Option<F> {
+        let length = direction.norm();
+        if !(length > F::zero() && length.is_finite() && max_range >= F::zero()) {
+            return None;
+        }
+        let unit = *direction / length;
+        let steps = (max_range / self.resolution).ceil().to_usize().unwrap_or(0);
+        (0..=steps)
+            .map(|step| (F::from(step).unwrap() * self.resolution).min(max_range))
+            .find(|distance| !self.checker.is_point_valid(&(origin + &(unit * *distance))))
+    }
 }
 
 #[derive(Clone)]
@@ -30,55 +265,3689 @@ pub struct Sphere<F: Float, const N: usize> {
     pub radius: F,
 }
 
+/// A set of spherical obstacles with exact point and edge checks.
+///
+/// The obstacles can be inflated by a margin, such as the radius of a circular robot, which is added to the radius
+/// of every sphere when checking points and edges. The stored spheres are not changed, so the margin can change
+/// between queries.
 pub struct EuclideanSphericalObstacleSet<F: Float, const N: usize> {
     spheres: Vec<Sphere<F, N>>,
+    inflation: F,
 }
 
 impl<F: Float, const N: usize> EuclideanSphericalObstacleSet<F, N> {
     pub fn new(spheres: Vec<Sphere<F, N>>) -> Self {
-        Self { spheres }
+        Self {
+            spheres,
+            inflation: F::zero(),
+        }
+    }
+
+    /// Constructs a set of spherical obstacles inflated by a margin.
+    ///
+    /// Parameters:
+    /// - `spheres`: The obstacles.
+    /// - `robot_radius`: The margin added to the radius of every sphere.
+    ///
+    /// Returns:
+    /// The obstacle set, or an error if the margin is negative or not finite.
+    pub fn with_inflation(
+        spheres: Vec<Sphere<F, N>>,
+        robot_radius: F,
+    ) -> Result<Self, &'static str> {
+        let mut obstacles = Self::new(spheres);
+        obstacles.set_inflation(robot_radius)?;
+        Ok(obstacles)
     }
 
+    /// Returns the obstacles, with their radii before inflation.
     pub fn spheres(&self) -> &Vec<Sphere<F, N>> {
         &self.spheres
     }
+
+    /// Sets the margin added to the radius of every sphere.
+    ///
+    /// Returns:
+    /// An error if the margin is negative or not finite, in which case it is unchanged.
+    pub fn set_inflation(&mut self, robot_radius: F) -> Result<(), &'static str> {
+        if !(robot_radius >= F::zero() && robot_radius.is_finite()) {
+            return Err("inflation must be non-negative and finite");
+        }
+        self.inflation = robot_radius;
+        Ok(())
+    }
+
+    pub fn inflation(&self) -> F {
+        self.inflation
+    }
+
+    /// Returns the radius of a sphere after inflation, which is the radius used for checking.
+    pub fn effective_radius(&self, sphere: &Sphere<F, N>) -> F {
+        sphere.radius + self.inflation
+    }
+
+    /// Returns the obstacles with their radii after inflation, for example to draw the inflated boundaries.
+    pub fn inflated_spheres(&self) -> impl Iterator<Item = Sphere<F, N>> + '_ {
+        self.spheres.iter().map(|sphere| Sphere {
+            center: sphere.center,
+            radius: self.effective_radius(sphere),
+        })
+    }
+
+    /// Adds an obstacle at the end of the set, so its index is the number of obstacles before it was added.
+    pub fn add_sphere(&mut self, sphere: Sphere<F, N>) {
+        self.spheres.push(sphere);
+    }
+
+    /// Removes the obstacle at an index. The obstacles after it move down by one index.
+    /// Panics if the index is out of bounds.
+    ///
+    /// Returns:
+    /// The removed obstacle.
+    pub fn remove_sphere(&mut self, index: usize) -> Sphere<F, N> {
+        self.spheres.remove(index)
+    }
+
+    /// Moves the obstacle at an index to a new center, for example to follow a moving obstacle.
+    /// Panics if the index is out of bounds.
+    pub fn update_center(&mut self, index: usize, center: Point<F, N>) {
+        self.spheres[index].center = center;
+    }
+
+    /// Removes every obstacle. The inflation is unchanged.
+    pub fn clear(&mut self) {
+        self.spheres.clear();
+    }
 }
 
 impl<F: Float, const N: usize> ValidityChecker<F, N> for EuclideanSphericalObstacleSet<F, N> {
     fn is_point_valid(&self, point: &Point<F, N>) -> bool {
-        for sphere in &self.spheres {
-            if euclidean_distance_squared(point, &sphere.center) <= sphere.radius * sphere.radius {
-                return false;
-            }
-        }
-        true
+        !self.spheres.iter().any(|sphere| {
+            sphere_contains_point(&sphere.center, self.effective_radius(sphere), point)
+        })
     }
 
     fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
         // Check if the edge intersects any sphere.
+        !self.spheres.iter().any(|sphere| {
+            sphere_intersects_segment(&sphere.center, self.effective_radius(sphere), a, b)
+        })
+    }
 
-        for sphere in &self.spheres {
-            // Find the closest point on the line segment ab to the sphere.
-            let ab = b - a;
-            let ap = sphere.center - *a;
-            let t = ab.dot(&ap) / ab.norm_squared();
-
-            // If the closest point is outside the line segment, check the endpoints.
-            let radius_squared = sphere.radius * sphere.radius;
-            if t < F::zero() || t > F::one() {
-                if euclidean_distance_squared(a, &sphere.center) <= radius_squared
-                    || euclidean_distance_squared(b, &sphere.center) <= radius_squared
-                {
-                    return false;
+    /// Computed exactly from where the edge enters each sphere.
+    fn first_collision(&self, a: &Point<F, N>, b: &Point<F, N>) -> Option<F> {
+        self.spheres
+            .iter()
+            .filter_map(|sphere| {
+                sphere_first_collision(&sphere.center, self.effective_radius(sphere), a, b)
+            })
+            .reduce(F::min)
+    }
+}
+
+/// Checks if a point is inside or on the surface of a sphere.
+fn sphere_contains_point<F: Float, const N: usize>(
+    center: &Point<F, N>,
+    radius: F,
+    point: &Point<F, N>,
+) -> bool {
+    euclidean_distance_squared(point, center) <= radius * radius
+}
+
+/// Checks if the segment from `a` to `b` touches a sphere.
+#[allow(clippy::op_ref)]
+fn sphere_intersects_segment<F: Float, const N: usize>(
+    center: &Point<F, N>,
+    radius: F,
+    a: &Point<F, N>,
+    b: &Point<F, N>,
+) -> bool {
+    // Find the closest point on the line segment ab to the sphere.
+    let ab = b - a;
+    let ap = center - a;
+    let t = ab.dot(&ap) / ab.norm_squared();
+
+    // If the closest point is outside the line segment, check the endpoints.
+    let radius_squared = radius * radius;
+    if t < F::zero() || t > F::one() {
+        return euclidean_distance_squared(a, center) <= radius_squared
+            || euclidean_distance_squared(b, center) <= radius_squared;
+    }
+
+    let closest = a + &(&ab * t);
+    euclidean_distance_squared(&closest, center) <= radius_squared
+}
+
+/// Finds where the segment from `a` to `b` enters a sphere, by solving for the points of the line at the radius
+/// from the center.
+///
+/// Returns:
+/// The parameter `t` in [0, 1] of the first point of the segment in the sphere, or None if it misses the sphere.
+fn sphere_first_collision<F: Float, const N: usize>(
+    center: &Point<F, N>,
+    radius: F,
+    a: &Point<F, N>,
+    b: &Point<F, N>,
+) -> Option<F> {
+    let offset = a - center;
+    let c = offset.norm_squared() - radius * radius;
+    if c <= F::zero() {
+        return Some(F::zero());
+    }
+
+    // Solve |offset + t * ab|^2 = radius^2. The start is outside the sphere, so the smaller root is the entry.
+    let ab = b - a;
+    let length_squared = ab.norm_squared();
+    if length_squared == F::zero() {
+        return None;
+    }
+    let half_b = ab.dot(&offset);
+    let discriminant = half_b * half_b - length_squared * c;
+    if discriminant < F::zero() {
+        return None;
+    }
+    let t = (-half_b - discriminant.sqrt()) / length_squared;
+    if t >= F::zero() && t <= F::one() {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+impl<F: Float, const N: usize> Raycast<F, N> for EuclideanSphericalObstacleSet<F, N> {
+    fn raycast(&self, origin: &Point<F, N>, direction: &Point<F, N>, max_range: F) -> Option<F> {
+        let end = ray_end(origin, direction, max_range)?;
+        self.first_collision(origin, &end).map(|t| t * max_range)
+    }
+}
+
+/// A set of spherical obstacles with the same exact checks as `EuclideanSphericalObstacleSet`,
+/// for environments with many thousands of spheres, such as obstacles built from a point cloud.
+///
+/// The centers of the spheres are stored in a kd-tree when the set is built. A point is only tested
+/// against the spheres whose centers are within the largest effective radius of it, and an edge is split
+/// into pieces no longer than twice that radius, each of which only tests the spheres near its midpoint.
+/// Sets with a few very large spheres among many small ones gain less, because the search radius is set
+/// by the largest sphere.
+///
+/// Adding and moving spheres update the kd-tree in place, while removing a sphere rebuilds it,
+/// because the indices of the later spheres change.
+pub struct IndexedSphericalObstacleSet<F: Float + Axis, const N: usize> {
+    obstacles: EuclideanSphericalObstacleSet<F, N>,
+    index: KdTree<F, usize, N, 32, u32>, // The centers of the spheres, with their indices
+    max_radius: F,                       // The largest radius before inflation
+}
+
+impl<F: Float + Axis, const N: usize> IndexedSphericalObstacleSet<F, N> {
+    pub fn new(spheres: Vec<Sphere<F, N>>) -> Self {
+        Self::from_obstacles(EuclideanSphericalObstacleSet::new(spheres))
+    }
+
+    /// Constructs a set of spherical obstacles inflated by a margin.
+    ///
+    /// Parameters:
+    /// - `spheres`: The obstacles.
+    /// - `robot_radius`: The margin added to the radius of every sphere.
+    ///
+    /// Returns:
+    /// The obstacle set, or an error if the margin is negative or not finite.
+    pub fn with_inflation(
+        spheres: Vec<Sphere<F, N>>,
+        robot_radius: F,
+    ) -> Result<Self, &'static str> {
+        let obstacles = EuclideanSphericalObstacleSet::with_inflation(spheres, robot_radius)?;
+        Ok(Self::from_obstacles(obstacles))
+    }
+
+    /// Builds the index for an existing set of spherical obstacles, keeping its inflation.
+    pub fn from_obstacles(obstacles: EuclideanSphericalObstacleSet<F, N>) -> Self {
+        let mut indexed = Self {
+            obstacles,
+            index: KdTree::new(),
+            max_radius: F::zero(),
+        };
+        indexed.rebuild_index();
+        indexed
+    }
+
+    fn rebuild_index(&mut self) {
+        self.index = KdTree::new();
+        for (i, sphere) in self.obstacles.spheres().iter().enumerate() {
+            self.index.add(sphere.center.coords(), i);
+        }
+        self.max_radius = self
+            .obstacles
+            .spheres()
+            .iter()
+            .map(|sphere| sphere.radius)
+            .fold(F::zero(), Float::max);
+    }
+
+    /// Gets the indices of the spheres whose centers are within a radius of a point.
+    fn spheres_near(&self, point: &Point<F, N>, radius: F) -> impl Iterator<Item = usize> {
+        self.index
+            .within::<SquaredEuclidean>(point.coords(), radius * radius)
+            .into_iter()
+            .map(|neighbor| neighbor.item)
+    }
+
+    /// Returns the obstacles, with their radii before inflation.
+    pub fn spheres(&self) -> &Vec<Sphere<F, N>> {
+        self.obstacles.spheres()
+    }
+
+    /// Returns the obstacles without the index, for example to use the exact clearance queries.
+    pub fn obstacles(&self) -> &EuclideanSphericalObstacleSet<F, N> {
+        &self.obstacles
+    }
+
+    /// Sets the margin added to the radius of every sphere. The index does not need to be rebuilt.
+    ///
+    /// Returns:
+    /// An error if the margin is negative or not finite, in which case it is unchanged.
+    pub fn set_inflation(&mut self, robot_radius: F) -> Result<(), &'static str> {
+        self.obstacles.set_inflation(robot_radius)
+    }
+
+    pub fn inflation(&self) -> F {
+        self.obstacles.inflation()
+    }
+
+    /// Returns the largest radius of any sphere after inflation, which is the radius of the index queries.
+    pub fn max_effective_radius(&self) -> F {
+        self.max_radius + self.obstacles.inflation()
+    }
+
+    /// Adds an obstacle at the end of the set, so its index is the number of obstacles before it was added.
+    pub fn add_sphere(&mut self, sphere: Sphere<F, N>) {
+        self.index
+            .add(sphere.center.coords(), self.obstacles.spheres().len());
+        self.max_radius = Float::max(self.max_radius, sphere.radius);
+        self.obstacles.add_sphere(sphere);
+    }
+
+    /// Removes the obstacle at an index and rebuilds the index. The obstacles after it move down by one index.
+    /// Panics if the index is out of bounds.
+    ///
+    /// Returns:
+    /// The removed obstacle.
+    pub fn remove_sphere(&mut self, index: usize) -> Sphere<F, N> {
+        let sphere = self.obstacles.remove_sphere(index);
+        self.rebuild_index();
+        sphere
+    }
+
+    /// Moves the obstacle at an index to a new center, for example to follow a moving obstacle.
+    /// Panics if the index is out of bounds.
+    pub fn update_center(&mut self, index: usize, center: Point<F, N>) {
+        let old_center = self.obstacles.spheres()[index].center;
+        self.index.remove(old_center.coords(), index);
+        self.index.add(center.coords(), index);
+        self.obstacles.update_center(index, center);
+    }
+
+    /// Removes every obstacle. The inflation is unchanged.
+    pub fn clear(&mut self) {
+        self.obstacles.clear();
+        self.rebuild_index();
+    }
+}
+
+impl<F: Float + Axis, const N: usize> ValidityChecker<F, N> for IndexedSphericalObstacleSet<F, N> {
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        let spheres = self.obstacles.spheres();
+        !self
+            .spheres_near(point, self.max_effective_radius())
+            .any(|i| {
+                let sphere = &spheres[i];
+                sphere_contains_point(
+                    &sphere.center,
+                    self.obstacles.effective_radius(sphere),
+                    point,
+                )
+            })
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        let spheres = self.obstacles.spheres();
+        !self.spheres_near_edge(a, b).any(|(_, mut candidates)| {
+            candidates.any(|i| {
+                let sphere = &spheres[i];
+                sphere_intersects_segment(
+                    &sphere.center,
+                    self.obstacles.effective_radius(sphere),
+                    a,
+                    b,
+                )
+            })
+        })
+    }
+
+    /// Computed exactly from where the edge enters each nearby sphere.
+    fn first_collision(&self, a: &Point<F, N>, b: &Point<F, N>) -> Option<F> {
+        let spheres = self.obstacles.spheres();
+        let mut first: Option<F> = None;
+        for (end, candidates) in self.spheres_near_edge(a, b) {
+            for i in candidates {
+                let sphere = &spheres[i];
+                let radius = self.obstacles.effective_radius(sphere);
+                if let Some(t) = sphere_first_collision(&sphere.center, radius, a, b) {
+                    first = Some(first.map_or(t, |first| Float::min(first, t)));
                 }
-                continue;
             }
-
-            let closest = a + &(&ab * t);
-            if euclidean_distance_squared(&closest, &sphere.center) <= radius_squared {
-                return false;
+            // A collision in a later piece can not be earlier than one already found before its start.
+            if first.is_some_and(|first| first <= end) {
+                break;
             }
         }
-        true
+        first
+    }
+}
+
+impl<F: Float + Axis, const N: usize> IndexedSphericalObstacleSet<F, N> {
+    /// Splits the edge from `a` to `b` into pieces no longer than twice the largest effective radius,
+    /// and finds the spheres that can touch each piece.
+    ///
+    /// Returns:
+    /// For each piece in order, the parameter of its end and the indices of the spheres near it.
+    fn spheres_near_edge<'a>(
+        &'a self,
+        a: &'a Point<F, N>,
+        b: &Point<F, N>,
+    ) -> impl Iterator<Item = (F, impl Iterator<Item = usize>)> + 'a {
+        let radius = self.max_effective_radius();
+        let ab = b - a;
+
+        // Every point within the radius of a piece is within half its length plus the radius
+        // of its midpoint, so the queries around the midpoints cover all the spheres the edge can touch.
+        let length = ab.norm();
+        let pieces = if radius > F::zero() {
+            Float::max(Float::ceil(length / (radius + radius)), F::one())
+        } else {
+            F::one()
+        };
+        let piece_count = pieces.to_usize().unwrap_or(1);
+        let half_piece = length / (pieces + pieces);
+        (0..piece_count).map(move |piece| {
+            let start = F::from(piece).unwrap();
+            let t = (start + F::from(0.5).unwrap()) / pieces;
+            let midpoint = a + &(ab * t);
+            let end = (start + F::one()) / pieces;
+            (end, self.spheres_near(&midpoint, half_piece + radius))
+        })
+    }
+}
+
+/// Finds the point on the segment from `a` to `b` that is closest to `point`.
+fn closest_point_on_segment<F: Float, const N: usize>(
+    a: &Point<F, N>,
+    b: &Point<F, N>,
+    point: &Point<F, N>,
+) -> Point<F, N> {
+    let ab = b - a;
+    let length_squared = ab.norm_squared();
+    if length_squared == F::zero() {
+        return *a;
+    }
+    let t = (ab.dot(&(point - a)) / length_squared)
+        .max(F::zero())
+        .min(F::one());
+    a + &(ab * t)
+}
+
+impl<F: Float, const N: usize> DistanceQuery<F, N> for EuclideanSphericalObstacleSet<F, N> {
+    /// The clearance is measured to the surface of the inflated spheres.
+    fn clearance(&self, point: &Point<F, N>) -> F {
+        self.inflated_spheres()
+            .map(|sphere| euclidean_distance_squared(point, &sphere.center).sqrt() - sphere.radius)
+            .fold(F::infinity(), F::min)
+    }
+
+    /// Computed exactly from the point of the edge closest to the center of each sphere.
+    fn edge_clearance(&self, a: &Point<F, N>, b: &Point<F, N>) -> F {
+        self.inflated_spheres()
+            .map(|sphere| {
+                let closest = closest_point_on_segment(a, b, &sphere.center);
+                euclidean_distance_squared(&closest, &sphere.center).sqrt() - sphere.radius
+            })
+            .fold(F::infinity(), F::min)
+    }
+}
+
+impl<F: Float, const N: usize> ObstacleSurface<F, N> for EuclideanSphericalObstacleSet<F, N> {
+    /// The nearest sphere is the one with the smallest signed distance from its surface,
+    /// so a point inside a sphere is projected out of that sphere. The surface includes the inflation.
+    fn nearest_obstacle_surface(&self, point: &Point<F, N>) -> Option<(Point<F, N>, Point<F, N>)> {
+        let sphere = self.inflated_spheres().min_by(|a, b| {
+            let distance_a = euclidean_distance_squared(point, &a.center).sqrt() - a.radius;
+            let distance_b = euclidean_distance_squared(point, &b.center).sqrt() - b.radius;
+            distance_a.partial_cmp(&distance_b).unwrap()
+        })?;
+
+        let offset = point - &sphere.center;
+        let distance = offset.norm();
+        let normal = if distance > F::zero() {
+            offset / distance
+        } else {
+            // At the center every direction is equally close to the surface.
+            let mut coords = [F::zero(); N];
+            coords[0] = F::one();
+            Point::new(coords)
+        };
+        Some((sphere.center + normal * sphere.radius, normal))
+    }
+}
+
+/// A spherical obstacle moving at a constant velocity, for planning in space-time.
+///
+/// The first `N - 1` coordinates are space and the last is time. The last coordinate of the
+/// center and of the velocity is ignored.
+#[derive(Clone)]
+pub struct MovingSphere<F: Float, const N: usize> {
+    pub center: Point<F, N>,   // The center at time zero
+    pub velocity: Point<F, N>, // The change in the center per unit of time
+    pub radius: F,
+}
+
+impl<F: Float, const N: usize> MovingSphere<F, N> {
+    /// Returns the center at a time, with the time as the last coordinate.
+    pub fn center_at(&self, time: F) -> Point<F, N> {
+        let mut coords = *self.center.coords();
+        for (i, coord) in coords.iter_mut().enumerate().take(N - 1) {
+            *coord = *coord + self.velocity[i] * time;
+        }
+        coords[N - 1] = time;
+        Point::new(coords)
+    }
+}
+
+/// A validity checker for spheres moving on straight lines, where the first `N - 1` coordinates of each point
+/// are space and the last is time.
+///
+/// A point is invalid if it is inside a sphere at the time of the point. An edge moves the robot at a constant
+/// velocity from its start to its end, so it is invalid if the robot comes within the radius of a sphere at any
+/// time in between, which is found exactly from the closest approach of the two motions.
+/// Edges that go backward in time are invalid, while edges that take no time are checked at that time.
+pub struct SpaceTimeSphereChecker<F: Float, const N: usize> {
+    spheres: Vec<MovingSphere<F, N>>,
+}
+
+impl<F: Float, const N: usize> SpaceTimeSphereChecker<F, N> {
+    /// Constructs a checker for moving spheres.
+    ///
+    /// Parameters:
+    /// - `spheres`: The moving obstacles.
+    ///
+    /// Returns:
+    /// The checker, or an error if the points do not have at least one spatial dimension and time.
+    pub fn new(spheres: Vec<MovingSphere<F, N>>) -> Result<Self, &'static str> {
+        if N < 2 {
+            return Err("space-time points need at least one spatial dimension and time");
+        }
+        Ok(Self { spheres })
+    }
+
+    pub fn spheres(&self) -> &Vec<MovingSphere<F, N>> {
+        &self.spheres
+    }
+
+    /// Returns the time coordinate of a point.
+    pub fn time(point: &Point<F, N>) -> F {
+        point[N - 1]
+    }
+}
+
+impl<F: Float, const N: usize> ValidityChecker<F, N> for SpaceTimeSphereChecker<F, N> {
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        let time = Self::time(point);
+        self.spheres.iter().all(|sphere| {
+            let center = sphere.center_at(time);
+            let distance_squared = (0..N - 1)
+                .map(|i| (point[i] - center[i]) * (point[i] - center[i]))
+                .fold(F::zero(), |sum, square| sum + square);
+            distance_squared > sphere.radius * sphere.radius
+        })
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        let (start_time, end_time) = (Self::time(a), Self::time(b));
+        if end_time < start_time {
+            return false;
+        }
+
+        self.spheres.iter().all(|sphere| {
+            // The position of the robot relative to the sphere changes linearly along the edge,
+            // from `offset` at its start to `offset + change` at its end.
+            let (start_center, end_center) =
+                (sphere.center_at(start_time), sphere.center_at(end_time));
+            let mut offset = [F::zero(); N];
+            let mut change = [F::zero(); N];
+            for i in 0..N - 1 {
+                offset[i] = a[i] - start_center[i];
+                change[i] = (b[i] - end_center[i]) - offset[i];
+            }
+            let closest = closest_point_on_segment(
+                &Point::new(offset),
+                &(Point::new(offset) + Point::new(change)),
+                &Point::new([F::zero(); N]),
+            );
+            closest.norm_squared() > sphere.radius * sphere.radius
+        })
+    }
+}
+
+/// An axis-aligned box, given by its minimum and maximum corners.
+/// Points on the boundary are inside the box, so an edge that grazes a face collides with it.
+#[derive(Clone)]
+pub struct Aabb<F: Float, const N: usize> {
+    pub min: Point<F, N>,
+    pub max: Point<F, N>,
+}
+
+impl<F: Float, const N: usize> Aabb<F, N> {
+    /// Constructs a box from any two opposite corners.
+    ///
+    /// Parameters:
+    /// - `a`: A corner of the box.
+    /// - `b`: The opposite corner of the box.
+    ///
+    /// Returns:
+    /// The box, whose minimum and maximum corners are the smaller and larger coordinates of each dimension.
+    pub fn from_corners(a: &Point<F, N>, b: &Point<F, N>) -> Self {
+        Self {
+            min: Point::new(std::array::from_fn(|i| a[i].min(b[i]))),
+            max: Point::new(std::array::from_fn(|i| a[i].max(b[i]))),
+        }
+    }
+
+    /// Checks if a point is inside the box or on its boundary.
+    pub fn contains(&self, point: &Point<F, N>) -> bool {
+        (0..N).all(|i| point[i] >= self.min[i] && point[i] <= self.max[i])
+    }
+
+    /// Checks if the line segment from `a` to `b` touches the box, using the slab method.
+    ///
+    /// Parameters:
+    /// - `a`: The start point of the segment.
+    /// - `b`: The end point of the segment.
+    ///
+    /// Returns:
+    /// Whether any point of the segment is inside the box or on its boundary.
+    pub fn intersects_segment(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        self.segment_entry(a, b).is_some()
+    }
+
+    /// Finds where the line segment from `a` to `b` enters the box, using the slab method.
+    ///
+    /// Parameters:
+    /// - `a`: The start point of the segment.
+    /// - `b`: The end point of the segment.
+    ///
+    /// Returns:
+    /// The parameter `t` in [0, 1] of the first point `a + t * (b - a)` inside the box or on its boundary,
+    /// or None if the segment misses the box.
+    pub fn segment_entry(&self, a: &Point<F, N>, b: &Point<F, N>) -> Option<F> {
+        // The segment is a + t * (b - a) for t in [0, 1]. Each dimension limits t to the interval
+        // where the segment is between the two faces of that dimension.
+        let mut t_enter = F::zero();
+        let mut t_exit = F::one();
+        for i in 0..N {
+            let direction = b[i] - a[i];
+            if direction == F::zero() {
+                // The segment is parallel to the faces of this dimension, so it is either always
+                // or never between them.
+                if a[i] < self.min[i] || a[i] > self.max[i] {
+                    return None;
+                }
+                continue;
+            }
+            let mut t_near = (self.min[i] - a[i]) / direction;
+            let mut t_far = (self.max[i] - a[i]) / direction;
+            if t_near > t_far {
+                std::mem::swap(&mut t_near, &mut t_far);
+            }
+            t_enter = t_enter.max(t_near);
+            t_exit = t_exit.min(t_far);
+            if t_enter > t_exit {
+                return None;
+            }
+        }
+        Some(t_enter)
+    }
+}
+
+impl<F: Float, const N: usize> Aabb<F, N> {
+    /// Computes the signed distance from a point to the box, which is negative inside the box.
+    pub fn signed_distance(&self, point: &Point<F, N>) -> F {
+        let mut outside_squared = F::zero();
+        let mut inside = F::infinity();
+        for i in 0..N {
+            let below = self.min[i] - point[i];
+            let above = point[i] - self.max[i];
+            let excess = below.max(above).max(F::zero());
+            outside_squared = outside_squared + excess * excess;
+            inside = inside.min(-below).min(-above);
+        }
+        if outside_squared > F::zero() {
+            outside_squared.sqrt()
+        } else {
+            -inside
+        }
+    }
+
+    /// Computes the smallest signed distance from a point of the segment from `a` to `b` to the box.
+    ///
+    /// The signed distance to a convex set is convex along the segment, so its minimum is found by
+    /// golden-section search, which is accurate to a tiny fraction of the segment length.
+    pub fn segment_signed_distance(&self, a: &Point<F, N>, b: &Point<F, N>) -> F {
+        let ab = b - a;
+        let distance_at = |t: F| self.signed_distance(&(a + &(ab * t)));
+        let ratio = F::from(0.618_033_988_749_894_9).unwrap();
+        let (mut low, mut high) = (F::zero(), F::one());
+        let mut x1 = high - (high - low) * ratio;
+        let mut x2 = low + (high - low) * ratio;
+        let (mut d1, mut d2) = (distance_at(x1), distance_at(x2));
+        for _ in 0..80 {
+            if d1 <= d2 {
+                high = x2;
+                x2 = x1;
+                d2 = d1;
+                x1 = high - (high - low) * ratio;
+                d1 = distance_at(x1);
+            } else {
+                low = x1;
+                x1 = x2;
+                d1 = d2;
+                x2 = low + (high - low) * ratio;
+                d2 = distance_at(x2);
+            }
+        }
+        d1.min(d2)
+            .min(distance_at(F::zero()))
+            .min(distance_at(F::one()))
+    }
+}
+
+/// A set of axis-aligned box obstacles with exact point and edge checks.
+pub struct AabbObstacleSet<F: Float, const N: usize> {
+    boxes: Vec<Aabb<F, N>>,
+}
+
+impl<F: Float, const N: usize> AabbObstacleSet<F, N> {
+    pub fn new(boxes: Vec<Aabb<F, N>>) -> Self {
+        Self { boxes }
+    }
+
+    pub fn boxes(&self) -> &Vec<Aabb<F, N>> {
+        &self.boxes
+    }
+}
+
+impl<F: Float, const N: usize> ValidityChecker<F, N> for AabbObstacleSet<F, N> {
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        !self.boxes.iter().any(|aabb| aabb.contains(point))
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        !self.boxes.iter().any(|aabb| aabb.intersects_segment(a, b))
+    }
+
+    /// Computed exactly from where the edge enters each box.
+    fn first_collision(&self, a: &Point<F, N>, b: &Point<F, N>) -> Option<F> {
+        self.boxes
+            .iter()
+            .filter_map(|aabb| aabb.segment_entry(a, b))
+            .reduce(F::min)
+    }
+}
+
+impl<F: Float, const N: usize> Raycast<F, N> for AabbObstacleSet<F, N> {
+    fn raycast(&self, origin: &Point<F, N>, direction: &Point<F, N>, max_range: F) -> Option<F> {
+        let end = ray_end(origin, direction, max_range)?;
+        self.first_collision(origin, &end).map(|t| t * max_range)
+    }
+}
+
+impl<F: Float, const N: usize> DistanceQuery<F, N> for AabbObstacleSet<F, N> {
+    fn clearance(&self, point: &Point<F, N>) -> F {
+        self.boxes
+            .iter()
+            .map(|aabb| aabb.signed_distance(point))
+            .fold(F::infinity(), F::min)
+    }
+
+    fn edge_clearance(&self, a: &Point<F, N>, b: &Point<F, N>) -> F {
+        self.boxes
+            .iter()
+            .map(|aabb| aabb.segment_signed_distance(a, b))
+            .fold(F::infinity(), F::min)
+    }
+}
+
+/// A box that can be rotated, such as a piece of furniture or a vehicle.
+///
+/// A point `local` in the frame of the box is at `center + rotation * local` in the world,
+/// and the box covers the local points from `-half_extents` to `half_extents`.
+/// Points on the boundary are inside the box.
+#[derive(Clone)]
+pub struct OrientedBox<F: Float, const N: usize> {
+    center: Point<F, N>,
+    half_extents: [F; N],
+    rotation: [[F; N]; N], // Row-major, so column `i` is axis `i` of the box in the world.
+}
+
+impl<F: Float, const N: usize> OrientedBox<F, N> {
+    /// Constructs a box from a rotation matrix.
+    ///
+    /// Parameters:
+    /// - `center`: The center of the box.
+    /// - `half_extents`: Half of the side length of the box along each of its axes.
+    /// - `rotation`: The row-major rotation matrix from the frame of the box to the world.
+    ///
+    /// Returns:
+    /// The box, or an error if a half extent is negative or not finite or the matrix is not orthonormal.
+    pub fn new(
+        center: Point<F, N>,
+        half_extents: [F; N],
+        rotation: [[F; N]; N],
+    ) -> Result<Self, &'static str> {
+        if !half_extents
+            .iter()
+            .all(|extent| *extent >= F::zero() && extent.is_finite())
+        {
+            return Err("half extents must be non-negative and finite");
+        }
+        if !is_orthonormal(&rotation) {
+            return Err("rotation must be an orthonormal matrix");
+        }
+        Ok(Self {
+            center,
+            half_extents,
+            rotation,
+        })
+    }
+
+    pub fn center(&self) -> &Point<F, N> {
+        &self.center
+    }
+
+    pub fn half_extents(&self) -> &[F; N] {
+        &self.half_extents
+    }
+
+    pub fn rotation(&self) -> &[[F; N]; N] {
+        &self.rotation
+    }
+
+    /// Transforms a point from the world into the frame of the box.
+    pub fn to_box_frame(&self, point: &Point<F, N>) -> Point<F, N> {
+        rotate_into_frame(&self.rotation, &(point - &self.center))
+    }
+
+    /// Returns the box in its own frame, centered at the origin.
+    fn local_aabb(&self) -> Aabb<F, N> {
+        Aabb {
+            min: Point::new(self.half_extents.map(|extent| -extent)),
+            max: Point::new(self.half_extents),
+        }
+    }
+
+    /// Checks if a point is inside the box or on its boundary.
+    pub fn contains(&self, point: &Point<F, N>) -> bool {
+        self.local_aabb().contains(&self.to_box_frame(point))
+    }
+
+    /// Checks if the line segment from `a` to `b` touches the box, using the slab method in the frame of the box.
+    pub fn intersects_segment(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        self.local_aabb()
+            .intersects_segment(&self.to_box_frame(a), &self.to_box_frame(b))
+    }
+}
+
+impl<F: Float> OrientedBox<F, 2> {
+    /// Constructs a 2D box rotated counterclockwise by an angle in radians.
+    ///
+    /// Returns:
+    /// The box, or an error if a half extent is negative or not finite or the angle is not finite.
+    pub fn from_angle(
+        center: Point<F, 2>,
+        half_extents: [F; 2],
+        angle: F,
+    ) -> Result<Self, &'static str> {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(center, half_extents, [[cos, -sin], [sin, cos]])
+    }
+}
+
+impl<F: Float> OrientedBox<F, 3> {
+    /// Constructs a 3D box rotated by an angle in radians about an axis, following the right-hand rule.
+    ///
+    /// Returns:
+    /// The box, or an error if a half extent is negative or not finite, the axis has zero length,
+    /// or the angle is not finite.
+    pub fn from_axis_angle(
+        center: Point<F, 3>,
+        half_extents: [F; 3],
+        axis: [F; 3],
+        angle: F,
+    ) -> Result<Self, &'static str> {
+        let length = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+        if !(length > F::zero() && length.is_finite()) {
+            return Err("rotation axis must have a positive, finite length");
+        }
+        let [x, y, z] = axis.map(|coord| coord / length);
+
+        // Rodrigues' rotation formula.
+        let (sin, cos) = angle.sin_cos();
+        let t = F::one() - cos;
+        let rotation = [
+            [t * x * x + cos, t * x * y - sin * z, t * x * z + sin * y],
+            [t * x * y + sin * z, t * y * y + cos, t * y * z - sin * x],
+            [t * x * z - sin * y, t * y * z + sin * x, t * z * z + cos],
+        ];
+        Self::new(center, half_extents, rotation)
+    }
+}
+
+/// Checks if the columns of a row-major matrix are orthogonal unit vectors, up to rounding errors.
+fn is_orthonormal<F: Float, const N: usize>(rotation: &[[F; N]; N]) -> bool {
+    let tolerance = F::epsilon().sqrt();
+    (0..N).all(|i| {
+        (0..N).all(|j| {
+            let dot = (0..N)
+                .map(|k| rotation[k][i] * rotation[k][j])
+                .fold(F::zero(), |sum, product| sum + product);
+            let expected = if i == j { F::one() } else { F::zero() };
+            (dot - expected).abs() <= tolerance
+        })
+    })
+}
+
+/// Multiplies a vector by the transpose of a row-major rotation matrix, which takes a vector from the world
+/// into the frame whose axes are the columns of the matrix.
+fn rotate_into_frame<F: Float, const N: usize>(
+    rotation: &[[F; N]; N],
+    vector: &Point<F, N>,
+) -> Point<F, N> {
+    Point::new(std::array::from_fn(|i| {
+        (0..N)
+            .map(|k| rotation[k][i] * vector[k])
+            .fold(F::zero(), |sum, product| sum + product)
+    }))
+}
+
+/// A set of oriented box obstacles with exact point and edge checks.
+pub struct ObbObstacleSet<F: Float, const N: usize> {
+    boxes: Vec<OrientedBox<F, N>>,
+}
+
+impl<F: Float, const N: usize> ObbObstacleSet<F, N> {
+    pub fn new(boxes: Vec<OrientedBox<F, N>>) -> Self {
+        Self { boxes }
+    }
+
+    pub fn boxes(&self) -> &Vec<OrientedBox<F, N>> {
+        &self.boxes
+    }
+}
+
+impl<F: Float, const N: usize> ValidityChecker<F, N> for ObbObstacleSet<F, N> {
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        !self.boxes.iter().any(|obb| obb.contains(point))
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        !self.boxes.iter().any(|obb| obb.intersects_segment(a, b))
+    }
+}
+
+/// An ellipsoid obstacle, which fits elongated obstacles much more tightly than a sphere.
+///
+/// The ellipsoid is axis-aligned unless it is given a rotation, in which case a point `local` in the frame of the
+/// ellipsoid is at `center + rotation * local` in the world. Points on the surface are inside the ellipsoid.
+#[derive(Clone)]
+pub struct Ellipsoid<F: Float, const N: usize> {
+    center: Point<F, N>,
+    semi_axes: [F; N],     // The radius along each axis of the ellipsoid.
+    rotation: [[F; N]; N], // Row-major, so column `i` is axis `i` of the ellipsoid in the world.
+}
+
+impl<F: Float, const N: usize> Ellipsoid<F, N> {
+    /// Constructs an axis-aligned ellipsoid.
+    ///
+    /// Parameters:
+    /// - `center`: The center of the ellipsoid.
+    /// - `semi_axes`: The radius of the ellipsoid along each axis.
+    ///
+    /// Returns:
+    /// The ellipsoid, or an error if a semi-axis is not positive and finite.
+    pub fn new(center: Point<F, N>, semi_axes: [F; N]) -> Result<Self, &'static str> {
+        if !semi_axes
+            .iter()
+            .all(|axis| *axis > F::zero() && axis.is_finite())
+        {
+            return Err("semi-axes must be positive and finite");
+        }
+        Ok(Self {
+            center,
+            semi_axes,
+            rotation: std::array::from_fn(|i| {
+                std::array::from_fn(|j| if i == j { F::one() } else { F::zero() })
+            }),
+        })
+    }
+
+    /// Rotates the ellipsoid about its center.
+    ///
+    /// Parameters:
+    /// - `rotation`: The row-major rotation matrix from the frame of the ellipsoid to the world.
+    ///
+    /// Returns:
+    /// The rotated ellipsoid, or an error if the matrix is not orthonormal.
+    pub fn with_rotation(mut self, rotation: [[F; N]; N]) -> Result<Self, &'static str> {
+        if !is_orthonormal(&rotation) {
+            return Err("rotation must be an orthonormal matrix");
+        }
+        self.rotation = rotation;
+        Ok(self)
+    }
+
+    pub fn center(&self) -> &Point<F, N> {
+        &self.center
+    }
+
+    pub fn semi_axes(&self) -> &[F; N] {
+        &self.semi_axes
+    }
+
+    pub fn rotation(&self) -> &[[F; N]; N] {
+        &self.rotation
+    }
+
+    /// Maps a point into the space where the ellipsoid is the unit sphere at the origin.
+    /// The map is linear, so segments stay segments.
+    pub fn to_unit_sphere(&self, point: &Point<F, N>) -> Point<F, N> {
+        let local = rotate_into_frame(&self.rotation, &(point - &self.center));
+        Point::new(std::array::from_fn(|i| local[i] / self.semi_axes[i]))
+    }
+
+    /// Checks if a point is inside the ellipsoid or on its surface.
+    pub fn contains(&self, point: &Point<F, N>) -> bool {
+        self.to_unit_sphere(point).norm_squared() <= F::one()
+    }
+
+    /// Checks if the line segment from `a` to `b` touches the ellipsoid.
+    pub fn intersects_segment(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        sphere_intersects_segment(
+            &Point::new([F::zero(); N]),
+            F::one(),
+            &self.to_unit_sphere(a),
+            &self.to_unit_sphere(b),
+        )
+    }
+}
+
+/// A set of ellipsoid obstacles with exact point and edge checks.
+pub struct EllipsoidObstacleSet<F: Float, const N: usize> {
+    ellipsoids: Vec<Ellipsoid<F, N>>,
+}
+
+impl<F: Float, const N: usize> EllipsoidObstacleSet<F, N> {
+    pub fn new(ellipsoids: Vec<Ellipsoid<F, N>>) -> Self {
+        Self { ellipsoids }
+    }
+
+    pub fn ellipsoids(&self) -> &Vec<Ellipsoid<F, N>> {
+        &self.ellipsoids
+    }
+}
+
+impl<F: Float, const N: usize> ValidityChecker<F, N> for EllipsoidObstacleSet<F, N> {
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        !self
+            .ellipsoids
+            .iter()
+            .any(|ellipsoid| ellipsoid.contains(point))
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        !self
+            .ellipsoids
+            .iter()
+            .any(|ellipsoid| ellipsoid.intersects_segment(a, b))
+    }
+}
+
+/// A validity checker that only accepts points inside the workspace, given by a range for each dimension.
+/// Points on the boundary are valid.
+///
+/// The workspace is convex, so an edge is valid if both of its endpoints are.
+/// Combine it with a checker for the obstacles inside the workspace using `CompositeChecker`.
+pub struct BoundsChecker<F: Float, const N: usize> {
+    ranges: [(F, F); N],
+}
+
+impl<F: Float, const N: usize> BoundsChecker<F, N> {
+    /// Constructs a new bounds checker.
+    ///
+    /// Parameters:
+    /// - `ranges`: The valid range of each dimension.
+    ///
+    /// Returns:
+    /// The bounds checker, or an error if a bound is NaN or a lower bound is above its upper bound.
+    pub fn new(ranges: [(F, F); N]) -> Result<Self, &'static str> {
+        if ranges.iter().any(|(min, max)| min.is_nan() || max.is_nan()) {
+            return Err("bounds must not be NaN");
+        }
+        if ranges.iter().any(|(min, max)| min > max) {
+            return Err("lower bounds must not be above upper bounds");
+        }
+        Ok(Self { ranges })
+    }
+
+    pub fn ranges(&self) -> &[(F, F); N] {
+        &self.ranges
+    }
+}
+
+impl<F: Float, const N: usize> ValidityChecker<F, N> for BoundsChecker<F, N> {
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        self.ranges
+            .iter()
+            .enumerate()
+            .all(|(i, (min, max))| point[i] >= *min && point[i] <= *max)
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        self.is_point_valid(a) && self.is_point_valid(b)
+    }
+}
+
+/// A validity checker that combines several checkers, such as the workspace bounds and the obstacles.
+/// A point or edge is valid only if every checker accepts it.
+///
+/// The checkers run in the order they were added and stop at the first rejection,
+/// so add the cheapest checkers (or the ones that reject most often) first.
+/// For a fixed set of checkers without dynamic dispatch, use a tuple such as `(bounds, obstacles)` instead,
+/// which is also a validity checker.
+pub struct CompositeChecker<F: Float, const N: usize> {
+    checkers: Vec<Box<dyn ValidityChecker<F, N> + Send + Sync>>,
+}
+
+impl<F: Float, const N: usize> CompositeChecker<F, N> {
+    /// Constructs a composite checker without any checkers, which accepts everything.
+    pub fn new() -> Self {
+        Self {
+            checkers: Vec::new(),
+        }
+    }
+
+    /// Adds a checker, which runs after the checkers already added.
+    ///
+    /// Parameters:
+    /// - `checker`: The checker to add.
+    pub fn push<VC>(&mut self, checker: VC)
+    where
+        VC: ValidityChecker<F, N> + Send + Sync + 'static,
+    {
+        self.checkers.push(Box::new(checker));
+    }
+
+    /// Adds a checker and returns the composite checker, for building it in one expression.
+    ///
+    /// Parameters:
+    /// - `checker`: The checker to add.
+    pub fn with<VC>(mut self, checker: VC) -> Self
+    where
+        VC: ValidityChecker<F, N> + Send + Sync + 'static,
+    {
+        self.push(checker);
+        self
+    }
+
+    /// Returns the number of checkers.
+    pub fn len(&self) -> usize {
+        self.checkers.len()
+    }
+
+    /// Returns true if there are no checkers.
+    pub fn is_empty(&self) -> bool {
+        self.checkers.is_empty()
+    }
+}
+
+impl<F: Float, const N: usize> Default for CompositeChecker<F, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Float, const N: usize> ValidityChecker<F, N> for CompositeChecker<F, N> {
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        self.checkers
+            .iter()
+            .all(|checker| checker.is_point_valid(point))
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        self.checkers
+            .iter()
+            .all(|checker| checker.is_edge_valid(a, b))
+    }
+
+    /// The earliest of the first collisions found by the checkers.
+    fn first_collision(&self, a: &Point<F, N>, b: &Point<F, N>) -> Option<F> {
+        self.checkers
+            .iter()
+            .filter_map(|checker| checker.first_collision(a, b))
+            .reduce(F::min)
+    }
+}
+
+/// Implements `ValidityChecker` for a tuple of validity checkers, which checks them in order.
+macro_rules! impl_tuple_checker {
+    ($($checker:ident: $index:tt),+) => {
+        impl<F: Float, const N: usize, $($checker: ValidityChecker<F, N>),+> ValidityChecker<F, N>
+            for ($($checker,)+)
+        {
+            fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+                $(self.$index.is_point_valid(point))&&+
+            }
+
+            fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+                $(self.$index.is_edge_valid(a, b))&&+
+            }
+
+            fn first_collision(&self, a: &Point<F, N>, b: &Point<F, N>) -> Option<F> {
+                [$(self.$index.first_collision(a, b)),+]
+                    .into_iter()
+                    .flatten()
+                    .reduce(F::min)
+            }
+        }
+    };
+}
+
+impl_tuple_checker!(A: 0, B: 1);
+impl_tuple_checker!(A: 0, B: 1, C: 2);
+impl_tuple_checker!(A: 0, B: 1, C: 2, D: 3);
+
+/// Wraps a validity checker that can only check points, and checks edges by checking evenly spaced points
+/// along them, including both endpoints. Point checks are passed through unchanged.
+///
+/// The number of points is computed from the length of each edge, so consecutive points are never
+/// further apart than the resolution. Obstacles thinner than the resolution can still be missed.
+pub struct DiscretizedEdgeChecker<F: Float, VC> {
+    checker: VC,   // The wrapped validity checker.
+    resolution: F, // The maximum distance between checked points.
+}
+
+impl<F: Float, VC> DiscretizedEdgeChecker<F, VC> {
+    /// Constructs a new discretized edge checker.
+    ///
+    /// Parameters:
+    /// - `checker`: The wrapped validity checker.
+    /// - `resolution`: The maximum distance between checked points along an edge.
+    ///
+    /// Returns:
+    /// The discretized edge checker, or an error if the resolution is not positive and finite.
+    pub fn new(checker: VC, resolution: F) -> Result<Self, &'static str> {
+        if !(resolution > F::zero() && resolution.is_finite()) {
+            return Err("resolution must be positive and finite");
+        }
+        Ok(Self {
+            checker,
+            resolution,
+        })
+    }
+
+    pub fn resolution(&self) -> F {
+        self.resolution
+    }
+
+    pub fn inner(&self) -> &VC {
+        &self.checker
+    }
+}
+
+impl<F: Float, const N: usize, VC: ValidityChecker<F, N>> ValidityChecker<F, N>
+    for DiscretizedEdgeChecker<F, VC>
+{
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        self.checker.is_point_valid(point)
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        interpolated_points_valid(a, b, self.resolution, |point| {
+            self.checker.is_point_valid(point)
+        })
+    }
+}
+
+/// Checks evenly spaced points along the edge from `a` to `b`, including both endpoints,
+/// with consecutive points no further apart than the resolution.
+///
+/// Parameters:
+/// - `a`: The start point of the edge.
+/// - `b`: The end point of the edge.
+/// - `resolution`: The maximum distance between checked points.
+/// - `is_point_valid`: Checks a single point.
+///
+/// Returns:
+/// Whether every checked point is valid.
+fn interpolated_points_valid<F: Float, const N: usize>(
+    a: &Point<F, N>,
+    b: &Point<F, N>,
+    resolution: F,
+    is_point_valid: impl Fn(&Point<F, N>) -> bool,
+) -> bool {
+    let ab = b - a;
+    let steps = (ab.norm() / resolution)
+        .ceil()
+        .to_usize()
+        .unwrap_or(0)
+        .max(1);
+    let steps_float = F::from(steps).unwrap();
+    (0..=steps).all(|step| {
+        let t = F::from(step).unwrap() / steps_float;
+        is_point_valid(&(a + &(ab * t)))
+    })
+}
+
+/// Wraps obstacles that can measure distances, and rejects points and edges that come closer to them
+/// than a minimum clearance, for example to keep a safety margin that grows with the speed of the robot.
+///
+/// Points and edges exactly at the minimum clearance are valid. Edges use `DistanceQuery::edge_clearance`,
+/// so they are checked exactly when the obstacles measure edges exactly, such as spheres and half-spaces.
+pub struct ClearanceChecker<F: Float, D> {
+    obstacles: D,
+    min_clearance: F, // The smallest allowed distance to the obstacles.
+}
+
+impl<F: Float, D> ClearanceChecker<F, D> {
+    /// Constructs a new clearance checker.
+    ///
+    /// Parameters:
+    /// - `obstacles`: The obstacles to keep away from.
+    /// - `min_clearance`: The smallest allowed distance to the obstacles.
+    ///
+    /// Returns:
+    /// The clearance checker, or an error if the minimum clearance is negative or not finite.
+    pub fn new(obstacles: D, min_clearance: F) -> Result<Self, &'static str> {
+        let mut checker = Self {
+            obstacles,
+            min_clearance: F::zero(),
+        };
+        checker.set_min_clearance(min_clearance)?;
+        Ok(checker)
+    }
+
+    /// Sets the smallest allowed distance to the obstacles.
+    ///
+    /// Returns:
+    /// An error if the minimum clearance is negative or not finite, in which case it is unchanged.
+    pub fn set_min_clearance(&mut self, min_clearance: F) -> Result<(), &'static str> {
+        if !(min_clearance >= F::zero() && min_clearance.is_finite()) {
+            return Err("minimum clearance must be non-negative and finite");
+        }
+        self.min_clearance = min_clearance;
+        Ok(())
+    }
+
+    pub fn min_clearance(&self) -> F {
+        self.min_clearance
+    }
+
+    pub fn inner(&self) -> &D {
+        &self.obstacles
+    }
+}
+
+impl<F: Float, const N: usize, D: DistanceQuery<F, N>> ValidityChecker<F, N>
+    for ClearanceChecker<F, D>
+{
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        self.obstacles.clearance(point) >= self.min_clearance
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        self.obstacles.edge_clearance(a, b) >= self.min_clearance
+    }
+}
+
+/// A validity checker for an occupancy grid, such as a 2D map from a SLAM system or a 3D voxel grid.
+///
+/// Cell `c` covers the points from `origin + c * resolution` to `origin + (c + 1) * resolution` in every dimension.
+/// The cells are stored with dimension 0 varying fastest, so a 2D grid is stored row by row with x along each row.
+/// Edges are checked by visiting every cell the edge passes through (Amanatides-Woo traversal),
+/// so even walls that are one cell thick can not be jumped over.
+pub struct OccupancyGrid<F: Float, const N: usize> {
+    occupied: Vec<bool>,       // Whether each cell is occupied.
+    dimensions: [usize; N],    // The number of cells in each dimension.
+    resolution: F,             // The side length of each cell.
+    origin: Point<F, N>,       // The world position of the minimum corner of the grid.
+    out_of_bounds_valid: bool, // Whether points outside the grid are valid.
+}
+
+impl<F: Float, const N: usize> OccupancyGrid<F, N> {
+    /// Constructs a new occupancy grid. Points outside the grid are invalid.
+    ///
+    /// Parameters:
+    /// - `occupied`: Whether each cell is occupied, with dimension 0 varying fastest.
+    /// - `dimensions`: The number of cells in each dimension.
+    /// - `resolution`: The side length of each cell.
+    /// - `origin`: The world position of the minimum corner of the grid.
+    ///
+    /// Returns:
+    /// The occupancy grid, or an error if the number of cells does not match the dimensions
+    /// or the resolution is not positive and finite.
+    pub fn new(
+        occupied: Vec<bool>,
+        dimensions: [usize; N],
+        resolution: F,
+        origin: Point<F, N>,
+    ) -> Result<Self, &'static str> {
+        let cells = dimensions
+            .iter()
+            .try_fold(1usize, |cells, &dimension| cells.checked_mul(dimension));
+        if cells != Some(occupied.len()) {
+            return Err("the number of cells does not match the dimensions");
+        }
+        if !(resolution > F::zero() && resolution.is_finite()) {
+            return Err("resolution must be positive and finite");
+        }
+        Ok(Self {
+            occupied,
+            dimensions,
+            resolution,
+            origin,
+            out_of_bounds_valid: false,
+        })
+    }
+
+    /// Constructs a new occupancy grid from occupancy values, such as the pixels of a map image.
+    /// Points outside the grid are invalid.
+    ///
+    /// Parameters:
+    /// - `values`: The occupancy value of each cell, with dimension 0 varying fastest (row-major for 2D maps).
+    /// - `threshold`: Cells with a value greater than or equal to the threshold are occupied.
+    /// - `dimensions`: The number of cells in each dimension.
+    /// - `resolution`: The side length of each cell.
+    /// - `origin`: The world position of the minimum corner of the grid.
+    ///
+    /// Returns:
+    /// The occupancy grid, or an error if the number of cells does not match the dimensions
+    /// or the resolution is not positive and finite.
+    pub fn from_bytes(
+        values: &[u8],
+        threshold: u8,
+        dimensions: [usize; N],
+        resolution: F,
+        origin: Point<F, N>,
+    ) -> Result<Self, &'static str> {
+        let occupied = values.iter().map(|&value| value >= threshold).collect();
+        Self::new(occupied, dimensions, resolution, origin)
+    }
+
+    /// Sets whether points outside the grid are valid (false by default).
+    pub fn set_out_of_bounds_valid(&mut self, valid: bool) {
+        self.out_of_bounds_valid = valid;
+    }
+
+    pub fn out_of_bounds_valid(&self) -> bool {
+        self.out_of_bounds_valid
+    }
+
+    pub fn dimensions(&self) -> &[usize; N] {
+        &self.dimensions
+    }
+
+    pub fn resolution(&self) -> F {
+        self.resolution
+    }
+
+    pub fn origin(&self) -> &Point<F, N> {
+        &self.origin
+    }
+
+    /// Finds the cell containing a point.
+    ///
+    /// Returns:
+    /// The cell, or None if the point is outside the grid.
+    pub fn world_to_cell(&self, point: &Point<F, N>) -> Option<[usize; N]> {
+        let cell = self.unbounded_cell(point)?;
+        let mut result = [0; N];
+        for i in 0..N {
+            if cell[i] < 0 || cell[i] >= self.dimensions[i] as i64 {
+                return None;
+            }
+            result[i] = cell[i] as usize;
+        }
+        Some(result)
+    }
+
+    /// Checks if a cell is occupied. Cells outside the grid are occupied unless `out_of_bounds_valid` is set.
+    pub fn is_occupied(&self, cell: &[usize; N]) -> bool {
+        self.is_cell_blocked(&cell.map(|c| c as i64))
+    }
+
+    /// Finds the cell containing a point, which may be outside the grid (None if a coordinate is not finite).
+    fn unbounded_cell(&self, point: &Point<F, N>) -> Option<[i64; N]> {
+        let mut cell = [0; N];
+        for i in 0..N {
+            cell[i] = ((point[i] - self.origin[i]) / self.resolution)
+                .floor()
+                .to_i64()?;
+        }
+        Some(cell)
+    }
+
+    /// Checks if a cell, which may be outside the grid, blocks the robot.
+    fn is_cell_blocked(&self, cell: &[i64; N]) -> bool {
+        let mut index = 0;
+        let mut stride = 1;
+        for (&c, &dimension) in cell.iter().zip(self.dimensions.iter()) {
+            if c < 0 || c >= dimension as i64 {
+                return !self.out_of_bounds_valid;
+            }
+            index += c as usize * stride;
+            stride *= dimension;
+        }
+        self.occupied[index]
+    }
+}
+
+impl<F: Float, const N: usize> ValidityChecker<F, N> for OccupancyGrid<F, N> {
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        match self.unbounded_cell(point) {
+            Some(cell) => !self.is_cell_blocked(&cell),
+            None => false,
+        }
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        let (Some(mut cell), Some(end)) = (self.unbounded_cell(a), self.unbounded_cell(b)) else {
+            return false;
+        };
+
+        // For each dimension, find the parameter t along the edge at which it crosses the next cell boundary,
+        // and how much t changes between boundaries.
+        let mut step = [0i64; N];
+        let mut t_next = [F::infinity(); N];
+        let mut t_delta = [F::infinity(); N];
+        for i in 0..N {
+            let direction = b[i] - a[i];
+            if direction > F::zero() {
+                step[i] = 1;
+                let boundary = self.origin[i] + F::from(cell[i] + 1).unwrap() * self.resolution;
+                t_next[i] = (boundary - a[i]) / direction;
+                t_delta[i] = self.resolution / direction;
+            } else if direction < F::zero() {
+                step[i] = -1;
+                let boundary = self.origin[i] + F::from(cell[i]).unwrap() * self.resolution;
+                t_next[i] = (boundary - a[i]) / direction;
+                t_delta[i] = -self.resolution / direction;
+            }
+        }
+
+        // Every step moves one cell closer to the end, which bounds the traversal despite rounding errors.
+        let steps: i64 = (0..N).map(|i| (end[i] - cell[i]).abs()).sum();
+        for _ in 0..=steps {
+            if self.is_cell_blocked(&cell) {
+                return false;
+            }
+            if cell == end {
+                break;
+            }
+            let mut axis = 0;
+            for i in 1..N {
+                if t_next[i] < t_next[axis] {
+                    axis = i;
+                }
+            }
+            if t_next[axis] > F::one() {
+                break;
+            }
+            cell[axis] += step[axis];
+            t_next[axis] = t_next[axis] + t_delta[axis];
+        }
+        true
+    }
+}
+
+/// A validity checker for terrain given as a grid of elevations, for example to plan the path of a UAV in 3D.
+///
+/// Elevation `(x, y)` is the height of the terrain at `origin + (x, y) * cell_size`, and the height between them
+/// is interpolated bilinearly. A point is invalid if it is below the terrain plus the safety margin.
+/// Edges are checked at points no further apart horizontally than a quarter of the cell size,
+/// so the tops of ridges and peaks between the checked points can be clipped by less than the margin.
+pub struct HeightmapChecker<F: Float> {
+    heights: Vec<F>,           // The elevations, row by row with x along each row.
+    dimensions: [usize; 2],    // The number of elevations along x and y.
+    cell_size: F,              // The distance between neighboring elevations.
+    origin: Point<F, 2>,       // The world position of the first elevation.
+    margin: F,                 // The minimum height above the terrain.
+    out_of_bounds_valid: bool, // Whether points outside the grid are valid.
+}
+
+impl<F: Float> HeightmapChecker<F> {
+    /// Constructs a heightmap without a safety margin. Points outside the grid are invalid.
+    ///
+    /// Parameters:
+    /// - `heights`: The elevations, row by row with x along each row.
+    /// - `width`: The number of elevations along x.
+    /// - `height`: The number of elevations along y.
+    /// - `cell_size`: The distance between neighboring elevations.
+    /// - `origin`: The world position of the first elevation.
+    ///
+    /// Returns:
+    /// The heightmap, or an error if there are fewer than two elevations along x or y, the number of elevations
+    /// does not match the dimensions, an elevation is not finite, or the cell size is not positive and finite.
+    pub fn new(
+        heights: Vec<F>,
+        width: usize,
+        height: usize,
+        cell_size: F,
+        origin: Point<F, 2>,
+    ) -> Result<Self, &'static str> {
+        if width < 2 || height < 2 {
+            return Err("the heightmap needs at least two elevations along each axis");
+        }
+        if width.checked_mul(height) != Some(heights.len()) {
+            return Err("the number of elevations does not match the dimensions");
+        }
+        if !heights.iter().all(|elevation| elevation.is_finite()) {
+            return Err("elevations must be finite");
+        }
+        if !(cell_size > F::zero() && cell_size.is_finite()) {
+            return Err("cell size must be positive and finite");
+        }
+        Ok(Self {
+            heights,
+            dimensions: [width, height],
+            cell_size,
+            origin,
+            margin: F::zero(),
+            out_of_bounds_valid: false,
+        })
+    }
+
+    /// Constructs a heightmap from a function of the world position, such as procedurally generated terrain.
+    ///
+    /// Parameters:
+    /// - `width`: The number of elevations along x.
+    /// - `height`: The number of elevations along y.
+    /// - `cell_size`: The distance between neighboring elevations.
+    /// - `origin`: The world position of the first elevation.
+    /// - `f`: Computes the elevation at a world position.
+    ///
+    /// Returns:
+    /// The heightmap, or an error as for `new`.
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        cell_size: F,
+        origin: Point<F, 2>,
+        f: impl Fn(F, F) -> F,
+    ) -> Result<Self, &'static str> {
+        let mut heights = Vec::with_capacity(width.saturating_mul(height));
+        for y in 0..height {
+            for x in 0..width {
+                heights.push(f(
+                    origin[0] + F::from(x).unwrap() * cell_size,
+                    origin[1] + F::from(y).unwrap() * cell_size,
+                ));
+            }
+        }
+        Self::new(heights, width, height, cell_size, origin)
+    }
+
+    /// Sets the minimum height above the terrain.
+    ///
+    /// Returns:
+    /// An error if the margin is negative or not finite, in which case it is unchanged.
+    pub fn set_margin(&mut self, margin: F) -> Result<(), &'static str> {
+        if !(margin >= F::zero() && margin.is_finite()) {
+            return Err("margin must be non-negative and finite");
+        }
+        self.margin = margin;
+        Ok(())
+    }
+
+    pub fn margin(&self) -> F {
+        self.margin
+    }
+
+    /// Sets whether points outside the grid are valid (false by default).
+    pub fn set_out_of_bounds_valid(&mut self, valid: bool) {
+        self.out_of_bounds_valid = valid;
+    }
+
+    pub fn out_of_bounds_valid(&self) -> bool {
+        self.out_of_bounds_valid
+    }
+
+    pub fn cell_size(&self) -> F {
+        self.cell_size
+    }
+
+    /// Computes the height of the terrain at a world position by bilinear interpolation.
+    ///
+    /// Returns:
+    /// The height, or None if the position is outside the grid.
+    pub fn height_at(&self, x: F, y: F) -> Option<F> {
+        let grid_x = (x - self.origin[0]) / self.cell_size;
+        let grid_y = (y - self.origin[1]) / self.cell_size;
+        let max_x = F::from(self.dimensions[0] - 1).unwrap();
+        let max_y = F::from(self.dimensions[1] - 1).unwrap();
+        if !(grid_x >= F::zero() && grid_x <= max_x && grid_y >= F::zero() && grid_y <= max_y) {
+            return None;
+        }
+
+        // The last row and column use the cell before them, so positions on the far edges are interpolated too.
+        let cell_x = grid_x.floor().min(max_x - F::one());
+        let cell_y = grid_y.floor().min(max_y - F::one());
+        let (fx, fy) = (grid_x - cell_x, grid_y - cell_y);
+        let (cx, cy) = (cell_x.to_usize()?, cell_y.to_usize()?);
+        let elevation = |x: usize, y: usize| self.heights[y * self.dimensions[0] + x];
+        let bottom = elevation(cx, cy) * (F::one() - fx) + elevation(cx + 1, cy) * fx;
+        let top = elevation(cx, cy + 1) * (F::one() - fx) + elevation(cx + 1, cy + 1) * fx;
+        Some(bottom * (F::one() - fy) + top * fy)
+    }
+}
+
+impl<F: Float> ValidityChecker<F, 3> for HeightmapChecker<F> {
+    fn is_point_valid(&self, point: &Point<F, 3>) -> bool {
+        match self.height_at(point[0], point[1]) {
+            Some(height) => point[2] >= height + self.margin,
+            None => self.out_of_bounds_valid,
+        }
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, 3>, b: &Point<F, 3>) -> bool {
+        // The spacing only depends on the horizontal length, so steep climbs need no extra checks.
+        let ab = b - a;
+        let horizontal_length = (ab[0] * ab[0] + ab[1] * ab[1]).sqrt();
+        let resolution = self.cell_size / F::from(4).unwrap();
+        let steps = (horizontal_length / resolution)
+            .ceil()
+            .to_usize()
+            .unwrap_or(0)
+            .max(1);
+        let steps_float = F::from(steps).unwrap();
+        (0..=steps).all(|step| {
+            let t = F::from(step).unwrap() / steps_float;
+            self.is_point_valid(&(a + &(ab * t)))
+        })
+    }
+}
+
+/// A convex polytope in any number of dimensions, given as the intersection of half-spaces.
+/// Each half-space is a normal and an offset, and contains the points `p` with `normal · p <= offset`.
+/// Points on the boundary are inside the polytope.
+#[derive(Clone)]
+pub struct ConvexPolytope<F: Float, const N: usize> {
+    constraints: Vec<(Point<F, N>, F)>,
+}
+
+impl<F: Float, const N: usize> ConvexPolytope<F, N> {
+    /// Constructs a convex polytope from its half-spaces.
+    /// The normals do not need to be unit vectors. Without any half-spaces, the polytope is the whole space.
+    ///
+    /// Parameters:
+    /// - `constraints`: The normal and offset of each half-space.
+    pub fn new(constraints: Vec<(Point<F, N>, F)>) -> Self {
+        Self { constraints }
+    }
+
+    /// Constructs an axis-aligned box as a convex polytope.
+    ///
+    /// Parameters:
+    /// - `min`: The minimum corner of the box.
+    /// - `max`: The maximum corner of the box.
+    pub fn from_box(min: &Point<F, N>, max: &Point<F, N>) -> Self {
+        let mut constraints = Vec::with_capacity(2 * N);
+        for i in 0..N {
+            let mut normal = [F::zero(); N];
+            normal[i] = F::one();
+            constraints.push((Point::new(normal), max[i]));
+            normal[i] = -F::one();
+            constraints.push((Point::new(normal), -min[i]));
+        }
+        Self::new(constraints)
+    }
+
+    pub fn constraints(&self) -> &[(Point<F, N>, F)] {
+        &self.constraints
+    }
+
+    /// Checks if a point is inside the polytope or on its boundary.
+    pub fn contains(&self, point: &Point<F, N>) -> bool {
+        self.constraints
+            .iter()
+            .all(|(normal, offset)| normal.dot(point) <= *offset)
+    }
+
+    /// Checks if the line segment from `a` to `b` touches the polytope,
+    /// by clipping the segment against every half-space (Liang-Barsky clipping).
+    ///
+    /// Parameters:
+    /// - `a`: The start point of the segment.
+    /// - `b`: The end point of the segment.
+    ///
+    /// Returns:
+    /// Whether any point of the segment is inside the polytope or on its boundary.
+    pub fn intersects_segment(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        // The segment is a + t * (b - a) for t in [0, 1]. Each half-space keeps the part of the segment
+        // on its inner side, which is an interval of t.
+        let direction = b - a;
+        let mut t_enter = F::zero();
+        let mut t_exit = F::one();
+        for (normal, offset) in &self.constraints {
+            let slack = *offset - normal.dot(a);
+            let rate = normal.dot(&direction);
+            if rate == F::zero() {
+                // The segment is parallel to the boundary, so it is either always or never inside.
+                if slack < F::zero() {
+                    return false;
+                }
+                continue;
+            }
+            let t = slack / rate;
+            if rate > F::zero() {
+                t_exit = t_exit.min(t);
+            } else {
+                t_enter = t_enter.max(t);
+            }
+            if t_enter > t_exit {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A set of convex polytope obstacles with exact point and edge checks.
+pub struct PolytopeObstacleSet<F: Float, const N: usize> {
+    polytopes: Vec<ConvexPolytope<F, N>>,
+}
+
+impl<F: Float, const N: usize> PolytopeObstacleSet<F, N> {
+    pub fn new(polytopes: Vec<ConvexPolytope<F, N>>) -> Self {
+        Self { polytopes }
+    }
+
+    pub fn polytopes(&self) -> &Vec<ConvexPolytope<F, N>> {
+        &self.polytopes
+    }
+}
+
+impl<F: Float, const N: usize> ValidityChecker<F, N> for PolytopeObstacleSet<F, N> {
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        !self
+            .polytopes
+            .iter()
+            .any(|polytope| polytope.contains(point))
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        !self
+            .polytopes
+            .iter()
+            .any(|polytope| polytope.intersects_segment(a, b))
+    }
+}
+
+/// A half-space obstacle, such as the space below a floor or behind a wall plane.
+/// The points `p` with `normal · p > offset` are inside the obstacle, so points on the plane are valid.
+#[derive(Clone)]
+pub struct HalfSpace<F: Float, const N: usize> {
+    normal: Point<F, N>, // The unit normal, pointing into the obstacle.
+    offset: F,           // The distance of the plane from the origin along the normal.
+}
+
+impl<F: Float, const N: usize> HalfSpace<F, N> {
+    /// Constructs a half-space from the normal and offset of its plane.
+    /// The normal does not need to be a unit vector, and the offset is scaled with it.
+    ///
+    /// Parameters:
+    /// - `normal`: The normal of the plane, pointing into the obstacle.
+    /// - `offset`: The value of `normal · p` on the plane.
+    ///
+    /// Returns:
+    /// The half-space, or an error if the normal has zero length or the offset is not finite.
+    pub fn new(normal: Point<F, N>, offset: F) -> Result<Self, &'static str> {
+        let length = normal.norm();
+        if !(length > F::zero() && length.is_finite()) {
+            return Err("normal must have a positive, finite length");
+        }
+        if !offset.is_finite() {
+            return Err("offset must be finite");
+        }
+        Ok(Self {
+            normal: normal / length,
+            offset: offset / length,
+        })
+    }
+
+    /// Constructs a half-space from a point on its plane and its normal.
+    ///
+    /// Parameters:
+    /// - `point`: Any point on the plane.
+    /// - `normal`: The normal of the plane, pointing into the obstacle.
+    ///
+    /// Returns:
+    /// The half-space, or an error if the normal has zero length or the point is not finite.
+    pub fn from_point_normal(
+        point: &Point<F, N>,
+        normal: Point<F, N>,
+    ) -> Result<Self, &'static str> {
+        let offset = normal.dot(point);
+        Self::new(normal, offset)
+    }
+
+    /// Returns the unit normal, pointing into the obstacle.
+    pub fn normal(&self) -> &Point<F, N> {
+        &self.normal
+    }
+
+    /// Returns the offset of the plane along the unit normal.
+    pub fn offset(&self) -> F {
+        self.offset
+    }
+
+    /// Returns the distance of a point into the obstacle, which is negative outside of it.
+    pub fn signed_distance(&self, point: &Point<F, N>) -> F {
+        self.normal.dot(point) - self.offset
+    }
+
+    /// Checks if a point is inside the obstacle. Points on the plane are not.
+    pub fn contains(&self, point: &Point<F, N>) -> bool {
+        self.signed_distance(point) > F::zero()
+    }
+
+    /// Checks if the line segment from `a` to `b` enters the obstacle.
+    /// The signed distance changes linearly along the segment, so the segment enters the obstacle,
+    /// including when it crosses the plane, exactly when one of its endpoints is inside.
+    pub fn intersects_segment(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        self.contains(a) || self.contains(b)
+    }
+}
+
+/// A set of half-space obstacles with exact point and edge checks.
+/// Points on the planes are valid, so a path may slide along a floor or a wall.
+pub struct HalfSpaceSet<F: Float, const N: usize> {
+    half_spaces: Vec<HalfSpace<F, N>>,
+}
+
+impl<F: Float, const N: usize> HalfSpaceSet<F, N> {
+    pub fn new(half_spaces: Vec<HalfSpace<F, N>>) -> Self {
+        Self { half_spaces }
+    }
+
+    pub fn half_spaces(&self) -> &Vec<HalfSpace<F, N>> {
+        &self.half_spaces
+    }
+}
+
+impl<F: Float, const N: usize> ValidityChecker<F, N> for HalfSpaceSet<F, N> {
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        !self
+            .half_spaces
+            .iter()
+            .any(|half_space| half_space.contains(point))
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        !self
+            .half_spaces
+            .iter()
+            .any(|half_space| half_space.intersects_segment(a, b))
+    }
+}
+
+impl<F: Float, const N: usize> DistanceQuery<F, N> for HalfSpaceSet<F, N> {
+    fn clearance(&self, point: &Point<F, N>) -> F {
+        self.half_spaces
+            .iter()
+            .map(|half_space| -half_space.signed_distance(point))
+            .fold(F::infinity(), F::min)
+    }
+
+    /// The distance to a plane changes linearly along the edge, so the closest point is an endpoint.
+    fn edge_clearance(&self, a: &Point<F, N>, b: &Point<F, N>) -> F {
+        self.clearance(a).min(self.clearance(b))
+    }
+}
+
+/// A simple polygon in 2D, which may be non-convex, given by its vertices in order.
+/// The last vertex is connected back to the first.
+///
+/// Points on the boundary are inside the polygon, so an edge that touches the boundary of a polygon,
+/// including a vertex or a collinear overlap with one of its sides, collides with it.
+#[derive(Clone)]
+pub struct Polygon<F: Float> {
+    vertices: Vec<Point<F, 2>>,
+}
+
+impl<F: Float> Polygon<F> {
+    /// Constructs a new polygon.
+    ///
+    /// Parameters:
+    /// - `vertices`: The vertices in order, either clockwise or counterclockwise.
+    ///
+    /// Returns:
+    /// The polygon, or an error if there are fewer than 3 vertices.
+    pub fn new(vertices: Vec<Point<F, 2>>) -> Result<Self, &'static str> {
+        if vertices.len() < 3 {
+            return Err("a polygon needs at least 3 vertices");
+        }
+        Ok(Self { vertices })
+    }
+
+    pub fn vertices(&self) -> &Vec<Point<F, 2>> {
+        &self.vertices
+    }
+
+    /// Returns the sides of the polygon as pairs of consecutive vertices.
+    pub fn sides(&self) -> impl Iterator<Item = (&Point<F, 2>, &Point<F, 2>)> {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+    }
+
+    /// Checks if a point is inside the polygon or on its boundary, using ray casting.
+    pub fn contains(&self, point: &Point<F, 2>) -> bool {
+        let mut inside = false;
+        for (a, b) in self.sides() {
+            if orientation(a, b, point) == F::zero() && within_bounds(a, b, point) {
+                return true;
+            }
+            // Count the sides crossed by a ray from the point in the positive x direction.
+            if (a[1] > point[1]) != (b[1] > point[1]) {
+                let x = a[0] + (point[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0]);
+                if point[0] < x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    /// Checks if the line segment from `a` to `b` touches the polygon.
+    ///
+    /// Parameters:
+    /// - `a`: The start point of the segment.
+    /// - `b`: The end point of the segment.
+    ///
+    /// Returns:
+    /// Whether the segment crosses or touches a side, or lies inside the polygon.
+    pub fn intersects_segment(&self, a: &Point<F, 2>, b: &Point<F, 2>) -> bool {
+        // A segment that does not touch any side is either entirely inside or entirely outside.
+        self.sides().any(|(c, d)| segments_intersect(a, b, c, d)) || self.contains(a)
+    }
+}
+
+/// Computes the cross product of `b - a` and `c - a`, which is positive if `a`, `b`, `c` turn counterclockwise,
+/// negative if they turn clockwise and zero if they are collinear.
+fn orientation<F: Float>(a: &Point<F, 2>, b: &Point<F, 2>, c: &Point<F, 2>) -> F {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Checks if `p` is within the bounding box of the segment from `a` to `b`.
+/// For a point collinear with the segment, this means it is on the segment.
+fn within_bounds<F: Float>(a: &Point<F, 2>, b: &Point<F, 2>, p: &Point<F, 2>) -> bool {
+    p[0] >= a[0].min(b[0])
+        && p[0] <= a[0].max(b[0])
+        && p[1] >= a[1].min(b[1])
+        && p[1] <= a[1].max(b[1])
+}
+
+/// Checks if the segments from `a` to `b` and from `c` to `d` have any point in common,
+/// including touching endpoints and collinear overlaps. The test only uses the signs of cross products,
+/// so it gives the same answer when the segments are swapped or reversed.
+///
+/// Parameters:
+/// - `a`: The start point of the first segment.
+/// - `b`: The end point of the first segment.
+/// - `c`: The start point of the second segment.
+/// - `d`: The end point of the second segment.
+///
+/// Returns:
+/// Whether the segments intersect or touch.
+pub fn segments_intersect<F: Float>(
+    a: &Point<F, 2>,
+    b: &Point<F, 2>,
+    c: &Point<F, 2>,
+    d: &Point<F, 2>,
+) -> bool {
+    let d1 = orientation(c, d, a);
+    let d2 = orientation(c, d, b);
+    let d3 = orientation(a, b, c);
+    let d4 = orientation(a, b, d);
+    let zero = F::zero();
+    if ((d1 > zero && d2 < zero) || (d1 < zero && d2 > zero))
+        && ((d3 > zero && d4 < zero) || (d3 < zero && d4 > zero))
+    {
+        return true;
+    }
+    (d1 == zero && within_bounds(c, d, a))
+        || (d2 == zero && within_bounds(c, d, b))
+        || (d3 == zero && within_bounds(a, b, c))
+        || (d4 == zero && within_bounds(a, b, d))
+}
+
+/// A set of 2D polygon obstacles with exact point and edge checks.
+pub struct PolygonObstacleSet<F: Float> {
+    polygons: Vec<Polygon<F>>,
+}
+
+impl<F: Float> PolygonObstacleSet<F> {
+    pub fn new(polygons: Vec<Polygon<F>>) -> Self {
+        Self { polygons }
+    }
+
+    pub fn polygons(&self) -> &Vec<Polygon<F>> {
+        &self.polygons
+    }
+}
+
+impl<F: Float> ValidityChecker<F, 2> for PolygonObstacleSet<F> {
+    fn is_point_valid(&self, point: &Point<F, 2>) -> bool {
+        !self.polygons.iter().any(|polygon| polygon.contains(point))
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, 2>, b: &Point<F, 2>) -> bool {
+        !self
+            .polygons
+            .iter()
+            .any(|polygon| polygon.intersects_segment(a, b))
+    }
+}
+
+/// A set of 2D wall obstacles, such as the walls of a floor plan, given as line segments.
+///
+/// Each wall can have a thickness, so the points within half the thickness of a wall collide with it.
+/// Without a thickness, only the points on the walls collide with them, and an edge is invalid if it touches
+/// a wall, including at an endpoint of either segment or by overlapping a wall along the same line.
+pub struct SegmentObstacleSet<F: Float> {
+    walls: Vec<(Point<F, 2>, Point<F, 2>)>,
+    thickness: F, // The thickness of every wall.
+}
+
+impl<F: Float> SegmentObstacleSet<F> {
+    /// Constructs a set of walls without thickness.
+    pub fn new(walls: Vec<(Point<F, 2>, Point<F, 2>)>) -> Self {
+        Self {
+            walls,
+            thickness: F::zero(),
+        }
+    }
+
+    /// Constructs a set of walls with a thickness.
+    ///
+    /// Parameters:
+    /// - `walls`: The start and end point of each wall.
+    /// - `thickness`: The thickness of every wall, centered on its segment.
+    ///
+    /// Returns:
+    /// The set of walls, or an error if the thickness is negative or not finite.
+    pub fn with_thickness(
+        walls: Vec<(Point<F, 2>, Point<F, 2>)>,
+        thickness: F,
+    ) -> Result<Self, &'static str> {
+        if !(thickness >= F::zero() && thickness.is_finite()) {
+            return Err("thickness must be non-negative and finite");
+        }
+        Ok(Self { walls, thickness })
+    }
+
+    pub fn walls(&self) -> &Vec<(Point<F, 2>, Point<F, 2>)> {
+        &self.walls
+    }
+
+    pub fn thickness(&self) -> F {
+        self.thickness
+    }
+}
+
+/// Computes the distance between the segments from `a` to `b` and from `c` to `d`.
+/// Segments that do not intersect are closest at an endpoint of one of them.
+fn segment_distance<F: Float>(
+    a: &Point<F, 2>,
+    b: &Point<F, 2>,
+    c: &Point<F, 2>,
+    d: &Point<F, 2>,
+) -> F {
+    if segments_intersect(a, b, c, d) {
+        return F::zero();
+    }
+    [
+        euclidean_distance_squared(a, &closest_point_on_segment(c, d, a)),
+        euclidean_distance_squared(b, &closest_point_on_segment(c, d, b)),
+        euclidean_distance_squared(c, &closest_point_on_segment(a, b, c)),
+        euclidean_distance_squared(d, &closest_point_on_segment(a, b, d)),
+    ]
+    .into_iter()
+    .fold(F::infinity(), F::min)
+    .sqrt()
+}
+
+impl<F: Float> ValidityChecker<F, 2> for SegmentObstacleSet<F> {
+    fn is_point_valid(&self, point: &Point<F, 2>) -> bool {
+        let half_thickness = self.thickness / (F::one() + F::one());
+        self.walls.iter().all(|(start, end)| {
+            let closest = closest_point_on_segment(start, end, point);
+            euclidean_distance_squared(point, &closest).sqrt() > half_thickness
+        })
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, 2>, b: &Point<F, 2>) -> bool {
+        let half_thickness = self.thickness / (F::one() + F::one());
+        self.walls
+            .iter()
+            .all(|(start, end)| segment_distance(a, b, start, end) > half_thickness)
+    }
+}
+
+/// Inverts the point validity of another checker, so the obstacles of the wrapped checker become
+/// the only valid regions. For example, the inverse of a set of spheres keeps the robot inside the spheres.
+///
+/// Inverting the edge checks of the wrapped checker would be wrong, since an edge that leaves the valid region
+/// in the middle still touches an obstacle of the wrapped checker. Edges are instead checked at evenly spaced
+/// points, so consecutive points are never further apart than the resolution.
+pub struct NotChecker<F: Float, VC> {
+    checker: VC,   // The inverted validity checker.
+    resolution: F, // The maximum distance between checked points along an edge.
+}
+
+impl<F: Float, VC> NotChecker<F, VC> {
+    /// Constructs a new inverted checker.
+    ///
+    /// Parameters:
+    /// - `checker`: The validity checker to invert.
+    /// - `resolution`: The maximum distance between checked points along an edge.
+    ///
+    /// Returns:
+    /// The inverted checker, or an error if the resolution is not positive and finite.
+    pub fn new(checker: VC, resolution: F) -> Result<Self, &'static str> {
+        if !(resolution > F::zero() && resolution.is_finite()) {
+            return Err("resolution must be positive and finite");
+        }
+        Ok(Self {
+            checker,
+            resolution,
+        })
+    }
+
+    pub fn resolution(&self) -> F {
+        self.resolution
+    }
+
+    pub fn inner(&self) -> &VC {
+        &self.checker
+    }
+}
+
+impl<F: Float, const N: usize, VC: ValidityChecker<F, N>> ValidityChecker<F, N>
+    for NotChecker<F, VC>
+{
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        !self.checker.is_point_valid(point)
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        interpolated_points_valid(a, b, self.resolution, |point| {
+            !self.checker.is_point_valid(point)
+        })
+    }
+}
+
+/// A convex region of a `RegionChecker`.
+#[derive(Clone)]
+pub enum Region<F: Float, const N: usize> {
+    Sphere(Sphere<F, N>),
+    Aabb(Aabb<F, N>),
+}
+
+impl<F: Float, const N: usize> Region<F, N> {
+    /// Checks if a point is inside the region or on its boundary.
+    pub fn contains(&self, point: &Point<F, N>) -> bool {
+        match self {
+            Region::Sphere(sphere) => {
+                euclidean_distance_squared(point, &sphere.center) <= sphere.radius * sphere.radius
+            }
+            Region::Aabb(aabb) => aabb.contains(point),
+        }
+    }
+}
+
+/// A validity checker that describes where the robot may be instead of where it may not be.
+/// A point is valid if it is inside at least one of the regions, such as a union of corridors.
+///
+/// Edges are checked at evenly spaced points, so consecutive points are never further apart than the resolution.
+/// An edge between two regions that passes outside both of them is rejected as long as the gap is wider
+/// than the resolution.
+pub struct RegionChecker<F: Float, const N: usize> {
+    regions: Vec<Region<F, N>>, // The valid regions.
+    resolution: F,              // The maximum distance between checked points along an edge.
+}
+
+impl<F: Float, const N: usize> RegionChecker<F, N> {
+    /// Constructs a new region checker.
+    ///
+    /// Parameters:
+    /// - `regions`: The valid regions.
+    /// - `resolution`: The maximum distance between checked points along an edge.
+    ///
+    /// Returns:
+    /// The region checker, or an error if the resolution is not positive and finite.
+    pub fn new(regions: Vec<Region<F, N>>, resolution: F) -> Result<Self, &'static str> {
+        if !(resolution > F::zero() && resolution.is_finite()) {
+            return Err("resolution must be positive and finite");
+        }
+        Ok(Self {
+            regions,
+            resolution,
+        })
+    }
+
+    pub fn regions(&self) -> &Vec<Region<F, N>> {
+        &self.regions
+    }
+
+    pub fn resolution(&self) -> F {
+        self.resolution
+    }
+}
+
+impl<F: Float, const N: usize> ValidityChecker<F, N> for RegionChecker<F, N> {
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        self.regions.iter().any(|region| region.contains(point))
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        interpolated_points_valid(a, b, self.resolution, |point| self.is_point_valid(point))
+    }
+}
+
+/// Counts of the queries made to a `ProfiledChecker`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CheckerStatistics {
+    /// The number of point queries, including the ones answered by the cache.
+    pub point_queries: u64,
+    /// The number of edge queries, including first collision queries.
+    pub edge_queries: u64,
+    /// The number of point queries answered by the cache without calling the wrapped checker.
+    pub point_cache_hits: u64,
+}
+
+/// Wraps a validity checker and counts the queries made to it, for example to find out how many checks
+/// a planner or a smoother makes.
+///
+/// Optionally, the answers to point queries are cached in a grid of cells, and a point in a cell that was
+/// already checked gets the answer of the first point checked in that cell without calling the wrapped checker.
+/// This is not exact: a point near an obstacle can take the answer of a point up to a cell diagonal away,
+/// so the cell size should be much smaller than the clearance the planner needs. Edge queries are never cached.
+///
+/// The counters and the cache use interior mutability, and the checker can be shared between threads.
+pub struct ProfiledChecker<F: Float, VC> {
+    checker: VC,
+    point_queries: AtomicU64,
+    edge_queries: AtomicU64,
+    point_cache_hits: AtomicU64,
+    cell_size: Option<F>, // The side length of the cache cells, or None without a cache
+    cache_capacity: usize, // The number of cells after which the cache is cleared
+    point_cache: Mutex<HashMap<Vec<i64>, bool>>, // The answer for each cell that was checked
+}
+
+impl<F: Float, VC> ProfiledChecker<F, VC> {
+    /// Constructs a wrapper that counts queries without caching them.
+    pub fn new(checker: VC) -> Self {
+        Self {
+            checker,
+            point_queries: AtomicU64::new(0),
+            edge_queries: AtomicU64::new(0),
+            point_cache_hits: AtomicU64::new(0),
+            cell_size: None,
+            cache_capacity: 0,
+            point_cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Constructs a wrapper that counts queries and caches the answers to point queries.
+    ///
+    /// Parameters:
+    /// - `checker`: The validity checker to wrap.
+    /// - `cell_size`: The side length of the cells that share an answer.
+    /// - `capacity`: The number of cells to remember. The cache is cleared when it is full.
+    ///
+    /// Returns:
+    /// The wrapper, or an error if the cell size is not positive and finite or the capacity is zero.
+    pub fn with_point_cache(
+        checker: VC,
+        cell_size: F,
+        capacity: usize,
+    ) -> Result<Self, &'static str> {
+        if !(cell_size > F::zero() && cell_size.is_finite()) {
+            return Err("cell size must be positive and finite");
+        }
+        if capacity == 0 {
+            return Err("cache capacity must be positive");
+        }
+        let mut profiled = Self::new(checker);
+        profiled.cell_size = Some(cell_size);
+        profiled.cache_capacity = capacity;
+        Ok(profiled)
+    }
+
+    pub fn inner(&self) -> &VC {
+        &self.checker
+    }
+
+    /// Returns the query counts since the checker was built or the counts were last reset.
+    pub fn stats(&self) -> CheckerStatistics {
+        CheckerStatistics {
+            point_queries: self.point_queries.load(Ordering::Relaxed),
+            edge_queries: self.edge_queries.load(Ordering::Relaxed),
+            point_cache_hits: self.point_cache_hits.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sets all the query counts to zero. The cache is kept.
+    pub fn reset_stats(&self) {
+        self.point_queries.store(0, Ordering::Relaxed);
+        self.edge_queries.store(0, Ordering::Relaxed);
+        self.point_cache_hits.store(0, Ordering::Relaxed);
+    }
+
+    /// Forgets the cached answers, for example after the obstacles have changed.
+    pub fn clear_cache(&self) {
+        self.point_cache.lock().unwrap().clear();
+    }
+}
+
+impl<F: Float, const N: usize, VC: ValidityChecker<F, N>> ValidityChecker<F, N>
+    for ProfiledChecker<F, VC>
+{
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        self.point_queries.fetch_add(1, Ordering::Relaxed);
+        let Some(cell_size) = self.cell_size else {
+            return self.checker.is_point_valid(point);
+        };
+
+        // Points too far away for their cell to fit in an integer are not cached.
+        let Some(cell) = point
+            .coords()
+            .iter()
+            .map(|coord| (*coord / cell_size).floor().to_i64())
+            .collect::<Option<Vec<i64>>>()
+        else {
+            return self.checker.is_point_valid(point);
+        };
+        if let Some(valid) = self.point_cache.lock().unwrap().get(&cell) {
+            self.point_cache_hits.fetch_add(1, Ordering::Relaxed);
+            return *valid;
+        }
+
+        // The lock is not held while the wrapped checker runs, so other threads can use the cache.
+        let valid = self.checker.is_point_valid(point);
+        let mut cache = self.point_cache.lock().unwrap();
+        if cache.len() >= self.cache_capacity {
+            cache.clear();
+        }
+        cache.insert(cell, valid);
+        valid
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        self.edge_queries.fetch_add(1, Ordering::Relaxed);
+        self.checker.is_edge_valid(a, b)
+    }
+
+    fn first_collision(&self, a: &Point<F, N>, b: &Point<F, N>) -> Option<F> {
+        self.edge_queries.fetch_add(1, Ordering::Relaxed);
+        self.checker.first_collision(a, b)
+    }
+}
+
+/// A validity checker for a 2D map image, such as a floor plan, where dark pixels are obstacles.
+///
+/// Pixel `(x, y)` covers the points from `origin + (x, y) * scale` to `origin + (x + 1, y + 1) * scale`,
+/// so the y axis points down the image as it does in the image itself. Points outside the image are invalid.
+/// Edges are checked by visiting every pixel they pass through, as in `OccupancyGrid`,
+/// so walls that are one pixel thick can not be jumped over.
+#[cfg(feature = "image")]
+pub struct BitmapChecker {
+    grid: OccupancyGrid<f32, 2>,
+}
+
+#[cfg(feature = "image")]
+impl BitmapChecker {
+    /// Constructs a checker from a grayscale image.
+    ///
+    /// Parameters:
+    /// - `img`: The map image.
+    /// - `threshold`: Pixels darker than the threshold are obstacles.
+    /// - `scale`: The side length of each pixel in world units, such as meters per pixel.
+    /// - `origin`: The world position of the top left corner of the image.
+    ///
+    /// Returns:
+    /// The checker, or an error if the scale is not positive and finite.
+    pub fn from_image(
+        img: &image::GrayImage,
+        threshold: u8,
+        scale: f32,
+        origin: Point<f32, 2>,
+    ) -> Result<Self, &'static str> {
+        // The pixels are stored row by row with x along each row, as the grid expects.
+        let occupied = img.pixels().map(|pixel| pixel.0[0] < threshold).collect();
+        let dimensions = [img.width() as usize, img.height() as usize];
+        Ok(Self {
+            grid: OccupancyGrid::new(occupied, dimensions, scale, origin)?,
+        })
+    }
+
+    /// Returns the grid of obstacle pixels.
+    pub fn grid(&self) -> &OccupancyGrid<f32, 2> {
+        &self.grid
+    }
+}
+
+#[cfg(feature = "image")]
+impl ValidityChecker<f32, 2> for BitmapChecker {
+    fn is_point_valid(&self, point: &Point<f32, 2>) -> bool {
+        self.grid.is_point_valid(point)
+    }
+
+    fn is_edge_valid(&self, a: &Point<f32, 2>, b: &Point<f32, 2>) -> bool {
+        self.grid.is_edge_valid(a, b)
+    }
+}
+
+/// Splits a stacked state of several robots into the state of each robot.
+/// Robot `i` has the coordinates from `i * D` to `(i + 1) * D` of the stacked state.
+///
+/// Panics if `N` is not a multiple of `D`.
+pub fn unpack_robot_states<F: Float, const N: usize, const D: usize>(
+    point: &Point<F, N>,
+) -> Vec<Point<F, D>> {
+    assert!(
+        D > 0 && N.is_multiple_of(D),
+        "the stacked dimension must be a multiple of the robot dimension"
+    );
+    (0..N / D)
+        .map(|robot| Point::new(std::array::from_fn(|i| point[robot * D + i])))
+        .collect()
+}
+
+/// Stacks the states of several robots into one state, the inverse of `unpack_robot_states`.
+///
+/// Returns:
+/// The stacked state, or an error if the robots do not have `N` coordinates in total.
+pub fn pack_robot_states<F: Float, const N: usize, const D: usize>(
+    robots: &[Point<F, D>],
+) -> Result<Point<F, N>, &'static str> {
+    if robots.len().checked_mul(D) != Some(N) {
+        return Err("the robots do not have as many coordinates as the stacked state");
+    }
+    Ok(Point::new(std::array::from_fn(|i| robots[i / D][i % D])))
+}
+
+/// A validity checker for several robots planned together, whose states of dimension `D` are stacked
+/// into one state of dimension `N`, as in `unpack_robot_states`.
+///
+/// Every robot is checked against the same environment, and every pair of robots must stay further apart
+/// than the separation. Along an edge, all robots move in straight lines at the same time, so the closest
+/// approach of each pair is found exactly from their relative motion. This catches robots that swap places,
+/// whose endpoints are far apart but who meet in the middle of the edge.
+pub struct MultiRobotChecker<F: Float, const N: usize, const D: usize, VC> {
+    environment: VC, // Checks the state of a single robot.
+    separation: F,   // The distance the robots must stay apart.
+}
+
+impl<F: Float, const N: usize, const D: usize, VC> MultiRobotChecker<F, N, D, VC> {
+    /// Constructs a new multi-robot checker.
+    ///
+    /// Parameters:
+    /// - `environment`: Checks the state of a single robot, and is shared by all robots.
+    /// - `separation`: The distance the robots must stay apart, such as twice the radius of each robot.
+    ///
+    /// Returns:
+    /// The checker, or an error if `N` is not a multiple of `D` or the separation is negative or not finite.
+    pub fn new(environment: VC, separation: F) -> Result<Self, &'static str> {
+        if D == 0 || !N.is_multiple_of(D) {
+            return Err("the stacked dimension must be a multiple of the robot dimension");
+        }
+        let mut checker = Self {
+            environment,
+            separation: F::zero(),
+        };
+        checker.set_separation(separation)?;
+        Ok(checker)
+    }
+
+    /// Sets the distance the robots must stay apart.
+    ///
+    /// Returns:
+    /// An error if the separation is negative or not finite, in which case it is unchanged.
+    pub fn set_separation(&mut self, separation: F) -> Result<(), &'static str> {
+        if !(separation >= F::zero() && separation.is_finite()) {
+            return Err("separation must be non-negative and finite");
+        }
+        self.separation = separation;
+        Ok(())
+    }
+
+    pub fn separation(&self) -> F {
+        self.separation
+    }
+
+    /// Returns the number of robots.
+    pub fn robots(&self) -> usize {
+        N / D
+    }
+
+    pub fn inner(&self) -> &VC {
+        &self.environment
+    }
+
+    /// Checks if two robots stay further apart than the separation while moving from `a` to `b`.
+    /// Their offset changes linearly along the edge, so the closest approach is the point of the segment
+    /// between the offsets at the start and the end that is closest to the origin.
+    fn separated(
+        &self,
+        a_i: &Point<F, D>,
+        b_i: &Point<F, D>,
+        a_j: &Point<F, D>,
+        b_j: &Point<F, D>,
+    ) -> bool {
+        let start = a_i - a_j;
+        let end = b_i - b_j;
+        let closest = closest_point_on_segment(&start, &end, &Point::new([F::zero(); D]));
+        closest.norm_squared() > self.separation * self.separation
+    }
+}
+
+impl<F: Float, const N: usize, const D: usize, VC: ValidityChecker<F, D>> ValidityChecker<F, N>
+    for MultiRobotChecker<F, N, D, VC>
+{
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        let robots = unpack_robot_states::<F, N, D>(point);
+        for i in 0..robots.len() {
+            for j in i + 1..robots.len() {
+                if !self.separated(&robots[i], &robots[i], &robots[j], &robots[j]) {
+                    return false;
+                }
+            }
+        }
+        robots
+            .iter()
+            .all(|robot| self.environment.is_point_valid(robot))
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        let starts = unpack_robot_states::<F, N, D>(a);
+        let ends = unpack_robot_states::<F, N, D>(b);
+        for i in 0..starts.len() {
+            for j in i + 1..starts.len() {
+                if !self.separated(&starts[i], &ends[i], &starts[j], &ends[j]) {
+                    return false;
+                }
+            }
+        }
+        starts
+            .iter()
+            .zip(ends.iter())
+            .all(|(start, end)| self.environment.is_edge_valid(start, end))
+    }
+}
+
+/// The cells of the two endpoints of an edge.
+type CellPair = (Vec<i64>, Vec<i64>);
+
+/// Wraps a validity checker and remembers the answers to edge queries, for smoothing methods that check
+/// the same edges many times, such as smoothing a path again every frame.
+///
+/// Each endpoint is rounded to a grid with the given quantum, and an edge gets the answer of the first edge
+/// checked between the same two cells, in either direction. This is not exact: an edge can take the answer of
+/// an edge whose endpoints are up to a cell diagonal away, so the quantum should be much smaller than the
+/// clearance of the path. Point queries are never cached. The cache is cleared when it reaches its capacity.
+pub struct EdgeCache<F: Float, VC> {
+    checker: VC,
+    quantum: F,      // The side length of the cells endpoints are rounded to
+    capacity: usize, // The number of edges after which the cache is cleared
+    edges: Mutex<HashMap<CellPair, bool>>, // The answer for each pair of cells, in sorted order
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<F: Float, VC> EdgeCache<F, VC> {
+    /// Constructs a new edge cache.
+    ///
+    /// Parameters:
+    /// - `checker`: The validity checker to wrap.
+    /// - `quantum`: The side length of the cells that endpoints are rounded to.
+    /// - `capacity`: The number of edges to remember.
+    ///
+    /// Returns:
+    /// The edge cache, or an error if the quantum is not positive and finite or the capacity is zero.
+    pub fn new(checker: VC, quantum: F, capacity: usize) -> Result<Self, &'static str> {
+        if !(quantum > F::zero() && quantum.is_finite()) {
+            return Err("quantum must be positive and finite");
+        }
+        if capacity == 0 {
+            return Err("cache capacity must be positive");
+        }
+        Ok(Self {
+            checker,
+            quantum,
+            capacity,
+            edges: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        })
+    }
+
+    pub fn inner(&self) -> &VC {
+        &self.checker
+    }
+
+    /// Returns the number of edge queries answered by the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of edge queries passed to the wrapped checker.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of edges remembered.
+    pub fn len(&self) -> usize {
+        self.edges.lock().unwrap().len()
+    }
+
+    /// Returns true if no edges are remembered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forgets the remembered edges and resets the counters, for example after the obstacles have changed.
+    pub fn clear(&self) {
+        self.edges.lock().unwrap().clear();
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    /// Finds the cell containing a point, or None if it is too far away for its cell to fit in an integer.
+    fn cell<const N: usize>(&self, point: &Point<F, N>) -> Option<Vec<i64>> {
+        point
+            .coords()
+            .iter()
+            .map(|coord| (*coord / self.quantum).floor().to_i64())
+            .collect()
+    }
+}
+
+impl<F: Float, const N: usize, VC: ValidityChecker<F, N>> ValidityChecker<F, N>
+    for EdgeCache<F, VC>
+{
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        self.checker.is_point_valid(point)
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        let (Some(cell_a), Some(cell_b)) = (self.cell(a), self.cell(b)) else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return self.checker.is_edge_valid(a, b);
+        };
+
+        // Sorting the cells makes the key the same in both directions.
+        let key = if cell_a <= cell_b {
+            (cell_a, cell_b)
+        } else {
+            (cell_b, cell_a)
+        };
+        if let Some(valid) = self.edges.lock().unwrap().get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return *valid;
+        }
+
+        // The lock is not held while the wrapped checker runs, so other threads can use the cache.
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let valid = self.checker.is_edge_valid(a, b);
+        let mut edges = self.edges.lock().unwrap();
+        if edges.len() >= self.capacity {
+            edges.clear();
+        }
+        edges.insert(key, valid);
+        valid
+    }
+}
+
+/// Wraps a validity checker and records the points and edges it rejects, for example to draw where
+/// a planner keeps running into obstacles.
+///
+/// At most `capacity` points and `capacity` edges are recorded, and later rejections are dropped
+/// until the records are cleared.
+pub struct RecordingChecker<F: Float, const N: usize, VC> {
+    checker: VC,
+    capacity: usize, // The largest number of points or edges recorded
+    rejected_points: Mutex<Vec<Point<F, N>>>, // The rejected points, oldest first
+    rejected_edges: Mutex<Vec<(Point<F, N>, Point<F, N>)>>, // The endpoints of the rejected edges, oldest first
+}
+
+impl<F: Float, const N: usize, VC> RecordingChecker<F, N, VC> {
+    /// Constructs a new recording checker.
+    ///
+    /// Parameters:
+    /// - `checker`: The validity checker to wrap.
+    /// - `capacity`: The largest number of points, and of edges, to record.
+    pub fn new(checker: VC, capacity: usize) -> Self {
+        Self {
+            checker,
+            capacity,
+            rejected_points: Mutex::new(Vec::new()),
+            rejected_edges: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn inner(&self) -> &VC {
+        &self.checker
+    }
+
+    /// Returns the rejected points, oldest first.
+    pub fn rejected_points(&self) -> Vec<Point<F, N>> {
+        self.rejected_points.lock().unwrap().clone()
+    }
+
+    /// Returns the endpoints of the rejected edges, oldest first.
+    pub fn rejected_edges(&self) -> Vec<(Point<F, N>, Point<F, N>)> {
+        self.rejected_edges.lock().unwrap().clone()
+    }
+
+    /// Forgets the recorded points and edges.
+    pub fn clear(&self) {
+        self.rejected_points.lock().unwrap().clear();
+        self.rejected_edges.lock().unwrap().clear();
+    }
+}
+
+impl<F: Float, const N: usize, VC: ValidityChecker<F, N>> ValidityChecker<F, N>
+    for RecordingChecker<F, N, VC>
+{
+    fn is_point_valid(&self, point: &Point<F, N>) -> bool {
+        let valid = self.checker.is_point_valid(point);
+        if !valid {
+            let mut rejected = self.rejected_points.lock().unwrap();
+            if rejected.len() < self.capacity {
+                rejected.push(*point);
+            }
+        }
+        valid
+    }
+
+    fn is_edge_valid(&self, a: &Point<F, N>, b: &Point<F, N>) -> bool {
+        let valid = self.checker.is_edge_valid(a, b);
+        if !valid {
+            let mut rejected = self.rejected_edges.lock().unwrap();
+            if rejected.len() < self.capacity {
+                rejected.push((*a, *b));
+            }
+        }
+        valid
+    }
+
+    fn first_collision(&self, a: &Point<F, N>, b: &Point<F, N>) -> Option<F> {
+        self.checker.first_collision(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn multi_robot_swap_is_rejected() {
+        let checker: MultiRobotChecker<f64, 4, 2, _> =
+            MultiRobotChecker::new(EuclideanSphericalObstacleSet::new(Vec::new()), 0.5).unwrap();
+        // The state is [x_a, y_a, x_b, y_b].
+        let before = Point::new([0.0, 0.0, 2.0, 0.0]);
+        let after = Point::new([2.0, 0.0, 0.0, 0.0]);
+        assert!(checker.is_point_valid(&before));
+        assert!(checker.is_point_valid(&after));
+        // Swapping along the same line, the robots meet in the middle.
+        assert!(!checker.is_edge_valid(&before, &after));
+
+        // Swapping with a detour keeps them apart.
+        let detour = Point::new([1.0, 0.0, 1.0, 1.0]);
+        assert!(checker.is_edge_valid(&before, &detour));
+        assert!(checker.is_edge_valid(&detour, &after));
+        assert!(!checker.is_point_valid(&Point::new([1.0, 0.0, 1.0, 0.4])));
+    }
+
+    /// Counts the point queries that reach it. Points with x below 5 are valid.
+    #[derive(Default)]
+    struct CountingChecker {
+        point_queries: AtomicU64,
+    }
+
+    impl ValidityChecker<f64, 2> for CountingChecker {
+        fn is_point_valid(&self, point: &Point<f64, 2>) -> bool {
+            self.point_queries.fetch_add(1, Ordering::Relaxed);
+            point[0] < 5.0
+        }
+
+        fn is_edge_valid(&self, a: &Point<f64, 2>, b: &Point<f64, 2>) -> bool {
+            a[0] < 5.0 && b[0] < 5.0
+        }
+    }
+
+    fn stats(point_queries: u64, edge_queries: u64, point_cache_hits: u64) -> CheckerStatistics {
+        CheckerStatistics {
+            point_queries,
+            edge_queries,
+            point_cache_hits,
+        }
+    }
+
+    #[test]
+    fn profiled_checker_counts_scripted_queries() {
+        let checker = ProfiledChecker::new(CountingChecker::default());
+        let (a, b) = (Point::new([1.0, 1.0]), Point::new([6.0, 1.0]));
+        assert!(checker.is_point_valid(&a));
+        assert!(!checker.is_point_valid(&b));
+        assert!(checker.is_point_valid(&a));
+        // Without a cache, every point query reaches the wrapped checker.
+        assert_eq!(checker.inner().point_queries.load(Ordering::Relaxed), 3);
+        assert!(!checker.is_edge_valid(&a, &b));
+        assert!(checker.is_edge_valid(&a, &a));
+        assert!(checker.first_collision(&a, &b).is_some());
+        // Edges found by `first_collision` count as edge queries.
+        assert_eq!(checker.stats(), stats(3, 3, 0));
+
+        checker.reset_stats();
+        assert_eq!(checker.stats(), stats(0, 0, 0));
+    }
+
+    #[test]
+    fn profiled_checker_caches_points_by_cell() {
+        let checker =
+            ProfiledChecker::with_point_cache(CountingChecker::default(), 1.0, 2).unwrap();
+        let inner_queries = || checker.inner().point_queries.load(Ordering::Relaxed);
+        // The first two points share a cell, and so does the fourth.
+        for point in [[0.2, 0.2], [0.7, 0.4], [1.5, 0.2], [0.1, 0.9]] {
+            checker.is_point_valid(&Point::new(point));
+        }
+        assert_eq!(checker.stats(), stats(4, 0, 2));
+        assert_eq!(inner_queries(), 2);
+
+        // Resetting the counts keeps the cache.
+        checker.reset_stats();
+        checker.is_point_valid(&Point::new([0.3, 0.3]));
+        assert_eq!(checker.stats(), stats(1, 0, 1));
+        assert_eq!(inner_queries(), 2);
+
+        // A third cell fills the cache, which is cleared first.
+        checker.is_point_valid(&Point::new([2.5, 0.5]));
+        checker.is_point_valid(&Point::new([0.3, 0.3]));
+        assert_eq!(checker.stats(), stats(3, 0, 1));
+        assert_eq!(inner_queries(), 4);
+
+        checker.clear_cache();
+        checker.is_point_valid(&Point::new([0.3, 0.3]));
+        assert_eq!(inner_queries(), 5);
+    }
+
+    #[test]
+    fn heightmap_edge_clipping_a_ridge_is_rejected() {
+        // Flat terrain with a ridge of height 5 along x = 5.
+        let mut heightmap =
+            HeightmapChecker::from_fn(11, 3, 1.0, Point::new([0.0, 0.0]), |x, _| {
+                if x == 5.0 {
+                    5.0
+                } else {
+                    0.0
+                }
+            })
+            .unwrap();
+        let a = Point::new([2.0, 1.0, 3.0]);
+        let b = Point::new([8.0, 1.0, 3.0]);
+        assert!(heightmap.is_point_valid(&a));
+        assert!(heightmap.is_point_valid(&b));
+        // Only the crest of the ridge, where the terrain is above 3, blocks the edge.
+        assert!(!heightmap.is_edge_valid(&a, &b));
+        assert!(heightmap.is_edge_valid(&Point::new([2.0, 1.0, 5.5]), &Point::new([8.0, 1.0, 5.5])));
+
+        heightmap.set_margin(1.0).unwrap();
+        assert!(
+            !heightmap.is_edge_valid(&Point::new([2.0, 1.0, 5.5]), &Point::new([8.0, 1.0, 5.5]))
+        );
+    }
+
+    #[test]
+    fn region_checker_rejects_edge_through_gap_between_regions() {
+        let left = Aabb::from_corners(&Point::new([0.0, 0.0]), &Point::new([2.0, 2.0]));
+        let right = Aabb::from_corners(&Point::new([3.0, 0.0]), &Point::new([5.0, 2.0]));
+        let checker =
+            RegionChecker::new(vec![Region::Aabb(left.clone()), Region::Aabb(right)], 0.1).unwrap();
+        let (a, b) = (Point::new([1.0, 1.0]), Point::new([4.0, 1.0]));
+        assert!(checker.is_point_valid(&a));
+        assert!(checker.is_point_valid(&b));
+        assert!(!checker.is_edge_valid(&a, &b));
+        assert!(checker.is_edge_valid(&a, &Point::new([1.9, 0.1])));
+
+        // The same holds for the inverse of obstacles: the gap between the spheres is an obstacle.
+        let spheres = EuclideanSphericalObstacleSet::new(vec![
+            Sphere {
+                center: a,
+                radius: 1.0,
+            },
+            Sphere {
+                center: b,
+                radius: 1.0,
+            },
+        ]);
+        let inverted = NotChecker::new(spheres, 0.1).unwrap();
+        assert!(inverted.is_point_valid(&a));
+        assert!(!inverted.is_edge_valid(&a, &b));
+    }
+
+    #[test]
+    fn occupancy_grid_edges_through_a_one_cell_gap() {
+        // A wall at x in [2, 3] with a gap at y in [2, 3].
+        let occupied = (0..25).map(|cell| cell % 5 == 2 && cell / 5 != 2).collect();
+        let grid = OccupancyGrid::new(occupied, [5, 5], 1.0, Point::new([0.0, 0.0])).unwrap();
+        assert!(grid.is_edge_valid(&Point::new([0.5, 2.5]), &Point::new([4.5, 2.5])));
+        assert!(grid.is_edge_valid(&Point::new([0.5, 1.0]), &Point::new([4.5, 4.0])));
+        // This edge enters the wall just below the gap.
+        assert!(!grid.is_edge_valid(&Point::new([0.5, 0.5]), &Point::new([4.5, 3.5])));
+        // This edge ends in the gap, but clips the corner of the wall cell above it on the way.
+        let (a, b) = (Point::new([1.5, 3.4]), Point::new([2.5, 2.8]));
+        assert!(grid.is_point_valid(&a));
+        assert!(grid.is_point_valid(&b));
+        assert!(!grid.is_edge_valid(&a, &b));
+    }
+
+    fn aabb_set(boxes: &[([f64; 2], [f64; 2])]) -> AabbObstacleSet<f64, 2> {
+        AabbObstacleSet::new(
+            boxes
+                .iter()
+                .map(|(a, b)| Aabb::from_corners(&Point::new(*a), &Point::new(*b)))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn aabb_edges_grazing_a_face_are_invalid() {
+        let boxes = aabb_set(&[([1.0, 1.0], [3.0, 3.0])]);
+        // Along the top face, touching it the whole way.
+        assert!(!boxes.is_edge_valid(&Point::new([0.0, 3.0]), &Point::new([4.0, 3.0])));
+        // Parallel to the top face, just above it.
+        assert!(boxes.is_edge_valid(&Point::new([0.0, 3.001]), &Point::new([4.0, 3.001])));
+        // Ending on the left face.
+        assert!(!boxes.is_edge_valid(&Point::new([0.0, 2.0]), &Point::new([1.0, 2.0])));
+        assert_eq!(
+            boxes.first_collision(&Point::new([0.0, 2.0]), &Point::new([4.0, 2.0])),
+            Some(0.25)
+        );
+    }
+
+    #[test]
+    fn aabb_edges_through_a_corner_are_invalid() {
+        let boxes = aabb_set(&[([1.0, 1.0], [3.0, 3.0])]);
+        // The edge only touches the corner at (3, 3).
+        assert!(!boxes.is_edge_valid(&Point::new([2.0, 4.0]), &Point::new([4.0, 2.0])));
+        assert!(boxes.is_edge_valid(&Point::new([2.01, 4.0]), &Point::new([4.0, 2.01])));
+    }
+
+    #[test]
+    fn aabb_edges_inside_a_box_are_invalid() {
+        let boxes = aabb_set(&[([1.0, 1.0], [3.0, 3.0])]);
+        let (a, b) = (Point::new([1.5, 1.5]), Point::new([2.5, 2.5]));
+        assert!(!boxes.is_point_valid(&a));
+        assert!(!boxes.is_edge_valid(&a, &b));
+        assert_eq!(boxes.first_collision(&a, &b), Some(0.0));
+
+        // A box with no thickness is a wall that can not be crossed.
+        let wall = aabb_set(&[([2.0, 0.0], [2.0, 4.0])]);
+        assert!(!wall.is_edge_valid(&Point::new([1.0, 2.0]), &Point::new([3.0, 2.0])));
+        assert!(wall.is_edge_valid(&Point::new([1.0, 0.0]), &Point::new([1.0, 4.0])));
+    }
+
+    /// Delegates to a sphere set without its exact `first_collision`, so the default bisection is used.
+    struct Bisecting<'a>(&'a EuclideanSphericalObstacleSet<f64, 3>);
+
+    impl ValidityChecker<f64, 3> for Bisecting<'_> {
+        fn is_point_valid(&self, point: &Point<f64, 3>) -> bool {
+            self.0.is_point_valid(point)
+        }
+
+        fn is_edge_valid(&self, a: &Point<f64, 3>, b: &Point<f64, 3>) -> bool {
+            self.0.is_edge_valid(a, b)
+        }
+    }
+
+    #[test]
+    fn exact_sphere_first_collision_matches_bisection() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let point = |rng: &mut StdRng| Point::new([(); 3].map(|_| rng.gen_range(0.0..10.0)));
+        let mut collisions = 0;
+        for _ in 0..20 {
+            let spheres = (0..5)
+                .map(|_| Sphere {
+                    center: point(&mut rng),
+                    radius: rng.gen_range(0.5..2.0),
+                })
+                .collect();
+            let exact = EuclideanSphericalObstacleSet::new(spheres);
+            for _ in 0..50 {
+                let (a, b) = (point(&mut rng), point(&mut rng));
+                match (
+                    exact.first_collision(&a, &b),
+                    Bisecting(&exact).first_collision(&a, &b),
+                ) {
+                    (Some(t), Some(bisected)) => {
+                        assert!((t - bisected).abs() < 1e-9, "{t} != {bisected}");
+                        collisions += 1;
+                    }
+                    (None, None) => {}
+                    (t, bisected) => panic!("{t:?} != {bisected:?}"),
+                }
+            }
+        }
+        assert!(collisions > 100);
+
+        // An edge through the middle of a sphere enters it at its surface.
+        let exact = EuclideanSphericalObstacleSet::new(vec![Sphere {
+            center: Point::new([5.0, 0.0, 0.0]),
+            radius: 1.0,
+        }]);
+        let (a, b) = (Point::new([0.0; 3]), Point::new([10.0, 0.0, 0.0]));
+        assert_eq!(exact.first_collision(&a, &b), Some(0.4));
+        assert!((Bisecting(&exact).first_collision(&a, &b).unwrap() - 0.4).abs() < 1e-12);
+    }
+
+    /// A floor below y = 1 and a wall beyond x = 8.
+    fn floor_and_wall() -> HalfSpaceSet<f64, 2> {
+        HalfSpaceSet::new(vec![
+            HalfSpace::from_point_normal(&Point::new([0.0, 1.0]), Point::new([0.0, -2.0])).unwrap(),
+            HalfSpace::new(Point::new([3.0, 0.0]), 24.0).unwrap(),
+        ])
+    }
+
+    fn edge_valid(checker: &HalfSpaceSet<f64, 2>, a: [f64; 2], b: [f64; 2]) -> bool {
+        checker.is_edge_valid(&Point::new(a), &Point::new(b))
+    }
+
+    #[test]
+    fn half_space_edges_parallel_to_the_plane() {
+        let checker = floor_and_wall();
+        assert!(edge_valid(&checker, [0.0, 2.0], [5.0, 2.0]));
+        assert!(edge_valid(&checker, [0.0, 1.0], [5.0, 1.0]));
+        assert!(!edge_valid(&checker, [0.0, 0.5], [5.0, 0.5]));
+        assert!(edge_valid(&checker, [8.0, 2.0], [8.0, 9.0]));
+        assert!(!edge_valid(&checker, [8.5, 2.0], [8.5, 9.0]));
+    }
+
+    #[test]
+    fn half_space_edges_touching_the_plane_are_valid() {
+        let checker = floor_and_wall();
+        assert!(edge_valid(&checker, [0.0, 3.0], [2.0, 1.0]));
+        assert!(edge_valid(&checker, [2.0, 1.0], [4.0, 3.0]));
+        assert!(edge_valid(&checker, [5.0, 5.0], [8.0, 1.0]));
+        assert!(checker.is_point_valid(&Point::new([3.0, 1.0])));
+    }
+
+    #[test]
+    fn half_space_edges_crossing_the_plane_are_invalid() {
+        let checker = floor_and_wall();
+        assert!(!edge_valid(&checker, [0.0, 3.0], [2.0, -1.0]));
+        assert!(!edge_valid(&checker, [3.0, 0.0], [3.0, 3.0]));
+        assert!(!edge_valid(&checker, [5.0, 5.0], [9.0, 5.0]));
+        assert!(!checker.is_point_valid(&Point::new([3.0, 0.5])));
+    }
+
+    #[test]
+    fn half_space_rejects_zero_normals() {
+        assert_eq!(
+            HalfSpace::new(Point::new([0.0, 0.0]), 1.0).err(),
+            Some("normal must have a positive, finite length")
+        );
+        assert!(
+            HalfSpace::from_point_normal(&Point::new([1.0, 1.0]), Point::new([0.0; 2])).is_err()
+        );
+        assert!(HalfSpace::new(Point::new([0.0, 1.0]), f64::NAN).is_err());
+
+        // Other normals are normalized, with the offset scaled to keep the plane in place.
+        let half_space = HalfSpace::new(Point::new([0.0, 4.0]), 8.0).unwrap();
+        assert_eq!(half_space.normal().coords(), &[0.0, 1.0]);
+        assert_eq!(half_space.offset(), 2.0);
+    }
+
+    #[test]
+    fn composite_rejects_what_any_member_rejects() {
+        let composite = || {
+            CompositeChecker::new()
+                .with(BoundsChecker::new([(0.0, 10.0); 2]).unwrap())
+                .with(EuclideanSphericalObstacleSet::new(vec![Sphere {
+                    center: Point::new([3.0, 5.0]),
+                    radius: 1.0,
+                }]))
+                .with(aabb_set(&[([6.0, 4.0], [8.0, 6.0])]))
+        };
+        let checker = composite();
+        assert_eq!(checker.len(), 3);
+        let point_valid = |x: f64, y: f64| checker.is_point_valid(&Point::new([x, y]));
+        let edge_valid =
+            |a: [f64; 2], b: [f64; 2]| checker.is_edge_valid(&Point::new(a), &Point::new(b));
+
+        // Accepted by every member.
+        assert!(point_valid(1.0, 1.0));
+        assert!(edge_valid([1.0, 1.0], [9.0, 1.0]));
+        // Each member alone rejects the point or edge.
+        assert!(!point_valid(-1.0, 1.0));
+        assert!(!point_valid(3.0, 5.5));
+        assert!(!point_valid(7.0, 5.0));
+        assert!(!edge_valid([1.0, 1.0], [11.0, 1.0]));
+        assert!(!edge_valid([3.0, 2.0], [3.0, 8.0]));
+        assert!(!edge_valid([7.0, 2.0], [7.0, 8.0]));
+
+        // The first collision is the earliest one over the members.
+        let (a, b) = (Point::new([0.0, 5.0]), Point::new([10.0, 5.0]));
+        assert_eq!(checker.first_collision(&a, &b), Some(0.2));
+        assert_eq!(
+            checker.first_collision(&Point::new([9.0, 5.0]), &Point::new([5.0, 5.0])),
+            Some(0.25)
+        );
+
+        // Without members, everything is valid.
+        let empty = CompositeChecker::<f64, 2>::default();
+        assert!(empty.is_empty());
+        assert!(empty.is_point_valid(&Point::new([-100.0, 3.0])));
+        assert!(empty.is_edge_valid(&a, &Point::new([-100.0, 3.0])));
+    }
+
+    #[test]
+    fn discretized_edges_miss_thin_obstacles_only_at_a_coarse_resolution() {
+        let thin = || {
+            EuclideanSphericalObstacleSet::new(vec![Sphere {
+                center: Point::new([5.5, 0.0]),
+                radius: 0.05,
+            }])
+        };
+        let (a, b) = (Point::new([0.0, 0.0]), Point::new([10.0, 0.0]));
+        assert!(!thin().is_edge_valid(&a, &b));
+
+        // The checked points are 1 apart, and the sphere lies between two of them.
+        let coarse = DiscretizedEdgeChecker::new(thin(), 1.0).unwrap();
+        assert!(coarse.is_edge_valid(&a, &b));
+        // The checked points are at most 0.04 apart, so one of them lands inside the sphere.
+        let fine = DiscretizedEdgeChecker::new(thin(), 0.04).unwrap();
+        assert!(!fine.is_edge_valid(&a, &b));
+        // The steps follow the length of the edge, so a short edge over the sphere is also caught.
+        let short = DiscretizedEdgeChecker::new(thin(), 0.09).unwrap();
+        assert!(!short.is_edge_valid(&Point::new([5.41, 0.0]), &Point::new([5.59, 0.0])));
+
+        // Point checks and endpoints are passed through.
+        assert!(!coarse.is_point_valid(&Point::new([5.5, 0.0])));
+        assert!(!coarse.is_edge_valid(&a, &Point::new([5.5, 0.0])));
+        assert!(DiscretizedEdgeChecker::new(thin(), 0.0).is_err());
+    }
+
+    #[test]
+    fn inflation_turns_valid_points_and_edges_invalid() {
+        let sphere = Sphere {
+            center: Point::new([5.0, 5.0]),
+            radius: 1.0,
+        };
+        let mut obstacles =
+            EuclideanSphericalObstacleSet::with_inflation(vec![sphere], 0.0).unwrap();
+        // The point is 1.5 from the center and the edge passes 1.5 from it.
+        let point = Point::new([6.5, 5.0]);
+        let (a, b) = (Point::new([0.0, 6.5]), Point::new([10.0, 6.5]));
+        assert!(obstacles.is_point_valid(&point));
+        assert!(obstacles.is_edge_valid(&a, &b));
+
+        obstacles.set_inflation(0.6).unwrap();
+        assert!(!obstacles.is_point_valid(&point));
+        assert!(!obstacles.is_edge_valid(&a, &b));
+        // The edge enters the inflated sphere where (x - 5)^2 + 1.5^2 = 1.6^2.
+        let t = obstacles.first_collision(&a, &b).unwrap();
+        assert!((t - (5.0 - 0.31f64.sqrt()) / 10.0).abs() < 1e-12, "{t}");
+        // The stored radius is unchanged, and the effective radius includes the margin.
+        assert_eq!(obstacles.spheres()[0].radius, 1.0);
+        assert_eq!(obstacles.effective_radius(&obstacles.spheres()[0]), 1.6);
+        assert_eq!(obstacles.inflated_spheres().next().unwrap().radius, 1.6);
+
+        // A margin just short of the clearance keeps them valid, and an invalid margin is rejected.
+        obstacles.set_inflation(0.4).unwrap();
+        assert!(obstacles.is_point_valid(&point));
+        assert!(obstacles.is_edge_valid(&a, &b));
+        assert!(obstacles.set_inflation(-0.1).is_err());
+        assert_eq!(obstacles.inflation(), 0.4);
+    }
+
+    #[test]
+    fn polytope_edges_entering_and_leaving_a_box_are_invalid() {
+        let obstacles = PolytopeObstacleSet::new(vec![ConvexPolytope::from_box(
+            &Point::new([2.0, 2.0, 2.0]),
+            &Point::new([4.0, 4.0, 4.0]),
+        )]);
+        let edge_valid =
+            |a: [f64; 3], b: [f64; 3]| obstacles.is_edge_valid(&Point::new(a), &Point::new(b));
+
+        // Both endpoints are outside, and the edge passes through the box between them.
+        let (a, b) = (Point::new([0.0, 3.0, 3.0]), Point::new([6.0, 3.0, 3.0]));
+        assert!(obstacles.is_point_valid(&a) && obstacles.is_point_valid(&b));
+        assert!(!obstacles.is_edge_valid(&a, &b));
+        assert!(!edge_valid([0.0, 0.0, 0.0], [6.0, 6.0, 6.0]));
+        assert!(!edge_valid([3.0, 3.0, 3.0], [3.5, 3.5, 3.5]));
+        // The edge stops short of the box, passes beside it, or only touches a face.
+        assert!(edge_valid([0.0, 3.0, 3.0], [1.9, 3.0, 3.0]));
+        assert!(edge_valid([0.0, 4.1, 3.0], [6.0, 4.1, 3.0]));
+        assert!(edge_valid([0.0, 0.0, 3.0], [5.0, 1.9, 3.0]));
+        assert!(!edge_valid([0.0, 4.0, 3.0], [6.0, 4.0, 3.0]));
+
+        // A polytope that is not a box: the triangle below the line x + y = 4 in the positive quadrant.
+        let triangle = PolytopeObstacleSet::new(vec![ConvexPolytope::new(vec![
+            (Point::new([1.0, 1.0]), 4.0),
+            (Point::new([-1.0, 0.0]), 0.0),
+            (Point::new([0.0, -1.0]), 0.0),
+        ])]);
+        let edge_valid =
+            |a: [f64; 2], b: [f64; 2]| triangle.is_edge_valid(&Point::new(a), &Point::new(b));
+        assert!(!edge_valid([-1.0, 1.0], [5.0, 1.0]));
+        assert!(edge_valid([0.0, 5.0], [5.0, 0.1]));
+        assert!(!edge_valid([-1.0, 3.0], [5.0, 0.0]));
+    }
+
+    #[test]
+    fn polygon_edges_around_a_u_shape() {
+        // A U open at the top, with arms at x in [0, 2] and [4, 6] above a base at y in [0, 1].
+        let u_shape = Polygon::new(
+            [
+                [0.0, 0.0],
+                [6.0, 0.0],
+                [6.0, 4.0],
+                [4.0, 4.0],
+                [4.0, 1.0],
+                [2.0, 1.0],
+                [2.0, 4.0],
+                [0.0, 4.0],
+            ]
+            .into_iter()
+            .map(Point::new)
+            .collect(),
+        )
+        .unwrap();
+        let obstacles = PolygonObstacleSet::new(vec![u_shape]);
+        let point_valid = |x: f64, y: f64| obstacles.is_point_valid(&Point::new([x, y]));
+        let edge_valid =
+            |a: [f64; 2], b: [f64; 2]| obstacles.is_edge_valid(&Point::new(a), &Point::new(b));
+
+        // The notch between the arms is outside the polygon.
+        assert!(point_valid(3.0, 3.0));
+        assert!(!point_valid(1.0, 3.0));
+        assert!(!point_valid(5.0, 3.0));
+        assert!(!point_valid(3.0, 0.5));
+
+        // A straight edge across both arms, and one from the notch into an arm.
+        assert!(!edge_valid([-1.0, 3.0], [7.0, 3.0]));
+        assert!(!edge_valid([3.0, 3.0], [5.0, 3.0]));
+        // An edge down into the notch, and one above the polygon.
+        assert!(edge_valid([3.0, 5.0], [3.0, 1.5]));
+        assert!(edge_valid([-1.0, 4.5], [7.0, 4.5]));
+        // An edge entirely inside an arm.
+        assert!(!edge_valid([0.5, 1.0], [1.5, 3.0]));
+        // Touching the boundary collides: along the bottom of the notch, and through the tip of an arm.
+        assert!(!edge_valid([2.5, 1.0], [3.5, 1.0]));
+        assert!(!edge_valid([3.0, 5.0], [5.0, 3.0]));
+        assert!(edge_valid([3.0, 5.0], [3.9, 4.1]));
+    }
+
+    #[test]
+    fn clearance_is_signed_and_edge_clearance_finds_the_tangent() {
+        let spheres = EuclideanSphericalObstacleSet::new(vec![Sphere {
+            center: Point::new([5.0, 5.0]),
+            radius: 2.0,
+        }]);
+        let clearance = |x: f64, y: f64| spheres.clearance(&Point::new([x, y]));
+        let edge_clearance =
+            |a: [f64; 2], b: [f64; 2]| spheres.edge_clearance(&Point::new(a), &Point::new(b));
+        // Negative inside, zero on the surface and positive outside.
+        assert_eq!(clearance(5.0, 5.0), -2.0);
+        assert_eq!(clearance(6.0, 5.0), -1.0);
+        assert_eq!(clearance(7.0, 5.0), 0.0);
+        assert_eq!(clearance(8.0, 9.0), 3.0);
+        // The minimum along an edge is between its endpoints, down to zero on a tangent.
+        assert_eq!(edge_clearance([0.0, 7.0], [10.0, 7.0]), 0.0);
+        assert_eq!(edge_clearance([0.0, 8.0], [10.0, 8.0]), 1.0);
+        assert_eq!(edge_clearance([0.0, 5.0], [10.0, 5.0]), -2.0);
+        assert_eq!(edge_clearance([0.0, 5.0], [2.0, 5.0]), 1.0);
+
+        let boxes = aabb_set(&[([1.0, 1.0], [3.0, 3.0])]);
+        let clearance = |x: f64, y: f64| boxes.clearance(&Point::new([x, y]));
+        let edge_clearance =
+            |a: [f64; 2], b: [f64; 2]| boxes.edge_clearance(&Point::new(a), &Point::new(b));
+        assert_eq!(clearance(2.0, 2.0), -1.0);
+        assert_eq!(clearance(2.5, 2.0), -0.5);
+        assert_eq!(clearance(3.0, 2.0), 0.0);
+        assert_eq!(clearance(5.0, 2.0), 2.0);
+        assert_eq!(clearance(6.0, 7.0), 5.0);
+        // Along the top face, parallel to it, and past the corner.
+        assert_eq!(edge_clearance([0.0, 3.0], [4.0, 3.0]), 0.0);
+        assert_eq!(edge_clearance([0.0, 3.5], [4.0, 3.5]), 0.5);
+        assert!((edge_clearance([3.0, 5.0], [5.0, 3.0]) - 2f64.sqrt()).abs() < 1e-12);
+        assert!(edge_clearance([0.0, 2.0], [4.0, 2.0]) < 0.0);
+    }
+
+    fn sphere_at(x: f64, y: f64, radius: f64) -> Sphere<f64, 2> {
+        Sphere {
+            center: Point::new([x, y]),
+            radius,
+        }
+    }
+
+    /// Checks that the indexed set gives the same answers as the plain set for random points and edges.
+    fn assert_index_matches(
+        plain: &EuclideanSphericalObstacleSet<f64, 2>,
+        indexed: &IndexedSphericalObstacleSet<f64, 2>,
+        rng: &mut StdRng,
+    ) {
+        for _ in 0..500 {
+            let a = Point::new([rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)]);
+            let b = Point::new([rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)]);
+            assert_eq!(indexed.is_point_valid(&a), plain.is_point_valid(&a));
+            assert_eq!(indexed.is_edge_valid(&a, &b), plain.is_edge_valid(&a, &b));
+        }
+    }
+
+    #[test]
+    fn moving_adding_and_removing_spheres_flips_validity() {
+        let spheres = vec![sphere_at(2.0, 8.0, 0.5), sphere_at(7.0, 1.5, 1.0)];
+        let mut plain = EuclideanSphericalObstacleSet::new(spheres.clone());
+        let mut indexed = IndexedSphericalObstacleSet::new(spheres);
+        let mut rng = StdRng::seed_from_u64(0);
+        let point = Point::new([5.1, 5.2]);
+        let (a, b) = (Point::new([0.0, 5.0]), Point::new([10.0, 5.0]));
+        let valid = |checker: &dyn ValidityChecker<f64, 2>| {
+            (
+                checker.is_point_valid(&point),
+                checker.is_edge_valid(&a, &b),
+            )
+        };
+        assert_eq!(valid(&plain), (true, true));
+        assert_eq!(valid(&indexed), (true, true));
+
+        // Moving a sphere onto the point and the edge, and away again.
+        plain.update_center(0, Point::new([5.0, 5.1]));
+        indexed.update_center(0, Point::new([5.0, 5.1]));
+        assert_eq!(valid(&plain), (false, false));
+        assert_eq!(valid(&indexed), (false, false));
+        assert_index_matches(&plain, &indexed, &mut rng);
+        plain.update_center(0, Point::new([2.0, 8.0]));
+        indexed.update_center(0, Point::new([2.0, 8.0]));
+        assert_eq!(valid(&indexed), (true, true));
+        assert_index_matches(&plain, &indexed, &mut rng);
+
+        // A larger sphere that only touches the edge, which the index must find with its new largest radius.
+        plain.add_sphere(sphere_at(8.25, 8.0, 3.0));
+        indexed.add_sphere(sphere_at(8.25, 8.0, 3.0));
+        assert_eq!(valid(&indexed), (true, false));
+        assert_index_matches(&plain, &indexed, &mut rng);
+
+        // Removing the first sphere moves the added one down to index 1, where it can still be moved.
+        assert_eq!(indexed.remove_sphere(0).center.coords(), &[2.0, 8.0]);
+        plain.remove_sphere(0);
+        plain.update_center(1, Point::new([5.4, 5.6]));
+        indexed.update_center(1, Point::new([5.4, 5.6]));
+        assert_eq!(valid(&indexed), (false, false));
+        assert_index_matches(&plain, &indexed, &mut rng);
+
+        plain.clear();
+        indexed.clear();
+        assert_eq!(valid(&indexed), (true, true));
+        assert_index_matches(&plain, &indexed, &mut rng);
+    }
+
+    #[test]
+    fn waiting_while_a_sphere_passes_is_invalid_at_the_crossing() {
+        // A sphere of radius 1 moving along the x axis, which is centered on the origin at time 5.
+        let checker = SpaceTimeSphereChecker::new(vec![MovingSphere {
+            center: Point::new([-5.0, 0.0, 0.0]),
+            velocity: Point::new([1.0, 0.0, 0.0]),
+            radius: 1.0,
+        }])
+        .unwrap();
+        let point_valid = |x: f64, y: f64, t: f64| checker.is_point_valid(&Point::new([x, y, t]));
+        let edge_valid =
+            |a: [f64; 3], b: [f64; 3]| checker.is_edge_valid(&Point::new(a), &Point::new(b));
+
+        // Waiting at the origin is only invalid while the sphere covers it, from time 4 to 6.
+        assert!(point_valid(0.0, 0.0, 2.0));
+        assert!(!point_valid(0.0, 0.0, 5.0));
+        assert!(point_valid(0.0, 0.0, 8.0));
+        assert!(!edge_valid([0.0, 0.0, 0.0], [0.0, 0.0, 10.0]));
+        assert!(edge_valid([0.0, 0.0, 0.0], [0.0, 0.0, 3.9]));
+        assert!(edge_valid([0.0, 0.0, 6.1], [0.0, 0.0, 10.0]));
+        // Waiting beside its path is valid the whole time, and so is stepping aside before it arrives.
+        assert!(edge_valid([0.0, 1.5, 0.0], [0.0, 1.5, 10.0]));
+        assert!(edge_valid([0.0, 0.0, 0.0], [0.0, 1.5, 3.0]));
+
+        // Going backward in time is invalid even between valid points, while an edge in no time is a point.
+        assert!(point_valid(0.0, 0.0, 9.0) && point_valid(0.0, 0.0, 10.0));
+        assert!(!edge_valid([0.0, 0.0, 10.0], [0.0, 0.0, 9.0]));
+        assert!(edge_valid([0.0, 0.0, 2.0], [0.0, 0.5, 2.0]));
+        assert!(!edge_valid([0.0, 0.0, 5.0], [0.0, 0.5, 5.0]));
+    }
+
+    #[test]
+    fn oriented_box_contains_its_rotated_corner() {
+        use std::f64::consts::{FRAC_PI_2, FRAC_PI_6};
+
+        let center = Point::new([5.0, 5.0]);
+        let obb = OrientedBox::from_angle(center, [2.0, 1.0], FRAC_PI_6).unwrap();
+        // The local corner (2, 1) rotated by 30 degrees.
+        let (sin, cos) = FRAC_PI_6.sin_cos();
+        let corner = center + Point::new([2.0 * cos - sin, 2.0 * sin + cos]);
+        let towards = |scale: f64| center + (corner - center) * scale;
+        assert!(obb.contains(&towards(0.999)));
+        assert!(!obb.contains(&towards(1.001)));
+        // The corner of the box before rotation is outside.
+        assert!(!obb.contains(&Point::new([7.0, 6.0])));
+        let obstacles = ObbObstacleSet::new(vec![obb]);
+        assert!(!obstacles.is_edge_valid(&towards(2.0), &towards(0.999)));
+        assert!(obstacles.is_edge_valid(&towards(2.0), &towards(1.001)));
+        assert!(obstacles.is_edge_valid(&Point::new([7.5, 7.0]), &Point::new([7.5, 3.0])));
+
+        // A long box along x, turned a quarter turn about z so that it lies along y.
+        let obb = OrientedBox::from_axis_angle(
+            Point::new([0.0, 0.0, 0.0]),
+            [3.0, 1.0, 1.0],
+            [0.0, 0.0, 2.0],
+            FRAC_PI_2,
+        )
+        .unwrap();
+        assert!(obb.contains(&Point::new([0.99, 2.99, 0.99])));
+        assert!(!obb.contains(&Point::new([2.9, 0.0, 0.0])));
+        assert!(obb.intersects_segment(&Point::new([-2.0, 2.5, 0.0]), &Point::new([2.0, 2.5, 0.0])));
+        assert!(
+            !obb.intersects_segment(&Point::new([2.0, -4.0, 0.0]), &Point::new([2.0, 4.0, 0.0]))
+        );
+    }
+
+    #[test]
+    fn segments_intersect_handles_touching_and_collinear_segments() {
+        let intersect = |a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]| {
+            let (a, b, c, d) = (Point::new(a), Point::new(b), Point::new(c), Point::new(d));
+            let answer = segments_intersect(&a, &b, &c, &d);
+            // The answer is the same with the segments swapped or reversed.
+            assert_eq!(segments_intersect(&c, &d, &a, &b), answer);
+            assert_eq!(segments_intersect(&b, &a, &d, &c), answer);
+            answer
+        };
+        // Crossing, and passing each other.
+        assert!(intersect([0.0, 0.0], [2.0, 2.0], [0.0, 2.0], [2.0, 0.0]));
+        assert!(!intersect([0.0, 0.0], [2.0, 2.0], [3.0, 0.0], [2.1, 1.9]));
+        // A shared endpoint, and an endpoint in the middle of the other segment.
+        assert!(intersect([0.0, 0.0], [1.0, 1.0], [1.0, 1.0], [2.0, 0.0]));
+        assert!(intersect([0.0, 0.0], [2.0, 0.0], [1.0, 0.0], [1.0, 3.0]));
+        // Collinear segments that overlap, touch end to end, or are apart.
+        assert!(intersect([0.0, 0.0], [2.0, 0.0], [1.0, 0.0], [3.0, 0.0]));
+        assert!(intersect([0.0, 0.0], [1.0, 0.0], [1.0, 0.0], [3.0, 0.0]));
+        assert!(!intersect([0.0, 0.0], [1.0, 0.0], [1.5, 0.0], [3.0, 0.0]));
+        // Parallel but on different lines.
+        assert!(!intersect([0.0, 0.0], [2.0, 0.0], [0.0, 0.1], [2.0, 0.1]));
+    }
+
+    #[test]
+    fn walls_block_edges_that_cross_or_touch_them() {
+        let wall = (Point::new([5.0, 0.0]), Point::new([5.0, 6.0]));
+        let thin = SegmentObstacleSet::new(vec![wall]);
+        let edge_valid = |checker: &SegmentObstacleSet<f64>, a: [f64; 2], b: [f64; 2]| {
+            checker.is_edge_valid(&Point::new(a), &Point::new(b))
+        };
+        // Only the points on a thin wall collide with it.
+        assert!(!thin.is_point_valid(&Point::new([5.0, 3.0])));
+        assert!(thin.is_point_valid(&Point::new([5.01, 3.0])));
+        assert!(!edge_valid(&thin, [0.0, 3.0], [10.0, 3.0]));
+        assert!(!edge_valid(&thin, [0.0, 3.0], [5.0, 3.0]));
+        assert!(!edge_valid(&thin, [0.0, 6.0], [10.0, 6.0]));
+        assert!(!edge_valid(&thin, [5.0, 5.0], [5.0, 8.0]));
+        // Around the end of the wall, and alongside it.
+        assert!(edge_valid(&thin, [0.0, 6.1], [10.0, 6.1]));
+        assert!(edge_valid(&thin, [4.9, 0.0], [4.9, 6.0]));
+
+        // A thick wall also blocks the band within half its thickness.
+        let thick = SegmentObstacleSet::with_thickness(vec![wall], 0.4).unwrap();
+        assert!(!thick.is_point_valid(&Point::new([5.15, 3.0])));
+        assert!(!thick.is_point_valid(&Point::new([5.0, 6.15])));
+        assert!(thick.is_point_valid(&Point::new([5.25, 3.0])));
+        assert!(!edge_valid(&thick, [4.9, 0.0], [4.9, 6.0]));
+        assert!(!edge_valid(&thick, [0.0, 6.1], [10.0, 6.1]));
+        assert!(edge_valid(&thick, [4.7, 0.0], [4.7, 6.0]));
+        assert!(edge_valid(&thick, [0.0, 6.3], [10.0, 6.3]));
+        assert!(SegmentObstacleSet::with_thickness(vec![wall], -0.1).is_err());
+    }
+
+    #[test]
+    fn corridor_twice_the_clearance_wide_only_admits_its_center_line() {
+        // A corridor from y = 0 to y = 1 between two walls.
+        let walls = aabb_set(&[([0.0, -5.0], [20.0, 0.0]), ([0.0, 1.0], [20.0, 6.0])]);
+        let mut checker = ClearanceChecker::new(walls, 0.5).unwrap();
+        let edge_valid =
+            |checker: &ClearanceChecker<f64, AabbObstacleSet<f64, 2>>, a: [f64; 2], b: [f64; 2]| {
+                checker.is_edge_valid(&Point::new(a), &Point::new(b))
+            };
+
+        assert!(checker.is_point_valid(&Point::new([5.0, 0.5])));
+        assert!(edge_valid(&checker, [1.0, 0.5], [19.0, 0.5]));
+        // Off-center edges, parallel or slanted, come closer than the clearance to one of the walls.
+        assert!(!checker.is_point_valid(&Point::new([5.0, 0.55])));
+        assert!(!edge_valid(&checker, [1.0, 0.45], [19.0, 0.45]));
+        assert!(!edge_valid(&checker, [1.0, 0.5], [19.0, 0.55]));
+        assert!(!edge_valid(&checker, [1.0, 0.4], [19.0, 0.6]));
+
+        // A smaller margin, for a slower robot, admits the off-center edges again.
+        checker.set_min_clearance(0.3).unwrap();
+        assert!(edge_valid(&checker, [1.0, 0.45], [19.0, 0.45]));
+        assert!(edge_valid(&checker, [1.0, 0.4], [19.0, 0.6]));
+        assert!(!edge_valid(&checker, [1.0, 0.2], [19.0, 0.2]));
+        assert!(checker.set_min_clearance(-1.0).is_err());
+    }
+
+    #[test]
+    fn eccentric_ellipsoids_are_checked_exactly() {
+        // A needle 20 long and 0.02 thick along the x axis, where a bounding sphere would block a radius of 10.
+        let needle = Ellipsoid::new(Point::new([0.0, 0.0]), [10.0, 0.01]).unwrap();
+        let obstacles = EllipsoidObstacleSet::new(vec![needle.clone()]);
+        let point_valid = |x: f64, y: f64| obstacles.is_point_valid(&Point::new([x, y]));
+        let edge_valid =
+            |a: [f64; 2], b: [f64; 2]| obstacles.is_edge_valid(&Point::new(a), &Point::new(b));
+
+        assert!(!point_valid(9.99, 0.0));
+        assert!(!point_valid(0.0, 0.0099));
+        assert!(point_valid(0.0, 0.0101));
+        assert!(point_valid(0.0, 0.5));
+        assert!(point_valid(5.0, 0.009));
+        // Crossing the needle anywhere along it, even close to its tip, and touching it from above.
+        assert!(!edge_valid([5.0, -1.0], [5.0, 1.0]));
+        assert!(!edge_valid([9.999, -1.0], [9.999, 1.0]));
+        assert!(!edge_valid([-1.0, 0.01], [1.0, 0.01]));
+        // Passing just above it, beyond its tip, or along a slant that stays clear.
+        assert!(edge_valid([-1.0, 0.0101], [1.0, 0.0101]));
+        assert!(edge_valid([10.001, -1.0], [10.001, 1.0]));
+        assert!(edge_valid([-10.0, 0.02], [10.0, 0.011]));
+
+        // Turned a quarter turn, the needle lies along the y axis instead.
+        let rotated = needle.with_rotation([[0.0, -1.0], [1.0, 0.0]]).unwrap();
+        assert!(rotated.contains(&Point::new([0.0, 9.99])));
+        assert!(!rotated.contains(&Point::new([9.99, 0.0])));
+        assert!(rotated.intersects_segment(&Point::new([-1.0, -9.0]), &Point::new([1.0, -9.0])));
+        assert!(!rotated.intersects_segment(&Point::new([-1.0, 0.0]), &Point::new([-0.011, 0.0])));
+    }
+
+    fn ray(
+        caster: &impl Raycast<f64, 2>,
+        origin: [f64; 2],
+        direction: [f64; 2],
+        max_range: f64,
+    ) -> Option<f64> {
+        caster.raycast(&Point::new(origin), &Point::new(direction), max_range)
+    }
+
+    #[test]
+    fn sphere_raycasts_from_inside_grazing_and_missing() {
+        let spheres = EuclideanSphericalObstacleSet::new(vec![sphere_at(5.0, 0.0, 1.0)]);
+        assert_eq!(ray(&spheres, [0.0, 0.0], [2.0, 0.0], 10.0), Some(4.0));
+        // Starting inside the sphere.
+        assert_eq!(ray(&spheres, [5.0, 0.5], [1.0, 0.0], 10.0), Some(0.0));
+        // Grazing the top of the sphere at (5, 1).
+        assert_eq!(ray(&spheres, [0.0, 1.0], [1.0, 0.0], 10.0), Some(5.0));
+        // Passing just above it, pointing away from it, or stopping short of it.
+        assert_eq!(ray(&spheres, [0.0, 1.001], [1.0, 0.0], 10.0), None);
+        assert_eq!(ray(&spheres, [0.0, 0.0], [-1.0, 0.0], 10.0), None);
+        assert_eq!(ray(&spheres, [0.0, 0.0], [1.0, 0.0], 3.9), None);
+    }
+
+    #[test]
+    fn aabb_raycasts_from_inside_grazing_and_missing() {
+        let boxes = aabb_set(&[([4.0, -1.0], [6.0, 1.0])]);
+        assert_eq!(ray(&boxes, [0.0, 0.0], [2.0, 0.0], 10.0), Some(4.0));
+        // Starting inside the box.
+        assert_eq!(ray(&boxes, [5.0, 0.5], [1.0, 0.0], 10.0), Some(0.0));
+        // Grazing along the top face, and through the corner at (4, 1).
+        assert_eq!(ray(&boxes, [0.0, 1.0], [1.0, 0.0], 10.0), Some(4.0));
+        let corner = ray(&boxes, [3.0, 2.0], [1.0, -1.0], 10.0).unwrap();
+        assert!((corner - 2f64.sqrt()).abs() < 1e-12, "{corner}");
+        // Passing just above it, pointing away from it, or stopping short of it.
+        assert_eq!(ray(&boxes, [0.0, 1.001], [1.0, 0.0], 10.0), None);
+        assert_eq!(ray(&boxes, [0.0, 0.0], [-1.0, 0.0], 10.0), None);
+        assert_eq!(ray(&boxes, [0.0, 0.0], [1.0, 0.0], 3.9), None);
+    }
+
+    #[test]
+    fn marched_raycasts_from_inside_grazing_and_missing() {
+        let spheres = EuclideanSphericalObstacleSet::new(vec![sphere_at(5.0, 0.0, 1.0)]);
+        let marcher = RayMarcher::new(&spheres, 0.01).unwrap();
+        // The distance is that of the first invalid point, up to the resolution too long.
+        let within_resolution = |distance: Option<f64>, exact: f64| {
+            let distance = distance.unwrap();
+            assert!(distance >= exact && distance <= exact + 0.01, "{distance}");
+        };
+        within_resolution(ray(&marcher, [0.0, 0.0], [2.0, 0.0], 10.0), 4.0);
+        // Starting inside the sphere.
+        assert_eq!(ray(&marcher, [5.0, 0.5], [1.0, 0.0], 10.0), Some(0.0));
+        // Grazing the sphere through a short chord around (5, 0.99).
+        let exact = ray(&spheres, [0.0, 0.99], [1.0, 0.0], 10.0).unwrap();
+        within_resolution(ray(&marcher, [0.0, 0.99], [1.0, 0.0], 10.0), exact);
+        // Passing just above it, pointing away from it, or stopping short of it.
+        assert_eq!(ray(&marcher, [0.0, 1.001], [1.0, 0.0], 10.0), None);
+        assert_eq!(ray(&marcher, [0.0, 0.0], [-1.0, 0.0], 10.0), None);
+        assert_eq!(ray(&marcher, [0.0, 0.0], [1.0, 0.0], 3.9), None);
     }
 }
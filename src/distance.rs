@@ -24,3 +24,99 @@ pub fn euclidean_distance_squared<F: Float, const N: usize>(a: &Point<F, N>, b:
 pub fn euclidean_distance<F: Float, const N: usize>(a: &Point<F, N>, b: &Point<F, N>) -> F {
     (a - b).norm()
 }
+
+/// Compute the total euclidean length of a path.
+///
+/// Parameters:
+/// - `path`: The points along the path.
+///
+/// Returns:
+/// The sum of the euclidean distances between consecutive points.
+/// Paths with less than two points have zero length.
+pub fn path_length<F: Float, const N: usize>(path: &[Point<F, N>]) -> F {
+    path.windows(2).fold(F::zero(), |length, pair| {
+        length + euclidean_distance(&pair[0], &pair[1])
+    })
+}
+
+/// Compute the shortest signed difference between two angles.
+///
+/// Parameters:
+/// - `from`: The first angle in radians.
+/// - `to`: The second angle in radians.
+///
+/// Returns:
+/// The angle in (-π, π] to add to `from` to reach `to`, up to a multiple of 2π.
+pub fn angular_difference<F: Float>(from: F, to: F) -> F {
+    wrap_to_pi(to - from)
+}
+
+/// Wraps an angle in radians to (-π, π], leaving angles already in the range unchanged.
+///
+/// This is the convention for every angle the crate wraps, including the headings of Dubins and Reeds-Shepp poses.
+pub fn wrap_to_pi<F: Float>(angle: F) -> F {
+    let pi = F::from(std::f64::consts::PI).unwrap();
+    if angle > -pi && angle <= pi {
+        return angle;
+    }
+    let two_pi = pi + pi;
+    let wrapped = angle - two_pi * ((angle + pi) / two_pi).floor();
+    // The floor maps odd multiples of π to -π, which belongs to the other end of the range.
+    if wrapped <= -pi {
+        wrapped + two_pi
+    } else {
+        wrapped
+    }
+}
+
+/// Wraps the angular coordinates of a point to (-π, π].
+///
+/// Parameters:
+/// - `point`: The point to normalize.
+/// - `angular`: Which dimensions are angles in radians.
+///
+/// Returns:
+/// The point with its angular coordinates wrapped and its other coordinates unchanged.
+pub fn normalize_angles<F: Float, const N: usize>(
+    point: &Point<F, N>,
+    angular: &[bool; N],
+) -> Point<F, N> {
+    let mut coords = *point.coords();
+    for (coord, &is_angle) in coords.iter_mut().zip(angular) {
+        if is_angle {
+            *coord = wrap_to_pi(*coord);
+        }
+    }
+    Point::new(coords)
+}
+
+/// Compute the weighted distance between two points whose angular dimensions wrap at ±π.
+///
+/// The difference in each angular dimension is the shortest angular difference,
+/// so angles on either side of ±π are close. Differences in other dimensions are unchanged.
+///
+/// Parameters:
+/// - `a`: The first point.
+/// - `b`: The second point.
+/// - `angular`: Which dimensions are angles in radians.
+/// - `weights`: The weight of each dimension, which scales its squared difference.
+///
+/// Returns:
+/// The square root of the weighted sum of the squared differences.
+pub fn weighted_so2_distance<F: Float, const N: usize>(
+    a: &Point<F, N>,
+    b: &Point<F, N>,
+    angular: &[bool; N],
+    weights: &[F; N],
+) -> F {
+    (0..N)
+        .fold(F::zero(), |sum, i| {
+            let difference = if angular[i] {
+                angular_difference(a[i], b[i])
+            } else {
+                b[i] - a[i]
+            };
+            sum + weights[i] * difference * difference
+        })
+        .sqrt()
+}
@@ -0,0 +1,286 @@
+use crate::distance::euclidean_distance_squared;
+use crate::point::Point;
+use num_traits::Float;
+use rand::rngs::ThreadRng;
+use rand::Rng;
+use std::marker::PhantomData;
+
+/// Checks if a point satisfies the goal condition of a planning problem.
+pub trait GoalChecker<F: Float, const N: usize> {
+    /// Checks if a point is in the goal.
+    ///
+    /// Parameters:
+    /// - `point`: The point to check.
+    ///
+    /// Returns:
+    /// Whether the point satisfies the goal condition.
+    fn is_goal(&self, point: &Point<F, N>) -> bool;
+
+    /// Samples a point from the goal, which can be used for goal biasing.
+    ///
+    /// Returns:
+    /// A point in the goal, or None if the goal can not be sampled.
+    fn sample_goal(&mut self) -> Option<Point<F, N>> {
+        None
+    }
+
+    /// Returns which goal a point has reached, for goal checkers made up of several goals.
+    ///
+    /// Parameters:
+    /// - `point`: The point to check.
+    ///
+    /// Returns:
+    /// The index of the reached goal, or None if the point is not in the goal
+    /// or the goal checker does not distinguish between goals.
+    fn goal_index(&self, _point: &Point<F, N>) -> Option<usize> {
+        None
+    }
+
+    /// Measures how far a point is from the goal, which is used to report progress to samplers.
+    ///
+    /// Parameters:
+    /// - `point`: The point to measure.
+    ///
+    /// Returns:
+    /// The distance from the point to the goal, or None if the goal checker can not measure it.
+    fn distance_to_goal(&self, _point: &Point<F, N>) -> Option<F> {
+        None
+    }
+}
+
+/// A goal consisting of a single point and a tolerance around it.
+#[derive(Debug, Clone, Copy)]
+pub struct PointGoal<F: Float, const N: usize> {
+    /// The goal point.
+    goal: Point<F, N>,
+    /// The tolerance for reaching the goal.
+    tolerance: F,
+}
+
+impl<F: Float, const N: usize> PointGoal<F, N> {
+    /// Constructs a new point goal.
+    ///
+    /// Parameters:
+    /// - `goal`: The goal point.
+    /// - `tolerance`: The tolerance for reaching the goal.
+    ///
+    /// Returns:
+    /// The point goal.
+    pub fn new(goal: Point<F, N>, tolerance: F) -> Self {
+        Self { goal, tolerance }
+    }
+
+    pub fn goal(&self) -> &Point<F, N> {
+        &self.goal
+    }
+
+    pub fn tolerance(&self) -> F {
+        self.tolerance
+    }
+}
+
+impl<F: Float, const N: usize> GoalChecker<F, N> for PointGoal<F, N> {
+    fn is_goal(&self, point: &Point<F, N>) -> bool {
+        euclidean_distance_squared(point, &self.goal) <= self.tolerance * self.tolerance
+    }
+
+    fn sample_goal(&mut self) -> Option<Point<F, N>> {
+        Some(self.goal)
+    }
+
+    fn goal_index(&self, point: &Point<F, N>) -> Option<usize> {
+        self.is_goal(point).then_some(0)
+    }
+
+    fn distance_to_goal(&self, point: &Point<F, N>) -> Option<F> {
+        Some(euclidean_distance_squared(point, &self.goal).sqrt())
+    }
+}
+
+/// A goal consisting of several points, any of which is accepted when reached within the tolerance.
+///
+/// The random number generator is used to pick a goal uniformly when sampling the goal.
+/// It defaults to `ThreadRng`.
+pub struct MultiPointGoal<F: Float, const N: usize, R: Rng = ThreadRng> {
+    /// The goal points.
+    goals: Vec<Point<F, N>>,
+    /// The tolerance for reaching any of the goals.
+    tolerance: F,
+    rng: R,
+}
+
+impl<F: Float, const N: usize> MultiPointGoal<F, N> {
+    /// Constructs a new multi-point goal.
+    ///
+    /// Parameters:
+    /// - `goals`: The goal points.
+    /// - `tolerance`: The tolerance for reaching a goal.
+    ///
+    /// Returns:
+    /// The multi-point goal.
+    pub fn new(goals: Vec<Point<F, N>>, tolerance: F) -> Self {
+        Self::new_with_rng(goals, tolerance, rand::thread_rng())
+    }
+}
+
+impl<F: Float, const N: usize, R: Rng> MultiPointGoal<F, N, R> {
+    /// Constructs a new multi-point goal with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `goals`: The goal points.
+    /// - `tolerance`: The tolerance for reaching a goal.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The multi-point goal.
+    pub fn new_with_rng(goals: Vec<Point<F, N>>, tolerance: F, rng: R) -> Self {
+        Self {
+            goals,
+            tolerance,
+            rng,
+        }
+    }
+
+    pub fn goals(&self) -> &[Point<F, N>] {
+        &self.goals
+    }
+
+    pub fn tolerance(&self) -> F {
+        self.tolerance
+    }
+}
+
+impl<F: Float, const N: usize, R: Rng> GoalChecker<F, N> for MultiPointGoal<F, N, R> {
+    fn is_goal(&self, point: &Point<F, N>) -> bool {
+        self.goal_index(point).is_some()
+    }
+
+    fn sample_goal(&mut self) -> Option<Point<F, N>> {
+        if self.goals.is_empty() {
+            return None;
+        }
+        let index = self.rng.gen_range(0..self.goals.len());
+        Some(self.goals[index])
+    }
+
+    /// Returns the closest goal within the tolerance of the point.
+    fn goal_index(&self, point: &Point<F, N>) -> Option<usize> {
+        let tolerance_squared = self.tolerance * self.tolerance;
+        self.goals
+            .iter()
+            .map(|goal| euclidean_distance_squared(point, goal))
+            .enumerate()
+            .filter(|(_, dist_squared)| *dist_squared <= tolerance_squared)
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .map(|(index, _)| index)
+    }
+
+    /// Returns the distance to the closest goal.
+    fn distance_to_goal(&self, point: &Point<F, N>) -> Option<F> {
+        self.goals
+            .iter()
+            .map(|goal| euclidean_distance_squared(point, goal))
+            .min_by(|a, b| a.partial_cmp(b).unwrap())
+            .map(|dist_squared| dist_squared.sqrt())
+    }
+}
+
+/// A goal region defined by an arbitrary predicate.
+///
+/// For example, `GoalRegion::new(|p: &Point<f64, 2>| p[0] > 0.9)` accepts
+/// any point whose first coordinate exceeds 0.9.
+pub struct GoalRegion<F: Float, const N: usize, P>
+where
+    P: Fn(&Point<F, N>) -> bool,
+{
+    predicate: P,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Float, const N: usize, P> GoalRegion<F, N, P>
+where
+    P: Fn(&Point<F, N>) -> bool,
+{
+    /// Constructs a new goal region.
+    ///
+    /// Parameters:
+    /// - `predicate`: Returns true for points inside the goal region.
+    ///
+    /// Returns:
+    /// The goal region.
+    pub fn new(predicate: P) -> Self {
+        Self {
+            predicate,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: Float, const N: usize, P> GoalChecker<F, N> for GoalRegion<F, N, P>
+where
+    P: Fn(&Point<F, N>) -> bool,
+{
+    fn is_goal(&self, point: &Point<F, N>) -> bool {
+        (self.predicate)(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn point_goal_accepts_points_within_the_tolerance() {
+        let goal = PointGoal::new(Point::new([1.0, 1.0]), 0.5);
+        let inside = Point::new([1.0, 1.5]);
+        let outside = Point::new([1.4, 1.4]);
+        assert!(goal.is_goal(&inside));
+        assert!(!goal.is_goal(&outside));
+        assert_eq!(goal.goal_index(&inside), Some(0));
+        assert_eq!(goal.goal_index(&outside), None);
+        assert_eq!(goal.distance_to_goal(&inside), Some(0.5));
+        assert_eq!(goal.distance_to_goal(&Point::new([4.0, 5.0])), Some(5.0));
+    }
+
+    #[test]
+    fn multi_point_goal_reports_the_closest_goal() {
+        let goals = vec![
+            Point::new([0.0, 0.0]),
+            Point::new([1.0, 0.0]),
+            Point::new([5.0, 5.0]),
+        ];
+        let goal = MultiPointGoal::new_with_rng(goals, 0.8, StdRng::seed_from_u64(0));
+
+        // Within the tolerance of the first two goals, but closer to the second.
+        let point = Point::new([0.6, 0.0]);
+        assert!(goal.is_goal(&point));
+        assert_eq!(goal.goal_index(&point), Some(1));
+        assert!((goal.distance_to_goal(&point).unwrap() - 0.4).abs() < 1e-12);
+
+        let point = Point::new([5.0, 4.5]);
+        assert_eq!(goal.goal_index(&point), Some(2));
+        assert_eq!(goal.distance_to_goal(&point), Some(0.5));
+
+        // Closest to the third goal, but outside the tolerance of every goal.
+        let point = Point::new([4.0, 4.0]);
+        assert!(!goal.is_goal(&point));
+        assert_eq!(goal.goal_index(&point), None);
+        assert!((goal.distance_to_goal(&point).unwrap() - 2.0.sqrt()).abs() < 1e-12);
+
+        let empty: MultiPointGoal<f64, 2> = MultiPointGoal::new(Vec::new(), 0.8);
+        assert!(!empty.is_goal(&point));
+        assert_eq!(empty.distance_to_goal(&point), None);
+    }
+
+    #[test]
+    fn goal_region_only_answers_is_goal() {
+        let goal = GoalRegion::new(|point: &Point<f64, 2>| point[0] > 0.9);
+        let inside = Point::new([0.95, 0.0]);
+        assert!(goal.is_goal(&inside));
+        assert!(!goal.is_goal(&Point::new([0.9, 0.0])));
+        assert_eq!(goal.goal_index(&inside), None);
+        assert_eq!(goal.distance_to_goal(&inside), None);
+    }
+}
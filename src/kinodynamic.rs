@@ -0,0 +1,323 @@
+use crate::collision::ValidityChecker;
+use crate::distance::path_length;
+use crate::goal::{GoalChecker, PointGoal};
+use crate::neighbors::NearestNeighbors;
+use crate::planner::Planner;
+use crate::point::Point;
+use crate::rrt::Node;
+use crate::sampling::SamplingDistribution;
+use crate::steering::{KinodynamicSteering, Trajectory};
+use num_traits::Float;
+
+/// A kinodynamic RRT planner for robots whose edges are not straight lines.
+///
+/// Each edge of the tree stores the trajectory produced by the kinodynamic steering function,
+/// and every intermediate state of that trajectory is checked by the validity checker.
+/// The path is the concatenation of the trajectories from the start to the goal.
+///
+/// Template Parameters:
+/// - `F`: The floating-point type.
+/// - `N`: The dimension of the state space.
+/// - `VC`: The validity checker.
+/// - `SD`: The sampling distribution.
+/// - `KS`: The kinodynamic steering function.
+/// - `NN`: The nearest neighbors data structure.
+pub struct KinodynamicRRT<F: Float, const N: usize, VC, SD, KS, NN>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    KS: KinodynamicSteering<F, N>,
+    NN: NearestNeighbors<F, N>,
+{
+    goal: PointGoal<F, N>,
+    /// The nodes in the tree.
+    nodes: Vec<Node<F, N>>,
+    /// The trajectory from the parent of each node to the node (empty for the root).
+    trajectories: Vec<Trajectory<F, N, KS::Control>>,
+    /// Index of the goal node in the tree (None if no solution has been found).
+    solution: Option<usize>,
+    validity_checker: VC,
+    sampling_distribution: SD,
+    steering: KS,
+    nearest_neighbors: NN,
+}
+
+impl<F: Float, const N: usize, VC, SD, KS, NN> KinodynamicRRT<F, N, VC, SD, KS, NN>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    KS: KinodynamicSteering<F, N>,
+    NN: NearestNeighbors<F, N>,
+{
+    /// Constructs a new kinodynamic RRT planner.
+    ///
+    /// Parameters:
+    /// - `start`: The start state.
+    /// - `goal`: The goal state and tolerance.
+    /// - `validity_checker`: Checks if the states and edges between consecutive states are valid.
+    /// - `sampling_distribution`: The sampling distribution.
+    /// - `steering`: The kinodynamic steering function.
+    ///
+    /// Returns the kinodynamic RRT planner.
+    pub fn new(
+        start: Point<F, N>,
+        goal: PointGoal<F, N>,
+        validity_checker: VC,
+        sampling_distribution: SD,
+        steering: KS,
+    ) -> Self {
+        let mut rrt = Self {
+            goal,
+            nodes: Vec::new(),
+            trajectories: Vec::new(),
+            solution: None,
+            validity_checker,
+            sampling_distribution,
+            steering,
+            nearest_neighbors: NN::new(),
+        };
+        let root_trajectory = Trajectory {
+            states: Vec::new(),
+            controls: Vec::new(),
+        };
+        rrt.add_node(Node::new(start, None), root_trajectory);
+        rrt
+    }
+
+    /// Attempts to find a solution within a maximum number of iterations.
+    ///
+    /// Terminates and returns true when a solution is found. Otherwise, returns false.
+    ///
+    /// Parameters:
+    /// - `max_iterations`: The maximum number of iterations.
+    pub fn solve(&mut self, max_iterations: u32) -> bool {
+        for _ in 0..max_iterations {
+            self.iteration();
+            if self.solved() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Returns true if the planner has found a solution.
+    pub fn solved(&self) -> bool {
+        self.solution.is_some()
+    }
+
+    /// Returns the states from the start to the goal, if a solution was found.
+    ///
+    /// This includes every intermediate state of the trajectories, not only the tree nodes.
+    pub fn get_path(&self) -> Option<Vec<Point<F, N>>> {
+        let indices = self.path_indices_to(self.solution?);
+        let mut path = vec![*self.nodes[0].point()];
+        for index in indices {
+            path.extend_from_slice(&self.trajectories[index].states);
+        }
+        Some(path)
+    }
+
+    /// Returns the controls that drive the robot from the start to the goal, if a solution was found.
+    ///
+    /// The i-th control leads from the i-th to the (i + 1)-th state of `get_path`.
+    pub fn get_controls(&self) -> Option<Vec<KS::Control>> {
+        let indices = self.path_indices_to(self.solution?);
+        let mut controls = Vec::new();
+        for index in indices {
+            controls.extend_from_slice(&self.trajectories[index].controls);
+        }
+        Some(controls)
+    }
+
+    /// Returns the trajectory from the parent of the node at `index` to the node (empty for the root).
+    pub fn get_edge_trajectory(&self, index: usize) -> &Trajectory<F, N, KS::Control> {
+        &self.trajectories[index]
+    }
+
+    pub fn get_sampling_distribution(&self) -> &SD {
+        &self.sampling_distribution
+    }
+
+    pub fn get_validity_checker(&self) -> &VC {
+        &self.validity_checker
+    }
+
+    /// Returns the vector of nodes in the tree.
+    pub fn get_tree(&self) -> &Vec<Node<F, N>> {
+        &self.nodes
+    }
+
+    /// Expands the tree by one iteration.
+    ///
+    /// Each iteration of the kinodynamic RRT algorithm consists of the following steps:
+    /// 1. Sample a state from the sampling distribution.
+    /// 2. Find the nearest node in the tree to the sample and steer towards it, producing a trajectory.
+    /// 3. If every state of the trajectory and every edge between consecutive states is valid,
+    ///    add the end of the trajectory as a new node.
+    /// 4. If the new node is in the goal, record it as the solution.
+    fn iteration(&mut self) {
+        let sample = self.sampling_distribution.sample();
+        let Some(nearest_index) = self.nearest_neighbors.nearest_one(&sample) else {
+            return;
+        };
+        let nearest_point = *self.nodes[nearest_index].point();
+        let trajectory = self.steering.steer(&nearest_point, &sample);
+        let Some(&new_point) = trajectory.end() else {
+            return;
+        };
+
+        let mut previous = nearest_point;
+        for state in &trajectory.states {
+            if !self.validity_checker.is_point_valid(state)
+                || !self.validity_checker.is_edge_valid(&previous, state)
+            {
+                return;
+            }
+            previous = *state;
+        }
+
+        let index = self.add_node(Node::new(new_point, Some(nearest_index)), trajectory);
+        if self.goal.is_goal(&new_point) {
+            self.solution = Some(index);
+        }
+    }
+
+    /// Returns the indices of the nodes on the path from the root to the node at `index`, excluding the root.
+    fn path_indices_to(&self, index: usize) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let mut current_index = index;
+        while let Some(parent_index) = self.nodes[current_index].parent() {
+            indices.push(current_index);
+            current_index = parent_index;
+        }
+        indices.reverse();
+        indices
+    }
+
+    /// Adds a node to the tree and the nearest neighbors data structure.
+    /// The cost of the node is the length of the trajectory from the root.
+    fn add_node(
+        &mut self,
+        mut node: Node<F, N>,
+        trajectory: Trajectory<F, N, KS::Control>,
+    ) -> usize {
+        let index = self.nodes.len();
+        self.nearest_neighbors.add(*node.point(), index);
+        if let Some(parent) = node.parent() {
+            let parent_node = &mut self.nodes[parent];
+            parent_node.add_child(index);
+            let mut states = vec![*parent_node.point()];
+            states.extend_from_slice(&trajectory.states);
+            node.set_cost(parent_node.cost() + path_length(&states));
+        }
+        self.nodes.push(node);
+        self.trajectories.push(trajectory);
+        index
+    }
+}
+
+impl<F: Float, const N: usize, VC, SD, KS, NN> Planner<F, N>
+    for KinodynamicRRT<F, N, VC, SD, KS, NN>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    KS: KinodynamicSteering<F, N>,
+    NN: NearestNeighbors<F, N>,
+{
+    fn solve(&mut self, max_iterations: u32) -> bool {
+        KinodynamicRRT::solve(self, max_iterations)
+    }
+
+    fn solved(&self) -> bool {
+        KinodynamicRRT::solved(self)
+    }
+
+    fn get_path(&self) -> Option<Vec<Point<F, N>>> {
+        KinodynamicRRT::get_path(self)
+    }
+
+    fn get_tree_size(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neighbors::KdTreeNearestNeighbors;
+    use crate::sampling::GoalBiasedUniformDistribution;
+    use crate::steering::DoubleIntegratorSteering;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const TIME_STEP: f64 = 0.1;
+
+    /// Keeps the position of an [x, y, vx, vy] state in a 10 x 10 box and out of a disc at (5, 5).
+    struct DiscObstacle;
+
+    impl ValidityChecker<f64, 4> for DiscObstacle {
+        fn is_point_valid(&self, point: &Point<f64, 4>) -> bool {
+            let (x, y) = (point[0], point[1]);
+            (0.0..=10.0).contains(&x) && (0.0..=10.0).contains(&y) && (x - 5.0).hypot(y - 5.0) > 2.0
+        }
+
+        fn is_edge_valid(&self, a: &Point<f64, 4>, b: &Point<f64, 4>) -> bool {
+            // The closest point of the position segment to the center of the disc.
+            let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+            let length_squared = dx * dx + dy * dy;
+            let t = if length_squared > 0.0 {
+                (((5.0 - a[0]) * dx + (5.0 - a[1]) * dy) / length_squared).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            (a[0] + t * dx - 5.0).hypot(a[1] + t * dy - 5.0) > 2.0
+        }
+    }
+
+    #[test]
+    fn double_integrator_trajectories_are_valid_and_follow_the_controls() {
+        let goal = Point::new([9.0, 9.0, 0.0, 0.0]);
+        let mut rrt: KinodynamicRRT<_, 4, _, _, _, KdTreeNearestNeighbors<f64, 4>> =
+            KinodynamicRRT::new(
+                Point::new([1.0, 1.0, 0.0, 0.0]),
+                PointGoal::new(goal, 1.0),
+                DiscObstacle,
+                GoalBiasedUniformDistribution::new_with_rng(
+                    [(0.0, 10.0), (0.0, 10.0), (-2.0, 2.0), (-2.0, 2.0)],
+                    goal,
+                    0.1,
+                    StdRng::seed_from_u64(0),
+                )
+                .unwrap(),
+                DoubleIntegratorSteering::new(TIME_STEP, 10, 2.0).unwrap(),
+            );
+        assert!(rrt.solve(20_000));
+
+        // Every state stored on an edge of the tree was checked on the way in.
+        let checker = rrt.get_validity_checker();
+        for (index, node) in rrt.get_tree().iter().enumerate().skip(1) {
+            let mut previous = *rrt.get_tree()[node.parent().unwrap()].point();
+            for state in &rrt.get_edge_trajectory(index).states {
+                assert!(checker.is_point_valid(state));
+                assert!(checker.is_edge_valid(&previous, state));
+                previous = *state;
+            }
+            assert_eq!(previous.coords(), node.point().coords());
+        }
+
+        // Integrating the controls from the start reproduces each state of the path.
+        let path = rrt.get_path().unwrap();
+        let controls = rrt.get_controls().unwrap();
+        assert_eq!(controls.len(), path.len() - 1);
+        for (i, control) in controls.iter().enumerate() {
+            let (from, to) = (&path[i], &path[i + 1]);
+            for axis in 0..2 {
+                assert!(control[axis].abs() <= 2.0);
+                let velocity = from[axis + 2] + control[axis] * TIME_STEP;
+                assert!((to[axis + 2] - velocity).abs() < 1e-9);
+                assert!((to[axis] - (from[axis] + velocity * TIME_STEP)).abs() < 1e-9);
+            }
+        }
+        assert!(PointGoal::new(goal, 1.0).is_goal(path.last().unwrap()));
+    }
+}
@@ -1,16 +1,33 @@
+pub mod builder;
 pub mod collision;
 pub mod distance;
+pub mod goal;
+pub mod kinodynamic;
 pub mod neighbors;
+pub mod planner;
 pub mod point;
 pub mod rrt;
+pub mod rrt_connect;
 pub mod sampling;
 pub mod smoothing;
+pub mod sst;
 pub mod steering;
+pub mod trrt;
 
 // Re-export symbols in submodules for convenience.
+pub use crate::builder::{BuildError, RRTBuilder};
 pub use crate::collision::ValidityChecker;
+pub use crate::goal::*;
+pub use crate::kinodynamic::KinodynamicRRT;
 pub use crate::neighbors::*;
+pub use crate::planner::Planner;
 pub use crate::point::*;
-pub use crate::rrt::RRT;
+pub use crate::rrt::{
+    ExtendMode, IterationEvent, PlanningError, ProgressReport, RunSummary, SolveOutcome,
+    StallDetection, StallEvent, Statistics, StepResult, TreeData, RRT,
+};
+pub use crate::rrt_connect::RRTConnect;
 pub use crate::sampling::*;
+pub use crate::sst::SST;
 pub use crate::steering::*;
+pub use crate::trrt::{StateCost, TRRTConfig, TRRT};
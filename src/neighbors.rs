@@ -43,6 +43,28 @@ pub trait NearestNeighbors<F: Float, const N: usize> {
     /// The items/indices of the k nearest neighbors.
     fn nearest_k(&self, point: &Point<F, N>, k: usize) -> Vec<usize>;
 
+    /// Gets the nearest neighbor to the given point along with its distance.
+    ///
+    /// Parameters:
+    /// - `point`: The point to find the nearest neighbor to.
+    ///
+    /// Returns:
+    /// The item/index of the nearest neighbor and its euclidean distance to the point, if any.
+    fn nearest_one_with_distance(&self, point: &Point<F, N>) -> Option<(usize, F)> {
+        self.nearest_k_with_distances(point, 1).into_iter().next()
+    }
+
+    /// Gets the k nearest neighbors to the given point along with their distances.
+    ///
+    /// Parameters:
+    /// - `point`: The point to find the nearest neighbors to.
+    /// - `k`: The number of neighbors to find.
+    ///
+    /// Returns:
+    /// The items/indices of the k nearest neighbors and their euclidean distances to the point,
+    /// sorted from nearest to farthest.
+    fn nearest_k_with_distances(&self, point: &Point<F, N>, k: usize) -> Vec<(usize, F)>;
+
     /// Gets all points within a given radius of the given point.
     ///
     /// Parameters:
@@ -82,16 +104,30 @@ impl<F: Float, const N: usize> NearestNeighbors<F, N> for LinearNearestNeighbors
         let mut nearest = self
             .points
             .iter()
-            .map(|(p, i)| (euclidean_distance_squared(&p, &point), *i))
+            .map(|(p, i)| (euclidean_distance_squared(p, point), *i))
             .collect::<Vec<_>>();
         nearest.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
         nearest.into_iter().take(k).map(|(_, i)| i).collect()
     }
 
+    fn nearest_k_with_distances(&self, point: &Point<F, N>, k: usize) -> Vec<(usize, F)> {
+        let mut nearest = self
+            .points
+            .iter()
+            .map(|(p, i)| (*i, euclidean_distance_squared(p, point)))
+            .collect::<Vec<_>>();
+        nearest.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        nearest
+            .into_iter()
+            .take(k)
+            .map(|(i, dist_squared)| (i, dist_squared.sqrt()))
+            .collect()
+    }
+
     fn within_radius(&self, point: &Point<F, N>, radius: F) -> Vec<usize> {
         self.points
             .iter()
-            .filter(|(p, _)| euclidean_distance_squared(&p, &point) <= radius * radius)
+            .filter(|(p, _)| euclidean_distance_squared(p, point) <= radius * radius)
             .map(|(_, i)| *i)
             .collect()
     }
@@ -125,6 +161,14 @@ impl<F: Float + Axis, const N: usize> NearestNeighbors<F, N> for KdTreeNearestNe
             .collect()
     }
 
+    fn nearest_k_with_distances(&self, point: &Point<F, N>, k: usize) -> Vec<(usize, F)> {
+        self.kdtree
+            .nearest_n::<SquaredEuclidean>(point.coords(), k)
+            .iter()
+            .map(|n| (n.item, n.distance.sqrt()))
+            .collect()
+    }
+
     fn within_radius(&self, point: &Point<F, N>, radius: F) -> Vec<usize> {
         self.kdtree
             .within::<SquaredEuclidean>(point.coords(), radius * radius)
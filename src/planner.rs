@@ -0,0 +1,111 @@
+use crate::point::Point;
+use num_traits::Float;
+
+/// A common interface for the planners, so they can be swapped without changing call sites.
+///
+/// The trait is object safe, so planners can also be used as `Box<dyn Planner<F, N>>`.
+pub trait Planner<F: Float, const N: usize> {
+    /// Attempts to find a solution within a maximum number of iterations.
+    ///
+    /// Parameters:
+    /// - `max_iterations`: The maximum number of iterations.
+    ///
+    /// Returns:
+    /// Whether a solution was found.
+    fn solve(&mut self, max_iterations: u32) -> bool;
+
+    /// Returns true if the planner has found a solution.
+    fn solved(&self) -> bool;
+
+    /// Returns the path from the start to the goal, if a solution was found.
+    fn get_path(&self) -> Option<Vec<Point<F, N>>>;
+
+    /// Returns the total number of nodes in the planner's trees.
+    fn get_tree_size(&self) -> usize;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::{EuclideanSphericalObstacleSet, Sphere};
+    use crate::goal::PointGoal;
+    use crate::neighbors::KdTreeNearestNeighbors;
+    use crate::rrt::RRT;
+    use crate::rrt_connect::RRTConnect;
+    use crate::sampling::UniformDistribution;
+    use crate::steering::EuclideanSteering;
+    use crate::trrt::{StateCost, TRRTConfig, TRRT};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// The same cost everywhere, so T-RRT behaves like RRT.
+    struct Flat;
+
+    impl StateCost<f64, 2> for Flat {
+        fn cost(&self, _point: &Point<f64, 2>) -> f64 {
+            0.0
+        }
+    }
+
+    fn obstacles() -> EuclideanSphericalObstacleSet<f64, 2> {
+        EuclideanSphericalObstacleSet::new(vec![Sphere {
+            center: Point::new([5.0, 5.0]),
+            radius: 2.0,
+        }])
+    }
+
+    fn sampling(seed: u64) -> UniformDistribution<f64, 2, StdRng> {
+        UniformDistribution::new_with_rng([(0.0, 10.0); 2], StdRng::seed_from_u64(seed)).unwrap()
+    }
+
+    /// Each planner from (1, 1) to (9, 9), around a sphere in the middle.
+    fn planners(seed: u64) -> Vec<Box<dyn Planner<f64, 2>>> {
+        let start = Point::new([1.0, 1.0]);
+        let goal = Point::new([9.0, 9.0]);
+        let rrt: RRT<_, 2, _, _, _, KdTreeNearestNeighbors<f64, 2>> = RRT::new(
+            start,
+            goal,
+            0.5,
+            obstacles(),
+            sampling(seed),
+            EuclideanSteering::new(0.5),
+        );
+        let rrt_connect: RRTConnect<_, 2, _, _, _, KdTreeNearestNeighbors<f64, 2>> =
+            RRTConnect::new(
+                start,
+                goal,
+                obstacles(),
+                sampling(seed),
+                EuclideanSteering::new(0.5),
+            );
+        let trrt: TRRT<_, 2, _, _, _, KdTreeNearestNeighbors<f64, 2>, _> = TRRT::new(
+            start,
+            PointGoal::new(goal, 0.5),
+            obstacles(),
+            sampling(seed),
+            EuclideanSteering::new(0.5),
+            Flat,
+            TRRTConfig::default(),
+        )
+        .unwrap();
+        vec![Box::new(rrt), Box::new(rrt_connect), Box::new(trrt)]
+    }
+
+    #[test]
+    fn planners_solve_through_trait_objects() {
+        for mut planner in planners(1) {
+            assert!(!planner.solved());
+            assert!(planner.get_path().is_none());
+            // RRT-Connect starts with a tree at each end.
+            assert!(planner.get_tree_size() <= 2);
+
+            assert!(planner.solve(20_000));
+            assert!(planner.solved());
+            let path = planner.get_path().unwrap();
+            assert_eq!(path.first().unwrap().coords(), &[1.0, 1.0]);
+            let end = path.last().unwrap();
+            assert!((end[0] - 9.0).hypot(end[1] - 9.0) <= 0.5);
+            assert!(planner.get_tree_size() >= path.len());
+        }
+    }
+}
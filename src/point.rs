@@ -32,9 +32,7 @@ impl<F: Float, const N: usize> Point<F, N> {
             return Err("Invalid number of coords");
         }
         let mut arr = [F::zero(); N];
-        for i in 0..N {
-            arr[i] = coords[i];
-        }
+        arr.copy_from_slice(&coords);
         Ok(Self { coords: arr })
     }
 
@@ -81,11 +79,7 @@ impl<F: Float, const N: usize> Add for Point<F, N> {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
-        let mut coords = [F::zero(); N];
-        for i in 0..N {
-            coords[i] = self.coords[i] + other.coords[i];
-        }
-        Point::new(coords)
+        Point::new(std::array::from_fn(|i| self.coords[i] + other.coords[i]))
     }
 }
 
@@ -93,11 +87,7 @@ impl<F: Float, const N: usize> Add for &Point<F, N> {
     type Output = Point<F, N>;
 
     fn add(self, other: Self) -> Point<F, N> {
-        let mut coords = [F::zero(); N];
-        for i in 0..N {
-            coords[i] = self.coords[i] + other.coords[i];
-        }
-        Point::new(coords)
+        Point::new(std::array::from_fn(|i| self.coords[i] + other.coords[i]))
     }
 }
 
@@ -105,11 +95,7 @@ impl<F: Float, const N: usize> Sub for Point<F, N> {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self {
-        let mut coords = [F::zero(); N];
-        for i in 0..N {
-            coords[i] = self.coords[i] - other.coords[i];
-        }
-        Point::new(coords)
+        Point::new(std::array::from_fn(|i| self.coords[i] - other.coords[i]))
     }
 }
 
@@ -117,11 +103,7 @@ impl<F: Float, const N: usize> Sub for &Point<F, N> {
     type Output = Point<F, N>;
 
     fn sub(self, other: Self) -> Point<F, N> {
-        let mut coords = [F::zero(); N];
-        for i in 0..N {
-            coords[i] = self.coords[i] - other.coords[i];
-        }
-        Point::new(coords)
+        Point::new(std::array::from_fn(|i| self.coords[i] - other.coords[i]))
     }
 }
 
@@ -129,11 +111,7 @@ impl<F: Float, const N: usize> Mul<F> for Point<F, N> {
     type Output = Self;
 
     fn mul(self, scalar: F) -> Self {
-        let mut coords = [F::zero(); N];
-        for i in 0..N {
-            coords[i] = self.coords[i] * scalar;
-        }
-        Point::new(coords)
+        Point::new(std::array::from_fn(|i| self.coords[i] * scalar))
     }
 }
 
@@ -141,11 +119,7 @@ impl<F: Float, const N: usize> Mul<F> for &Point<F, N> {
     type Output = Point<F, N>;
 
     fn mul(self, scalar: F) -> Point<F, N> {
-        let mut coords = [F::zero(); N];
-        for i in 0..N {
-            coords[i] = self.coords[i] * scalar;
-        }
-        Point::new(coords)
+        Point::new(std::array::from_fn(|i| self.coords[i] * scalar))
     }
 }
 
@@ -153,11 +127,7 @@ impl<F: Float, const N: usize> Div<F> for Point<F, N> {
     type Output = Self;
 
     fn div(self, scalar: F) -> Self {
-        let mut coords = [F::zero(); N];
-        for i in 0..N {
-            coords[i] = self.coords[i] / scalar;
-        }
-        Point::new(coords)
+        Point::new(std::array::from_fn(|i| self.coords[i] / scalar))
     }
 }
 
@@ -165,10 +135,52 @@ impl<F: Float, const N: usize> Div<F> for &Point<F, N> {
     type Output = Point<F, N>;
 
     fn div(self, scalar: F) -> Point<F, N> {
-        let mut coords = [F::zero(); N];
-        for i in 0..N {
-            coords[i] = self.coords[i] / scalar;
+        Point::new(std::array::from_fn(|i| self.coords[i] / scalar))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<F: Float + serde::Serialize, const N: usize> serde::Serialize for Point<F, N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTuple;
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for coord in &self.coords {
+            tuple.serialize_element(coord)?;
+        }
+        tuple.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, F: Float + serde::Deserialize<'de>, const N: usize> serde::Deserialize<'de>
+    for Point<F, N>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PointVisitor<F, const N: usize>(std::marker::PhantomData<F>);
+
+        impl<'de, F: Float + serde::Deserialize<'de>, const N: usize> serde::de::Visitor<'de>
+            for PointVisitor<F, N>
+        {
+            type Value = Point<F, N>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(formatter, "a sequence of {} coordinates", N)
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut coords = [F::zero(); N];
+                for (i, coord) in coords.iter_mut().enumerate() {
+                    *coord = seq
+                        .next_element()?
+                        .ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+                }
+                Ok(Point::new(coords))
+            }
         }
-        Point::new(coords)
+
+        deserializer.deserialize_tuple(N, PointVisitor(std::marker::PhantomData))
     }
 }
@@ -1,18 +1,49 @@
 use crate::collision::ValidityChecker;
-use crate::distance::euclidean_distance_squared;
+use crate::distance::{euclidean_distance, euclidean_distance_squared};
+use crate::goal::{GoalChecker, PointGoal};
 use crate::neighbors::NearestNeighbors;
+use crate::planner::Planner;
 use crate::point::Point;
-use crate::sampling::SamplingDistribution;
+use crate::sampling::{SampleFeedback, SamplingDistribution};
 use crate::steering::Steering;
 use num_traits::Float;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::cmp;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 /// A node in the RRT tree.
+///
+/// Template Parameters:
+/// - `F`: The floating-point type.
+/// - `N`: The dimension of the space.
+/// - `T`: Data attached to the node, such as the control used to reach it (defaults to none).
 #[derive(Clone)]
-pub struct Node<F: Float, const N: usize> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node<F: Float, const N: usize, T = ()> {
     /// The point in N-dimensional space.
     point: Point<F, N>,
     /// The index of the parent node (None if the node is the root).
     parent: Option<usize>,
+    /// The cost-to-come: the euclidean length of the path from the root to this node.
+    /// Computed by the tree when the node is added.
+    cost: F,
+    /// The iteration of the planner in which the node was added (0 for the root).
+    #[cfg_attr(feature = "serde", serde(default))]
+    created_at: u64,
+    /// The indices of the child nodes. Maintained by the tree when nodes are added.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    children: Vec<usize>,
+    /// Whether the edge from the parent was added without being checked, in lazy edge checking mode.
+    #[cfg_attr(feature = "serde", serde(default))]
+    edge_unchecked: bool,
+    /// The data attached to the node.
+    #[cfg_attr(feature = "serde", serde(default))]
+    data: T,
 }
 
 impl<F: Float, const N: usize> Node<F, N> {
@@ -21,7 +52,26 @@ impl<F: Float, const N: usize> Node<F, N> {
     /// - `point`: The point in N-dimensional space.
     /// - `parent`: The index of the parent node (None if the node is the root).
     pub fn new(point: Point<F, N>, parent: Option<usize>) -> Self {
-        Self { point, parent }
+        Self::with_data(point, parent, ())
+    }
+}
+
+impl<F: Float, const N: usize, T> Node<F, N, T> {
+    /// Constructs a new node with attached data.
+    /// Parameters:
+    /// - `point`: The point in N-dimensional space.
+    /// - `parent`: The index of the parent node (None if the node is the root).
+    /// - `data`: The data attached to the node.
+    pub fn with_data(point: Point<F, N>, parent: Option<usize>, data: T) -> Self {
+        Self {
+            point,
+            parent,
+            cost: F::zero(),
+            created_at: 0,
+            children: Vec::new(),
+            edge_unchecked: false,
+            data,
+        }
     }
 
     pub fn point(&self) -> &Point<F, N> {
@@ -31,6 +81,260 @@ impl<F: Float, const N: usize> Node<F, N> {
     pub fn parent(&self) -> Option<usize> {
         self.parent
     }
+
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+
+    /// Returns the euclidean length of the path from the root to this node.
+    pub fn cost(&self) -> F {
+        self.cost
+    }
+
+    /// Sets the cost-to-come of the node, for planners that rewire the tree.
+    pub(crate) fn set_cost(&mut self, cost: F) {
+        self.cost = cost;
+    }
+
+    /// Returns the iteration of the planner in which the node was added (0 for the root).
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    /// Returns false if the edge from the parent was added in lazy edge checking mode
+    /// and has not been checked yet.
+    pub fn is_edge_checked(&self) -> bool {
+        !self.edge_unchecked
+    }
+
+    /// Returns the data attached to the node.
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    /// Returns a mutable reference to the data attached to the node.
+    pub fn data_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+
+    /// Records the node at `index` as a child of this node.
+    pub(crate) fn add_child(&mut self, index: usize) {
+        self.children.push(index);
+    }
+}
+
+/// A snapshot of the nodes of an RRT tree and its solution.
+/// Used to checkpoint a planner and resume it later.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        deserialize = "F: serde::Deserialize<'de>, T: serde::Deserialize<'de> + Default"
+    ))
+)]
+pub struct TreeData<F: Float, const N: usize, T = ()> {
+    /// The nodes in the tree. The node at index 0 is the root of the first start.
+    pub nodes: Vec<Node<F, N, T>>,
+    /// Index of the solution node (None if no solution has been found).
+    pub solution: Option<usize>,
+    /// Whether the tree is a forest with a root for each of several starts (see `RRT::with_starts`).
+    /// If false, node 0 must be the only root. Data saved without this field is read as a single tree.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub forest: bool,
+}
+
+/// Errors that can occur when setting up a planning problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanningError {
+    /// The start point is not valid according to the validity checker.
+    InvalidStart,
+    /// The goal point is not valid according to the validity checker.
+    InvalidGoal,
+}
+
+impl fmt::Display for PlanningError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            PlanningError::InvalidStart => "start point is not valid",
+            PlanningError::InvalidGoal => "goal point is not valid",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for PlanningError {}
+
+/// Counters describing the behavior of the planner.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Statistics {
+    /// The number of iterations run.
+    pub iterations: u64,
+    /// The number of samples rejected because the steered point was invalid.
+    pub point_rejections: u64,
+    /// The number of samples rejected because the edge to the steered point was invalid.
+    pub edge_rejections: u64,
+    /// The number of samples rejected because the steered point was closer than the minimum node separation to the tree.
+    pub separation_rejections: u64,
+    /// The number of nodes added to the tree (not counting the root).
+    /// Nodes removed because a lazily checked edge to them was invalid are not counted.
+    pub nodes_added: u64,
+    /// The iteration in which the first solution was found (None if no solution has been found).
+    pub first_solution_iteration: Option<u64>,
+    /// The number of times the tree stalled (see `RRT::set_stall_detection`).
+    pub stalls: u64,
+}
+
+impl Statistics {
+    /// Returns the total number of samples rejected for any reason.
+    pub fn samples_rejected(&self) -> u64 {
+        self.point_rejections + self.edge_rejections + self.separation_rejections
+    }
+}
+
+/// The reason a solve terminated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveOutcome {
+    /// A solution was found.
+    Solved {
+        /// The number of iterations run by the solve.
+        iterations: u32,
+    },
+    /// The iteration (or time) budget was exhausted without finding a solution.
+    IterationLimit,
+    /// The tree reached the maximum number of nodes without finding a solution.
+    NodeLimit,
+    /// The solve was cancelled before finding a solution.
+    Cancelled {
+        /// The number of iterations run before the cancellation.
+        iterations: u32,
+    },
+}
+
+impl SolveOutcome {
+    /// Returns true if the solve found a solution.
+    pub fn is_solved(&self) -> bool {
+        matches!(self, SolveOutcome::Solved { .. })
+    }
+}
+
+/// A summary of a call to `RRT::run_iterations`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunSummary {
+    /// How the run terminated.
+    pub outcome: SolveOutcome,
+    /// The number of iterations run.
+    pub iterations: u32,
+    /// The number of nodes added to the tree during the run.
+    pub nodes_added: u64,
+    /// The number of samples rejected during the run (see `Statistics::samples_rejected`).
+    pub samples_rejected: u64,
+}
+
+/// A report on the progress of a solve, passed to the progress handler of the planner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressReport<F: Float> {
+    /// The number of iterations completed since the solve started.
+    pub iterations: u32,
+    /// The number of nodes in the tree.
+    pub node_count: usize,
+    /// The smallest distance from any node to the goal (None if the goal checker can not measure it).
+    pub best_goal_distance: Option<F>,
+    /// The time elapsed since the solve started.
+    pub elapsed: Duration,
+}
+
+/// Configuration of the detection of a stalled tree, which adds no nodes for many iterations in a row.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StallDetection<F: Float> {
+    /// The number of consecutive iterations without a new node after which the tree has stalled.
+    pub max_failed_iterations: u32,
+    /// If set, the ranges of the sampling distribution are widened by this factor when the tree stalls
+    /// (see `SamplingDistribution::expand_ranges`).
+    pub expand_factor: Option<F>,
+}
+
+/// Describes a stall of the tree, passed to the stall handler of the planner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StallEvent {
+    /// The iteration in which the stall was detected.
+    pub iteration: u64,
+    /// The number of consecutive iterations that added no node.
+    pub failed_iterations: u32,
+    /// Whether the ranges of the sampling distribution were widened.
+    pub ranges_expanded: bool,
+}
+
+/// How far the tree is extended towards each sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExtendMode {
+    /// Take a single steering step towards the sample.
+    #[default]
+    Single,
+    /// Keep steering towards the sample until it is reached or an extension is invalid.
+    /// Each intermediate point is added as a node.
+    Greedy,
+}
+
+/// The result of a single step of the RRT, following the classic EXTEND semantics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepResult {
+    /// No node was added because the steered point or edge was invalid.
+    Trapped,
+    /// A new node was added to the tree.
+    Advanced { node_index: usize },
+    /// A new node was added to the tree and it reached the goal.
+    Reached { node_index: usize },
+}
+
+/// Describes what happened during a single iteration of the RRT.
+#[derive(Debug, Clone, Copy)]
+pub enum IterationEvent<F: Float, const N: usize> {
+    /// The steered point or edge was invalid, so no node was added.
+    SampleRejected {
+        /// The point drawn from the sampling distribution.
+        sample: Point<F, N>,
+    },
+    /// A new node was added to the tree.
+    NodeAdded {
+        /// The point drawn from the sampling distribution.
+        sample: Point<F, N>,
+        /// The index of the new node.
+        index: usize,
+        /// The point of the new node.
+        point: Point<F, N>,
+        /// The index of the parent of the new node.
+        parent: usize,
+    },
+    /// The tree has reached the maximum number of nodes, so no sample was drawn.
+    NodeLimitReached,
+    /// A new node was added to the tree and it reached the goal.
+    SolutionFound {
+        /// The point drawn from the sampling distribution.
+        sample: Point<F, N>,
+        /// The index of the new node.
+        index: usize,
+        /// The point of the new node.
+        point: Point<F, N>,
+        /// The index of the parent of the new node.
+        parent: usize,
+    },
+}
+
+/// Computes the data of a new node from its parent node and its point.
+type NodeDataHook<F, const N: usize, T> = Box<dyn FnMut(&Node<F, N, T>, &Point<F, N>) -> T + Send>;
+
+type StallHandler = Box<dyn FnMut(StallEvent) + Send>;
+
+/// Receives progress reports at a fixed interval of iterations during a solve.
+struct ProgressHandler<F: Float> {
+    /// The number of iterations between reports.
+    interval: u32,
+    handler: Box<dyn FnMut(ProgressReport<F>) + Send>,
+    /// When the current solve started.
+    started: Instant,
+    /// The number of iterations completed since the current solve started.
+    iterations: u32,
 }
 
 /// A Rapidly-exploring Random Tree (RRT) planner.
@@ -41,35 +345,80 @@ impl<F: Float, const N: usize> Node<F, N> {
 /// - `SD`: The sampling distribution.
 /// - `ST`: The steering function.
 /// - `NN`: The nearest neighbors data structure.
-pub struct RRT<F: Float, const N: usize, VC, SD, ST, NN>
+/// - `GC`: The goal checker (defaults to a goal point with a tolerance).
+/// - `T`: Data attached to each node (defaults to none). See `set_node_data_hook`.
+pub struct RRT<F: Float, const N: usize, VC, SD, ST, NN, GC = PointGoal<F, N>, T = ()>
 where
     VC: ValidityChecker<F, N>,
     SD: SamplingDistribution<F, N>,
     ST: Steering<F, N>,
     NN: NearestNeighbors<F, N>,
+    GC: GoalChecker<F, N>,
 {
-    /// The goal state.
-    goal: Point<F, N>,
-    /// The tolerance for reaching the goal.
-    goal_tolerance: F,
+    /// Checks if a node has reached the goal.
+    goal_checker: GC,
     /// The nodes in the tree.
-    nodes: Vec<Node<F, N>>,
+    nodes: Vec<Node<F, N, T>>,
+    /// The indices of the root nodes, in the order the starts were added.
+    roots: Vec<usize>,
     /// Index of the solution node (None if no solution has been found).
     solution: Option<usize>,
+    /// Indices of all nodes that reached the goal.
+    goal_nodes: Vec<usize>,
+    /// The total number of iterations run.
+    iterations: u64,
+    /// Counters describing the behavior of the planner since the last reset.
+    statistics: Statistics,
+    /// The maximum number of nodes in the tree (None for no limit).
+    max_nodes: Option<usize>,
+    /// Whether to attempt a direct connection to the goal after adding each node.
+    connect_to_goal: bool,
+    /// Whether to check for a direct connection from the start to the goal before solving.
+    direct_connection: bool,
+    /// Whether edges are only checked once they are on the path to a node that reached the goal.
+    lazy_edge_checking: bool,
+    /// How far the tree is extended towards each sample.
+    extend_mode: ExtendMode,
+    /// New nodes closer than this to an existing node are not added, unless they reach the goal (zero to disable).
+    min_node_separation: F,
+    /// The largest spacing of the waypoints checked along each steered edge (None to check the straight edge).
+    waypoint_resolution: Option<F>,
+    /// The outcome of the most recent solve.
+    last_outcome: Option<SolveOutcome>,
+    /// The node closest to the goal and its distance to the goal (None if the goal checker can not measure it).
+    closest_approach: Option<(usize, F)>,
+    /// Whether to record the history of the closest approach to the goal.
+    record_progress: bool,
+    /// The iterations at which the closest approach to the goal improved, and the new distances.
+    progress_history: Vec<(u64, F)>,
+    /// Computes the data of each new node from its parent (None to use `T::default()`).
+    node_data_hook: Option<NodeDataHook<F, N, T>>,
+    /// Receives progress reports during solves (None to disable).
+    progress_handler: Option<ProgressHandler<F>>,
+    /// How stalls of the tree are detected (None to disable).
+    stall_detection: Option<StallDetection<F>>,
+    /// Receives an event whenever the tree stalls (None to disable).
+    stall_handler: Option<StallHandler>,
+    /// The number of consecutive iterations that added no node.
+    failed_iterations: u32,
     validity_checker: VC,
     sampling_distribution: SD,
     steering: ST,
     nearest_neighbors: NN,
 }
 
-impl<F: Float, const N: usize, VC, SD, ST, NN> RRT<F, N, VC, SD, ST, NN>
+impl<F: Float, const N: usize, VC, SD, ST, NN, T: Default>
+    RRT<F, N, VC, SD, ST, NN, PointGoal<F, N>, T>
 where
     VC: ValidityChecker<F, N>,
     SD: SamplingDistribution<F, N>,
     ST: Steering<F, N>,
     NN: NearestNeighbors<F, N>,
 {
-    /// Constructs a new RRT planner.
+    /// Constructs a new RRT planner with a goal point and tolerance.
+    ///
+    /// The start and goal points are not checked for validity. Prefer `try_new`,
+    /// since a planner whose start is invalid can never grow its tree.
     ///
     /// Parameters:
     /// - `start`: The start point.
@@ -78,6 +427,7 @@ where
     /// - `validity_checker`: Checks if the edges or nodes as valid.
     /// - `sampling_distribution`: The sampling distribution.
     /// - `steering`: The steering function.
+    ///
     /// Returns the RRT planner.
     pub fn new(
         start: Point<F, N>,
@@ -87,140 +437,3428 @@ where
         sampling_distribution: SD,
         steering: ST,
     ) -> Self {
-        let mut rrt = Self {
+        Self::with_goal_checker(
+            start,
+            PointGoal::new(goal, goal_tolerance),
+            validity_checker,
+            sampling_distribution,
+            steering,
+        )
+    }
+
+    /// Constructs a new RRT planner with a goal point and tolerance,
+    /// checking that the start and goal points are valid.
+    ///
+    /// Parameters:
+    /// - `start`: The start point.
+    /// - `goal`: The goal point.
+    /// - `goal_tolerance`: The tolerance for reaching the goal.
+    /// - `validity_checker`: Checks if the edges or nodes as valid.
+    /// - `sampling_distribution`: The sampling distribution.
+    /// - `steering`: The steering function.
+    ///
+    /// Returns the RRT planner, or an error if the start or goal point is not valid.
+    pub fn try_new(
+        start: Point<F, N>,
+        goal: Point<F, N>,
+        goal_tolerance: F,
+        validity_checker: VC,
+        sampling_distribution: SD,
+        steering: ST,
+    ) -> Result<Self, PlanningError> {
+        if !validity_checker.is_point_valid(&start) {
+            return Err(PlanningError::InvalidStart);
+        }
+        if !validity_checker.is_point_valid(&goal) {
+            return Err(PlanningError::InvalidGoal);
+        }
+        Ok(Self::new(
+            start,
             goal,
             goal_tolerance,
-            solution: None,
-            nodes: Vec::new(),
             validity_checker,
             sampling_distribution,
             steering,
-            nearest_neighbors: NN::new(),
-        };
-        let root = Node::new(start, None);
-        rrt.add_node(root);
-        rrt
+        ))
     }
 
-    /// Attempts to find a solution within a maximum number of iterations.
+    /// Changes the goal without rebuilding the tree.
     ///
-    /// Terminates and returns true when a solution is found. Otherwise, returns false.
+    /// The solution is cleared and replaced by the existing node within the new goal tolerance
+    /// with the shortest path, if there is one.
+    /// In lazy edge checking mode, only nodes whose path is valid are considered.
+    /// The new goal is also passed on to the sampling distribution through `SamplingDistribution::set_goal`.
     ///
     /// Parameters:
-    /// - `max_iterations`: The maximum number of iterations.
-    pub fn solve(&mut self, max_iterations: u32) -> bool {
-        for _ in 0..max_iterations {
-            self.iteration();
-            if self.solved() {
-                return true;
+    /// - `goal`: The new goal point.
+    /// - `tolerance`: The new tolerance for reaching the goal.
+    pub fn set_goal(&mut self, goal: Point<F, N>, tolerance: F) {
+        self.goal_checker = PointGoal::new(goal, tolerance);
+        self.sampling_distribution.set_goal(goal);
+        // In lazy mode, checking the path to a reached node can remove nodes and re-index the tree,
+        // so the search for reached nodes starts over.
+        'search: loop {
+            self.solution = None;
+            self.goal_nodes.clear();
+            let mut reached = self.nearest_neighbors.within_radius(&goal, tolerance);
+            reached.sort_unstable();
+            for index in reached {
+                if !self.validate_lazy_path(index) {
+                    continue 'search;
+                }
+                self.record_goal_node(index);
             }
+            break;
+        }
+        self.closest_approach = self.nearest_neighbors.nearest_one_with_distance(&goal);
+    }
+
+    /// Returns the index of the node closest to the goal and its euclidean distance to the goal.
+    ///
+    /// If a solution was found, the solution node is returned.
+    /// Otherwise, the nearest neighbors data structure is queried for the node nearest to the goal.
+    /// The distance can be used to decide whether the best partial path is worth executing.
+    pub fn closest_node_to_goal(&self) -> (usize, F) {
+        let goal = self.goal_checker.goal();
+        match self.solution {
+            Some(index) => (index, euclidean_distance(&self.nodes[index].point, goal)),
+            None => self.nearest_node(goal).unwrap(),
         }
-        return false;
     }
 
-    /// Run a fixed number of iterations of the RRT algorithm. Does not terminate early if a solution is found.
+    /// Returns the path from the start to the node closest to the goal.
+    ///
+    /// If a solution was found, this is the same as the solution path,
+    /// so it can be used whether or not the planner succeeded.
+    pub fn get_best_partial_path(&self) -> Vec<Point<F, N>> {
+        let (index, _) = self.closest_node_to_goal();
+        self.path_to(index)
+    }
+
+    /// Removes leaf nodes farthest from the goal until at most `keep` nodes remain.
     ///
-    /// Returns true if the RRT found a solution.
+    /// The roots and the nodes on the solution path are never removed.
+    /// Removing a leaf can turn its parent into a leaf, which can then be removed as well.
+    /// Node indices change, so indices obtained before pruning are no longer valid.
     ///
     /// Parameters:
-    /// - `iterations`: The number of iterations to run.
-    pub fn run_iterations(&mut self, iterations: u32) -> bool {
-        for _ in 0..iterations {
-            self.iteration();
-            if self.solved() {
-                return true;
+    /// - `keep`: The number of nodes to keep.
+    ///
+    /// Returns:
+    /// The number of nodes removed.
+    pub fn prune_leaves(&mut self, keep: usize) -> usize {
+        let goal = *self.goal_checker.goal();
+        let mut protected = vec![false; self.nodes.len()];
+        for &root in &self.roots {
+            protected[root] = true;
+        }
+        for index in self.get_path_indices().unwrap_or_default() {
+            protected[index] = true;
+        }
+
+        let mut remaining_children: Vec<usize> =
+            self.nodes.iter().map(|node| node.children.len()).collect();
+        let mut leaves: BinaryHeap<PruneCandidate<F>> = (0..self.nodes.len())
+            .filter(|&index| remaining_children[index] == 0 && !protected[index])
+            .map(|index| PruneCandidate {
+                distance_squared: euclidean_distance_squared(&self.nodes[index].point, &goal),
+                index,
+            })
+            .collect();
+
+        let mut keep_nodes = vec![true; self.nodes.len()];
+        let mut remaining = self.nodes.len();
+        while remaining > keep {
+            let Some(leaf) = leaves.pop() else {
+                break;
+            };
+            keep_nodes[leaf.index] = false;
+            remaining -= 1;
+
+            let parent = self.nodes[leaf.index].parent.unwrap();
+            remaining_children[parent] -= 1;
+            if remaining_children[parent] == 0 && !protected[parent] {
+                leaves.push(PruneCandidate {
+                    distance_squared: euclidean_distance_squared(&self.nodes[parent].point, &goal),
+                    index: parent,
+                });
             }
         }
-        return false;
+        self.retain_nodes(&keep_nodes)
     }
+}
 
-    /// Returns true if a solution was found.
-    pub fn solved(&self) -> bool {
-        self.solution.is_some()
+/// Checks that following the parents from every node ends at a root, with parent indices known to be in range.
+fn parents_are_acyclic<F: Float, const N: usize, T>(nodes: &[Node<F, N, T>]) -> bool {
+    // Nodes whose parent chain is known to end at a root.
+    let mut reaches_root = vec![false; nodes.len()];
+    for index in 0..nodes.len() {
+        let mut chain = Vec::new();
+        let mut current = index;
+        while !reaches_root[current] {
+            // A chain longer than the tree must revisit a node.
+            if chain.len() > nodes.len() {
+                return false;
+            }
+            chain.push(current);
+            match nodes[current].parent {
+                Some(parent) => current = parent,
+                None => break,
+            }
+        }
+        for node in chain {
+            reaches_root[node] = true;
+        }
     }
+    true
+}
 
-    /// Returns the path from the start to the goal, if a solution was found.
-    pub fn get_path(&self) -> Option<Vec<Point<F, N>>> {
-        if !self.solved() {
-            return None;
-        }
+/// Checks a steered edge along its waypoints, or along the straight edge if there are none.
+fn is_steered_edge_valid<F: Float, const N: usize, VC: ValidityChecker<F, N>>(
+    validity_checker: &VC,
+    from: &Point<F, N>,
+    to: &Point<F, N>,
+    waypoints: Option<&[Point<F, N>]>,
+) -> bool {
+    match waypoints {
+        Some(waypoints) => waypoints
+            .windows(2)
+            .all(|edge| validity_checker.is_edge_valid(&edge[0], &edge[1])),
+        None => validity_checker.is_edge_valid(from, to),
+    }
+}
 
-        let mut path = Vec::new();
-        let mut current_index = self.solution.unwrap();
+/// A leaf that can be pruned, ordered by its distance to the goal so that the farthest leaf is popped first.
+struct PruneCandidate<F: Float> {
+    distance_squared: F,
+    index: usize,
+}
 
-        // Reconstruct the path by backtracking up the tree (following the parent pointers).
-        while let Some(parent_index) = self.nodes[current_index].parent {
-            path.push(self.nodes[current_index].point);
-            current_index = parent_index;
-        }
-        path.push(self.nodes[current_index].point);
+impl<F: Float> PartialEq for PruneCandidate<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
 
-        // Reverse the path so that it goes from the start to the goal.
-        path.reverse();
-        Some(path)
+impl<F: Float> Eq for PruneCandidate<F> {}
+
+impl<F: Float> PartialOrd for PruneCandidate<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
     }
+}
 
-    pub fn get_nearest_neighbors(&self) -> &NN {
-        &self.nearest_neighbors
+impl<F: Float> Ord for PruneCandidate<F> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.distance_squared
+            .partial_cmp(&other.distance_squared)
+            .unwrap_or(cmp::Ordering::Equal)
+            .then(self.index.cmp(&other.index))
     }
+}
 
-    pub fn get_sampling_distribution(&self) -> &SD {
-        &self.sampling_distribution
+impl<F: Float, const N: usize, VC, SD, ST, NN, GC, T: Default> RRT<F, N, VC, SD, ST, NN, GC, T>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    ST: Steering<F, N>,
+    NN: NearestNeighbors<F, N>,
+    GC: GoalChecker<F, N>,
+{
+    /// Constructs a new RRT planner with an arbitrary goal condition.
+    ///
+    /// Parameters:
+    /// - `start`: The start point.
+    /// - `goal_checker`: Checks if a point has reached the goal.
+    /// - `validity_checker`: Checks if the edges or nodes as valid.
+    /// - `sampling_distribution`: The sampling distribution.
+    /// - `steering`: The steering function.
+    ///
+    /// Returns the RRT planner.
+    pub fn with_goal_checker(
+        start: Point<F, N>,
+        goal_checker: GC,
+        validity_checker: VC,
+        sampling_distribution: SD,
+        steering: ST,
+    ) -> Self {
+        let mut rrt = Self {
+            goal_checker,
+            solution: None,
+            goal_nodes: Vec::new(),
+            iterations: 0,
+            statistics: Statistics::default(),
+            max_nodes: None,
+            connect_to_goal: false,
+            direct_connection: false,
+            lazy_edge_checking: false,
+            extend_mode: ExtendMode::Single,
+            min_node_separation: F::zero(),
+            waypoint_resolution: None,
+            last_outcome: None,
+            closest_approach: None,
+            record_progress: false,
+            progress_history: Vec::new(),
+            node_data_hook: None,
+            progress_handler: None,
+            stall_detection: None,
+            stall_handler: None,
+            failed_iterations: 0,
+            nodes: Vec::new(),
+            roots: Vec::new(),
+            validity_checker,
+            sampling_distribution,
+            steering,
+            nearest_neighbors: NN::new(),
+        };
+        let root = Node::with_data(start, None, T::default());
+        rrt.add_node(root);
+        rrt
     }
 
-    pub fn get_validity_checker(&self) -> &VC {
-        &self.validity_checker
+    /// Constructs a new RRT planner that grows a forest from several candidate starts.
+    ///
+    /// Every start is a root of the forest, and the solution is the first path found from any of them.
+    /// Use `solution_root` to find out which start the solution comes from.
+    ///
+    /// Parameters:
+    /// - `starts`: The candidate start points.
+    /// - `goal_checker`: Checks if a point has reached the goal.
+    /// - `validity_checker`: Checks if the edges or nodes as valid.
+    /// - `sampling_distribution`: The sampling distribution.
+    /// - `steering`: The steering function.
+    ///
+    /// Returns:
+    /// The RRT planner, or an error if `starts` is empty.
+    pub fn with_starts(
+        starts: Vec<Point<F, N>>,
+        goal_checker: GC,
+        validity_checker: VC,
+        sampling_distribution: SD,
+        steering: ST,
+    ) -> Result<Self, &'static str> {
+        let (&first, rest) = starts
+            .split_first()
+            .ok_or("starts must contain at least one point")?;
+        let mut rrt = Self::with_goal_checker(
+            first,
+            goal_checker,
+            validity_checker,
+            sampling_distribution,
+            steering,
+        );
+        for &start in rest {
+            rrt.add_start(start);
+        }
+        Ok(rrt)
     }
 
-    /// Returns the vector of nodes in the tree.
-    pub fn get_tree(&self) -> &Vec<Node<F, N>> {
-        &self.nodes
+    /// Adds another start to the tree as a new root.
+    ///
+    /// Parameters:
+    /// - `start`: The start point.
+    ///
+    /// Returns:
+    /// The index of the start, as reported by `root_of` and `solution_root`.
+    pub fn add_start(&mut self, start: Point<F, N>) -> usize {
+        self.add_node(Node::with_data(start, None, T::default()));
+        self.roots.len() - 1
     }
 
-    /// Expands the tree by one iteration.
+    /// Returns the indices of the root nodes, in the order the starts were added.
+    pub fn roots(&self) -> &[usize] {
+        &self.roots
+    }
+
+    /// Returns the index of the start that the node at `index` descends from.
+    pub fn root_of(&self, index: usize) -> usize {
+        let mut current_index = index;
+        while let Some(parent_index) = self.nodes[current_index].parent {
+            current_index = parent_index;
+        }
+        self.roots
+            .iter()
+            .position(|&root| root == current_index)
+            .expect("every root is recorded")
+    }
+
+    /// Returns the index of the start that the solution path begins at, if a solution was found.
+    pub fn solution_root(&self) -> Option<usize> {
+        self.solution.map(|solution| self.root_of(solution))
+    }
+
+    /// Clears the tree so that the planner can be reused with the same configuration.
     ///
-    /// Each iteration of the RRT algorithm consists of the following steps:
-    /// 1. Sample a point from the sampling distribution.
-    /// 2. Find the nearest node in the tree to the sample point.
-    /// 3. Steer the nearest node towards the sample point.
-    /// 4. Add the new node to as a child of the nearest node if the edge is valid.
-    /// 5. If the goal is reached, update the solution node.
-    fn iteration(&mut self) {
-        // Sample a point from the sampling distribution.
-        let sample = self.sampling_distribution.sample();
+    /// The nodes, solution and statistics are cleared and a new root is inserted at `start`.
+    /// Any additional starts are removed.
+    /// The goal, validity checker, sampling distribution, steering and node limit are kept.
+    ///
+    /// Parameters:
+    /// - `start`: The start point of the new tree.
+    pub fn reset(&mut self, start: Point<F, N>) {
+        self.nodes = Vec::new();
+        self.roots.clear();
+        self.nearest_neighbors = NN::new();
+        self.solution = None;
+        self.goal_nodes.clear();
+        self.last_outcome = None;
+        self.closest_approach = None;
+        self.progress_history.clear();
+        self.failed_iterations = 0;
+        self.statistics = Statistics::default();
+        self.add_node(Node::with_data(start, None, T::default()));
+    }
 
-        // Find the nearest node in the tree to the sample point.
-        let nearest_node_index = self.nearest_neighbors.nearest_one(&sample).unwrap();
-        let nearest_point = &self.nodes[nearest_node_index].point;
+    /// Consumes the planner and returns ownership of its validity checker, sampling distribution and steering.
+    pub fn into_parts(self) -> (VC, SD, ST) {
+        (
+            self.validity_checker,
+            self.sampling_distribution,
+            self.steering,
+        )
+    }
 
-        // Steer the nearest node towards the sample point to get a new point.
-        let new_point = self.steering.steer(nearest_point, &sample);
+    /// Attempts to find a solution within a maximum number of iterations.
+    ///
+    /// Terminates when a solution is found, the iterations are exhausted or the node limit is reached.
+    ///
+    /// Parameters:
+    /// - `max_iterations`: The maximum number of iterations.
+    ///
+    /// Returns:
+    /// The outcome of the solve.
+    pub fn solve(&mut self, max_iterations: u32) -> SolveOutcome {
+        self.start_progress();
+        if self.direct_connection_pre_check().is_some() {
+            return self.record_outcome(0);
+        }
+        let mut iterations = 0;
+        while iterations < max_iterations {
+            if self.node_limit_reached() {
+                break;
+            }
+            self.step();
+            iterations += 1;
+            if self.solved() {
+                break;
+            }
+        }
+        self.record_outcome(iterations)
+    }
 
-        // If the new point or edge is invalid, return.
-        if !self.validity_checker.is_point_valid(&new_point)
-            || !self
-                .validity_checker
-                .is_edge_valid(nearest_point, &new_point)
-        {
-            return;
+    /// Attempts to find a solution within a maximum number of iterations, stopping early if `cancel` is set.
+    ///
+    /// The flag is checked before every iteration, so this can be cancelled from another thread.
+    /// The tree is always left in a consistent state, so partial progress remains usable,
+    /// for example through `get_best_partial_path`.
+    ///
+    /// Parameters:
+    /// - `max_iterations`: The maximum number of iterations.
+    /// - `cancel`: Cancels the solve when set to true.
+    ///
+    /// Returns:
+    /// The outcome of the solve.
+    pub fn solve_cancellable(&mut self, max_iterations: u32, cancel: &AtomicBool) -> SolveOutcome {
+        self.start_progress();
+        if self.direct_connection_pre_check().is_some() {
+            return self.record_outcome(0);
+        }
+        let mut iterations = 0;
+        while iterations < max_iterations {
+            if cancel.load(Ordering::Relaxed) {
+                let outcome = SolveOutcome::Cancelled { iterations };
+                self.last_outcome = Some(outcome);
+                return outcome;
+            }
+            if self.node_limit_reached() {
+                break;
+            }
+            self.step();
+            iterations += 1;
+            if self.solved() {
+                break;
+            }
         }
+        self.record_outcome(iterations)
+    }
 
-        // Add the new node to as a child of the nearest node.
-        let new_node = Node::new(new_point, Some(nearest_node_index));
-        let new_node_index = self.add_node(new_node);
+    /// Runs a fixed number of iterations, continuing to improve the solution after the first one is found.
+    ///
+    /// Every node that reaches the goal is recorded, and the solution is the goal node with the shortest path.
+    /// Terminates early only if the node limit is reached.
+    ///
+    /// Parameters:
+    /// - `max_iterations`: The number of iterations to run.
+    ///
+    /// Returns:
+    /// The outcome of the solve. It is `Solved` if any solution was found.
+    pub fn solve_anytime(&mut self, max_iterations: u32) -> SolveOutcome {
+        self.start_progress();
+        self.direct_connection_pre_check();
+        let mut iterations = 0;
+        while iterations < max_iterations {
+            if self.node_limit_reached() {
+                break;
+            }
+            self.step();
+            iterations += 1;
+        }
+        self.record_outcome(iterations)
+    }
 
-        // If the goal is reached, update the solution node.
-        let dist_squared = euclidean_distance_squared(&new_point, &self.goal);
-        if dist_squared <= self.goal_tolerance * self.goal_tolerance {
-            self.solution = Some(new_node_index);
+    /// Run a fixed number of iterations of the RRT algorithm.
+    /// Stops early if a solution is found or the node limit is reached.
+    ///
+    /// Parameters:
+    /// - `iterations`: The number of iterations to run.
+    ///
+    /// Returns:
+    /// A summary of the run, with its outcome and how the tree changed.
+    pub fn run_iterations(&mut self, iterations: u32) -> RunSummary {
+        let before = self.statistics;
+        let outcome = self.solve(iterations);
+        let after = self.statistics;
+        RunSummary {
+            outcome,
+            iterations: (after.iterations - before.iterations) as u32,
+            nodes_added: after.nodes_added.saturating_sub(before.nodes_added),
+            samples_rejected: after.samples_rejected() - before.samples_rejected(),
         }
     }
 
-    /// Adds a node to the tree and the nearest neighbors data structure.
-    fn add_node(&mut self, node: Node<F, N>) -> usize {
-        let index = self.nodes.len();
-        self.nearest_neighbors.add(node.point().clone(), index);
-        self.nodes.push(node);
+    /// Run a fixed number of iterations of the RRT algorithm, drawing all of their samples in one batch
+    /// with `SamplingDistribution::sample_batch`. This cuts the per-sample overhead of the sampling distribution,
+    /// which is noticeable when validity checks are cheap.
+    /// Stops early if a solution is found or the node limit is reached, and the remaining samples are discarded.
+    ///
+    /// Parameters:
+    /// - `batch_size`: The number of iterations to run.
+    ///
+    /// Returns:
+    /// A summary of the run, with its outcome and how the tree changed.
+    pub fn run_iterations_batched(&mut self, batch_size: u32) -> RunSummary {
+        let before = self.statistics;
+        self.start_progress();
+        let outcome = if self.direct_connection_pre_check().is_some() {
+            self.record_outcome(0)
+        } else {
+            let mut samples = Vec::with_capacity(batch_size as usize);
+            self.sampling_distribution
+                .sample_batch(batch_size as usize, &mut samples);
+            let mut iterations = 0;
+            for sample in samples {
+                if self.node_limit_reached() {
+                    break;
+                }
+                self.iteration_towards(Some(sample));
+                iterations += 1;
+                if self.solved() {
+                    break;
+                }
+            }
+            self.record_outcome(iterations)
+        };
+        let after = self.statistics;
+        RunSummary {
+            outcome,
+            iterations: (after.iterations - before.iterations) as u32,
+            nodes_added: after.nodes_added.saturating_sub(before.nodes_added),
+            samples_rejected: after.samples_rejected() - before.samples_rejected(),
+        }
+    }
+
+    /// Attempts to find a solution within a wall-clock time budget.
+    ///
+    /// Terminates when a solution is found, the budget is exhausted or the node limit is reached.
+    /// Running out of time is reported as `SolveOutcome::IterationLimit`.
+    ///
+    /// Parameters:
+    /// - `budget`: The maximum time to spend solving.
+    ///
+    /// Returns:
+    /// The outcome of the solve.
+    pub fn solve_with_timeout(&mut self, budget: Duration) -> SolveOutcome {
+        self.start_progress();
+        let start_time = Instant::now();
+        if self.direct_connection_pre_check().is_some() {
+            return self.record_outcome(0);
+        }
+        let mut iterations: u32 = 0;
+        while start_time.elapsed() < budget {
+            if self.node_limit_reached() {
+                break;
+            }
+            self.step();
+            iterations = iterations.saturating_add(1);
+            if self.solved() {
+                break;
+            }
+        }
+        self.record_outcome(iterations)
+    }
+
+    /// Attempts to find a solution within a maximum number of iterations,
+    /// calling `callback` after every iteration with an event describing what happened.
+    ///
+    /// Terminates when a solution is found, the iterations are exhausted or the node limit is reached.
+    /// Nodes inserted by the direct connection pre-check are reported first, as `NodeAdded` events ending in
+    /// `SolutionFound`, with the goal point as their sample. When the node limit stops the solve,
+    /// `NodeLimitReached` is reported once.
+    ///
+    /// Parameters:
+    /// - `max_iterations`: The maximum number of iterations.
+    /// - `callback`: Called with the planner and the event of each iteration.
+    ///
+    /// Returns:
+    /// The outcome of the solve.
+    pub fn solve_with_callback<C>(&mut self, max_iterations: u32, mut callback: C) -> SolveOutcome
+    where
+        C: FnMut(&Self, IterationEvent<F, N>),
+    {
+        self.start_progress();
+        let first_new_index = self.nodes.len();
+        if let Some(goal) = self.direct_connection_pre_check() {
+            let last_index = self.nodes.len() - 1;
+            for index in first_new_index..=last_index {
+                let node = &self.nodes[index];
+                let (point, parent) = (node.point, node.parent.unwrap());
+                let event = if index == last_index {
+                    IterationEvent::SolutionFound {
+                        sample: goal,
+                        index,
+                        point,
+                        parent,
+                    }
+                } else {
+                    IterationEvent::NodeAdded {
+                        sample: goal,
+                        index,
+                        point,
+                        parent,
+                    }
+                };
+                callback(self, event);
+            }
+            return self.record_outcome(0);
+        }
+        let mut iterations = 0;
+        while iterations < max_iterations {
+            if self.node_limit_reached() {
+                callback(self, IterationEvent::NodeLimitReached);
+                break;
+            }
+            let event = self.iteration();
+            iterations += 1;
+            callback(self, event);
+            if self.solved() {
+                break;
+            }
+        }
+        self.record_outcome(iterations)
+    }
+
+    /// Runs iterations until a user-defined condition holds, such as the path cost dropping below a threshold.
+    ///
+    /// The predicate is evaluated after every iteration, and the solve stops as soon as it returns true.
+    /// Finding a solution does not stop the solve, so every node that reaches the goal is recorded, like in
+    /// `solve_anytime`. The predicate runs once per iteration, so heavy predicates should throttle themselves,
+    /// for example by only doing their work every few hundred iterations.
+    ///
+    /// Parameters:
+    /// - `max_iterations`: The maximum number of iterations.
+    /// - `predicate`: Called with the planner after each iteration. Returns true to stop.
+    ///
+    /// Returns:
+    /// The outcome of the solve. It is `Solved` if any solution was found, and `Cancelled`
+    /// if the predicate stopped the solve before a solution was found.
+    pub fn solve_until<P>(&mut self, max_iterations: u32, mut predicate: P) -> SolveOutcome
+    where
+        P: FnMut(&Self) -> bool,
+    {
+        self.start_progress();
+        self.direct_connection_pre_check();
+        let mut iterations = 0;
+        while iterations < max_iterations {
+            if self.node_limit_reached() {
+                break;
+            }
+            self.step();
+            iterations += 1;
+            if predicate(self) {
+                if !self.solved() {
+                    let outcome = SolveOutcome::Cancelled { iterations };
+                    self.last_outcome = Some(outcome);
+                    return outcome;
+                }
+                break;
+            }
+        }
+        self.record_outcome(iterations)
+    }
+
+    /// Runs a single iteration of the RRT algorithm.
+    ///
+    /// This allows building custom planning loops on top of the RRT.
+    ///
+    /// Returns:
+    /// Whether the tree was trapped, advanced, or reached the goal.
+    /// Once the node limit is reached, every step is trapped.
+    pub fn step(&mut self) -> StepResult {
+        match self.iteration() {
+            IterationEvent::SampleRejected { .. } | IterationEvent::NodeLimitReached => {
+                StepResult::Trapped
+            }
+            IterationEvent::NodeAdded { index, .. } => StepResult::Advanced { node_index: index },
+            IterationEvent::SolutionFound { index, .. } => {
+                StepResult::Reached { node_index: index }
+            }
+        }
+    }
+
+    /// Returns the outcome of the most recent solve, or None if the planner has not been solved yet.
+    pub fn solve_result(&self) -> Option<SolveOutcome> {
+        self.last_outcome
+    }
+
+    /// Sets the maximum number of nodes in the tree (None for no limit).
+    ///
+    /// Once the tree reaches this size, no more nodes are added and solving terminates early.
+    pub fn set_max_nodes(&mut self, max_nodes: Option<usize>) {
+        self.max_nodes = max_nodes;
+    }
+
+    pub fn max_nodes(&self) -> Option<usize> {
+        self.max_nodes
+    }
+
+    /// Sets whether to attempt a direct connection to the goal after adding each node.
+    ///
+    /// When enabled, each new node is steered straight towards a point sampled from the goal
+    /// (see `GoalChecker::sample_goal`). If the steered point reaches the goal and the edge is valid,
+    /// it is added as the solution node. This is disabled by default.
+    pub fn set_connect_to_goal(&mut self, connect_to_goal: bool) {
+        self.connect_to_goal = connect_to_goal;
+    }
+
+    pub fn connect_to_goal(&self) -> bool {
+        self.connect_to_goal
+    }
+
+    /// Sets the hook that computes the data attached to each new node, such as the control used to reach it.
+    ///
+    /// The hook is called with the parent node and the point of the new node, whenever a node is added
+    /// to the tree by extension, goal connection or path insertion. Root nodes get `T::default()`,
+    /// as do all nodes when no hook is set.
+    ///
+    /// Parameters:
+    /// - `hook`: Computes the data of a new node from its parent node and its point.
+    pub fn set_node_data_hook<H>(&mut self, hook: H)
+    where
+        H: FnMut(&Node<F, N, T>, &Point<F, N>) -> T + Send + 'static,
+    {
+        self.node_data_hook = Some(Box::new(hook));
+    }
+
+    /// Removes the node data hook, so new nodes get `T::default()`.
+    pub fn clear_node_data_hook(&mut self) {
+        self.node_data_hook = None;
+    }
+
+    /// Sets a handler that receives a progress report every `interval` iterations of a solve.
+    ///
+    /// Reports are emitted from `solve`, `solve_anytime`, `solve_cancellable`, `solve_with_timeout`,
+    /// `solve_with_callback`, `solve_until`, `run_iterations` and `run_iterations_batched`. The iterations and elapsed time
+    /// are counted from the start of the current solve. An interval of zero is treated as one.
+    ///
+    /// Parameters:
+    /// - `interval`: The number of iterations between reports.
+    /// - `handler`: Receives the progress reports.
+    pub fn set_progress_handler<H>(&mut self, interval: u32, handler: H)
+    where
+        H: FnMut(ProgressReport<F>) + Send + 'static,
+    {
+        self.progress_handler = Some(ProgressHandler {
+            interval: interval.max(1),
+            handler: Box::new(handler),
+            started: Instant::now(),
+            iterations: 0,
+        });
+    }
+
+    /// Removes the progress handler.
+    pub fn clear_progress_handler(&mut self) {
+        self.progress_handler = None;
+    }
+
+    /// Sets how stalls of the tree are detected, or None to disable stall detection (the default).
+    ///
+    /// The tree has stalled when `max_failed_iterations` consecutive iterations add no node, for example
+    /// because the ranges of the sampling distribution do not cover the free space around the tree.
+    /// Each stall is counted in `Statistics::stalls`, reported to the stall handler, and optionally widens
+    /// the ranges of the sampling distribution. The count of failed iterations then starts over.
+    ///
+    /// Returns:
+    /// An error if the expand factor is not positive and finite, in which case the setting is unchanged.
+    pub fn set_stall_detection(
+        &mut self,
+        stall_detection: Option<StallDetection<F>>,
+    ) -> Result<(), &'static str> {
+        let expand_factor =
+            stall_detection.and_then(|stall_detection| stall_detection.expand_factor);
+        if expand_factor.is_some_and(|factor| !(factor > F::zero() && factor.is_finite())) {
+            return Err("expand factor must be positive and finite");
+        }
+        self.stall_detection = stall_detection;
+        self.failed_iterations = 0;
+        Ok(())
+    }
+
+    pub fn stall_detection(&self) -> Option<StallDetection<F>> {
+        self.stall_detection
+    }
+
+    /// Sets a handler that receives an event whenever the tree stalls (see `set_stall_detection`).
+    ///
+    /// Parameters:
+    /// - `handler`: Receives the stall events.
+    pub fn set_stall_handler<H>(&mut self, handler: H)
+    where
+        H: FnMut(StallEvent) + Send + 'static,
+    {
+        self.stall_handler = Some(Box::new(handler));
+    }
+
+    /// Removes the stall handler.
+    pub fn clear_stall_handler(&mut self) {
+        self.stall_handler = None;
+    }
+
+    /// Returns the smallest distance from any node to the goal,
+    /// or None if the goal checker can not measure distances to the goal.
+    pub fn best_goal_distance(&self) -> Option<F> {
+        self.closest_approach.map(|(_, distance)| distance)
+    }
+
+    /// Returns the index of the node closest to the goal and its distance to the goal,
+    /// or None if the goal checker can not measure distances to the goal.
+    pub fn closest_approach(&self) -> Option<(usize, F)> {
+        self.closest_approach
+    }
+
+    /// Sets whether to record the history of the closest approach to the goal (see `progress_history`).
+    /// This is disabled by default, so the history does not grow without bound.
+    pub fn set_record_progress(&mut self, record_progress: bool) {
+        self.record_progress = record_progress;
+    }
+
+    pub fn record_progress(&self) -> bool {
+        self.record_progress
+    }
+
+    /// Returns the iterations at which the closest approach to the goal improved, and the new distances,
+    /// recorded while `set_record_progress` is enabled. This can be used to plot progress against iterations.
+    pub fn progress_history(&self) -> &[(u64, F)] {
+        &self.progress_history
+    }
+
+    /// Returns a mutable reference to the data attached to the node at `index`.
+    pub fn node_data_mut(&mut self, index: usize) -> &mut T {
+        self.nodes[index].data_mut()
+    }
+
+    /// Sets whether to check for a direct connection from the start to the goal before solving.
+    ///
+    /// When enabled, every solve first calls `try_direct_connection` if no solution has been found yet,
+    /// so queries in open space are answered without sampling, and the solve returns after zero iterations.
+    /// `solve_anytime` keeps iterating to improve the solution. This is disabled by default.
+    pub fn set_direct_connection(&mut self, direct_connection: bool) {
+        self.direct_connection = direct_connection;
+    }
+
+    pub fn direct_connection(&self) -> bool {
+        self.direct_connection
+    }
+
+    /// Attempts to connect a root of the tree straight to the goal.
+    ///
+    /// A point is sampled from the goal (see `GoalChecker::sample_goal`) and the edge from each root
+    /// to it is checked. If the edge is valid, the root is repeatedly steered towards the goal point,
+    /// so when the goal is farther than the steering range, intermediate nodes are inserted along the
+    /// edge, spaced by the steering range. Each of these edges is checked too, in case the steering
+    /// function does not follow the straight line. The tree is only changed if the whole chain is valid
+    /// and fits within the node limit.
+    ///
+    /// Returns:
+    /// True if the goal was connected and marked as the solution.
+    pub fn try_direct_connection(&mut self) -> bool {
+        self.connect_directly().is_some()
+    }
+
+    /// Implements `try_direct_connection`.
+    ///
+    /// Returns:
+    /// The goal point the chain was steered towards, or None if the goal was not connected.
+    fn connect_directly(&mut self) -> Option<Point<F, N>> {
+        let goal = self.goal_checker.sample_goal()?;
+        if !self.validity_checker.is_point_valid(&goal) {
+            return None;
+        }
+        for root_position in 0..self.roots.len() {
+            let root_index = self.roots[root_position];
+            let start = self.nodes[root_index].point;
+            if !self.validity_checker.is_edge_valid(&start, &goal) {
+                continue;
+            }
+            let Some(chain) = self.direct_chain(&start, &goal) else {
+                continue;
+            };
+
+            let mut parent_index = root_index;
+            for point in chain {
+                parent_index = self.add_child_node(parent_index, point);
+                self.statistics.nodes_added += 1;
+            }
+            self.mark_solution(parent_index);
+            return Some(goal);
+        }
+        None
+    }
+
+    /// Sets whether edges are checked lazily.
+    ///
+    /// When enabled, new nodes are added after checking only their points, and their edges are marked
+    /// as unchecked. Once a node reaches the goal, the edges on its path are checked. At the first edge
+    /// that is not valid, the node it leads to is removed together with its subtree, the remaining nodes
+    /// are re-indexed, and planning continues. This pays off when edge checks are much more expensive
+    /// than point checks. This is disabled by default.
+    pub fn set_lazy_edge_checking(&mut self, lazy_edge_checking: bool) {
+        self.lazy_edge_checking = lazy_edge_checking;
+    }
+
+    pub fn lazy_edge_checking(&self) -> bool {
+        self.lazy_edge_checking
+    }
+
+    /// Sets how far the tree is extended towards each sample.
+    pub fn set_extend_mode(&mut self, extend_mode: ExtendMode) {
+        self.extend_mode = extend_mode;
+    }
+
+    pub fn extend_mode(&self) -> ExtendMode {
+        self.extend_mode
+    }
+
+    /// Sets the minimum distance between a new node and the existing nodes of the tree.
+    ///
+    /// New nodes closer than this to an existing node are not added, unless they reach the goal.
+    /// This keeps the tree from piling up nearly identical nodes, for example near obstacles.
+    /// It should be smaller than the steering step, otherwise most extensions are rejected.
+    /// Zero disables the check, which is the default.
+    pub fn set_min_node_separation(&mut self, min_node_separation: F) {
+        self.min_node_separation = min_node_separation;
+    }
+
+    pub fn min_node_separation(&self) -> F {
+        self.min_node_separation
+    }
+
+    /// Sets the spacing of the waypoints checked along each steered edge (None to check the straight edge).
+    ///
+    /// By default, the straight edge between a node and its parent is checked, which is only what the robot
+    /// drives when the steering function moves in straight lines. When enabled, edges are steered with
+    /// `Steering::steer_with_waypoints` and the edges between consecutive waypoints are checked instead,
+    /// so curved steering functions such as `DubinsSteering` are checked along the curve they follow.
+    /// Edges of the tree that are checked again, for example by `revalidate`, are steered again from the parent.
+    pub fn set_waypoint_resolution(&mut self, waypoint_resolution: Option<F>) {
+        self.waypoint_resolution = waypoint_resolution;
+    }
+
+    pub fn waypoint_resolution(&self) -> Option<F> {
+        self.waypoint_resolution
+    }
+
+    /// Returns true if the tree has reached the maximum number of nodes.
+    pub fn node_limit_reached(&self) -> bool {
+        self.max_nodes
+            .is_some_and(|max_nodes| self.nodes.len() >= max_nodes)
+    }
+
+    /// Returns the total number of iterations run by the planner.
+    pub fn iterations_run(&self) -> u64 {
+        self.iterations
+    }
+
+    /// Returns the statistics collected since construction or the last call to `reset_statistics`.
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Resets all statistics counters to zero.
+    pub fn reset_statistics(&mut self) {
+        self.statistics = Statistics::default();
+    }
+
+    /// Returns true if a solution was found.
+    pub fn solved(&self) -> bool {
+        self.solution.is_some()
+    }
+
+    /// Returns an iterator over the edges of the tree as (parent, child) point pairs, for example for drawing.
+    /// Roots have no parent, so a tree with only roots has no edges.
+    pub fn edges(&self) -> impl Iterator<Item = (&Point<F, N>, &Point<F, N>)> + '_ {
+        self.nodes.iter().filter_map(move |node| {
+            node.parent
+                .map(|parent| (&self.nodes[parent].point, &node.point))
+        })
+    }
+
+    /// Returns an iterator over the edges of the solution path as (parent, child) point pairs,
+    /// from the start to the goal. It is empty if no solution was found.
+    pub fn solution_edges(&self) -> impl Iterator<Item = (&Point<F, N>, &Point<F, N>)> + '_ {
+        self.solution
+            .map(|solution| self.path_indices_to(solution))
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(move |index| {
+                let node = &self.nodes[index];
+                node.parent
+                    .map(|parent| (&self.nodes[parent].point, &node.point))
+            })
+    }
+
+    /// Returns an iterator over the indices of all descendants of the node at `index` (not including itself).
+    ///
+    /// The subtree is traversed in depth-first order.
+    pub fn descendants(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut stack = self.nodes[index].children.clone();
+        std::iter::from_fn(move || {
+            let current = stack.pop()?;
+            stack.extend(&self.nodes[current].children);
+            Some(current)
+        })
+    }
+
+    /// Returns the indices of all nodes that reached the goal.
+    pub fn goal_nodes(&self) -> &[usize] {
+        &self.goal_nodes
+    }
+
+    /// Returns the index of the solution node, if a solution was found.
+    pub fn solution_index(&self) -> Option<usize> {
+        self.solution
+    }
+
+    /// Returns the index of the goal that the solution reaches, if a solution was found.
+    ///
+    /// Only goal checkers with several goals, such as `MultiPointGoal`, report an index
+    /// (see `GoalChecker::goal_index`).
+    pub fn reached_goal_index(&self) -> Option<usize> {
+        let solution = self.solution?;
+        self.goal_checker.goal_index(&self.nodes[solution].point)
+    }
+
+    /// Finds the node in the tree closest to a point.
+    ///
+    /// Parameters:
+    /// - `point`: The point to find the closest node to.
+    ///
+    /// Returns:
+    /// The index of the closest node and its euclidean distance to the point.
+    pub fn nearest_node(&self, point: &Point<F, N>) -> Option<(usize, F)> {
+        self.nearest_neighbors.nearest_one_with_distance(point)
+    }
+
+    /// Finds all nodes in the tree within a radius of a point.
+    ///
+    /// Parameters:
+    /// - `point`: The center of the query.
+    /// - `radius`: The euclidean radius.
+    ///
+    /// Returns:
+    /// The indices of the nodes within the radius, in no particular order.
+    pub fn nodes_within(&self, point: &Point<F, N>, radius: F) -> Vec<usize> {
+        self.nearest_neighbors.within_radius(point, radius)
+    }
+
+    /// Returns the indices of the nodes on the path from the start to the goal, if a solution was found.
+    pub fn get_path_indices(&self) -> Option<Vec<usize>> {
+        self.solution.map(|solution| self.path_indices_to(solution))
+    }
+
+    /// Returns the path from the start to the goal, if a solution was found.
+    pub fn get_path(&self) -> Option<Vec<Point<F, N>>> {
+        self.solution.map(|solution| self.path_to(solution))
+    }
+
+    /// Returns the euclidean length of the path from the start to the goal, if a solution was found.
+    pub fn get_path_cost(&self) -> Option<F> {
+        self.solution.map(|solution| self.nodes[solution].cost)
+    }
+
+    /// Returns the euclidean length of the path from the root to the node at `index`.
+    pub fn node_cost(&self, index: usize) -> F {
+        self.nodes[index].cost
+    }
+
+    pub fn get_nearest_neighbors(&self) -> &NN {
+        &self.nearest_neighbors
+    }
+
+    pub fn get_sampling_distribution(&self) -> &SD {
+        &self.sampling_distribution
+    }
+
+    /// Returns a mutable reference to the sampling distribution, for example to change the sampled region.
+    /// Changes only affect future samples.
+    pub fn sampling_distribution_mut(&mut self) -> &mut SD {
+        &mut self.sampling_distribution
+    }
+
+    pub fn get_validity_checker(&self) -> &VC {
+        &self.validity_checker
+    }
+
+    /// Returns a mutable reference to the validity checker, for example to move obstacles.
+    ///
+    /// The existing tree and solution are not re-checked automatically, so they may pass through
+    /// obstacles after the checker changes. Call `invalidate_solution_if_blocked` to re-check only
+    /// the solution path, or `revalidate` to remove every node that became invalid.
+    pub fn validity_checker_mut(&mut self) -> &mut VC {
+        &mut self.validity_checker
+    }
+
+    pub fn get_steering(&self) -> &ST {
+        &self.steering
+    }
+
+    /// Returns a mutable reference to the steering function, for example to change the step size.
+    /// Changes only affect future extensions. Existing edges are kept.
+    pub fn steering_mut(&mut self) -> &mut ST {
+        &mut self.steering
+    }
+
+    /// Re-checks the current solution path against the validity checker and, if any point or edge on
+    /// it is no longer valid, replaces the solution with the cheapest other goal node whose path is
+    /// still valid. The solution is cleared if there is none. Goal nodes found blocked are forgotten.
+    ///
+    /// This is cheaper than `revalidate`, since only paths to goal nodes are checked, but the tree keeps
+    /// its invalid nodes. Solving again may therefore find a path through them; call `revalidate`
+    /// before solving again to avoid that.
+    ///
+    /// Returns:
+    /// True if the solution path was blocked.
+    pub fn invalidate_solution_if_blocked(&mut self) -> bool {
+        let Some(solution) = self.solution else {
+            return false;
+        };
+        if !self.is_path_blocked(solution) {
+            return false;
+        }
+
+        // Check the other goal nodes from the cheapest, and promote the first with a valid path.
+        let mut candidates = self.goal_nodes.clone();
+        candidates.retain(|&index| index != solution);
+        candidates.sort_by(|&a, &b| self.nodes[a].cost.partial_cmp(&self.nodes[b].cost).unwrap());
+        let mut blocked = vec![solution];
+        self.solution = None;
+        for index in candidates {
+            if self.is_path_blocked(index) {
+                blocked.push(index);
+            } else {
+                self.solution = Some(index);
+                break;
+            }
+        }
+        self.goal_nodes.retain(|index| !blocked.contains(index));
+        if self.solution.is_none() {
+            self.last_outcome = None;
+        }
+        true
+    }
+
+    /// Checks if any point or edge on the path from the root to the node at `index` is no longer valid.
+    fn is_path_blocked(&self, index: usize) -> bool {
+        let path = self.path_to(index);
+        path.iter()
+            .any(|point| !self.validity_checker.is_point_valid(point))
+            || path
+                .windows(2)
+                .any(|edge| !self.is_tree_edge_valid(&edge[0], &edge[1]))
+    }
+
+    pub fn get_goal_checker(&self) -> &GC {
+        &self.goal_checker
+    }
+
+    /// Returns the vector of nodes in the tree.
+    pub fn get_tree(&self) -> &Vec<Node<F, N, T>> {
+        &self.nodes
+    }
+
+    /// Inserts a path into the tree, for example the solution of a previous plan, to warm-start the planner.
+    ///
+    /// The path is attached to the node nearest to its first point and inserted as a chain of nodes.
+    /// Insertion stops at the first point or edge that is not valid, so no disconnected nodes are created.
+    /// It also stops when the tree reaches the maximum number of nodes.
+    /// Points that coincide with the node they would be attached to are skipped.
+    /// If an inserted node reaches the goal with a shorter path than the current solution, it becomes the solution.
+    /// In lazy edge checking mode, the path to such a node is checked first, and insertion stops if it is not valid.
+    ///
+    /// Parameters:
+    /// - `path`: The points of the path to insert.
+    ///
+    /// Returns:
+    /// The number of nodes added to the tree.
+    pub fn insert_path(&mut self, path: &[Point<F, N>]) -> usize {
+        let Some(first) = path.first() else {
+            return 0;
+        };
+        let mut parent_index = self.nearest_neighbors.nearest_one(first).unwrap();
+        let mut added = 0;
+
+        for point in path {
+            if self.node_limit_reached() {
+                break;
+            }
+            let parent_point = self.nodes[parent_index].point;
+            if euclidean_distance_squared(&parent_point, point) == F::zero() {
+                continue;
+            }
+            if !self.validity_checker.is_point_valid(point)
+                || !self.validity_checker.is_edge_valid(&parent_point, point)
+            {
+                break;
+            }
+
+            parent_index = self.add_child_node(parent_index, *point);
+            added += 1;
+
+            if self.goal_checker.is_goal(point) {
+                if !self.validate_lazy_path(parent_index) {
+                    break;
+                }
+                self.mark_solution(parent_index);
+            }
+        }
+        added
+    }
+
+    /// Re-checks the tree against the validity checker, for example after obstacles moved.
+    ///
+    /// Every node point and every edge to a parent is checked.
+    /// Invalid nodes are removed together with their entire subtrees, and the remaining nodes are re-indexed.
+    /// The roots are never removed. If the solution node is removed, the solution is cleared.
+    ///
+    /// Returns:
+    /// The number of nodes removed.
+    pub fn revalidate(&mut self) -> usize {
+        let mut keep = vec![false; self.nodes.len()];
+        for &root in &self.roots {
+            keep[root] = true;
+        }
+        let mut stack = self.roots.clone();
+        while let Some(index) = stack.pop() {
+            let point = self.nodes[index].point;
+            for &child in &self.nodes[index].children {
+                let child_point = &self.nodes[child].point;
+                if self.validity_checker.is_point_valid(child_point)
+                    && self.is_tree_edge_valid(&point, child_point)
+                {
+                    keep[child] = true;
+                    stack.push(child);
+                }
+            }
+        }
+        // Every kept edge was just checked, including edges added lazily.
+        for (node, &kept) in self.nodes.iter_mut().zip(&keep) {
+            if kept {
+                node.edge_unchecked = false;
+            }
+        }
+        self.retain_nodes(&keep)
+    }
+
+    /// Makes the node at `new_root_index` the root of its tree, for example to replan from the
+    /// current pose of the robot while keeping the rest of the tree.
+    ///
+    /// The parent pointers along the path from the old root to the new root are reversed,
+    /// so the old root and its other branches stay in the tree, now reached through the new root.
+    /// The costs are recomputed, and the solution becomes the goal node with the shortest path
+    /// from the new root. The nodes are re-indexed, and the new root takes the place of the old root
+    /// in `roots` (it is node 0 if the old root was node 0).
+    ///
+    /// Parameters:
+    /// - `new_root_index`: The index of the node to make the root.
+    /// - `discard_unreachable`: Whether to remove the nodes that are not reachable from the new root,
+    ///   which are the trees of the other starts.
+    ///
+    /// Returns:
+    /// The number of nodes removed.
+    pub fn reroot(&mut self, new_root_index: usize, discard_unreachable: bool) -> usize {
+        let path = self.path_indices_to(new_root_index);
+        let old_root = path[0];
+        let root_position = self
+            .roots
+            .iter()
+            .position(|&root| root == old_root)
+            .expect("every root is recorded");
+
+        // Reverse the edges along the path. Each edge moves from the child to the parent,
+        // so its lazy edge checking flag moves with it.
+        for pair in path.windows(2) {
+            let (parent, child) = (pair[0], pair[1]);
+            self.nodes[parent].parent = Some(child);
+            self.nodes[parent].edge_unchecked = self.nodes[child].edge_unchecked;
+        }
+        self.nodes[new_root_index].parent = None;
+        self.nodes[new_root_index].edge_unchecked = false;
+        self.roots[root_position] = new_root_index;
+        self.rebuild_index();
+
+        let mut removed = 0;
+        if discard_unreachable {
+            let mut keep = vec![false; self.nodes.len()];
+            keep[new_root_index] = true;
+            for descendant in self.descendants(new_root_index) {
+                keep[descendant] = true;
+            }
+            self.roots = vec![new_root_index];
+            removed = self.retain_nodes(&keep);
+        }
+
+        // Keep the root of the first start at index 0.
+        let first_root = self.roots[0];
+        if first_root != 0 {
+            self.swap_nodes(0, first_root);
+        }
+
+        self.solution = self
+            .goal_nodes
+            .iter()
+            .copied()
+            .min_by(|&a, &b| self.nodes[a].cost.partial_cmp(&self.nodes[b].cost).unwrap());
+        removed
+    }
+
+    /// Makes the node nearest to a point the root of its tree (see `reroot`).
+    ///
+    /// Parameters:
+    /// - `point`: The point, such as the current pose of the robot.
+    /// - `discard_unreachable`: Whether to remove the nodes that are not reachable from the new root.
+    ///
+    /// Returns:
+    /// The number of nodes removed.
+    pub fn reroot_at_point(&mut self, point: &Point<F, N>, discard_unreachable: bool) -> usize {
+        let (nearest_index, _) = self.nearest_node(point).unwrap();
+        self.reroot(nearest_index, discard_unreachable)
+    }
+
+    /// Combines the branches of two trees into a single path, for bidirectional planners built on two RRTs.
+    ///
+    /// The path follows tree `a` from its root to the node at `a_index`, then follows tree `b`
+    /// from the node at `b_index` back to its root. The edge between the two nodes is assumed to be valid.
+    /// If the two nodes are at the same point, the junction point appears only once.
+    ///
+    /// Parameters:
+    /// - `a`: The tree rooted at the start.
+    /// - `a_index`: The index of the connecting node in tree `a`.
+    /// - `b`: The tree rooted at the goal.
+    /// - `b_index`: The index of the connecting node in tree `b`.
+    ///
+    /// Returns:
+    /// The path from the root of `a` to the root of `b`, or an error if an index is out of range.
+    pub fn merge_paths<VC2, SD2, ST2, NN2, GC2, T2: Default>(
+        a: &Self,
+        a_index: usize,
+        b: &RRT<F, N, VC2, SD2, ST2, NN2, GC2, T2>,
+        b_index: usize,
+    ) -> Result<Vec<Point<F, N>>, &'static str>
+    where
+        VC2: ValidityChecker<F, N>,
+        SD2: SamplingDistribution<F, N>,
+        ST2: Steering<F, N>,
+        NN2: NearestNeighbors<F, N>,
+        GC2: GoalChecker<F, N>,
+    {
+        if a_index >= a.nodes.len() {
+            return Err("a_index is out of range");
+        }
+        if b_index >= b.nodes.len() {
+            return Err("b_index is out of range");
+        }
+
+        let mut path = a.path_to(a_index);
+        let mut b_branch = b.path_to(b_index);
+        b_branch.reverse();
+
+        let junction = path.last().unwrap().coords() == b_branch[0].coords();
+        path.extend(b_branch.into_iter().skip(junction as usize));
+        Ok(path)
+    }
+
+    /// Exports the nodes and the solution index of the tree.
+    pub fn export_tree(&self) -> TreeData<F, N, T>
+    where
+        T: Clone,
+    {
+        TreeData {
+            nodes: self.nodes.clone(),
+            solution: self.solution,
+            forest: self.roots.len() > 1,
+        }
+    }
+
+    /// Replaces the tree with previously exported tree data.
+    ///
+    /// The nearest neighbors data structure is rebuilt from the imported nodes.
+    /// The configuration of the planner (goal, validity checker, sampling distribution and steering) is kept.
+    ///
+    /// Parameters:
+    /// - `tree`: The tree data to import.
+    ///
+    /// Returns:
+    /// An error if node 0 is not a root, if another node is a root and the tree is not marked as a forest,
+    /// if any parent or solution index is out of range, or if following the parents from a node leads back to it.
+    /// The roots of a forest are taken as the starts in index order.
+    pub fn import_tree(&mut self, tree: TreeData<F, N, T>) -> Result<(), &'static str> {
+        if tree.nodes.is_empty() || tree.nodes[0].parent.is_some() {
+            return Err("Node 0 must be the root of the tree");
+        }
+        if !tree.forest && tree.nodes[1..].iter().any(|node| node.parent.is_none()) {
+            return Err("Node 0 must be the only root of the tree");
+        }
+        if tree
+            .nodes
+            .iter()
+            .any(|node| node.parent.is_some_and(|parent| parent >= tree.nodes.len()))
+        {
+            return Err("Parent index out of range");
+        }
+        if tree
+            .solution
+            .is_some_and(|solution| solution >= tree.nodes.len())
+        {
+            return Err("Solution index out of range");
+        }
+        if !parents_are_acyclic(&tree.nodes) {
+            return Err("Parent chain contains a cycle");
+        }
+
+        self.nodes = tree.nodes;
+        self.roots = (0..self.nodes.len())
+            .filter(|&index| self.nodes[index].parent.is_none())
+            .collect();
+        self.rebuild_index();
+        self.goal_nodes = (0..self.nodes.len())
+            .filter(|&index| self.goal_checker.is_goal(&self.nodes[index].point))
+            .collect();
+        self.solution = tree.solution;
+        Ok(())
+    }
+
+    /// Exports the tree as a Graphviz digraph in the DOT language.
+    ///
+    /// Each node is labeled with its index and coordinates, and there is an edge from each parent to its child.
+    /// If a solution was found, the nodes on the solution path are colored red.
+    ///
+    /// Parameters:
+    /// - `precision`: The number of decimal places used to format coordinates.
+    ///
+    /// Returns:
+    /// The DOT representation of the tree.
+    pub fn to_dot(&self, precision: usize) -> String {
+        let mut on_solution_path = vec![false; self.nodes.len()];
+        for index in self.get_path_indices().unwrap_or_default() {
+            on_solution_path[index] = true;
+        }
+
+        let mut dot = String::from("digraph rrt {\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let coords = node
+                .point
+                .coords()
+                .iter()
+                .map(|c| format!("{:.*}", precision, c.to_f64().unwrap_or(f64::NAN)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let color = if on_solution_path[index] {
+                ", color=red"
+            } else {
+                ""
+            };
+            writeln!(
+                dot,
+                "    {} [label=\"{}: ({})\"{}];",
+                index, index, coords, color
+            )
+            .unwrap();
+        }
+        for (index, node) in self.nodes.iter().enumerate() {
+            if let Some(parent) = node.parent {
+                let color = if on_solution_path[index] {
+                    " [color=red]"
+                } else {
+                    ""
+                };
+                writeln!(dot, "    {} -> {}{};", parent, index, color).unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Expands the tree by one iteration.
+    ///
+    /// Each iteration of the RRT algorithm consists of the following steps:
+    /// 1. Sample a point from the sampling distribution.
+    /// 2. Find the nearest node in the tree to the sample point.
+    /// 3. Steer the nearest node towards the sample point.
+    /// 4. Add the new node to as a child of the nearest node if the edge is valid.
+    ///    In greedy mode, steps 3 and 4 repeat from the new node until the sample is reached or an extension fails.
+    /// 5. If the goal is reached, update the solution node.
+    /// 6. Otherwise, if enabled, try to connect the new node directly to the goal.
+    ///
+    /// After the iteration, the sampling distribution receives feedback about the sample,
+    /// stalls are detected if enabled, and the progress handler receives a report if one is due.
+    ///
+    /// Returns an event describing the outcome of the iteration.
+    fn iteration(&mut self) -> IterationEvent<F, N> {
+        self.iteration_towards(None)
+    }
+
+    /// Runs a single iteration towards a sample that was already drawn,
+    /// or towards a new sample from the sampling distribution if it is None.
+    fn iteration_towards(&mut self, sample: Option<Point<F, N>>) -> IterationEvent<F, N> {
+        self.iterations += 1;
+        self.statistics.iterations += 1;
+
+        if self.node_limit_reached() {
+            return IterationEvent::NodeLimitReached;
+        }
+
+        let sample = sample.unwrap_or_else(|| self.sampling_distribution.sample());
+        let event = self.extend_towards(sample);
+        let node_added = matches!(
+            event,
+            IterationEvent::NodeAdded { .. } | IterationEvent::SolutionFound { .. }
+        );
+        self.send_feedback(node_added);
+        self.detect_stall(node_added);
+        self.report_progress();
+        event
+    }
+
+    /// Extends the tree towards a sampled point (steps 2 to 6 of an iteration).
+    fn extend_towards(&mut self, sample: Point<F, N>) -> IterationEvent<F, N> {
+        // Find the nearest node in the tree to the sample point.
+        let nearest_node_index = self.nearest_neighbors.nearest_one(&sample).unwrap();
+
+        // Steer the nearest node towards the sample point and add the new node if it is valid.
+        // In greedy mode, keep steering from the last added node until the sample is reached.
+        let mut parent_index = nearest_node_index;
+        let mut added: Option<(usize, Point<F, N>)> = None;
+        loop {
+            let parent_point = self.nodes[parent_index].point;
+            let (new_point, waypoints) = self.steer_edge(&parent_point, &sample);
+
+            if added.is_some() {
+                // Stop greedy extension if the steering function made no progress towards the sample.
+                // This also terminates the loop once the sample has been reached.
+                if euclidean_distance_squared(&new_point, &sample)
+                    >= euclidean_distance_squared(&parent_point, &sample)
+                    || self.node_limit_reached()
+                {
+                    break;
+                }
+            } else if euclidean_distance_squared(&new_point, &parent_point) == F::zero() {
+                // The sample coincides with an existing node, so there is nothing to add.
+                return IterationEvent::SampleRejected { sample };
+            }
+
+            // If the new point is too close to the tree, stop extending.
+            if self.violates_node_separation(&new_point) {
+                if added.is_none() {
+                    self.statistics.separation_rejections += 1;
+                    return IterationEvent::SampleRejected { sample };
+                }
+                break;
+            }
+
+            // If the new point or edge is invalid, stop extending.
+            if !self.validity_checker.is_point_valid(&new_point) {
+                self.sampling_distribution
+                    .extension_failed(&parent_point, &new_point);
+                if added.is_none() {
+                    self.statistics.point_rejections += 1;
+                    return IterationEvent::SampleRejected { sample };
+                }
+                break;
+            }
+            if !self.lazy_edge_checking
+                && !is_steered_edge_valid(
+                    &self.validity_checker,
+                    &parent_point,
+                    &new_point,
+                    waypoints.as_deref(),
+                )
+            {
+                self.sampling_distribution
+                    .extension_failed(&parent_point, &new_point);
+                if added.is_none() {
+                    self.statistics.edge_rejections += 1;
+                    return IterationEvent::SampleRejected { sample };
+                }
+                break;
+            }
+
+            // Add the new node to as a child of the parent node.
+            let new_node_index = self.add_child_node(parent_index, new_point);
+            self.nodes[new_node_index].edge_unchecked = self.lazy_edge_checking;
+            self.statistics.nodes_added += 1;
+
+            // If the goal is reached, update the solution node.
+            if self.goal_checker.is_goal(&new_point) {
+                // In lazy mode, an invalid edge on the path removes the new node.
+                if !self.validate_lazy_path(new_node_index) {
+                    return IterationEvent::SampleRejected { sample };
+                }
+                self.mark_solution(new_node_index);
+                return IterationEvent::SolutionFound {
+                    sample,
+                    index: new_node_index,
+                    point: new_point,
+                    parent: parent_index,
+                };
+            }
+
+            added = Some((new_node_index, new_point));
+            if self.extend_mode == ExtendMode::Single {
+                break;
+            }
+            parent_index = new_node_index;
+        }
+        let (new_node_index, new_point) = added.unwrap();
+        let parent = self.nodes[new_node_index].parent.unwrap();
+
+        // Optionally, try to connect the new node directly to the goal.
+        if self.connect_to_goal {
+            let tree_size = self.nodes.len();
+            if let Some((goal_index, goal_point)) = self.try_connect_to_goal(new_node_index) {
+                return IterationEvent::SolutionFound {
+                    sample,
+                    index: goal_index,
+                    point: goal_point,
+                    parent: new_node_index,
+                };
+            }
+            // In lazy mode, the connection may have found an invalid edge and removed the new node.
+            if self.nodes.len() < tree_size {
+                return IterationEvent::SampleRejected { sample };
+            }
+        }
+
+        IterationEvent::NodeAdded {
+            sample,
+            index: new_node_index,
+            point: new_point,
+            parent,
+        }
+    }
+
+    /// Attempts to steer from the node at `from_index` straight to a point sampled from the goal.
+    /// If the steered point reaches the goal and is valid, it is added as a child and marked as the solution.
+    ///
+    /// Returns:
+    /// The index and point of the added goal node, or None if the connection failed.
+    fn try_connect_to_goal(&mut self, from_index: usize) -> Option<(usize, Point<F, N>)> {
+        let goal = self.goal_checker.sample_goal()?;
+        let from_point = self.nodes[from_index].point;
+        let (goal_point, waypoints) = self.steer_edge(&from_point, &goal);
+
+        // Avoid adding duplicate nodes when an existing node already sits at the goal point.
+        let nearest_index = self.nearest_neighbors.nearest_one(&goal_point).unwrap();
+        if euclidean_distance_squared(&self.nodes[nearest_index].point, &goal_point) == F::zero() {
+            return None;
+        }
+
+        if self.node_limit_reached()
+            || !self.goal_checker.is_goal(&goal_point)
+            || !self.validity_checker.is_point_valid(&goal_point)
+            || !is_steered_edge_valid(
+                &self.validity_checker,
+                &from_point,
+                &goal_point,
+                waypoints.as_deref(),
+            )
+            || !self.validate_lazy_path(from_index)
+        {
+            return None;
+        }
+
+        let goal_index = self.add_child_node(from_index, goal_point);
+        self.statistics.nodes_added += 1;
+        self.mark_solution(goal_index);
+        Some((goal_index, goal_point))
+    }
+
+    /// Runs `try_direct_connection` before a solve, if enabled and no solution has been found.
+    ///
+    /// Returns:
+    /// The goal point the tree was connected to, or None if the direct connection was not found.
+    fn direct_connection_pre_check(&mut self) -> Option<Point<F, N>> {
+        if !self.direct_connection || self.solved() {
+            return None;
+        }
+        self.connect_directly()
+    }
+
+    /// Steers from `start` towards `goal` until the goal is reached.
+    ///
+    /// Returns:
+    /// The points after `start`, ending in the goal, or None if steering stalls, an edge or point
+    /// is not valid, or the points would exceed the node limit.
+    fn direct_chain(&self, start: &Point<F, N>, goal: &Point<F, N>) -> Option<Vec<Point<F, N>>> {
+        let remaining = self
+            .max_nodes
+            .map(|max_nodes| max_nodes.saturating_sub(self.nodes.len()));
+        let mut chain = Vec::new();
+        let mut current = *start;
+        loop {
+            if remaining.is_some_and(|remaining| chain.len() >= remaining) {
+                return None;
+            }
+            let (next, waypoints) = self.steer_edge(&current, goal);
+            if euclidean_distance_squared(&current, &next) == F::zero()
+                || !self.validity_checker.is_point_valid(&next)
+                || !is_steered_edge_valid(
+                    &self.validity_checker,
+                    &current,
+                    &next,
+                    waypoints.as_deref(),
+                )
+            {
+                return None;
+            }
+            chain.push(next);
+            if self.goal_checker.is_goal(&next) {
+                return Some(chain);
+            }
+            current = next;
+        }
+    }
+
+    /// Checks the unchecked edges on the path from the root to the node at `index`,
+    /// which were added in lazy edge checking mode.
+    ///
+    /// Valid edges are marked as checked. At the first edge that is not valid, the node it leads to
+    /// is removed together with its subtree, and the remaining nodes are re-indexed.
+    ///
+    /// Returns:
+    /// True if every edge on the path is valid.
+    fn validate_lazy_path(&mut self, index: usize) -> bool {
+        for node_index in self.path_indices_to(index) {
+            let node = &self.nodes[node_index];
+            if !node.edge_unchecked {
+                continue;
+            }
+            let parent_point = self.nodes[node.parent.unwrap()].point;
+            if self.is_tree_edge_valid(&parent_point, &node.point) {
+                self.nodes[node_index].edge_unchecked = false;
+            } else {
+                self.statistics.edge_rejections += 1;
+                let mut keep = vec![true; self.nodes.len()];
+                keep[node_index] = false;
+                for descendant in self.descendants(node_index) {
+                    keep[descendant] = false;
+                }
+                // Nodes removed with an invalid lazy edge do not count as added.
+                let removed = self.retain_nodes(&keep);
+                self.statistics.nodes_added =
+                    self.statistics.nodes_added.saturating_sub(removed as u64);
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Records that the node at `index` reached the goal.
+    /// It becomes the solution if its path is shorter than the path of the current solution.
+    fn mark_solution(&mut self, index: usize) {
+        if self.statistics.first_solution_iteration.is_none() {
+            self.statistics.first_solution_iteration = Some(self.statistics.iterations);
+        }
+        self.record_goal_node(index);
+    }
+
+    /// Adds the node at `index` to the goal nodes and updates the solution if its path is the shortest.
+    fn record_goal_node(&mut self, index: usize) {
+        self.goal_nodes.push(index);
+        let cost = self.nodes[index].cost;
+        let improves = self
+            .get_path_cost()
+            .is_none_or(|best_cost| cost < best_cost);
+        if improves {
+            self.solution = Some(index);
+        }
+    }
+
+    /// Checks if a new point is closer than the minimum node separation to the tree and does not reach the goal.
+    fn violates_node_separation(&self, point: &Point<F, N>) -> bool {
+        self.min_node_separation > F::zero()
+            && !self.goal_checker.is_goal(point)
+            && self
+                .nearest_node(point)
+                .is_some_and(|(_, distance)| distance < self.min_node_separation)
+    }
+
+    /// Steers from a point towards another.
+    ///
+    /// Returns:
+    /// The steered point, and the waypoints to check on the way if a waypoint resolution is set.
+    fn steer_edge(
+        &self,
+        from: &Point<F, N>,
+        to: &Point<F, N>,
+    ) -> (Point<F, N>, Option<Vec<Point<F, N>>>) {
+        match self.waypoint_resolution {
+            Some(resolution) => {
+                let (new_point, waypoints) =
+                    self.steering.steer_with_waypoints(from, to, resolution);
+                (new_point, Some(waypoints))
+            }
+            None => (self.steering.steer(from, to), None),
+        }
+    }
+
+    /// Checks an edge of the tree, steering it again if a waypoint resolution is set.
+    fn is_tree_edge_valid(&self, parent_point: &Point<F, N>, point: &Point<F, N>) -> bool {
+        let (_, waypoints) = self.steer_edge(parent_point, point);
+        is_steered_edge_valid(
+            &self.validity_checker,
+            parent_point,
+            point,
+            waypoints.as_deref(),
+        )
+    }
+
+    /// Records the outcome of a solve once it has terminated.
+    ///
+    /// Parameters:
+    /// - `iterations`: The number of iterations run by the solve.
+    ///
+    /// Returns:
+    /// The outcome of the solve.
+    fn record_outcome(&mut self, iterations: u32) -> SolveOutcome {
+        let outcome = if self.solved() {
+            SolveOutcome::Solved { iterations }
+        } else if self.node_limit_reached() {
+            SolveOutcome::NodeLimit
+        } else {
+            SolveOutcome::IterationLimit
+        };
+        self.last_outcome = Some(outcome);
+        outcome
+    }
+
+    /// Returns the indices of the nodes on the path from the root to the node at `index`.
+    fn path_indices_to(&self, index: usize) -> Vec<usize> {
+        let mut current_index = index;
+        let mut path = vec![current_index];
+
+        // Reconstruct the path by backtracking up the tree (following the parent pointers).
+        while let Some(parent_index) = self.nodes[current_index].parent {
+            path.push(parent_index);
+            current_index = parent_index;
+        }
+
+        // Reverse the path so that it goes from the root to the node.
+        path.reverse();
+        path
+    }
+
+    /// Returns the points on the path from the root to the node at `index`.
+    fn path_to(&self, index: usize) -> Vec<Point<F, N>> {
+        self.path_indices_to(index)
+            .iter()
+            .map(|&i| self.nodes[i].point)
+            .collect()
+    }
+
+    /// Adds a node to the tree and the nearest neighbors data structure.
+    fn add_node(&mut self, mut node: Node<F, N, T>) -> usize {
+        let index = self.nodes.len();
+        self.nearest_neighbors.add(*node.point(), index);
+        if let Some(parent) = node.parent {
+            let parent_node = &mut self.nodes[parent];
+            parent_node.add_child(index);
+            node.cost = parent_node.cost + euclidean_distance(&parent_node.point, &node.point);
+        } else {
+            node.cost = F::zero();
+            self.roots.push(index);
+        }
+        node.created_at = self.iterations;
+        if let Some(distance) = self.update_closest_approach(index, &node.point) {
+            if self.record_progress {
+                self.progress_history.push((self.iterations, distance));
+            }
+        }
+        self.nodes.push(node);
         index
     }
+
+    /// Records the node at `index` as the closest approach if `point` is closer to the goal.
+    ///
+    /// Returns:
+    /// The new closest distance to the goal, or None if it did not improve.
+    fn update_closest_approach(&mut self, index: usize, point: &Point<F, N>) -> Option<F> {
+        let distance = self.goal_checker.distance_to_goal(point)?;
+        let improves = self
+            .closest_approach
+            .is_none_or(|(_, best)| distance < best);
+        if improves {
+            self.closest_approach = Some((index, distance));
+        }
+        improves.then_some(distance)
+    }
+
+    /// Counts consecutive iterations without a new node, and handles a stall when there are too many.
+    fn detect_stall(&mut self, node_added: bool) {
+        let Some(stall_detection) = self.stall_detection else {
+            return;
+        };
+        if node_added {
+            self.failed_iterations = 0;
+            return;
+        }
+        self.failed_iterations += 1;
+        if self.failed_iterations < stall_detection.max_failed_iterations {
+            return;
+        }
+
+        let ranges_expanded = stall_detection
+            .expand_factor
+            .is_some_and(|factor| self.sampling_distribution.expand_ranges(factor));
+        self.statistics.stalls += 1;
+        if let Some(handler) = self.stall_handler.as_mut() {
+            handler(StallEvent {
+                iteration: self.iterations,
+                failed_iterations: self.failed_iterations,
+                ranges_expanded,
+            });
+        }
+        self.failed_iterations = 0;
+    }
+
+    /// Restarts the iteration count and the clock of the progress handler at the start of a solve.
+    fn start_progress(&mut self) {
+        if let Some(progress) = self.progress_handler.as_mut() {
+            progress.started = Instant::now();
+            progress.iterations = 0;
+        }
+    }
+
+    /// Counts an iteration and sends a progress report to the progress handler if one is due.
+    fn report_progress(&mut self) {
+        let best_goal_distance = self.best_goal_distance();
+        let Some(progress) = self.progress_handler.as_mut() else {
+            return;
+        };
+        progress.iterations += 1;
+        if progress.iterations % progress.interval == 0 {
+            (progress.handler)(ProgressReport {
+                iterations: progress.iterations,
+                node_count: self.nodes.len(),
+                best_goal_distance,
+                elapsed: progress.started.elapsed(),
+            });
+        }
+    }
+
+    /// Reports to the sampling distribution whether the last sample added a node and the best distance to the goal.
+    fn send_feedback(&mut self, node_added: bool) {
+        self.sampling_distribution.feedback(SampleFeedback {
+            node_added,
+            best_goal_distance: self.best_goal_distance(),
+        });
+    }
+
+    /// Adds a node at `point` as a child of the node at `parent_index`.
+    /// Its data is computed by the node data hook, or is `T::default()` if there is no hook.
+    fn add_child_node(&mut self, parent_index: usize, point: Point<F, N>) -> usize {
+        let data = match self.node_data_hook.as_mut() {
+            Some(hook) => hook(&self.nodes[parent_index], &point),
+            None => T::default(),
+        };
+        self.add_node(Node::with_data(point, Some(parent_index), data))
+    }
+
+    /// Removes all nodes for which `keep` is false and re-indexes the remaining nodes.
+    ///
+    /// The parents of kept nodes must also be kept. Parent pointers and the solution index are remapped,
+    /// and the solution is cleared if its node is removed.
+    ///
+    /// Returns:
+    /// The number of nodes removed.
+    fn retain_nodes(&mut self, keep: &[bool]) -> usize {
+        let mut new_indices = vec![None; self.nodes.len()];
+        let mut next_index = 0;
+        for (index, &kept) in keep.iter().enumerate() {
+            if kept {
+                new_indices[index] = Some(next_index);
+                next_index += 1;
+            }
+        }
+
+        let removed = self.nodes.len() - next_index;
+        if removed == 0 {
+            return 0;
+        }
+
+        let nodes = std::mem::take(&mut self.nodes);
+        self.nodes = nodes
+            .into_iter()
+            .zip(keep)
+            .filter(|(_, &kept)| kept)
+            .map(|(mut node, _)| {
+                node.parent = node
+                    .parent
+                    .map(|parent| new_indices[parent].expect("parent of a kept node must be kept"));
+                node
+            })
+            .collect();
+        self.solution = self.solution.and_then(|solution| new_indices[solution]);
+        self.roots = self
+            .roots
+            .iter()
+            .map(|&root| new_indices[root].expect("roots must be kept"))
+            .collect();
+        self.goal_nodes = self
+            .goal_nodes
+            .iter()
+            .filter_map(|&index| new_indices[index])
+            .collect();
+        self.rebuild_index();
+        removed
+    }
+
+    /// Exchanges the indices of the nodes at `a` and `b`, and re-indexes the tree.
+    fn swap_nodes(&mut self, a: usize, b: usize) {
+        let remap = |index: usize| {
+            if index == a {
+                b
+            } else if index == b {
+                a
+            } else {
+                index
+            }
+        };
+        self.nodes.swap(a, b);
+        for node in &mut self.nodes {
+            node.parent = node.parent.map(remap);
+        }
+        self.solution = self.solution.map(remap);
+        for index in self.roots.iter_mut().chain(self.goal_nodes.iter_mut()) {
+            *index = remap(*index);
+        }
+        self.rebuild_index();
+    }
+
+    /// Rebuilds the nearest neighbors data structure, the children lists and the costs
+    /// from the nodes and their parents.
+    fn rebuild_index(&mut self) {
+        self.nearest_neighbors = NN::new();
+        self.closest_approach = None;
+        for node in &mut self.nodes {
+            node.children.clear();
+        }
+        for index in 0..self.nodes.len() {
+            let point = self.nodes[index].point;
+            self.nearest_neighbors.add(point, index);
+            self.update_closest_approach(index, &point);
+            if let Some(parent) = self.nodes[index].parent {
+                self.nodes[parent].add_child(index);
+            }
+        }
+
+        // Propagate the costs down from the root, since parents may come after their children.
+        let mut stack = Vec::new();
+        for index in 0..self.nodes.len() {
+            if self.nodes[index].parent.is_none() {
+                self.nodes[index].cost = F::zero();
+                stack.push(index);
+            }
+        }
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let (point, cost) = (node.point, node.cost);
+            for child in node.children.clone() {
+                let child_node = &mut self.nodes[child];
+                child_node.cost = cost + euclidean_distance(&point, &child_node.point);
+                stack.push(child);
+            }
+        }
+    }
+}
+
+impl<F: Float, const N: usize, VC, SD, ST, NN, GC, T: Default> Planner<F, N>
+    for RRT<F, N, VC, SD, ST, NN, GC, T>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    ST: Steering<F, N>,
+    NN: NearestNeighbors<F, N>,
+    GC: GoalChecker<F, N>,
+{
+    fn solve(&mut self, max_iterations: u32) -> bool {
+        RRT::solve(self, max_iterations).is_solved()
+    }
+
+    fn solved(&self) -> bool {
+        RRT::solved(self)
+    }
+
+    fn get_path(&self) -> Option<Vec<Point<F, N>>> {
+        RRT::get_path(self)
+    }
+
+    fn get_tree_size(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<F: Float, const N: usize, VC, SD, ST, NN, GC, T: Default> RRT<F, N, VC, SD, ST, NN, GC, T>
+where
+    F: Send + Sync,
+    VC: ValidityChecker<F, N> + Sync,
+    SD: SamplingDistribution<F, N>,
+    ST: Steering<F, N>,
+    NN: NearestNeighbors<F, N>,
+    GC: GoalChecker<F, N>,
+{
+    /// Runs a batch of iterations, checking the validity of the new points and edges in parallel.
+    ///
+    /// All samples are drawn, connected to their nearest node and steered using the tree from the
+    /// start of the batch. The validity checks then run in parallel with rayon, and the valid points
+    /// are inserted into the tree serially so that parent indices stay consistent.
+    /// Each point is extended by a single step, regardless of the extend mode,
+    /// and no direct connection to the goal is attempted. Stalls are detected after each sample, in the order drawn.
+    /// Edges are always checked, even in lazy edge checking mode. If a new node reaches the goal
+    /// through an invalid lazily added edge, the rest of the batch is dropped, since the tree is re-indexed.
+    ///
+    /// Parameters:
+    /// - `batch_size`: The number of samples in the batch. Each processed sample counts as one iteration,
+    ///   so dropped samples, and the whole batch when the node limit is already reached, are not counted.
+    ///
+    /// Returns:
+    /// A summary of the batch, with its outcome and how the tree changed.
+    pub fn run_iterations_parallel(&mut self, batch_size: u32) -> RunSummary {
+        let before = self.statistics;
+        let first_iteration = self.iterations + 1;
+        let drawn = if self.node_limit_reached() {
+            0
+        } else {
+            batch_size
+        };
+
+        // Sample and steer serially, since the sampling distribution and the tree are not shared.
+        let mut candidates = Vec::with_capacity(drawn as usize);
+        for offset in 0..drawn as u64 {
+            let sample = self.sampling_distribution.sample();
+            let parent_index = self.nearest_neighbors.nearest_one(&sample).unwrap();
+            let parent_point = self.nodes[parent_index].point;
+            let (new_point, waypoints) = self.steer_edge(&parent_point, &sample);
+            if euclidean_distance_squared(&new_point, &parent_point) > F::zero() {
+                candidates.push((
+                    first_iteration + offset,
+                    parent_index,
+                    parent_point,
+                    new_point,
+                    waypoints,
+                ));
+            }
+        }
+
+        // Check the validity of the candidates in parallel.
+        let validity_checker = &self.validity_checker;
+        let checks: Vec<(bool, bool)> = candidates
+            .par_iter()
+            .map(|(_, _, parent_point, new_point, waypoints)| {
+                if !validity_checker.is_point_valid(new_point) {
+                    (false, false)
+                } else {
+                    (
+                        true,
+                        is_steered_edge_valid(
+                            validity_checker,
+                            parent_point,
+                            new_point,
+                            waypoints.as_deref(),
+                        ),
+                    )
+                }
+            })
+            .collect();
+
+        // Insert the valid points serially.
+        let mut processed = drawn;
+        let mut tree_reindexed = false;
+        let mut next_iteration = first_iteration;
+        for ((iteration, parent_index, parent_point, new_point, _), (point_valid, edge_valid)) in
+            candidates.into_iter().zip(checks)
+        {
+            // Samples that steered nowhere added no node either.
+            for skipped in next_iteration..iteration {
+                self.iterations = skipped;
+                self.detect_stall(false);
+            }
+            next_iteration = iteration + 1;
+            // Nodes and progress are recorded at the iteration of their sample.
+            self.iterations = iteration;
+            let node_added = 'insert: {
+                if !point_valid || !edge_valid {
+                    self.sampling_distribution
+                        .extension_failed(&parent_point, &new_point);
+                }
+                if !point_valid {
+                    self.statistics.point_rejections += 1;
+                    break 'insert false;
+                }
+                if !edge_valid {
+                    self.statistics.edge_rejections += 1;
+                    break 'insert false;
+                }
+                if self.violates_node_separation(&new_point) {
+                    self.statistics.separation_rejections += 1;
+                    break 'insert false;
+                }
+                // Samples in the same batch can steer to the same point, for example when the goal is sampled repeatedly.
+                let duplicate = self
+                    .nearest_neighbors
+                    .nearest_one_with_distance(&new_point)
+                    .is_some_and(|(_, distance)| distance == F::zero());
+                if duplicate || self.node_limit_reached() {
+                    break 'insert false;
+                }
+
+                let new_node_index = self.add_child_node(parent_index, new_point);
+                self.statistics.nodes_added += 1;
+                if self.goal_checker.is_goal(&new_point) {
+                    if !self.validate_lazy_path(new_node_index) {
+                        tree_reindexed = true;
+                        break 'insert false;
+                    }
+                    self.mark_solution(new_node_index);
+                }
+                true
+            };
+            self.send_feedback(node_added);
+            self.detect_stall(node_added);
+            if tree_reindexed {
+                processed = (iteration + 1 - first_iteration) as u32;
+                break;
+            }
+        }
+        if !tree_reindexed {
+            for skipped in next_iteration..first_iteration + drawn as u64 {
+                self.iterations = skipped;
+                self.detect_stall(false);
+            }
+        }
+        self.iterations = first_iteration - 1 + processed as u64;
+        self.statistics.iterations += processed as u64;
+
+        let after = self.statistics;
+        RunSummary {
+            outcome: self.record_outcome(processed),
+            iterations: processed,
+            nodes_added: after.nodes_added.saturating_sub(before.nodes_added),
+            samples_rejected: after.samples_rejected() - before.samples_rejected(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{BuildError, RRTBuilder};
+    use crate::collision::{EuclideanSphericalObstacleSet, Sphere};
+    use crate::goal::MultiPointGoal;
+    use crate::neighbors::KdTreeNearestNeighbors;
+    use crate::sampling::UniformDistribution;
+    use crate::steering::EuclideanSteering;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    type TestRRT = RRT<
+        f64,
+        2,
+        EuclideanSphericalObstacleSet<f64, 2>,
+        UniformDistribution<f64, 2, StdRng>,
+        EuclideanSteering<f64, 2>,
+        KdTreeNearestNeighbors<f64, 2>,
+    >;
+
+    fn sphere(x: f64, y: f64, radius: f64) -> Sphere<f64, 2> {
+        Sphere {
+            center: Point::new([x, y]),
+            radius,
+        }
+    }
+
+    /// A planner from (1, 1) to (9, 9) in a 10 by 10 square.
+    fn planner(spheres: Vec<Sphere<f64, 2>>, range: f64, seed: u64) -> TestRRT {
+        RRT::new(
+            Point::new([1.0, 1.0]),
+            Point::new([9.0, 9.0]),
+            0.5,
+            EuclideanSphericalObstacleSet::new(spheres),
+            UniformDistribution::new_with_rng([(0.0, 10.0); 2], StdRng::seed_from_u64(seed))
+                .unwrap(),
+            EuclideanSteering::new(range),
+        )
+    }
+
+    /// A planner from (1, 1) to (9, 9) that samples only within the given ranges.
+    fn planner_in(ranges: [(f64, f64); 2], range: f64, seed: u64) -> TestRRT {
+        RRT::new(
+            Point::new([1.0, 1.0]),
+            Point::new([9.0, 9.0]),
+            0.5,
+            EuclideanSphericalObstacleSet::new(Vec::new()),
+            UniformDistribution::new_with_rng(ranges, StdRng::seed_from_u64(seed)).unwrap(),
+            EuclideanSteering::new(range),
+        )
+    }
+
+    /// Samples a scripted sequence of points, repeating the last one.
+    #[cfg(feature = "parallel")]
+    struct ScriptedSamples(Vec<Point<f64, 2>>);
+
+    #[cfg(feature = "parallel")]
+    impl SamplingDistribution<f64, 2> for ScriptedSamples {
+        fn sample(&mut self) -> Point<f64, 2> {
+            if self.0.len() > 1 {
+                self.0.remove(0)
+            } else {
+                self.0[0]
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    fn scripted_planner(
+        spheres: Vec<Sphere<f64, 2>>,
+        samples: Vec<Point<f64, 2>>,
+        range: f64,
+    ) -> RRT<
+        f64,
+        2,
+        EuclideanSphericalObstacleSet<f64, 2>,
+        ScriptedSamples,
+        EuclideanSteering<f64, 2>,
+        KdTreeNearestNeighbors<f64, 2>,
+    > {
+        RRT::new(
+            Point::new([1.0, 1.0]),
+            Point::new([9.0, 9.0]),
+            0.5,
+            EuclideanSphericalObstacleSet::new(spheres),
+            ScriptedSamples(samples),
+            EuclideanSteering::new(range),
+        )
+    }
+
+    /// A planner from (1, 1) to (9, 9) whose tree is imported from the given points and parents.
+    fn tree_planner(
+        points: &[(f64, f64)],
+        parents: &[Option<usize>],
+        solution: Option<usize>,
+    ) -> TestRRT {
+        let mut nodes: Vec<Node<f64, 2>> = Vec::new();
+        for (&(x, y), &parent) in points.iter().zip(parents) {
+            let mut node = Node::new(Point::new([x, y]), parent);
+            if let Some(parent) = parent {
+                let parent: &Node<f64, 2> = &nodes[parent];
+                node.set_cost(parent.cost() + (x - parent.point()[0]).hypot(y - parent.point()[1]));
+            }
+            nodes.push(node);
+        }
+        let mut rrt = planner(Vec::new(), 0.5, 1);
+        rrt.import_tree(TreeData {
+            nodes,
+            solution,
+            forest: parents.iter().filter(|parent| parent.is_none()).count() > 1,
+        })
+        .unwrap();
+        rrt
+    }
+
+    /// Returns the index of the node at a point.
+    fn index_at(rrt: &TestRRT, x: f64, y: f64) -> usize {
+        rrt.get_tree()
+            .iter()
+            .position(|node| node.point().coords() == &[x, y])
+            .unwrap()
+    }
+
+    fn assert_same_path(a: &[Point<f64, 2>], b: &[Point<f64, 2>]) {
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(b) {
+            assert_eq!(a.coords(), b.coords());
+        }
+    }
+
+    #[test]
+    fn import_tree_round_trips_exported_tree() {
+        let mut rrt = planner(vec![sphere(5.0, 5.0, 2.0)], 0.5, 1);
+        assert!(rrt.solve(10_000).is_solved());
+        let exported = rrt.export_tree();
+
+        let mut imported = planner(vec![sphere(5.0, 5.0, 2.0)], 0.5, 2);
+        assert!(!exported.forest);
+        imported.import_tree(exported.clone()).unwrap();
+        assert_eq!(imported.get_tree().len(), exported.nodes.len());
+        assert!(imported.solved());
+        assert_same_path(&imported.get_path().unwrap(), &rrt.get_path().unwrap());
+
+        // The nearest neighbors structure is rebuilt, so the imported tree keeps growing.
+        let size = imported.get_tree().len();
+        imported.run_iterations(100);
+        assert!(imported.get_tree().len() > size);
+    }
+
+    #[test]
+    fn import_tree_rejects_invalid_trees() {
+        let point = |x: f64| Point::new([x, x]);
+        let tree = |nodes: Vec<Node<f64, 2>>, solution| TreeData {
+            nodes,
+            solution,
+            forest: false,
+        };
+        let mut rrt = planner(Vec::new(), 0.5, 1);
+
+        assert!(rrt.import_tree(tree(Vec::new(), None)).is_err());
+        assert!(rrt
+            .import_tree(tree(vec![Node::new(point(0.0), Some(0))], None))
+            .is_err());
+        assert!(rrt
+            .import_tree(tree(
+                vec![Node::new(point(0.0), None), Node::new(point(1.0), Some(5))],
+                None
+            ))
+            .is_err());
+        assert!(rrt
+            .import_tree(tree(vec![Node::new(point(0.0), None)], Some(1)))
+            .is_err());
+
+        // A node that is its own parent.
+        assert!(rrt
+            .import_tree(tree(
+                vec![Node::new(point(0.0), None), Node::new(point(1.0), Some(1))],
+                None
+            ))
+            .is_err());
+
+        // Two nodes that are each other's parent.
+        let cycle = vec![
+            Node::new(point(0.0), None),
+            Node::new(point(1.0), Some(2)),
+            Node::new(point(2.0), Some(1)),
+        ];
+        assert!(rrt.import_tree(tree(cycle, Some(1))).is_err());
+
+        // A second root in a tree that is not marked as a forest.
+        let two_roots = vec![Node::new(point(0.0), None), Node::new(point(1.0), None)];
+        assert!(rrt.import_tree(tree(two_roots, None)).is_err());
+
+        // A failed import leaves the tree unchanged.
+        assert_eq!(rrt.get_tree().len(), 1);
+        assert_eq!(rrt.get_tree()[0].point().coords(), &[1.0, 1.0]);
+    }
+
+    #[test]
+    fn stall_widens_ranges_until_goal_is_reached() {
+        // The sampled square excludes the goal, and the node separation lets the tree fill it.
+        let mut rrt = planner_in([(0.0, 2.0); 2], 0.5, 3);
+        rrt.set_min_node_separation(0.3);
+        rrt.set_stall_detection(Some(StallDetection {
+            max_failed_iterations: 50,
+            expand_factor: Some(2.0),
+        }))
+        .unwrap();
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        rrt.set_stall_handler(move |event| recorded.lock().unwrap().push(event));
+
+        assert!(rrt.solve(20_000).is_solved());
+        let events = events.lock().unwrap();
+        assert!(!events.is_empty());
+        assert!(events.iter().all(|event| event.ranges_expanded));
+        assert_eq!(rrt.statistics().stalls, events.len() as u64);
+        let ranges = rrt.get_sampling_distribution().ranges();
+        assert!(ranges.iter().all(|&(min, max)| min <= 9.0 && max >= 9.0));
+    }
+
+    #[test]
+    fn stall_detection_rejects_invalid_expand_factors() {
+        let mut rrt = planner_in([(0.0, 10.0); 2], 0.5, 1);
+        for factor in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let stall_detection = StallDetection {
+                max_failed_iterations: 10,
+                expand_factor: Some(factor),
+            };
+            assert!(rrt.set_stall_detection(Some(stall_detection)).is_err());
+        }
+        assert!(rrt.stall_detection().is_none());
+        let stall_detection = StallDetection {
+            max_failed_iterations: 10,
+            expand_factor: None,
+        };
+        assert!(rrt.set_stall_detection(Some(stall_detection)).is_ok());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_batch_counts_only_processed_samples() {
+        // The first node is added lazily through the sphere, and the batch then reaches the goal from it.
+        let samples = vec![Point::new([9.0, 8.2]), Point::new([9.0, 9.0])];
+        let mut rrt = scripted_planner(vec![sphere(5.0, 5.0, 2.0)], samples, 20.0);
+        rrt.set_lazy_edge_checking(true);
+        rrt.run_iterations(1);
+        assert_eq!(rrt.get_tree().len(), 2);
+
+        // The first sample of the batch finds the invalid edge, which drops the rest of the batch.
+        let summary = rrt.run_iterations_parallel(5);
+        assert_eq!(summary.iterations, 1);
+        assert_eq!(rrt.iterations_run(), 2);
+        assert_eq!(rrt.statistics().iterations, 2);
+        assert!(!rrt.solved());
+
+        // A full batch counts every sample, and a batch at the node limit counts none.
+        let mut rrt = planner(Vec::new(), 0.5, 1);
+        assert_eq!(rrt.run_iterations_parallel(20).iterations, 20);
+        assert_eq!(rrt.iterations_run(), 20);
+        rrt.set_max_nodes(Some(rrt.get_tree().len()));
+        assert_eq!(rrt.run_iterations_parallel(20).iterations, 0);
+        assert_eq!(rrt.iterations_run(), 20);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_batches_detect_stalls() {
+        let mut rrt = planner_in([(0.0, 2.0); 2], 0.5, 3);
+        rrt.set_min_node_separation(0.3);
+        rrt.set_stall_detection(Some(StallDetection {
+            max_failed_iterations: 50,
+            expand_factor: Some(2.0),
+        }))
+        .unwrap();
+        for _ in 0..200 {
+            if rrt.solved() {
+                break;
+            }
+            rrt.run_iterations_parallel(100);
+        }
+        assert!(rrt.solved());
+        assert!(rrt.statistics().stalls > 0);
+    }
+
+    /// Points are all valid, but only edges moving up and to the right are,
+    /// so most edges added without checking are invalid.
+    struct MonotoneEdges;
+
+    impl ValidityChecker<f64, 2> for MonotoneEdges {
+        fn is_point_valid(&self, _point: &Point<f64, 2>) -> bool {
+            true
+        }
+
+        fn is_edge_valid(&self, a: &Point<f64, 2>, b: &Point<f64, 2>) -> bool {
+            b[0] >= a[0] && b[1] >= a[1]
+        }
+    }
+
+    fn lazy_monotone_planner(
+        seed: u64,
+    ) -> RRT<
+        f64,
+        2,
+        MonotoneEdges,
+        UniformDistribution<f64, 2, StdRng>,
+        EuclideanSteering<f64, 2>,
+        KdTreeNearestNeighbors<f64, 2>,
+    > {
+        let mut rrt = RRT::new(
+            Point::new([1.0, 1.0]),
+            Point::new([9.0, 9.0]),
+            0.5,
+            MonotoneEdges,
+            UniformDistribution::new_with_rng([(0.0, 10.0); 2], StdRng::seed_from_u64(seed))
+                .unwrap(),
+            EuclideanSteering::new(1.0),
+        );
+        rrt.set_lazy_edge_checking(true);
+        rrt
+    }
+
+    #[test]
+    fn lazy_nodes_added_excludes_removed_subtrees() {
+        let mut rrt = lazy_monotone_planner(4);
+        assert!(rrt.solve(100_000).is_solved());
+
+        let tree = rrt.get_tree();
+        let invalid = tree
+            .iter()
+            .filter(|node| {
+                node.parent.is_some_and(|parent| {
+                    !MonotoneEdges.is_edge_valid(&tree[parent].point, &node.point)
+                })
+            })
+            .count();
+        assert!(2 * invalid > tree.len());
+        assert!(rrt.statistics().edge_rejections > 0);
+        assert_eq!(rrt.statistics().nodes_added, tree.len() as u64 - 1);
+        let path = rrt.get_path().unwrap();
+        for edge in path.windows(2) {
+            assert!(MonotoneEdges.is_edge_valid(&edge[0], &edge[1]));
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_lazy_nodes_added_excludes_removed_subtrees() {
+        let mut rrt = lazy_monotone_planner(4);
+        // Grow an unchecked tree first, so the batches reach the goal through invalid edges.
+        rrt.run_iterations(2_000);
+        assert!(!rrt.solved());
+        while !rrt.solved() {
+            rrt.run_iterations_parallel(64);
+            assert_eq!(
+                rrt.statistics().nodes_added,
+                rrt.get_tree().len() as u64 - 1
+            );
+        }
+    }
+
+    #[test]
+    fn blocked_solution_falls_back_to_valid_goal_node() {
+        // Goal nodes at 2 and 5 are reached through (1, 9), and the goal node at 4 through (9, 1).
+        let points = [
+            (1.0, 1.0),
+            (1.0, 9.0),
+            (8.8, 9.1),
+            (9.0, 1.0),
+            (9.1, 8.6),
+            (8.7, 9.3),
+        ];
+        let parents = [None, Some(0), Some(1), Some(0), Some(3), Some(1)];
+        let mut rrt = tree_planner(&points, &parents, Some(4));
+        assert!(!rrt.invalidate_solution_if_blocked());
+
+        // The cheapest other goal node with a valid path is promoted.
+        rrt.validity_checker_mut().add_sphere(sphere(5.0, 1.0, 0.5));
+        assert!(rrt.invalidate_solution_if_blocked());
+        assert_eq!(rrt.solution, Some(5));
+        assert_eq!(rrt.goal_nodes, vec![2, 5]);
+
+        rrt.validity_checker_mut().add_sphere(sphere(1.0, 5.0, 0.5));
+        assert!(rrt.invalidate_solution_if_blocked());
+        assert!(!rrt.solved());
+        assert!(rrt.goal_nodes.is_empty());
+        assert!(!rrt.invalidate_solution_if_blocked());
+    }
+
+    #[test]
+    fn blocked_start_never_contributes_to_the_solution() {
+        // The first start is enclosed by a ring of overlapping spheres.
+        let blocked = Point::new([2.0, 8.0]);
+        let ring: Vec<_> = (0..16)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::PI / 8.0;
+                sphere(2.0 + angle.cos(), 8.0 + angle.sin(), 0.3)
+            })
+            .collect();
+        let mut rrt: TestRRT = RRT::with_starts(
+            vec![blocked, Point::new([1.0, 1.0])],
+            PointGoal::new(Point::new([9.0, 9.0]), 0.5),
+            EuclideanSphericalObstacleSet::new(ring),
+            UniformDistribution::new_with_rng([(0.0, 10.0); 2], StdRng::seed_from_u64(5)).unwrap(),
+            EuclideanSteering::new(0.5),
+        )
+        .unwrap();
+        assert!(rrt.solve(10_000).is_solved());
+        assert_eq!(rrt.solution_root(), Some(1));
+        assert_eq!(rrt.get_path().unwrap()[0].coords(), &[1.0, 1.0]);
+
+        // The blocked start grew, but only inside the ring.
+        let enclosed: Vec<_> = (0..rrt.get_tree().len())
+            .filter(|&index| rrt.root_of(index) == 0)
+            .collect();
+        assert!(enclosed.len() > 1);
+        for index in enclosed {
+            assert!(euclidean_distance(rrt.get_tree()[index].point(), &blocked) < 1.0);
+        }
+    }
+
+    /// Returns the point of the parent of the node at a point.
+    fn parent_at(rrt: &TestRRT, x: f64, y: f64) -> Option<[f64; 2]> {
+        let node = &rrt.get_tree()[index_at(rrt, x, y)];
+        node.parent()
+            .map(|parent| *rrt.get_tree()[parent].point().coords())
+    }
+
+    #[test]
+    fn reroot_reverses_a_chain() {
+        let points = [(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (3.0, 0.0), (4.0, 0.0)];
+        let parents = [None, Some(0), Some(1), Some(2), Some(3)];
+        let mut rrt = tree_planner(&points, &parents, None);
+        assert_eq!(rrt.reroot(2, false), 0);
+
+        assert_eq!(rrt.get_tree().len(), 5);
+        assert_eq!(rrt.roots(), &[0]);
+        assert_eq!(rrt.get_tree()[0].point().coords(), &[2.0, 0.0]);
+        assert_eq!(parent_at(&rrt, 1.0, 0.0), Some([2.0, 0.0]));
+        assert_eq!(parent_at(&rrt, 0.0, 0.0), Some([1.0, 0.0]));
+        assert_eq!(parent_at(&rrt, 3.0, 0.0), Some([2.0, 0.0]));
+        assert_eq!(parent_at(&rrt, 4.0, 0.0), Some([3.0, 0.0]));
+        for (x, cost) in [(0.0, 2.0), (1.0, 1.0), (2.0, 0.0), (3.0, 1.0), (4.0, 2.0)] {
+            assert_eq!(rrt.node_cost(index_at(&rrt, x, 0.0)), cost);
+        }
+    }
+
+    #[test]
+    fn reroot_keeps_the_branches_of_a_tree() {
+        // A branch off the path to the new root at (1, 1), a branch off the old root to the goal,
+        // and the tree of a second start at (5, 5).
+        let points = [
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (1.0, 1.0),
+            (0.0, 1.0),
+            (9.0, 9.0),
+            (5.0, 5.0),
+            (5.0, 6.0),
+        ];
+        let parents = [
+            None,
+            Some(0),
+            Some(1),
+            Some(1),
+            Some(0),
+            Some(4),
+            None,
+            Some(6),
+        ];
+        let mut rrt = tree_planner(&points, &parents, Some(5));
+        assert_eq!(rrt.reroot(2, false), 0);
+        assert_eq!(rrt.roots().len(), 2);
+        assert_eq!(parent_at(&rrt, 1.0, 1.0), Some([1.0, 0.0]));
+        assert_eq!(parent_at(&rrt, 0.0, 1.0), Some([0.0, 0.0]));
+        assert_eq!(rrt.node_cost(index_at(&rrt, 1.0, 1.0)), 2.0);
+        assert_eq!(rrt.node_cost(index_at(&rrt, 0.0, 1.0)), 3.0);
+
+        // The solution now starts at the new root and runs back through the old root.
+        let expected: Vec<_> = [(2.0, 0.0), (1.0, 0.0), (0.0, 0.0), (0.0, 1.0), (9.0, 9.0)]
+            .iter()
+            .map(|&(x, y)| Point::new([x, y]))
+            .collect();
+        assert_same_path(&rrt.get_path().unwrap(), &expected);
+
+        // Discarding the unreachable nodes removes the tree of the second start.
+        let new_root = index_at(&rrt, 2.0, 0.0);
+        assert_eq!(rrt.reroot(new_root, true), 2);
+        assert_eq!(rrt.roots(), &[0]);
+        assert_eq!(rrt.get_tree().len(), 6);
+        assert_same_path(&rrt.get_path().unwrap(), &expected);
+    }
+
+    #[test]
+    fn short_direct_connection_solves_without_sampling() {
+        let mut rrt = planner(Vec::new(), 20.0, 1);
+        rrt.set_direct_connection(true);
+        assert_eq!(rrt.solve(100), SolveOutcome::Solved { iterations: 0 });
+        assert_eq!(rrt.get_tree().len(), 2);
+        let expected = [Point::new([1.0, 1.0]), Point::new([9.0, 9.0])];
+        assert_same_path(&rrt.get_path().unwrap(), &expected);
+    }
+
+    #[test]
+    fn long_direct_connection_inserts_nodes_spaced_by_the_range() {
+        let mut rrt = planner(Vec::new(), 1.0, 1);
+        rrt.set_direct_connection(true);
+        assert_eq!(rrt.solve(100), SolveOutcome::Solved { iterations: 0 });
+
+        // The goal is 8 * sqrt(2) away, so 11 steps along the diagonal reach its tolerance.
+        let path = rrt.get_path().unwrap();
+        assert_eq!(path.len(), 12);
+        assert_eq!(rrt.get_tree().len(), 12);
+        for pair in path.windows(2) {
+            let distance = euclidean_distance_squared(&pair[0], &pair[1]).sqrt();
+            assert!((distance - 1.0).abs() < 1e-9);
+            assert!((pair[1][0] - pair[1][1]).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn blocked_direct_connection_leaves_the_tree_unchanged() {
+        let mut rrt = planner(vec![sphere(5.0, 5.0, 1.0)], 1.0, 1);
+        assert!(!rrt.try_direct_connection());
+        assert_eq!(rrt.get_tree().len(), 1);
+
+        rrt.set_direct_connection(true);
+        assert_eq!(rrt.solve(1), SolveOutcome::IterationLimit);
+        assert!(rrt.get_tree().len() <= 2);
+    }
+
+    #[test]
+    fn long_direct_connection_fails_beyond_the_node_limit() {
+        let mut rrt = planner(Vec::new(), 1.0, 1);
+        rrt.set_max_nodes(Some(5));
+        assert!(!rrt.try_direct_connection());
+        assert_eq!(rrt.get_tree().len(), 1);
+        assert!(!rrt.solved());
+    }
+
+    #[test]
+    fn connecting_to_goal_takes_fewer_iterations() {
+        const MAX_ITERATIONS: u32 = 5_000;
+        let spheres = vec![
+            sphere(3.0, 3.0, 1.0),
+            sphere(5.0, 6.0, 1.5),
+            sphere(7.0, 3.0, 1.0),
+        ];
+        let iterations = |connect_to_goal: bool, seed: u64| {
+            let mut rrt: TestRRT = RRT::new(
+                Point::new([1.0, 1.0]),
+                Point::new([9.0, 9.0]),
+                0.05,
+                EuclideanSphericalObstacleSet::new(spheres.clone()),
+                UniformDistribution::new_with_rng([(0.0, 10.0); 2], StdRng::seed_from_u64(seed))
+                    .unwrap(),
+                EuclideanSteering::new(1.0),
+            );
+            rrt.set_connect_to_goal(connect_to_goal);
+            match rrt.solve(MAX_ITERATIONS) {
+                SolveOutcome::Solved { iterations } => {
+                    // The goal node is linked into the tree, so the path reaches it from the start.
+                    let path = rrt.get_path().unwrap();
+                    assert_eq!(path[0].coords(), &[1.0, 1.0]);
+                    let end = path.last().unwrap();
+                    assert!((end[0] - 9.0).hypot(end[1] - 9.0) <= 0.05);
+                    iterations
+                }
+                _ => MAX_ITERATIONS,
+            }
+        };
+
+        let mut with_connection = 0;
+        let mut without_connection = 0;
+        for seed in 0..5 {
+            let connected = iterations(true, seed);
+            assert!(connected < MAX_ITERATIONS);
+            with_connection += connected;
+            without_connection += iterations(false, seed);
+        }
+        assert!(
+            with_connection * 5 < without_connection,
+            "{with_connection} iterations with goal connection, {without_connection} without"
+        );
+    }
+
+    #[test]
+    fn tree_stops_growing_at_the_node_limit() {
+        // The goal is inside a sphere, so it is never reached.
+        let mut rrt = planner(vec![sphere(9.0, 9.0, 1.0)], 0.5, 1);
+        rrt.set_max_nodes(Some(50));
+        assert_eq!(rrt.solve(10_000), SolveOutcome::NodeLimit);
+        assert_eq!(rrt.get_tree().len(), 50);
+        assert!(rrt.node_limit_reached());
+        assert!(rrt.iterations_run() < 10_000);
+
+        // Further solves and steps add no nodes.
+        assert_eq!(rrt.solve(10_000), SolveOutcome::NodeLimit);
+        for _ in 0..100 {
+            rrt.step();
+        }
+        assert_eq!(rrt.get_tree().len(), 50);
+        assert!(rrt.get_path().is_none());
+    }
+
+    #[test]
+    fn dot_export_round_trips() {
+        let mut rrt = planner(vec![sphere(5.0, 5.0, 2.0)], 0.5, 1);
+        assert!(rrt.solve(10_000).is_solved());
+        let dot = rrt.to_dot(3);
+
+        let mut lines: Vec<&str> = dot.lines().collect();
+        assert_eq!(lines.remove(0), "digraph rrt {");
+        assert_eq!(lines.pop(), Some("}"));
+        let mut labels = Vec::new();
+        let mut edges = Vec::new();
+        let mut red = Vec::new();
+        for line in lines {
+            let line = line.trim().strip_suffix(';').unwrap();
+            if let Some((parent, child)) = line.split_once(" -> ") {
+                let child: usize = child.split(' ').next().unwrap().parse().unwrap();
+                edges.push((parent.parse::<usize>().unwrap(), child));
+            } else {
+                let (index, attributes) = line.split_once(' ').unwrap();
+                let index: usize = index.parse().unwrap();
+                let label = attributes.split('"').nth(1).unwrap();
+                let coords = label
+                    .split_once(": (")
+                    .unwrap()
+                    .1
+                    .strip_suffix(')')
+                    .unwrap();
+                let coords: Vec<f64> = coords.split(", ").map(|c| c.parse().unwrap()).collect();
+                labels.push((index, coords));
+                if attributes.contains("color=red") {
+                    red.push(index);
+                }
+            }
+        }
+
+        let nodes = rrt.get_tree();
+        assert_eq!(labels.len(), nodes.len());
+        assert_eq!(edges.len(), nodes.len() - 1);
+        for (index, coords) in labels {
+            for (parsed, actual) in coords.iter().zip(nodes[index].point().coords()) {
+                assert!((parsed - actual).abs() <= 5e-4);
+            }
+        }
+        for (parent, child) in edges {
+            assert_eq!(nodes[child].parent(), Some(parent));
+        }
+        let mut path = rrt.get_path_indices().unwrap();
+        path.sort_unstable();
+        assert_eq!(red, path);
+    }
+
+    /// Data attached to each node of a test planner: its depth in the tree and the heading of its edge.
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct Step {
+        depth: usize,
+        heading: f64,
+    }
+
+    #[test]
+    fn node_data_follows_the_solution_path() {
+        let mut rrt: RRT<_, 2, _, _, _, KdTreeNearestNeighbors<f64, 2>, PointGoal<f64, 2>, Step> =
+            RRT::new(
+                Point::new([1.0, 1.0]),
+                Point::new([9.0, 9.0]),
+                0.5,
+                EuclideanSphericalObstacleSet::new(vec![sphere(5.0, 5.0, 2.0)]),
+                UniformDistribution::new_with_rng([(0.0, 10.0); 2], StdRng::seed_from_u64(1))
+                    .unwrap(),
+                EuclideanSteering::new(0.5),
+            );
+        rrt.set_connect_to_goal(true);
+        rrt.set_node_data_hook(|parent: &Node<f64, 2, Step>, point: &Point<f64, 2>| Step {
+            depth: parent.data().depth + 1,
+            heading: (point[1] - parent.point()[1]).atan2(point[0] - parent.point()[0]),
+        });
+        assert!(rrt.solve(10_000).is_solved());
+
+        let indices = rrt.get_path_indices().unwrap();
+        assert_eq!(rrt.get_tree()[indices[0]].data(), &Step::default());
+        for (depth, pair) in indices.windows(2).enumerate() {
+            let (parent, child) = (&rrt.get_tree()[pair[0]], &rrt.get_tree()[pair[1]]);
+            let heading =
+                (child.point()[1] - parent.point()[1]).atan2(child.point()[0] - parent.point()[0]);
+            assert_eq!(
+                child.data(),
+                &Step {
+                    depth: depth + 1,
+                    heading
+                }
+            );
+        }
+
+        let goal = *indices.last().unwrap();
+        rrt.node_data_mut(goal).heading = 0.0;
+        assert_eq!(rrt.get_tree()[goal].data().heading, 0.0);
+        assert_eq!(rrt.get_tree()[goal].data().depth, indices.len() - 1);
+    }
+
+    #[test]
+    fn blocked_samples_are_all_counted_as_rejections() {
+        // A ring of spheres encloses the start, and one more sphere covers some of the points
+        // steered towards the sampled region, so every sample is rejected by a point or an edge check.
+        let mut spheres: Vec<_> = (0..16)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::PI / 8.0;
+                sphere(1.0 + 0.5 * angle.cos(), 1.0 + 0.5 * angle.sin(), 0.3)
+            })
+            .collect();
+        let diagonal = std::f64::consts::FRAC_1_SQRT_2;
+        spheres.push(sphere(1.0 + diagonal, 1.0 + diagonal, 0.3));
+        let mut rrt: TestRRT = RRT::new(
+            Point::new([1.0, 1.0]),
+            Point::new([9.0, 9.0]),
+            0.5,
+            EuclideanSphericalObstacleSet::new(spheres),
+            UniformDistribution::new_with_rng([(5.0, 10.0); 2], StdRng::seed_from_u64(1)).unwrap(),
+            EuclideanSteering::new(1.0),
+        );
+        assert_eq!(rrt.solve(500), SolveOutcome::IterationLimit);
+
+        let statistics = *rrt.statistics();
+        assert_eq!(statistics.iterations, 500);
+        assert!(statistics.point_rejections > 0);
+        assert!(statistics.edge_rejections > 0);
+        assert_eq!(
+            statistics.point_rejections + statistics.edge_rejections,
+            500
+        );
+        assert_eq!(statistics.samples_rejected(), 500);
+        assert_eq!(statistics.nodes_added, 0);
+        assert_eq!(statistics.first_solution_iteration, None);
+        assert_eq!(rrt.get_tree().len(), 1);
+
+        rrt.reset_statistics();
+        let statistics = rrt.statistics();
+        assert_eq!(statistics.iterations, 0);
+        assert_eq!(statistics.samples_rejected(), 0);
+        assert_eq!(statistics.nodes_added, 0);
+    }
+
+    /// Checks that the cost of every node is the length of the path to it from its root.
+    fn assert_costs_match_paths(rrt: &TestRRT) {
+        for index in 0..rrt.get_tree().len() {
+            let path = rrt.path_indices_to(index);
+            let length: f64 = path
+                .windows(2)
+                .map(|edge| {
+                    euclidean_distance(
+                        rrt.get_tree()[edge[0]].point(),
+                        rrt.get_tree()[edge[1]].point(),
+                    )
+                })
+                .sum();
+            assert!((rrt.node_cost(index) - length).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn node_costs_are_path_lengths() {
+        let mut rrt = planner(vec![sphere(5.0, 5.0, 2.0)], 0.5, 1);
+        assert!(rrt.solve(10_000).is_solved());
+        assert_costs_match_paths(&rrt);
+        assert_eq!(
+            rrt.get_path_cost(),
+            Some(rrt.node_cost(rrt.solution_index().unwrap()))
+        );
+
+        // Rerooting at a node halfway along the solution path reverses the edges before it.
+        let path = rrt.get_path_indices().unwrap();
+        let removed = rrt.reroot(path[path.len() / 2], false);
+        assert_eq!(removed, 0);
+        assert_costs_match_paths(&rrt);
+        assert_eq!(rrt.node_cost(0), 0.0);
+
+        rrt.prune_leaves(rrt.get_tree().len() / 2);
+        assert_costs_match_paths(&rrt);
+        assert_eq!(
+            rrt.get_path_cost(),
+            Some(rrt.node_cost(rrt.solution_index().unwrap()))
+        );
+    }
+
+    #[test]
+    fn anytime_solution_cost_never_increases() {
+        let mut rrt = planner(vec![sphere(5.0, 5.0, 2.0)], 0.5, 1);
+        let mut costs = Vec::new();
+        for _ in 0..50 {
+            rrt.solve_anytime(200);
+            if let Some(cost) = rrt.get_path_cost() {
+                costs.push(cost);
+            }
+        }
+        assert!(costs.len() > 40);
+        assert!(costs.windows(2).all(|pair| pair[1] <= pair[0]), "{costs:?}");
+        assert!(costs.last() < costs.first());
+
+        // The solution is the cheapest of the goal nodes found.
+        assert!(rrt.goal_nodes().len() > 1);
+        let cheapest = rrt
+            .goal_nodes()
+            .iter()
+            .map(|&index| rrt.node_cost(index))
+            .fold(f64::INFINITY, f64::min);
+        assert_eq!(rrt.get_path_cost(), Some(cheapest));
+    }
+
+    #[test]
+    fn children_and_descendants_follow_the_parents() {
+        // A ternary tree, built by inserting 120 nodes one at a time.
+        let mut rrt = planner(Vec::new(), 0.5, 1);
+        for index in 1..=120 {
+            let parent = (index - 1) / 3;
+            let added = rrt.add_child_node(
+                parent,
+                Point::new([index as f64 * 0.05, 1.0 + index as f64 * 0.03]),
+            );
+            assert_eq!(added, index);
+        }
+
+        let nodes = rrt.get_tree();
+        for (index, node) in nodes.iter().enumerate() {
+            let expected: Vec<usize> = (3 * index + 1..=3 * index + 3)
+                .filter(|&child| child < nodes.len())
+                .collect();
+            assert_eq!(node.children(), expected.as_slice());
+        }
+
+        // The descendants are the nodes whose chain of parents passes through the node.
+        for index in [0, 1, 5, 13, 40, 120] {
+            let mut descendants: Vec<usize> = rrt.descendants(index).collect();
+            descendants.sort_unstable();
+            let expected: Vec<usize> = (0..nodes.len())
+                .filter(|&other| {
+                    let mut current = nodes[other].parent();
+                    while let Some(parent) = current {
+                        if parent == index {
+                            return true;
+                        }
+                        current = nodes[parent].parent();
+                    }
+                    false
+                })
+                .collect();
+            assert_eq!(descendants, expected);
+        }
+        assert_eq!(rrt.descendants(0).count(), 120);
+        assert_eq!(rrt.descendants(120).count(), 0);
+    }
+
+    #[test]
+    fn prune_leaves_keeps_the_solution_roots_and_goal_nodes() {
+        let mut rrt = planner(vec![sphere(5.0, 5.0, 2.0)], 0.2, 1);
+        rrt.add_start(Point::new([1.0, 9.0]));
+        rrt.solve_anytime(5_000);
+        let points = |rrt: &TestRRT, indices: &[usize]| -> Vec<[f64; 2]> {
+            indices
+                .iter()
+                .map(|&index| *rrt.get_tree()[index].point().coords())
+                .collect()
+        };
+        let path = points(&rrt, &rrt.get_path_indices().unwrap());
+        let roots = points(&rrt, rrt.roots());
+        let goal_nodes = points(&rrt, rrt.goal_nodes());
+        assert!(path.len() > 40);
+        assert!(goal_nodes.len() > 1);
+
+        let before = rrt.get_tree().len();
+        let keep = path.len() + 100;
+        assert_eq!(rrt.prune_leaves(keep), before - keep);
+        assert_eq!(rrt.get_tree().len(), keep);
+        assert_costs_match_paths(&rrt);
+
+        assert_eq!(points(&rrt, &rrt.get_path_indices().unwrap()), path);
+        assert_eq!(points(&rrt, rrt.roots()), roots);
+
+        // The remaining goal nodes are remapped to their new indices, in their original order.
+        let remaining = points(&rrt, rrt.goal_nodes());
+        assert!(remaining.len() > 1);
+        assert!(remaining.contains(path.last().unwrap()));
+        let kept: Vec<[f64; 2]> = goal_nodes
+            .into_iter()
+            .filter(|point| {
+                rrt.get_tree()
+                    .iter()
+                    .any(|node| node.point().coords() == point)
+            })
+            .collect();
+        assert_eq!(remaining, kept);
+    }
+
+    /// Returns the smallest distance between two nodes of the tree.
+    fn min_pairwise_distance(rrt: &TestRRT) -> f64 {
+        let nodes = rrt.get_tree();
+        let mut min = f64::INFINITY;
+        for (i, a) in nodes.iter().enumerate() {
+            for b in &nodes[i + 1..] {
+                min = min.min(euclidean_distance(a.point(), b.point()));
+            }
+        }
+        min
+    }
+
+    #[test]
+    fn min_node_separation_spaces_out_the_tree() {
+        let mut rrt = planner(vec![sphere(5.0, 5.0, 2.0)], 0.5, 1);
+        rrt.set_min_node_separation(0.2);
+        rrt.solve(2_000);
+        assert!(rrt.get_tree().len() > 100);
+        assert!(rrt.statistics().separation_rejections > 0);
+        assert!(min_pairwise_distance(&rrt) >= 0.2);
+
+        // Without the check, the same planner places nodes closer together.
+        let mut rrt = planner(vec![sphere(5.0, 5.0, 2.0)], 0.5, 1);
+        rrt.solve(2_000);
+        assert!(min_pairwise_distance(&rrt) < 0.2);
+    }
+
+    #[test]
+    fn solve_until_stops_at_a_node_count() {
+        // The goal is inside a sphere, so the predicate stops the solve before any solution.
+        let mut rrt = planner(vec![sphere(9.0, 9.0, 1.0)], 0.5, 1);
+        let outcome = rrt.solve_until(10_000, |rrt| rrt.get_tree().len() >= 50);
+        assert_eq!(rrt.get_tree().len(), 50);
+        assert_eq!(
+            outcome,
+            SolveOutcome::Cancelled {
+                iterations: rrt.iterations_run() as u32
+            }
+        );
+        assert!(rrt.iterations_run() < 10_000);
+    }
+
+    #[test]
+    fn solve_until_keeps_going_after_a_solution() {
+        let mut rrt = planner(vec![sphere(5.0, 5.0, 2.0)], 0.5, 1);
+        let outcome = rrt.solve_until(20_000, |rrt| rrt.goal_nodes().len() >= 3);
+        assert!(outcome.is_solved());
+        assert_eq!(rrt.goal_nodes().len(), 3);
+        assert!(rrt.iterations_run() < 20_000);
+
+        // Without a solution, the iteration limit ends the solve as usual.
+        let mut rrt = planner(vec![sphere(9.0, 9.0, 1.0)], 0.5, 1);
+        assert_eq!(
+            rrt.solve_until(100, |_| false),
+            SolveOutcome::IterationLimit
+        );
+    }
+
+    #[test]
+    fn edges_of_empty_and_single_node_trees() {
+        let mut rrt = planner(Vec::new(), 0.5, 1);
+        assert_eq!(rrt.edges().count(), 0);
+        assert_eq!(rrt.solution_edges().count(), 0);
+
+        // A second start is another root, so there are still no edges.
+        rrt.add_start(Point::new([1.0, 9.0]));
+        assert_eq!(rrt.edges().count(), 0);
+
+        // The public API always keeps a root, so the tree is emptied directly.
+        rrt.nodes.clear();
+        rrt.roots.clear();
+        assert_eq!(rrt.edges().count(), 0);
+        assert_eq!(rrt.solution_edges().count(), 0);
+    }
+
+    #[test]
+    fn edges_pair_each_node_with_its_parent() {
+        let mut rrt = planner(vec![sphere(5.0, 5.0, 2.0)], 0.5, 1);
+        assert!(rrt.solve(10_000).is_solved());
+        let nodes = rrt.get_tree();
+        let expected: Vec<_> = nodes
+            .iter()
+            .filter_map(|node| {
+                node.parent()
+                    .map(|parent| (nodes[parent].point(), node.point()))
+            })
+            .collect();
+        let edges: Vec<_> = rrt.edges().collect();
+        assert_eq!(edges.len(), nodes.len() - 1);
+        for ((a, b), (c, d)) in edges.iter().zip(&expected) {
+            assert!(std::ptr::eq(*a, *c) && std::ptr::eq(*b, *d));
+        }
+
+        let path = rrt.get_path().unwrap();
+        let solution_edges: Vec<_> = rrt.solution_edges().collect();
+        assert_eq!(solution_edges.len(), path.len() - 1);
+        for ((a, b), pair) in solution_edges.into_iter().zip(path.windows(2)) {
+            assert_eq!(a.coords(), pair[0].coords());
+            assert_eq!(b.coords(), pair[1].coords());
+        }
+    }
+
+    #[test]
+    fn solve_with_callback_reports_direct_connection_nodes() {
+        let mut rrt = planner(Vec::new(), 1.0, 1);
+        rrt.set_direct_connection(true);
+        let mut events = Vec::new();
+        let outcome = rrt.solve_with_callback(100, |_, event| events.push(event));
+        assert_eq!(outcome, SolveOutcome::Solved { iterations: 0 });
+
+        // One event for each of the 11 nodes inserted along the diagonal, in order, ending in the solution.
+        assert_eq!(events.len(), 11);
+        assert_eq!(rrt.get_tree().len(), 12);
+        for (position, event) in events.iter().enumerate() {
+            let (sample, index, point, parent) = match *event {
+                IterationEvent::NodeAdded {
+                    sample,
+                    index,
+                    point,
+                    parent,
+                } if position < 10 => (sample, index, point, parent),
+                IterationEvent::SolutionFound {
+                    sample,
+                    index,
+                    point,
+                    parent,
+                } if position == 10 => (sample, index, point, parent),
+                event => panic!("unexpected event {event:?} at {position}"),
+            };
+            assert_eq!(sample.coords(), &[9.0, 9.0]);
+            assert_eq!(index, position + 1);
+            assert_eq!(parent, position);
+            assert_eq!(point.coords(), rrt.get_tree()[index].point().coords());
+        }
+        assert_eq!(rrt.solution_index(), Some(11));
+    }
+
+    #[test]
+    fn solve_with_callback_reports_the_node_limit() {
+        // The goal is inside a sphere, so it is never reached.
+        let mut rrt = planner(vec![sphere(9.0, 9.0, 1.0)], 0.5, 1);
+        rrt.set_max_nodes(Some(20));
+        let mut events = Vec::new();
+        let outcome = rrt.solve_with_callback(10_000, |_, event| events.push(event));
+        assert_eq!(outcome, SolveOutcome::NodeLimit);
+        assert_eq!(rrt.get_tree().len(), 20);
+
+        let limits = events
+            .iter()
+            .filter(|event| matches!(event, IterationEvent::NodeLimitReached))
+            .count();
+        assert_eq!(limits, 1);
+        assert!(matches!(
+            events.last(),
+            Some(IterationEvent::NodeLimitReached)
+        ));
+        let added = events
+            .iter()
+            .filter(|event| matches!(event, IterationEvent::NodeAdded { .. }))
+            .count();
+        assert_eq!(added, 19);
+    }
+
+    #[test]
+    fn import_tree_round_trips_a_forest() {
+        let forest_planner = |seed: u64| -> TestRRT {
+            RRT::with_starts(
+                vec![
+                    Point::new([1.0, 1.0]),
+                    Point::new([1.0, 9.0]),
+                    Point::new([9.0, 1.0]),
+                ],
+                PointGoal::new(Point::new([9.0, 9.0]), 0.5),
+                EuclideanSphericalObstacleSet::new(vec![sphere(5.0, 5.0, 2.0)]),
+                UniformDistribution::new_with_rng([(0.0, 10.0); 2], StdRng::seed_from_u64(seed))
+                    .unwrap(),
+                EuclideanSteering::new(0.5),
+            )
+            .unwrap()
+        };
+        let mut rrt = forest_planner(1);
+        assert!(rrt.solve(10_000).is_solved());
+        let exported = rrt.export_tree();
+        assert!(exported.forest);
+
+        // The roots are restored as the starts, so the solution keeps the start it came from.
+        let mut imported = planner(Vec::new(), 0.5, 2);
+        imported.import_tree(exported.clone()).unwrap();
+        assert_eq!(imported.roots(), rrt.roots());
+        assert_eq!(imported.solution_root(), rrt.solution_root());
+        assert_same_path(&imported.get_path().unwrap(), &rrt.get_path().unwrap());
+
+        // Without the forest flag, the same nodes are rejected.
+        let single = TreeData {
+            forest: false,
+            ..exported
+        };
+        assert!(imported.import_tree(single).is_err());
+    }
+
+    #[test]
+    fn reached_goal_index_matches_the_goal_at_the_solution() {
+        let goals = vec![
+            Point::new([1.0, 9.0]),
+            Point::new([9.0, 9.0]),
+            Point::new([9.0, 1.0]),
+        ];
+        let mut reached = Vec::new();
+        for seed in 0..8 {
+            let mut rrt: RRT<_, 2, _, _, _, KdTreeNearestNeighbors<f64, 2>, _> =
+                RRT::with_goal_checker(
+                    Point::new([5.0, 5.0]),
+                    MultiPointGoal::new_with_rng(goals.clone(), 0.5, StdRng::seed_from_u64(seed)),
+                    EuclideanSphericalObstacleSet::new(Vec::new()),
+                    UniformDistribution::new_with_rng(
+                        [(0.0, 10.0); 2],
+                        StdRng::seed_from_u64(seed),
+                    )
+                    .unwrap(),
+                    EuclideanSteering::new(0.5),
+                );
+            assert_eq!(rrt.reached_goal_index(), None);
+            assert!(rrt.solve(10_000).is_solved());
+
+            let index = rrt.reached_goal_index().unwrap();
+            let end = *rrt.get_path().unwrap().last().unwrap();
+            for (i, goal) in goals.iter().enumerate() {
+                let distance = (end[0] - goal[0]).hypot(end[1] - goal[1]);
+                assert_eq!(distance <= 0.5, i == index);
+            }
+            reached.push(index);
+        }
+        // The goals are equally far from the start, so different seeds reach different goals.
+        assert!(reached.iter().any(|&index| index != reached[0]));
+    }
+
+    #[test]
+    fn start_inside_a_sphere_is_rejected() {
+        let obstacles = || {
+            EuclideanSphericalObstacleSet::new(vec![Sphere {
+                center: Point::new([2.0, 2.0]),
+                radius: 1.5,
+            }])
+        };
+        let sampling = || UniformDistribution::new([(0.0, 10.0); 2]).unwrap();
+        let try_new = |start: [f64; 2], goal: [f64; 2]| {
+            RRT::<_, 2, _, _, _, KdTreeNearestNeighbors<f64, 2>>::try_new(
+                Point::new(start),
+                Point::new(goal),
+                0.5,
+                obstacles(),
+                sampling(),
+                EuclideanSteering::new(0.5),
+            )
+            .err()
+        };
+        assert_eq!(
+            try_new([2.5, 1.5], [9.0, 9.0]),
+            Some(PlanningError::InvalidStart)
+        );
+        assert_eq!(
+            try_new([9.0, 9.0], [2.0, 2.0]),
+            Some(PlanningError::InvalidGoal)
+        );
+        assert_eq!(try_new([5.0, 5.0], [9.0, 9.0]), None);
+
+        let build = RRTBuilder::new()
+            .start(Point::new([2.5, 1.5]))
+            .goal(Point::new([9.0, 9.0]))
+            .goal_tolerance(0.5)
+            .validity_checker(obstacles())
+            .sampling_distribution(sampling())
+            .steering(EuclideanSteering::new(0.5))
+            .build();
+        assert_eq!(build.err(), Some(BuildError::StartInCollision));
+
+        let build = RRTBuilder::new()
+            .start(Point::new([9.0, 9.0]))
+            .goal(Point::new([2.0, 2.0]))
+            .goal_tolerance(0.5)
+            .validity_checker(obstacles())
+            .sampling_distribution(sampling())
+            .steering(EuclideanSteering::new(0.5))
+            .build();
+        assert_eq!(build.err(), Some(BuildError::GoalInCollision));
+    }
 }
@@ -0,0 +1,408 @@
+use crate::collision::ValidityChecker;
+use crate::distance::{euclidean_distance, euclidean_distance_squared};
+use crate::neighbors::NearestNeighbors;
+use crate::planner::Planner;
+use crate::point::Point;
+use crate::rrt::Node;
+use crate::sampling::SamplingDistribution;
+use crate::steering::Steering;
+use num_traits::Float;
+
+/// A single tree grown by the RRT-Connect planner.
+struct Tree<F: Float, const N: usize, NN: NearestNeighbors<F, N>> {
+    /// The nodes in the tree.
+    nodes: Vec<Node<F, N>>,
+    nearest_neighbors: NN,
+}
+
+impl<F: Float, const N: usize, NN: NearestNeighbors<F, N>> Tree<F, N, NN> {
+    /// Constructs a new tree containing only the root.
+    fn new(root: Point<F, N>) -> Self {
+        let mut tree = Self {
+            nodes: Vec::new(),
+            nearest_neighbors: NN::new(),
+        };
+        tree.add_node(Node::new(root, None));
+        tree
+    }
+
+    /// Adds a node to the tree and the nearest neighbors data structure.
+    fn add_node(&mut self, mut node: Node<F, N>) -> usize {
+        let index = self.nodes.len();
+        self.nearest_neighbors.add(*node.point(), index);
+        if let Some(parent) = node.parent() {
+            let parent_node = &mut self.nodes[parent];
+            parent_node.add_child(index);
+            node.set_cost(
+                parent_node.cost() + euclidean_distance(parent_node.point(), node.point()),
+            );
+        }
+        self.nodes.push(node);
+        index
+    }
+
+    /// Returns the points from the root to the node at `index`.
+    fn path_to(&self, index: usize) -> Vec<Point<F, N>> {
+        let mut path = Vec::new();
+        let mut current_index = index;
+        while let Some(parent_index) = self.nodes[current_index].parent() {
+            path.push(*self.nodes[current_index].point());
+            current_index = parent_index;
+        }
+        path.push(*self.nodes[current_index].point());
+        path.reverse();
+        path
+    }
+
+    /// Steers from the node at `from_index` towards `target` and adds the new node if it is valid.
+    ///
+    /// Returns:
+    /// The index of the new node, or None if the extension was invalid.
+    fn extend_from(
+        &mut self,
+        from_index: usize,
+        target: &Point<F, N>,
+        steering: &impl Steering<F, N>,
+        validity_checker: &impl ValidityChecker<F, N>,
+    ) -> Option<usize> {
+        let from_point = *self.nodes[from_index].point();
+        let new_point = steering.steer(&from_point, target);
+
+        if !validity_checker.is_point_valid(&new_point)
+            || !validity_checker.is_edge_valid(&from_point, &new_point)
+        {
+            return None;
+        }
+
+        Some(self.add_node(Node::new(new_point, Some(from_index))))
+    }
+
+    /// Extends the nearest node in the tree one step towards `target`.
+    ///
+    /// Returns:
+    /// The index of the new node, or None if the extension was invalid.
+    fn extend(
+        &mut self,
+        target: &Point<F, N>,
+        steering: &impl Steering<F, N>,
+        validity_checker: &impl ValidityChecker<F, N>,
+    ) -> Option<usize> {
+        let nearest_index = self.nearest_neighbors.nearest_one(target)?;
+        self.extend_from(nearest_index, target, steering, validity_checker)
+    }
+
+    /// Greedily extends the tree towards `target` until it is reached or an extension fails.
+    ///
+    /// Returns:
+    /// The index of the node at `target` if it was reached, otherwise None.
+    fn connect(
+        &mut self,
+        target: &Point<F, N>,
+        steering: &impl Steering<F, N>,
+        validity_checker: &impl ValidityChecker<F, N>,
+    ) -> Option<usize> {
+        let mut last_index = self.nearest_neighbors.nearest_one(target)?;
+        let mut last_dist_squared =
+            euclidean_distance_squared(self.nodes[last_index].point(), target);
+        while last_dist_squared > F::zero() {
+            last_index = self.extend_from(last_index, target, steering, validity_checker)?;
+            let dist_squared = euclidean_distance_squared(self.nodes[last_index].point(), target);
+
+            // Stop if the steering function made no progress, otherwise we would loop forever.
+            if dist_squared >= last_dist_squared {
+                return None;
+            }
+            last_dist_squared = dist_squared;
+        }
+        Some(last_index)
+    }
+}
+
+/// A bidirectional RRT-Connect planner.
+///
+/// Grows one tree from the start and one from the goal.
+/// Each iteration extends one tree towards a random sample and then greedily connects the other
+/// tree towards the newly added node. The roles of the trees are swapped after every iteration.
+///
+/// Template Parameters:
+/// - `F`: The floating-point type.
+/// - `N`: The dimension of the space.
+/// - `VC`: The validity checker.
+/// - `SD`: The sampling distribution.
+/// - `ST`: The steering function.
+/// - `NN`: The nearest neighbors data structure.
+pub struct RRTConnect<F: Float, const N: usize, VC, SD, ST, NN>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    ST: Steering<F, N>,
+    NN: NearestNeighbors<F, N>,
+{
+    /// The tree rooted at the start point.
+    start_tree: Tree<F, N, NN>,
+    /// The tree rooted at the goal point.
+    goal_tree: Tree<F, N, NN>,
+    /// Indices of the connected nodes in the start and goal trees (None if no solution has been found).
+    connection: Option<(usize, usize)>,
+    /// Whether the start tree is extended towards the sample in the next iteration.
+    extend_start_tree: bool,
+    validity_checker: VC,
+    sampling_distribution: SD,
+    steering: ST,
+}
+
+impl<F: Float, const N: usize, VC, SD, ST, NN> RRTConnect<F, N, VC, SD, ST, NN>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    ST: Steering<F, N>,
+    NN: NearestNeighbors<F, N>,
+{
+    /// Constructs a new RRT-Connect planner.
+    ///
+    /// Parameters:
+    /// - `start`: The start point.
+    /// - `goal`: The goal point.
+    /// - `validity_checker`: Checks if the edges or nodes as valid.
+    /// - `sampling_distribution`: The sampling distribution.
+    /// - `steering`: The steering function.
+    ///
+    /// Returns the RRT-Connect planner.
+    pub fn new(
+        start: Point<F, N>,
+        goal: Point<F, N>,
+        validity_checker: VC,
+        sampling_distribution: SD,
+        steering: ST,
+    ) -> Self {
+        Self {
+            start_tree: Tree::new(start),
+            goal_tree: Tree::new(goal),
+            connection: None,
+            extend_start_tree: true,
+            validity_checker,
+            sampling_distribution,
+            steering,
+        }
+    }
+
+    /// Attempts to find a solution within a maximum number of iterations.
+    ///
+    /// Terminates and returns true when a solution is found. Otherwise, returns false.
+    ///
+    /// Parameters:
+    /// - `max_iterations`: The maximum number of iterations.
+    pub fn solve(&mut self, max_iterations: u32) -> bool {
+        for _ in 0..max_iterations {
+            self.iteration();
+            if self.solved() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Run a fixed number of iterations of the RRT-Connect algorithm.
+    /// Stops early once the trees are connected, since they no longer grow afterwards.
+    ///
+    /// Returns true if the RRT-Connect found a solution.
+    ///
+    /// Parameters:
+    /// - `iterations`: The number of iterations to run.
+    pub fn run_iterations(&mut self, iterations: u32) -> bool {
+        self.solve(iterations)
+    }
+
+    /// Returns true if the two trees have been connected.
+    pub fn solved(&self) -> bool {
+        self.connection.is_some()
+    }
+
+    /// Returns the path from the start to the goal, if a solution was found.
+    ///
+    /// The path is the branch of the start tree up to the connection point
+    /// followed by the branch of the goal tree back down to the goal.
+    pub fn get_path(&self) -> Option<Vec<Point<F, N>>> {
+        let (start_index, goal_index) = self.connection?;
+
+        let mut path = self.start_tree.path_to(start_index);
+        let mut goal_branch = self.goal_tree.path_to(goal_index);
+        goal_branch.reverse();
+
+        // Both branches contain the connection point, so skip it in the goal branch.
+        path.extend(goal_branch.into_iter().skip(1));
+        Some(path)
+    }
+
+    pub fn get_sampling_distribution(&self) -> &SD {
+        &self.sampling_distribution
+    }
+
+    pub fn get_validity_checker(&self) -> &VC {
+        &self.validity_checker
+    }
+
+    /// Returns the vector of nodes in the tree rooted at the start.
+    pub fn get_start_tree(&self) -> &Vec<Node<F, N>> {
+        &self.start_tree.nodes
+    }
+
+    /// Returns the vector of nodes in the tree rooted at the goal.
+    pub fn get_goal_tree(&self) -> &Vec<Node<F, N>> {
+        &self.goal_tree.nodes
+    }
+
+    /// Expands the trees by one iteration.
+    ///
+    /// Each iteration of the RRT-Connect algorithm consists of the following steps:
+    /// 1. Sample a point from the sampling distribution.
+    /// 2. Extend the active tree one step towards the sample point.
+    /// 3. If a node was added, greedily connect the other tree towards the new node.
+    /// 4. If the other tree reaches the new node, record the connection as the solution.
+    /// 5. Swap the roles of the two trees.
+    fn iteration(&mut self) {
+        let sample = self.sampling_distribution.sample();
+
+        let (active, other) = if self.extend_start_tree {
+            (&mut self.start_tree, &mut self.goal_tree)
+        } else {
+            (&mut self.goal_tree, &mut self.start_tree)
+        };
+
+        if let Some(new_index) = active.extend(&sample, &self.steering, &self.validity_checker) {
+            let target = *active.nodes[new_index].point();
+            if let Some(other_index) =
+                other.connect(&target, &self.steering, &self.validity_checker)
+            {
+                self.connection = Some(if self.extend_start_tree {
+                    (new_index, other_index)
+                } else {
+                    (other_index, new_index)
+                });
+            }
+        }
+
+        self.extend_start_tree = !self.extend_start_tree;
+    }
+}
+
+impl<F: Float, const N: usize, VC, SD, ST, NN> Planner<F, N> for RRTConnect<F, N, VC, SD, ST, NN>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    ST: Steering<F, N>,
+    NN: NearestNeighbors<F, N>,
+{
+    fn solve(&mut self, max_iterations: u32) -> bool {
+        RRTConnect::solve(self, max_iterations)
+    }
+
+    fn solved(&self) -> bool {
+        RRTConnect::solved(self)
+    }
+
+    fn get_path(&self) -> Option<Vec<Point<F, N>>> {
+        RRTConnect::get_path(self)
+    }
+
+    fn get_tree_size(&self) -> usize {
+        self.start_tree.nodes.len() + self.goal_tree.nodes.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::{EuclideanSphericalObstacleSet, Sphere};
+    use crate::distance::euclidean_distance;
+    use crate::neighbors::KdTreeNearestNeighbors;
+    use crate::sampling::UniformDistribution;
+    use crate::steering::EuclideanSteering;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// A wall of spheres along x = 5 with a single gap around y = 5.
+    fn wall_with_gap() -> EuclideanSphericalObstacleSet<f64, 2> {
+        let spheres = (0..=20)
+            .map(|i| i as f64 * 0.5)
+            .filter(|y| (y - 5.0).abs() > 0.9)
+            .map(|y| Sphere {
+                center: Point::new([5.0, y]),
+                radius: 0.3,
+            })
+            .collect();
+        EuclideanSphericalObstacleSet::new(spheres)
+    }
+
+    type TestPlanner = RRTConnect<
+        f64,
+        2,
+        EuclideanSphericalObstacleSet<f64, 2>,
+        UniformDistribution<f64, 2, StdRng>,
+        EuclideanSteering<f64, 2>,
+        KdTreeNearestNeighbors<f64, 2>,
+    >;
+
+    fn planner(obstacles: EuclideanSphericalObstacleSet<f64, 2>, seed: u64) -> TestPlanner {
+        RRTConnect::new(
+            Point::new([1.0, 1.0]),
+            Point::new([9.0, 9.0]),
+            obstacles,
+            UniformDistribution::new_with_rng([(0.0, 10.0); 2], StdRng::seed_from_u64(seed))
+                .unwrap(),
+            EuclideanSteering::new(0.5),
+        )
+    }
+
+    #[test]
+    fn path_connects_start_to_goal_through_gap() {
+        for seed in 0..10 {
+            let mut rrt = planner(wall_with_gap(), seed);
+            assert!(rrt.solve(20_000), "seed {} did not solve", seed);
+
+            let path = rrt.get_path().unwrap();
+            assert_eq!(path.first().unwrap().coords(), &[1.0, 1.0]);
+            assert_eq!(path.last().unwrap().coords(), &[9.0, 9.0]);
+            let checker = rrt.get_validity_checker();
+            for edge in path.windows(2) {
+                assert!(euclidean_distance(&edge[0], &edge[1]) <= 0.5 + 1e-9);
+                assert!(checker.is_edge_valid(&edge[0], &edge[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn trees_are_rooted_at_start_and_goal() {
+        let mut rrt = planner(wall_with_gap(), 1);
+        assert!(rrt.get_path().is_none());
+        rrt.solve(20_000);
+
+        let start_tree = rrt.get_start_tree();
+        let goal_tree = rrt.get_goal_tree();
+        assert_eq!(start_tree[0].point().coords(), &[1.0, 1.0]);
+        assert_eq!(goal_tree[0].point().coords(), &[9.0, 9.0]);
+        assert!(start_tree[0].parent().is_none() && goal_tree[0].parent().is_none());
+        for node in start_tree.iter().skip(1).chain(goal_tree.iter().skip(1)) {
+            assert!(node.parent().is_some());
+        }
+        assert!(start_tree.len() > 1 && goal_tree.len() > 1);
+    }
+
+    #[test]
+    fn no_solution_when_goal_is_enclosed() {
+        // A ring of spheres around the goal that the trees can not pass.
+        let spheres = (0..64)
+            .map(|i| {
+                let angle = i as f64 / 64.0 * std::f64::consts::TAU;
+                Sphere {
+                    center: Point::new([9.0 + angle.cos(), 9.0 + angle.sin()]),
+                    radius: 0.2,
+                }
+            })
+            .collect();
+        let mut rrt = planner(EuclideanSphericalObstacleSet::new(spheres), 2);
+        assert!(!rrt.solve(2_000));
+        assert!(!rrt.solved());
+        assert!(rrt.get_path().is_none());
+    }
+}
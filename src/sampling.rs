@@ -1,86 +1,3602 @@
+use crate::collision::ObstacleSurface;
 use crate::point::Point;
+use crate::rrt::Node;
 use num_traits::Float;
-use rand::distributions::{uniform::SampleUniform, Bernoulli, Distribution, Uniform};
+use rand::distributions::{
+    uniform::SampleUniform, Bernoulli, Distribution, Uniform, WeightedIndex,
+};
+use rand::rngs::ThreadRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_distr::{Normal, StandardNormal};
+use std::fmt;
 
 /// A trait for sampling distributions.
 pub trait SamplingDistribution<F: Float, const N: usize> {
     /// Samples a point from the distribution.
     fn sample(&mut self) -> Point<F, N>;
+
+    /// Samples several points at once, which lets distributions amortize the cost of sampling.
+    ///
+    /// Distributions that adapt to `feedback` only receive it after the whole batch has been sampled,
+    /// so the samples of a batch do not adapt to each other.
+    ///
+    /// Parameters:
+    /// - `n`: The number of points to sample.
+    /// - `out`: The buffer the points are appended to.
+    fn sample_batch(&mut self, n: usize, out: &mut Vec<Point<F, N>>) {
+        out.reserve(n);
+        for _ in 0..n {
+            out.push(self.sample());
+        }
+    }
+
+    /// Updates the goal of the distribution when the goal of the planner changes.
+    /// Distributions that do not depend on the goal can ignore this.
+    ///
+    /// Parameters:
+    /// - `goal`: The new goal point.
+    fn set_goal(&mut self, _goal: Point<F, N>) {}
+
+    /// Receives feedback from the planner after each sample, for distributions that adapt as the tree grows.
+    /// Distributions that ignore feedback keep working unchanged.
+    ///
+    /// Parameters:
+    /// - `feedback`: What the last sample did to the tree.
+    fn feedback(&mut self, _feedback: SampleFeedback<F>) {}
+
+    /// Receives the steered edge when an extension of the tree fails because the new point or the edge
+    /// is not valid, for distributions that focus on difficult regions such as narrow passages.
+    /// It is called before `feedback` for the same sample.
+    ///
+    /// Parameters:
+    /// - `from`: The point of the node the tree was extended from.
+    /// - `to`: The steered point that could not be added.
+    fn extension_failed(&mut self, _from: &Point<F, N>, _to: &Point<F, N>) {}
+
+    /// Widens the sampled region, for example when the planner detects that the tree has stalled
+    /// because the ranges are too narrow. Distributions without ranges can ignore this.
+    ///
+    /// Parameters:
+    /// - `factor`: The factor by which to scale the width of each range about its center.
+    ///
+    /// Returns:
+    /// Whether the distribution widened its ranges. Distributions keep their ranges and return false
+    /// if the factor is not positive and finite, or the widened ranges would not be finite.
+    fn expand_ranges(&mut self, _factor: F) -> bool {
+        false
+    }
+}
+
+/// Feedback from the planner about the last sample of a sampling distribution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampleFeedback<F: Float> {
+    /// Whether the sample added at least one node to the tree.
+    pub node_added: bool,
+    /// The smallest distance from any node of the tree to the goal
+    /// (None if the goal checker can not measure distances to the goal).
+    pub best_goal_distance: Option<F>,
+}
+
+/// Errors when constructing sampling distributions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SamplingError {
+    /// The lower bound of the range of a dimension is above its upper bound.
+    InvertedRange { dimension: usize },
+    /// A bound of the range of a dimension is NaN or infinite.
+    NonFiniteBound { dimension: usize },
+    /// The goal bias is not in the range [0, 1].
+    InvalidGoalBias,
+    /// No goal points were given.
+    NoGoals,
+    /// The goal radius is negative or not finite.
+    InvalidGoalRadius,
+    /// A dimension index is not less than the number of dimensions.
+    InvalidDimension { dimension: usize },
+    /// The sequence supports at most `max` dimensions.
+    TooManyDimensions { max: usize },
+    /// The number of strata is 0.
+    ZeroStrata,
+    /// There would be more than `max` cells.
+    TooManyCells { max: usize },
+    /// The number of lattice values in a dimension is 0.
+    ZeroCount { dimension: usize },
+    /// The number of lattice points overflows.
+    TooManyPoints,
+}
+
+impl fmt::Display for SamplingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SamplingError::InvertedRange { dimension } => write!(
+                f,
+                "lower bound of dimension {} is above its upper bound",
+                dimension
+            ),
+            SamplingError::NonFiniteBound { dimension } => {
+                write!(f, "range of dimension {} is not finite", dimension)
+            }
+            SamplingError::InvalidGoalBias => write!(f, "goal_bias must be in the range [0, 1]"),
+            SamplingError::NoGoals => write!(f, "goals must not be empty"),
+            SamplingError::InvalidGoalRadius => {
+                write!(f, "goal_radius must be non-negative and finite")
+            }
+            SamplingError::InvalidDimension { dimension } => {
+                write!(f, "dimension {} is out of range", dimension)
+            }
+            SamplingError::TooManyDimensions { max } => {
+                write!(f, "the sequence supports at most {} dimensions", max)
+            }
+            SamplingError::ZeroStrata => write!(f, "strata must be at least 1"),
+            SamplingError::TooManyCells { max } => write!(
+                f,
+                "more than {} cells, reduce strata or the number of dimensions",
+                max
+            ),
+            SamplingError::ZeroCount { dimension } => {
+                write!(f, "count of dimension {} must be at least 1", dimension)
+            }
+            SamplingError::TooManyPoints => write!(f, "too many lattice points"),
+        }
+    }
+}
+
+impl std::error::Error for SamplingError {}
+
+/// Checks that every range is finite and that no lower bound is above its upper bound.
+/// A range where both bounds are equal is allowed, and always samples that value.
+fn validate_ranges<F: Float, const N: usize>(ranges: &[(F, F); N]) -> Result<(), SamplingError> {
+    for (dimension, (min, max)) in ranges.iter().enumerate() {
+        if !(min.is_finite() && max.is_finite()) {
+            return Err(SamplingError::NonFiniteBound { dimension });
+        }
+        if min > max {
+            return Err(SamplingError::InvertedRange { dimension });
+        }
+    }
+    Ok(())
+}
+
+/// Scales the width of each range about its center, for `SamplingDistribution::expand_ranges`.
+///
+/// Returns:
+/// The widened ranges, or None if the factor is not positive and finite or a widened bound or width is not finite.
+fn expanded_ranges<F: Float, const N: usize>(
+    ranges: &[(F, F); N],
+    factor: F,
+) -> Option<[(F, F); N]> {
+    if !(factor > F::zero() && factor.is_finite()) {
+        return None;
+    }
+    let two = F::one() + F::one();
+    let expanded = ranges.map(|(min, max)| {
+        let center = (min + max) / two;
+        let half_width = (max - min) / two * factor;
+        (center - half_width, center + half_width)
+    });
+    validate_ranges(&expanded).ok()?;
+    // Uniform distributions also need the width itself to be finite.
+    if !expanded.iter().all(|(min, max)| (*max - *min).is_finite()) {
+        return None;
+    }
+    Some(expanded)
 }
 
 /// A uniform distribution for sampling points.
 /// Each dimension has a range of values.
-pub struct UniformDistribution<F: Float + SampleUniform, const N: usize> {
+///
+/// The random number generator defaults to `ThreadRng`.
+/// Use `new_with_rng` with a seeded generator for reproducible samples.
+pub struct UniformDistribution<F: Float + SampleUniform, const N: usize, R: Rng = ThreadRng> {
+    ranges: [(F, F); N],
     uniforms: [Uniform<F>; N],
-    rng: rand::rngs::ThreadRng,
+    rng: R,
 }
 
 impl<F: Float + SampleUniform, const N: usize> UniformDistribution<F, N> {
     /// Constructs a new uniform distribution.
+    ///
     /// Parameters:
     /// - `ranges`: The ranges for each dimension.
+    ///
     /// Returns:
-    /// The uniform distribution.
-    pub fn new(ranges: [(F, F); N]) -> Self {
+    /// The uniform distribution, or an error if a range is not finite or is inverted.
+    pub fn new(ranges: [(F, F); N]) -> Result<Self, SamplingError> {
+        Self::new_with_rng(ranges, rand::thread_rng())
+    }
+}
+
+impl<F: Float + SampleUniform, const N: usize, R: Rng> UniformDistribution<F, N, R> {
+    /// Constructs a new uniform distribution with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The uniform distribution, or an error if a range is not finite or is inverted.
+    pub fn new_with_rng(ranges: [(F, F); N], rng: R) -> Result<Self, SamplingError> {
+        validate_ranges(&ranges)?;
         let uniforms: [Uniform<F>; N] =
             std::array::from_fn(|i| Uniform::new_inclusive(ranges[i].0, ranges[i].1));
-        Self {
+        Ok(Self {
+            ranges,
             uniforms,
-            rng: rand::thread_rng(),
-        }
+            rng,
+        })
+    }
+
+    /// Returns the ranges for each dimension.
+    pub fn ranges(&self) -> &[(F, F); N] {
+        &self.ranges
     }
 }
 
-impl<F: Float + SampleUniform, const N: usize> SamplingDistribution<F, N>
-    for UniformDistribution<F, N>
+impl<F: Float + SampleUniform, const N: usize, R: Rng> SamplingDistribution<F, N>
+    for UniformDistribution<F, N, R>
 {
     fn sample(&mut self) -> Point<F, N> {
         let values: [F; N] = std::array::from_fn(|i| self.uniforms[i].sample(&mut self.rng));
         Point::new(values)
     }
+
+    fn sample_batch(&mut self, n: usize, out: &mut Vec<Point<F, N>>) {
+        let (uniforms, rng) = (&self.uniforms, &mut self.rng);
+        out.extend((0..n).map(|_| Point::new(std::array::from_fn(|i| uniforms[i].sample(rng)))));
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        let Some(ranges) = expanded_ranges(&self.ranges, factor) else {
+            return false;
+        };
+        self.ranges = ranges;
+        for (range, uniform) in self.ranges.iter().zip(self.uniforms.iter_mut()) {
+            *uniform = Uniform::new_inclusive(range.0, range.1);
+        }
+        true
+    }
 }
 
 /// A uniform distribution that occasionally samples the goal with a given goal_bias probability.
-pub struct GoalBiasedUniformDistribution<F: Float + SampleUniform, const N: usize> {
-    uniform: UniformDistribution<F, N>, // Uniform distribution for sampling points.
-    bernoulli: Bernoulli,               // Bernoulli distribution for goal bias.
-    goal: Point<F, N>,                  // The goal point.
-    rng: rand::rngs::ThreadRng,
+pub struct GoalBiasedUniformDistribution<
+    F: Float + SampleUniform,
+    const N: usize,
+    R: Rng = ThreadRng,
+> {
+    uniform: UniformDistribution<F, N, R>, // Uniform distribution for sampling points (also owns the rng).
+    bernoulli: Bernoulli,                  // Bernoulli distribution for goal bias.
+    goal_bias: f64,                        // The probability of sampling the goal.
+    goal: Point<F, N>,                     // The goal point.
 }
 
 impl<F: Float + SampleUniform, const N: usize> GoalBiasedUniformDistribution<F, N> {
     /// Constructs a new goal-biased uniform distribution.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `goal`: The goal point.
+    /// - `goal_bias`: The probability of sampling the goal.
+    ///
+    /// Returns:
+    /// The goal-biased uniform distribution, or an error if a range or the goal_bias is not valid.
+    pub fn new(
+        ranges: [(F, F); N],
+        goal: Point<F, N>,
+        goal_bias: f64,
+    ) -> Result<Self, SamplingError> {
+        Self::new_with_rng(ranges, goal, goal_bias, rand::thread_rng())
+    }
+}
+
+impl<F: Float + SampleUniform, const N: usize, R: Rng> GoalBiasedUniformDistribution<F, N, R> {
+    /// Constructs a new goal-biased uniform distribution with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `goal`: The goal point.
+    /// - `goal_bias`: The probability of sampling the goal.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The goal-biased uniform distribution, or an error if a range or the goal_bias is not valid.
+    pub fn new_with_rng(
+        ranges: [(F, F); N],
+        goal: Point<F, N>,
+        goal_bias: f64,
+        rng: R,
+    ) -> Result<Self, SamplingError> {
+        if !(0.0..=1.0).contains(&goal_bias) {
+            return Err(SamplingError::InvalidGoalBias);
+        }
+        Ok(Self {
+            uniform: UniformDistribution::new_with_rng(ranges, rng)?,
+            bernoulli: Bernoulli::new(goal_bias).unwrap(),
+            goal_bias,
+            goal,
+        })
+    }
+
+    pub fn goal(&self) -> &Point<F, N> {
+        &self.goal
+    }
+
+    /// Changes the goal point returned on biased draws.
+    /// The planner also does this through `SamplingDistribution::set_goal` when its goal changes.
+    pub fn set_goal(&mut self, goal: Point<F, N>) {
+        self.goal = goal;
+    }
+
+    /// Returns the probability of sampling the goal.
+    pub fn goal_bias(&self) -> f64 {
+        self.goal_bias
+    }
+
+    /// Changes the probability of sampling the goal.
+    ///
+    /// Returns:
+    /// An error if the goal_bias is not in the range [0, 1], in which case it is unchanged.
+    pub fn set_goal_bias(&mut self, goal_bias: f64) -> Result<(), SamplingError> {
+        if !(0.0..=1.0).contains(&goal_bias) {
+            return Err(SamplingError::InvalidGoalBias);
+        }
+        self.bernoulli = Bernoulli::new(goal_bias).unwrap();
+        self.goal_bias = goal_bias;
+        Ok(())
+    }
+}
+
+impl<F: Float + SampleUniform, const N: usize, R: Rng> SamplingDistribution<F, N>
+    for GoalBiasedUniformDistribution<F, N, R>
+{
+    fn sample(&mut self) -> Point<F, N> {
+        if self.bernoulli.sample(&mut self.uniform.rng) {
+            self.goal
+        } else {
+            self.uniform.sample()
+        }
+    }
+
+    fn sample_batch(&mut self, n: usize, out: &mut Vec<Point<F, N>>) {
+        let uniform = &mut self.uniform;
+        let (uniforms, rng) = (&uniform.uniforms, &mut uniform.rng);
+        out.extend((0..n).map(|_| {
+            if self.bernoulli.sample(rng) {
+                self.goal
+            } else {
+                Point::new(std::array::from_fn(|i| uniforms[i].sample(rng)))
+            }
+        }));
+    }
+
+    fn set_goal(&mut self, goal: Point<F, N>) {
+        self.goal = goal;
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        self.uniform.expand_ranges(factor)
+    }
+}
+
+/// A uniform distribution that occasionally samples the region around the goal with a given goal_bias probability.
+///
+/// Unlike `GoalBiasedUniformDistribution`, the biased samples are drawn uniformly from a ball of `goal_radius`
+/// around the goal instead of being the exact goal point. When the steering range is shorter than the distance
+/// to the goal, this avoids repeatedly extending the tree along the same ray, which helps in cluttered goal regions.
+/// A goal radius of zero always samples the exact goal.
+pub struct GoalRegionBiasedUniformDistribution<
+    F: Float + SampleUniform,
+    const N: usize,
+    R: Rng = ThreadRng,
+> where
+    StandardNormal: Distribution<F>,
+{
+    uniform: UniformDistribution<F, N, R>, // Uniform distribution for sampling points (also owns the rng).
+    bernoulli: Bernoulli,                  // Bernoulli distribution for goal bias.
+    goal: Point<F, N>,                     // The goal point.
+    goal_radius: F, // The radius of the ball around the goal for biased samples.
+}
+
+impl<F: Float + SampleUniform, const N: usize> GoalRegionBiasedUniformDistribution<F, N>
+where
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new goal-region-biased uniform distribution.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `goal`: The goal point.
+    /// - `goal_radius`: The radius of the ball around the goal for biased samples, usually the goal tolerance.
+    /// - `goal_bias`: The probability of sampling the goal region.
+    ///
+    /// Returns:
+    /// The goal-region-biased uniform distribution, or an error if a range, the goal_radius or the goal_bias is not valid.
+    pub fn new(
+        ranges: [(F, F); N],
+        goal: Point<F, N>,
+        goal_radius: F,
+        goal_bias: f64,
+    ) -> Result<Self, SamplingError> {
+        Self::new_with_rng(ranges, goal, goal_radius, goal_bias, rand::thread_rng())
+    }
+}
+
+impl<F: Float + SampleUniform, const N: usize, R: Rng> GoalRegionBiasedUniformDistribution<F, N, R>
+where
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new goal-region-biased uniform distribution with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `goal`: The goal point.
+    /// - `goal_radius`: The radius of the ball around the goal for biased samples, usually the goal tolerance.
+    /// - `goal_bias`: The probability of sampling the goal region.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The goal-region-biased uniform distribution, or an error if a range, the goal_radius or the goal_bias is not valid.
+    pub fn new_with_rng(
+        ranges: [(F, F); N],
+        goal: Point<F, N>,
+        goal_radius: F,
+        goal_bias: f64,
+        rng: R,
+    ) -> Result<Self, SamplingError> {
+        if !(0.0..=1.0).contains(&goal_bias) {
+            return Err(SamplingError::InvalidGoalBias);
+        }
+        if !(goal_radius >= F::zero() && goal_radius.is_finite()) {
+            return Err(SamplingError::InvalidGoalRadius);
+        }
+        Ok(Self {
+            uniform: UniformDistribution::new_with_rng(ranges, rng)?,
+            bernoulli: Bernoulli::new(goal_bias).unwrap(),
+            goal,
+            goal_radius,
+        })
+    }
+
+    /// Returns the radius of the ball around the goal for biased samples.
+    pub fn goal_radius(&self) -> F {
+        self.goal_radius
+    }
+}
+
+impl<F: Float + SampleUniform, const N: usize, R: Rng> SamplingDistribution<F, N>
+    for GoalRegionBiasedUniformDistribution<F, N, R>
+where
+    StandardNormal: Distribution<F>,
+{
+    fn sample(&mut self) -> Point<F, N> {
+        if self.bernoulli.sample(&mut self.uniform.rng) {
+            sample_in_ball(&mut self.uniform.rng, self.goal, self.goal_radius, false)
+        } else {
+            self.uniform.sample()
+        }
+    }
+
+    fn set_goal(&mut self, goal: Point<F, N>) {
+        self.goal = goal;
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        self.uniform.expand_ranges(factor)
+    }
+}
+
+/// A uniform distribution that occasionally samples the goal in only some of the dimensions,
+/// with a given goal_bias probability.
+///
+/// This is useful when only part of the state matters for the goal, such as the position
+/// in a state with a heading and a velocity. On a biased draw, the dimensions in the mask are copied
+/// from the goal and the other dimensions are sampled uniformly from their ranges.
+pub struct MaskedGoalBiasedUniformDistribution<
+    F: Float + SampleUniform,
+    const N: usize,
+    R: Rng = ThreadRng,
+> {
+    uniform: UniformDistribution<F, N, R>, // Uniform distribution for sampling points (also owns the rng).
+    bernoulli: Bernoulli,                  // Bernoulli distribution for goal bias.
+    goal: Point<F, N>,                     // The goal point.
+    mask: [bool; N], // Whether each dimension is copied from the goal on a biased draw.
+}
+
+impl<F: Float + SampleUniform, const N: usize> MaskedGoalBiasedUniformDistribution<F, N> {
+    /// Constructs a new masked goal-biased uniform distribution.
+    ///
     /// Parameters:
     /// - `ranges`: The ranges for each dimension.
     /// - `goal`: The goal point.
+    /// - `mask`: Whether each dimension is copied from the goal on a biased draw.
     /// - `goal_bias`: The probability of sampling the goal.
+    ///
     /// Returns:
-    /// The goal-biased uniform distribution.
+    /// The masked goal-biased uniform distribution, or an error if a range or the goal_bias is not valid.
     pub fn new(
         ranges: [(F, F); N],
         goal: Point<F, N>,
+        mask: [bool; N],
+        goal_bias: f64,
+    ) -> Result<Self, SamplingError> {
+        Self::new_with_rng(ranges, goal, mask, goal_bias, rand::thread_rng())
+    }
+
+    /// Constructs a new masked goal-biased uniform distribution from the indices of the goal dimensions.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `goal`: The goal point.
+    /// - `dimensions`: The indices of the dimensions copied from the goal on a biased draw.
+    /// - `goal_bias`: The probability of sampling the goal.
+    ///
+    /// Returns:
+    /// The masked goal-biased uniform distribution, or an error if a range, a dimension or the goal_bias is not valid.
+    pub fn from_dimensions(
+        ranges: [(F, F); N],
+        goal: Point<F, N>,
+        dimensions: &[usize],
+        goal_bias: f64,
+    ) -> Result<Self, SamplingError> {
+        let mut mask = [false; N];
+        for &dimension in dimensions {
+            if dimension >= N {
+                return Err(SamplingError::InvalidDimension { dimension });
+            }
+            mask[dimension] = true;
+        }
+        Self::new(ranges, goal, mask, goal_bias)
+    }
+}
+
+impl<F: Float + SampleUniform, const N: usize, R: Rng>
+    MaskedGoalBiasedUniformDistribution<F, N, R>
+{
+    /// Constructs a new masked goal-biased uniform distribution with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `goal`: The goal point.
+    /// - `mask`: Whether each dimension is copied from the goal on a biased draw.
+    /// - `goal_bias`: The probability of sampling the goal.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The masked goal-biased uniform distribution, or an error if a range or the goal_bias is not valid.
+    pub fn new_with_rng(
+        ranges: [(F, F); N],
+        goal: Point<F, N>,
+        mask: [bool; N],
+        goal_bias: f64,
+        rng: R,
+    ) -> Result<Self, SamplingError> {
+        if !(0.0..=1.0).contains(&goal_bias) {
+            return Err(SamplingError::InvalidGoalBias);
+        }
+        Ok(Self {
+            uniform: UniformDistribution::new_with_rng(ranges, rng)?,
+            bernoulli: Bernoulli::new(goal_bias).unwrap(),
+            goal,
+            mask,
+        })
+    }
+
+    /// Returns whether each dimension is copied from the goal on a biased draw.
+    pub fn mask(&self) -> &[bool; N] {
+        &self.mask
+    }
+}
+
+impl<F: Float + SampleUniform, const N: usize, R: Rng> SamplingDistribution<F, N>
+    for MaskedGoalBiasedUniformDistribution<F, N, R>
+{
+    fn sample(&mut self) -> Point<F, N> {
+        let sample = self.uniform.sample();
+        if !self.bernoulli.sample(&mut self.uniform.rng) {
+            return sample;
+        }
+        let values: [F; N] = std::array::from_fn(|i| {
+            if self.mask[i] {
+                self.goal[i]
+            } else {
+                sample[i]
+            }
+        });
+        Point::new(values)
+    }
+
+    fn set_goal(&mut self, goal: Point<F, N>) {
+        self.goal = goal;
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        self.uniform.expand_ranges(factor)
+    }
+}
+
+/// A gaussian distribution for sampling points near a region of interest, such as the start or a previous path.
+/// Each dimension is sampled independently around the mean with its own standard deviation.
+/// Samples can optionally be clamped to bounds, so they stay in the workspace.
+///
+/// The random number generator defaults to `ThreadRng`.
+/// Use `new_with_rng` with a seeded generator for reproducible samples.
+pub struct GaussianDistribution<F: Float, const N: usize, R: Rng = ThreadRng>
+where
+    StandardNormal: Distribution<F>,
+{
+    normals: [Normal<F>; N],
+    bounds: Option<[(F, F); N]>,
+    rng: R,
+}
+
+impl<F: Float, const N: usize> GaussianDistribution<F, N>
+where
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new gaussian distribution.
+    ///
+    /// Parameters:
+    /// - `mean`: The mean of the distribution.
+    /// - `std_devs`: The standard deviation for each dimension.
+    /// - `bounds`: The bounds to clamp each dimension to (None to not clamp).
+    ///
+    /// Returns:
+    /// The gaussian distribution, or an error if a standard deviation is not positive
+    /// or a lower bound is above its upper bound.
+    pub fn new(
+        mean: Point<F, N>,
+        std_devs: [F; N],
+        bounds: Option<[(F, F); N]>,
+    ) -> Result<Self, &'static str> {
+        Self::new_with_rng(mean, std_devs, bounds, rand::thread_rng())
+    }
+}
+
+impl<F: Float, const N: usize, R: Rng> GaussianDistribution<F, N, R>
+where
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new gaussian distribution with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `mean`: The mean of the distribution.
+    /// - `std_devs`: The standard deviation for each dimension.
+    /// - `bounds`: The bounds to clamp each dimension to (None to not clamp).
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The gaussian distribution, or an error if a standard deviation is not positive
+    /// or a lower bound is above its upper bound.
+    pub fn new_with_rng(
+        mean: Point<F, N>,
+        std_devs: [F; N],
+        bounds: Option<[(F, F); N]>,
+        rng: R,
+    ) -> Result<Self, &'static str> {
+        if std_devs
+            .iter()
+            .any(|&std_dev| !(std_dev > F::zero() && std_dev.is_finite()))
+        {
+            return Err("standard deviations must be positive and finite");
+        }
+        if bounds.is_some_and(|bounds| bounds.iter().any(|(min, max)| min > max)) {
+            return Err("lower bounds must not be above upper bounds");
+        }
+        let normals: [Normal<F>; N] =
+            std::array::from_fn(|i| Normal::new(mean[i], std_devs[i]).unwrap());
+        Ok(Self {
+            normals,
+            bounds,
+            rng,
+        })
+    }
+
+    /// Returns the mean of the distribution.
+    pub fn mean(&self) -> Point<F, N> {
+        Point::new(std::array::from_fn(|i| self.normals[i].mean()))
+    }
+
+    /// Returns the standard deviation for each dimension.
+    pub fn std_devs(&self) -> [F; N] {
+        std::array::from_fn(|i| self.normals[i].std_dev())
+    }
+}
+
+impl<F: Float, const N: usize, R: Rng> SamplingDistribution<F, N> for GaussianDistribution<F, N, R>
+where
+    StandardNormal: Distribution<F>,
+{
+    fn sample(&mut self) -> Point<F, N> {
+        let values: [F; N] = std::array::from_fn(|i| {
+            let value = self.normals[i].sample(&mut self.rng);
+            match self.bounds {
+                Some(bounds) => value.max(bounds[i].0).min(bounds[i].1),
+                None => value,
+            }
+        });
+        Point::new(values)
+    }
+}
+
+/// A gaussian distribution that occasionally samples the goal with a given goal_bias probability.
+pub struct GoalBiasedGaussianDistribution<F: Float, const N: usize, R: Rng = ThreadRng>
+where
+    StandardNormal: Distribution<F>,
+{
+    gaussian: GaussianDistribution<F, N, R>, // Gaussian distribution for sampling points (also owns the rng).
+    bernoulli: Bernoulli,                    // Bernoulli distribution for goal bias.
+    goal: Point<F, N>,                       // The goal point.
+}
+
+impl<F: Float, const N: usize> GoalBiasedGaussianDistribution<F, N>
+where
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new goal-biased gaussian distribution.
+    ///
+    /// Parameters:
+    /// - `mean`: The mean of the distribution.
+    /// - `std_devs`: The standard deviation for each dimension.
+    /// - `bounds`: The bounds to clamp each dimension to (None to not clamp).
+    /// - `goal`: The goal point.
+    /// - `goal_bias`: The probability of sampling the goal.
+    ///
+    /// Returns:
+    /// The goal-biased gaussian distribution, or an error if a parameter is not valid.
+    pub fn new(
+        mean: Point<F, N>,
+        std_devs: [F; N],
+        bounds: Option<[(F, F); N]>,
+        goal: Point<F, N>,
+        goal_bias: f64,
+    ) -> Result<Self, &'static str> {
+        Self::new_with_rng(mean, std_devs, bounds, goal, goal_bias, rand::thread_rng())
+    }
+}
+
+impl<F: Float, const N: usize, R: Rng> GoalBiasedGaussianDistribution<F, N, R>
+where
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new goal-biased gaussian distribution with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `mean`: The mean of the distribution.
+    /// - `std_devs`: The standard deviation for each dimension.
+    /// - `bounds`: The bounds to clamp each dimension to (None to not clamp).
+    /// - `goal`: The goal point.
+    /// - `goal_bias`: The probability of sampling the goal.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The goal-biased gaussian distribution, or an error if a parameter is not valid.
+    pub fn new_with_rng(
+        mean: Point<F, N>,
+        std_devs: [F; N],
+        bounds: Option<[(F, F); N]>,
+        goal: Point<F, N>,
         goal_bias: f64,
+        rng: R,
     ) -> Result<Self, &'static str> {
-        if goal_bias < 0.0 || goal_bias > 1.0 {
+        if !(0.0..=1.0).contains(&goal_bias) {
             return Err("goal_bias must be in the range [0, 1]");
         }
         Ok(Self {
-            uniform: UniformDistribution::new(ranges),
+            gaussian: GaussianDistribution::new_with_rng(mean, std_devs, bounds, rng)?,
             bernoulli: Bernoulli::new(goal_bias).unwrap(),
             goal,
-            rng: rand::thread_rng(),
         })
     }
 }
 
-impl<F: Float + SampleUniform, const N: usize> SamplingDistribution<F, N>
-    for GoalBiasedUniformDistribution<F, N>
+impl<F: Float, const N: usize, R: Rng> SamplingDistribution<F, N>
+    for GoalBiasedGaussianDistribution<F, N, R>
+where
+    StandardNormal: Distribution<F>,
 {
     fn sample(&mut self) -> Point<F, N> {
-        if self.bernoulli.sample(&mut self.rng) {
-            self.goal.clone()
+        if self.bernoulli.sample(&mut self.gaussian.rng) {
+            self.goal
         } else {
-            self.uniform.sample()
+            self.gaussian.sample()
         }
     }
+
+    fn set_goal(&mut self, goal: Point<F, N>) {
+        self.goal = goal;
+    }
+}
+
+/// A uniform distribution that occasionally samples one of several goals with a given goal_bias probability.
+/// The goal is picked uniformly among the goals.
+pub struct MultiGoalBiasedUniformDistribution<
+    F: Float + SampleUniform,
+    const N: usize,
+    R: Rng = ThreadRng,
+> {
+    uniform: UniformDistribution<F, N, R>, // Uniform distribution for sampling points (also owns the rng).
+    bernoulli: Bernoulli,                  // Bernoulli distribution for goal bias.
+    goals: Vec<Point<F, N>>,               // The goal points.
+}
+
+impl<F: Float + SampleUniform, const N: usize> MultiGoalBiasedUniformDistribution<F, N> {
+    /// Constructs a new multi-goal-biased uniform distribution.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `goals`: The goal points.
+    /// - `goal_bias`: The probability of sampling a goal.
+    ///
+    /// Returns:
+    /// The multi-goal-biased uniform distribution, or an error if a range or the goal_bias is not valid,
+    /// or there are no goals.
+    pub fn new(
+        ranges: [(F, F); N],
+        goals: Vec<Point<F, N>>,
+        goal_bias: f64,
+    ) -> Result<Self, SamplingError> {
+        Self::new_with_rng(ranges, goals, goal_bias, rand::thread_rng())
+    }
+}
+
+impl<F: Float + SampleUniform, const N: usize, R: Rng> MultiGoalBiasedUniformDistribution<F, N, R> {
+    /// Constructs a new multi-goal-biased uniform distribution with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `goals`: The goal points.
+    /// - `goal_bias`: The probability of sampling a goal.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The multi-goal-biased uniform distribution, or an error if a range or the goal_bias is not valid,
+    /// or there are no goals.
+    pub fn new_with_rng(
+        ranges: [(F, F); N],
+        goals: Vec<Point<F, N>>,
+        goal_bias: f64,
+        rng: R,
+    ) -> Result<Self, SamplingError> {
+        if !(0.0..=1.0).contains(&goal_bias) {
+            return Err(SamplingError::InvalidGoalBias);
+        }
+        if goals.is_empty() {
+            return Err(SamplingError::NoGoals);
+        }
+        Ok(Self {
+            uniform: UniformDistribution::new_with_rng(ranges, rng)?,
+            bernoulli: Bernoulli::new(goal_bias).unwrap(),
+            goals,
+        })
+    }
+}
+
+impl<F: Float + SampleUniform, const N: usize, R: Rng> SamplingDistribution<F, N>
+    for MultiGoalBiasedUniformDistribution<F, N, R>
+{
+    fn sample(&mut self) -> Point<F, N> {
+        if self.bernoulli.sample(&mut self.uniform.rng) {
+            let index = self.uniform.rng.gen_range(0..self.goals.len());
+            self.goals[index]
+        } else {
+            self.uniform.sample()
+        }
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        self.uniform.expand_ranges(factor)
+    }
+}
+
+/// A sampling distribution that samples the goal with a bias that adapts to the progress of the planner.
+///
+/// The bias starts low, so the tree explores instead of running into obstacles between the start and the goal.
+/// Whenever no node has gotten closer to the goal for `patience` consecutive samples, the bias is doubled
+/// (up to `max_bias`). It is reset to the initial bias as soon as a node gets closer to the goal.
+/// The progress is reported by the planner through `SamplingDistribution::feedback`.
+/// Otherwise, points are sampled from the wrapped distribution.
+pub struct AdaptiveGoalBias<F: Float, const N: usize, SD, R: Rng = ThreadRng>
+where
+    SD: SamplingDistribution<F, N>,
+{
+    base: SD,                      // The distribution sampled when the goal is not sampled.
+    goal: Point<F, N>,             // The goal point.
+    initial_bias: f64,             // The bias used while the tree makes progress.
+    max_bias: f64,                 // The largest bias.
+    bias: f64,                     // The current bias.
+    patience: u32, // The number of samples without progress before the bias is raised.
+    samples_without_progress: u32, // The number of samples since the bias was last changed without progress.
+    best_goal_distance: Option<F>, // The smallest distance to the goal reported so far.
+    rng: R,                        // The random number generator.
+}
+
+impl<F: Float, const N: usize, SD> AdaptiveGoalBias<F, N, SD>
+where
+    SD: SamplingDistribution<F, N>,
+{
+    /// Constructs a new adaptive goal bias distribution.
+    ///
+    /// Parameters:
+    /// - `base`: The distribution sampled when the goal is not sampled.
+    /// - `goal`: The goal point.
+    /// - `initial_bias`: The initial probability of sampling the goal.
+    /// - `max_bias`: The largest probability of sampling the goal.
+    /// - `patience`: The number of samples without progress after which the bias is doubled.
+    ///
+    /// Returns:
+    /// The adaptive goal bias distribution, or an error if the biases or the patience are not valid.
+    pub fn new(
+        base: SD,
+        goal: Point<F, N>,
+        initial_bias: f64,
+        max_bias: f64,
+        patience: u32,
+    ) -> Result<Self, &'static str> {
+        Self::new_with_rng(
+            base,
+            goal,
+            initial_bias,
+            max_bias,
+            patience,
+            rand::thread_rng(),
+        )
+    }
+}
+
+impl<F: Float, const N: usize, SD, R: Rng> AdaptiveGoalBias<F, N, SD, R>
+where
+    SD: SamplingDistribution<F, N>,
+{
+    /// Constructs a new adaptive goal bias distribution with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `base`: The distribution sampled when the goal is not sampled.
+    /// - `goal`: The goal point.
+    /// - `initial_bias`: The initial probability of sampling the goal.
+    /// - `max_bias`: The largest probability of sampling the goal.
+    /// - `patience`: The number of samples without progress after which the bias is doubled.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The adaptive goal bias distribution, or an error if the biases or the patience are not valid.
+    pub fn new_with_rng(
+        base: SD,
+        goal: Point<F, N>,
+        initial_bias: f64,
+        max_bias: f64,
+        patience: u32,
+        rng: R,
+    ) -> Result<Self, &'static str> {
+        if !(initial_bias > 0.0 && initial_bias <= 1.0) {
+            return Err("initial_bias must be in the range (0, 1]");
+        }
+        if !(initial_bias..=1.0).contains(&max_bias) {
+            return Err("max_bias must be in the range [initial_bias, 1]");
+        }
+        if patience == 0 {
+            return Err("patience must be positive");
+        }
+        Ok(Self {
+            base,
+            goal,
+            initial_bias,
+            max_bias,
+            bias: initial_bias,
+            patience,
+            samples_without_progress: 0,
+            best_goal_distance: None,
+            rng,
+        })
+    }
+
+    /// Returns the current probability of sampling the goal.
+    pub fn bias(&self) -> f64 {
+        self.bias
+    }
+
+    pub fn base(&self) -> &SD {
+        &self.base
+    }
+}
+
+impl<F: Float, const N: usize, SD, R: Rng> SamplingDistribution<F, N>
+    for AdaptiveGoalBias<F, N, SD, R>
+where
+    SD: SamplingDistribution<F, N>,
+{
+    fn sample(&mut self) -> Point<F, N> {
+        if self.rng.gen::<f64>() < self.bias {
+            self.goal
+        } else {
+            self.base.sample()
+        }
+    }
+
+    fn set_goal(&mut self, goal: Point<F, N>) {
+        self.goal = goal;
+        self.best_goal_distance = None;
+        self.bias = self.initial_bias;
+        self.samples_without_progress = 0;
+        self.base.set_goal(goal);
+    }
+
+    fn feedback(&mut self, feedback: SampleFeedback<F>) {
+        let progress = match (feedback.best_goal_distance, self.best_goal_distance) {
+            (Some(distance), Some(best)) => distance < best,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if progress {
+            self.best_goal_distance = feedback.best_goal_distance;
+            self.bias = self.initial_bias;
+            self.samples_without_progress = 0;
+        } else {
+            self.samples_without_progress += 1;
+            if self.samples_without_progress >= self.patience {
+                self.bias = (self.bias * 2.0).min(self.max_bias);
+                self.samples_without_progress = 0;
+            }
+        }
+        self.base.feedback(feedback);
+    }
+
+    fn extension_failed(&mut self, from: &Point<F, N>, to: &Point<F, N>) {
+        self.base.extension_failed(from, to);
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        self.base.expand_ranges(factor)
+    }
+}
+
+/// A quasi-random sampler that generates the Halton sequence, which covers the space more evenly
+/// than random samples and is fully deterministic.
+///
+/// Dimension `i` uses the `i`-th prime number as its base, and each sample is scaled into the ranges.
+/// The early points of the sequence are correlated between dimensions with large bases,
+/// so the first `skip` points can be discarded.
+pub struct HaltonSampler<F: Float, const N: usize> {
+    ranges: [(F, F); N], // The ranges for each dimension.
+    bases: [u64; N],     // The prime base for each dimension.
+    index: u64,          // The index of the last generated point in the sequence.
+}
+
+impl<F: Float, const N: usize> HaltonSampler<F, N> {
+    /// Constructs a new Halton sampler.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `skip`: The number of points at the start of the sequence to discard.
+    ///
+    /// Returns:
+    /// The Halton sampler, or an error if a range is not finite or is inverted.
+    pub fn new(ranges: [(F, F); N], skip: u64) -> Result<Self, SamplingError> {
+        validate_ranges(&ranges)?;
+        let mut primes =
+            (2..).filter(|&n: &u64| (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0));
+        let bases: [u64; N] = std::array::from_fn(|_| primes.next().unwrap());
+        Ok(Self {
+            ranges,
+            bases,
+            index: skip,
+        })
+    }
+
+    /// Returns the index of the last generated point in the sequence.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Returns the ranges for each dimension.
+    pub fn ranges(&self) -> &[(F, F); N] {
+        &self.ranges
+    }
+
+    /// Computes the radical inverse of `index` in `base`, which lies in [0, 1).
+    fn radical_inverse(mut index: u64, base: u64) -> f64 {
+        let mut result = 0.0;
+        let mut fraction = 1.0 / base as f64;
+        while index > 0 {
+            result += (index % base) as f64 * fraction;
+            index /= base;
+            fraction /= base as f64;
+        }
+        result
+    }
+}
+
+impl<F: Float, const N: usize> SamplingDistribution<F, N> for HaltonSampler<F, N> {
+    fn sample(&mut self) -> Point<F, N> {
+        // The sequence starts at index 1, since index 0 is the origin in every dimension.
+        self.index += 1;
+        let values: [F; N] = std::array::from_fn(|i| {
+            let (min, max) = self.ranges[i];
+            let t = F::from(Self::radical_inverse(self.index, self.bases[i])).unwrap();
+            min + (max - min) * t
+        });
+        Point::new(values)
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        let Some(ranges) = expanded_ranges(&self.ranges, factor) else {
+            return false;
+        };
+        self.ranges = ranges;
+        true
+    }
+}
+
+/// The maximum number of dimensions supported by `SobolSampler`.
+pub const MAX_SOBOL_DIMENSIONS: usize = 16;
+
+/// The degree `s`, polynomial coefficients `a` and initial direction numbers `m` of dimensions 2 to 16,
+/// from the new-joe-kuo-6.21201 table by Joe and Kuo. Dimension 1 uses the van der Corput sequence.
+const SOBOL_PARAMETERS: [(u32, u32, &[u32]); MAX_SOBOL_DIMENSIONS - 1] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+];
+
+/// The number of bits of each coordinate of a Sobol point.
+const SOBOL_BITS: usize = 32;
+
+/// A quasi-random sampler that generates the Sobol sequence, which stays evenly distributed
+/// in more dimensions than the Halton sequence. Up to `MAX_SOBOL_DIMENSIONS` dimensions are supported.
+///
+/// Each sample is scaled into the ranges. The sequence is fully deterministic, and any point
+/// can be computed directly from its index, so sampling can be restarted from any index with `set_index`.
+/// The sequence repeats after 2^32 points.
+pub struct SobolSampler<F: Float, const N: usize> {
+    ranges: [(F, F); N],                // The ranges for each dimension.
+    directions: [[u32; SOBOL_BITS]; N], // The direction numbers for each dimension.
+    index: u64,                         // The index of the last generated point in the sequence.
+}
+
+impl<F: Float, const N: usize> SobolSampler<F, N> {
+    /// Constructs a new Sobol sampler.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `skip`: The number of points at the start of the sequence to discard.
+    ///
+    /// Returns:
+    /// The Sobol sampler, or an error if there are more than `MAX_SOBOL_DIMENSIONS` dimensions
+    /// or a range is not finite or is inverted.
+    pub fn new(ranges: [(F, F); N], skip: u64) -> Result<Self, SamplingError> {
+        if N > MAX_SOBOL_DIMENSIONS {
+            return Err(SamplingError::TooManyDimensions {
+                max: MAX_SOBOL_DIMENSIONS,
+            });
+        }
+        validate_ranges(&ranges)?;
+        let directions: [[u32; SOBOL_BITS]; N] = std::array::from_fn(Self::direction_numbers);
+        Ok(Self {
+            ranges,
+            directions,
+            index: skip,
+        })
+    }
+
+    /// Returns the index of the last generated point in the sequence.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Restarts the sequence, so the next sample is the point after `index`.
+    ///
+    /// Parameters:
+    /// - `index`: The index of the last generated point in the sequence.
+    pub fn set_index(&mut self, index: u64) {
+        self.index = index;
+    }
+
+    /// Returns the ranges for each dimension.
+    pub fn ranges(&self) -> &[(F, F); N] {
+        &self.ranges
+    }
+
+    /// Computes the direction numbers of a dimension, scaled to the top bits of a u32.
+    fn direction_numbers(dimension: usize) -> [u32; SOBOL_BITS] {
+        let mut directions = [0u32; SOBOL_BITS];
+        if dimension == 0 {
+            for (k, direction) in directions.iter_mut().enumerate() {
+                *direction = 1 << (SOBOL_BITS - 1 - k);
+            }
+            return directions;
+        }
+
+        let (degree, coefficients, initial) = SOBOL_PARAMETERS[dimension - 1];
+        let degree = degree as usize;
+        for k in 0..SOBOL_BITS {
+            directions[k] = if k < degree {
+                initial[k] << (SOBOL_BITS - 1 - k)
+            } else {
+                let mut direction = directions[k - degree] ^ (directions[k - degree] >> degree);
+                for j in 1..degree {
+                    if (coefficients >> (degree - 1 - j)) & 1 == 1 {
+                        direction ^= directions[k - j];
+                    }
+                }
+                direction
+            };
+        }
+        directions
+    }
+}
+
+impl<F: Float, const N: usize> SamplingDistribution<F, N> for SobolSampler<F, N> {
+    fn sample(&mut self) -> Point<F, N> {
+        // The sequence starts at index 1, since index 0 is the origin in every dimension.
+        self.index += 1;
+        // Points are computed from the gray code of the index, which visits the same points
+        // in a different order and lets each point be computed without the previous ones.
+        let gray = (self.index ^ (self.index >> 1)) as u32;
+        let values: [F; N] = std::array::from_fn(|i| {
+            let mut bits = 0u32;
+            for (k, direction) in self.directions[i].iter().enumerate() {
+                if (gray >> k) & 1 == 1 {
+                    bits ^= direction;
+                }
+            }
+            let (min, max) = self.ranges[i];
+            let t = F::from(bits as f64 / (1u64 << SOBOL_BITS) as f64).unwrap();
+            min + (max - min) * t
+        });
+        Point::new(values)
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        let Some(ranges) = expanded_ranges(&self.ranges, factor) else {
+            return false;
+        };
+        self.ranges = ranges;
+        true
+    }
+}
+
+/// Wraps any sampling distribution so that it occasionally samples the goal with a given goal_bias probability,
+/// for example to add a goal bias to a `HaltonSampler`.
+///
+/// Use `new_with_rng` with a seeded generator for reproducible samples.
+pub struct GoalBiasedDistribution<F: Float, const N: usize, SD, R: Rng = ThreadRng>
+where
+    SD: SamplingDistribution<F, N>,
+{
+    base: SD,             // The distribution sampled when the goal is not sampled.
+    bernoulli: Bernoulli, // Bernoulli distribution for goal bias.
+    goal: Point<F, N>,    // The goal point.
+    rng: R,               // The random number generator for the goal bias.
+}
+
+impl<F: Float, const N: usize, SD> GoalBiasedDistribution<F, N, SD>
+where
+    SD: SamplingDistribution<F, N>,
+{
+    /// Constructs a new goal-biased distribution.
+    ///
+    /// Parameters:
+    /// - `base`: The distribution sampled when the goal is not sampled.
+    /// - `goal`: The goal point.
+    /// - `goal_bias`: The probability of sampling the goal.
+    ///
+    /// Returns:
+    /// The goal-biased distribution.
+    pub fn new(base: SD, goal: Point<F, N>, goal_bias: f64) -> Result<Self, &'static str> {
+        Self::new_with_rng(base, goal, goal_bias, rand::thread_rng())
+    }
+}
+
+impl<F: Float, const N: usize, SD, R: Rng> GoalBiasedDistribution<F, N, SD, R>
+where
+    SD: SamplingDistribution<F, N>,
+{
+    /// Constructs a new goal-biased distribution with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `base`: The distribution sampled when the goal is not sampled.
+    /// - `goal`: The goal point.
+    /// - `goal_bias`: The probability of sampling the goal.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The goal-biased distribution.
+    pub fn new_with_rng(
+        base: SD,
+        goal: Point<F, N>,
+        goal_bias: f64,
+        rng: R,
+    ) -> Result<Self, &'static str> {
+        if !(0.0..=1.0).contains(&goal_bias) {
+            return Err("goal_bias must be in the range [0, 1]");
+        }
+        Ok(Self {
+            base,
+            bernoulli: Bernoulli::new(goal_bias).unwrap(),
+            goal,
+            rng,
+        })
+    }
+
+    pub fn base(&self) -> &SD {
+        &self.base
+    }
+}
+
+impl<F: Float, const N: usize, SD, R: Rng> SamplingDistribution<F, N>
+    for GoalBiasedDistribution<F, N, SD, R>
+where
+    SD: SamplingDistribution<F, N>,
+{
+    fn sample(&mut self) -> Point<F, N> {
+        if self.bernoulli.sample(&mut self.rng) {
+            self.goal
+        } else {
+            self.base.sample()
+        }
+    }
+
+    fn set_goal(&mut self, goal: Point<F, N>) {
+        self.goal = goal;
+        self.base.set_goal(goal);
+    }
+
+    fn feedback(&mut self, feedback: SampleFeedback<F>) {
+        self.base.feedback(feedback);
+    }
+
+    fn extension_failed(&mut self, from: &Point<F, N>, to: &Point<F, N>) {
+        self.base.extension_failed(from, to);
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        self.base.expand_ranges(factor)
+    }
+}
+
+/// A sampling distribution that moves samples just outside the surface of the nearest obstacle
+/// (as in Obstacle-Based RRT), which helps the tree follow walls and pass through narrow gaps.
+///
+/// With the given probability, a sample from the base distribution is projected onto the surface of the
+/// nearest obstacle and moved `offset` outwards along the surface normal. Samples inside an obstacle are
+/// projected out of it. Otherwise, or if there are no obstacles, the base sample is returned unchanged.
+pub struct ObstacleBoundarySampler<F: Float, const N: usize, SD, O, R: Rng = ThreadRng>
+where
+    SD: SamplingDistribution<F, N>,
+    O: ObstacleSurface<F, N>,
+{
+    base: SD,             // The distribution of the samples before projection.
+    obstacles: O,         // The obstacles whose surfaces the samples are projected to.
+    offset: F,            // The distance outside the surface at which projected samples are placed.
+    bernoulli: Bernoulli, // Bernoulli distribution for projecting a sample.
+    rng: R,               // The random number generator for the projection probability.
+}
+
+impl<F: Float, const N: usize, SD, O> ObstacleBoundarySampler<F, N, SD, O>
+where
+    SD: SamplingDistribution<F, N>,
+    O: ObstacleSurface<F, N>,
+{
+    /// Constructs a new obstacle boundary sampler.
+    ///
+    /// Parameters:
+    /// - `base`: The distribution of the samples before projection.
+    /// - `obstacles`: The obstacles, such as a reference to the validity checker.
+    /// - `offset`: The distance outside the surface at which projected samples are placed.
+    /// - `probability`: The probability of projecting a sample.
+    ///
+    /// Returns:
+    /// The obstacle boundary sampler, or an error if the offset is negative or the probability is not valid.
+    pub fn new(base: SD, obstacles: O, offset: F, probability: f64) -> Result<Self, &'static str> {
+        Self::new_with_rng(base, obstacles, offset, probability, rand::thread_rng())
+    }
+}
+
+impl<F: Float, const N: usize, SD, O, R: Rng> ObstacleBoundarySampler<F, N, SD, O, R>
+where
+    SD: SamplingDistribution<F, N>,
+    O: ObstacleSurface<F, N>,
+{
+    /// Constructs a new obstacle boundary sampler with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `base`: The distribution of the samples before projection.
+    /// - `obstacles`: The obstacles, such as a reference to the validity checker.
+    /// - `offset`: The distance outside the surface at which projected samples are placed.
+    /// - `probability`: The probability of projecting a sample.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The obstacle boundary sampler, or an error if the offset is negative or the probability is not valid.
+    pub fn new_with_rng(
+        base: SD,
+        obstacles: O,
+        offset: F,
+        probability: f64,
+        rng: R,
+    ) -> Result<Self, &'static str> {
+        if offset < F::zero() {
+            return Err("offset must not be negative");
+        }
+        if !(0.0..=1.0).contains(&probability) {
+            return Err("probability must be in the range [0, 1]");
+        }
+        Ok(Self {
+            base,
+            obstacles,
+            offset,
+            bernoulli: Bernoulli::new(probability).unwrap(),
+            rng,
+        })
+    }
+
+    pub fn base(&self) -> &SD {
+        &self.base
+    }
+
+    pub fn obstacles(&self) -> &O {
+        &self.obstacles
+    }
+}
+
+impl<F: Float, const N: usize, SD, O, R: Rng> SamplingDistribution<F, N>
+    for ObstacleBoundarySampler<F, N, SD, O, R>
+where
+    SD: SamplingDistribution<F, N>,
+    O: ObstacleSurface<F, N>,
+{
+    fn sample(&mut self) -> Point<F, N> {
+        let sample = self.base.sample();
+        if !self.bernoulli.sample(&mut self.rng) {
+            return sample;
+        }
+        match self.obstacles.nearest_obstacle_surface(&sample) {
+            Some((surface, normal)) => surface + normal * self.offset,
+            None => sample,
+        }
+    }
+
+    fn set_goal(&mut self, goal: Point<F, N>) {
+        self.base.set_goal(goal);
+    }
+
+    fn feedback(&mut self, feedback: SampleFeedback<F>) {
+        self.base.feedback(feedback);
+    }
+
+    fn extension_failed(&mut self, from: &Point<F, N>, to: &Point<F, N>) {
+        self.base.extension_failed(from, to);
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        self.base.expand_ranges(factor)
+    }
+}
+
+/// A sampling distribution that focuses samples near a reference path, for refining an existing solution.
+///
+/// With the given path_bias probability, a random segment of the path is chosen, a point is picked
+/// uniformly along it, and zero-mean gaussian noise is added to each dimension.
+/// Otherwise, or if the path is empty, the base distribution is sampled.
+/// The path can be replaced with `set_path` as better solutions are found.
+pub struct PathBiasedSampler<F: Float, const N: usize, SD, R: Rng = ThreadRng>
+where
+    SD: SamplingDistribution<F, N>,
+    StandardNormal: Distribution<F>,
+{
+    base: SD,               // The distribution sampled when the path is not sampled.
+    path: Vec<Point<F, N>>, // The reference path.
+    noise: Normal<F>,       // The zero-mean gaussian noise added to points on the path.
+    bernoulli: Bernoulli,   // Bernoulli distribution for path bias.
+    rng: R,                 // The random number generator for the path samples.
+}
+
+impl<F: Float, const N: usize, SD> PathBiasedSampler<F, N, SD>
+where
+    SD: SamplingDistribution<F, N>,
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new path biased sampler.
+    ///
+    /// Parameters:
+    /// - `base`: The distribution sampled when the path is not sampled.
+    /// - `path`: The reference path.
+    /// - `sigma`: The standard deviation of the noise added to points on the path.
+    /// - `path_bias`: The probability of sampling near the path.
+    ///
+    /// Returns:
+    /// The path biased sampler, or an error if sigma is negative or not finite, or the path_bias is not valid.
+    pub fn new(
+        base: SD,
+        path: Vec<Point<F, N>>,
+        sigma: F,
+        path_bias: f64,
+    ) -> Result<Self, &'static str> {
+        Self::new_with_rng(base, path, sigma, path_bias, rand::thread_rng())
+    }
+}
+
+impl<F: Float, const N: usize, SD, R: Rng> PathBiasedSampler<F, N, SD, R>
+where
+    SD: SamplingDistribution<F, N>,
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new path biased sampler with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `base`: The distribution sampled when the path is not sampled.
+    /// - `path`: The reference path.
+    /// - `sigma`: The standard deviation of the noise added to points on the path.
+    /// - `path_bias`: The probability of sampling near the path.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The path biased sampler, or an error if sigma is negative or not finite, or the path_bias is not valid.
+    pub fn new_with_rng(
+        base: SD,
+        path: Vec<Point<F, N>>,
+        sigma: F,
+        path_bias: f64,
+        rng: R,
+    ) -> Result<Self, &'static str> {
+        if !(0.0..=1.0).contains(&path_bias) {
+            return Err("path_bias must be in the range [0, 1]");
+        }
+        if !(sigma >= F::zero() && sigma.is_finite()) {
+            return Err("sigma must be non-negative and finite");
+        }
+        let noise = Normal::new(F::zero(), sigma).unwrap();
+        Ok(Self {
+            base,
+            path,
+            noise,
+            bernoulli: Bernoulli::new(path_bias).unwrap(),
+            rng,
+        })
+    }
+
+    /// Replaces the reference path, for example when a better solution is found.
+    ///
+    /// Parameters:
+    /// - `path`: The new reference path.
+    pub fn set_path(&mut self, path: Vec<Point<F, N>>) {
+        self.path = path;
+    }
+
+    pub fn path(&self) -> &[Point<F, N>] {
+        &self.path
+    }
+
+    pub fn base(&self) -> &SD {
+        &self.base
+    }
+
+    /// Picks a uniformly random point on a uniformly random segment of the path.
+    fn sample_on_path(&mut self) -> Point<F, N> {
+        if self.path.len() == 1 {
+            return self.path[0];
+        }
+        let segment = self.rng.gen_range(0..self.path.len() - 1);
+        let t = F::from(self.rng.gen::<f64>()).unwrap();
+        let start = self.path[segment];
+        start + (self.path[segment + 1] - start) * t
+    }
+}
+
+impl<F: Float, const N: usize, SD, R: Rng> SamplingDistribution<F, N>
+    for PathBiasedSampler<F, N, SD, R>
+where
+    SD: SamplingDistribution<F, N>,
+    StandardNormal: Distribution<F>,
+{
+    fn sample(&mut self) -> Point<F, N> {
+        if self.path.is_empty() || !self.bernoulli.sample(&mut self.rng) {
+            return self.base.sample();
+        }
+        let point = self.sample_on_path();
+        let values: [F; N] = std::array::from_fn(|i| point[i] + self.noise.sample(&mut self.rng));
+        Point::new(values)
+    }
+
+    fn set_goal(&mut self, goal: Point<F, N>) {
+        self.base.set_goal(goal);
+    }
+
+    fn feedback(&mut self, feedback: SampleFeedback<F>) {
+        self.base.feedback(feedback);
+    }
+
+    fn extension_failed(&mut self, from: &Point<F, N>, to: &Point<F, N>) {
+        self.base.extension_failed(from, to);
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        self.base.expand_ranges(factor)
+    }
+}
+
+/// A sampling distribution that combines several weighted component distributions,
+/// for example a goal bias, a gaussian around a region of interest and a uniform distribution.
+///
+/// Each sample is drawn from a component chosen with probability proportional to its weight.
+/// Goal changes, feedback and range expansion are forwarded to every component.
+pub struct MixtureSampler<F: Float, const N: usize, R: Rng = ThreadRng> {
+    components: Vec<Box<dyn SamplingDistribution<F, N>>>, // The component distributions.
+    weights: Vec<f64>,         // The normalized weight of each component.
+    index: WeightedIndex<f64>, // Categorical distribution for choosing a component.
+    rng: R,                    // The random number generator for choosing a component.
+}
+
+impl<F: Float, const N: usize> MixtureSampler<F, N> {
+    /// Constructs a new mixture sampler.
+    ///
+    /// Parameters:
+    /// - `components`: The weight and distribution of each component. The weights do not need to sum to 1.
+    ///
+    /// Returns:
+    /// The mixture sampler, or an error if there are no components or a weight is not positive and finite.
+    pub fn new(
+        components: Vec<(f64, Box<dyn SamplingDistribution<F, N>>)>,
+    ) -> Result<Self, &'static str> {
+        Self::new_with_rng(components, rand::thread_rng())
+    }
+}
+
+impl<F: Float, const N: usize, R: Rng> MixtureSampler<F, N, R> {
+    /// Constructs a new mixture sampler with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `components`: The weight and distribution of each component. The weights do not need to sum to 1.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The mixture sampler, or an error if there are no components or a weight is not positive and finite.
+    pub fn new_with_rng(
+        components: Vec<(f64, Box<dyn SamplingDistribution<F, N>>)>,
+        rng: R,
+    ) -> Result<Self, &'static str> {
+        if components.is_empty() {
+            return Err("at least one component is required");
+        }
+        if components
+            .iter()
+            .any(|(weight, _)| !(*weight > 0.0 && weight.is_finite()))
+        {
+            return Err("weights must be positive and finite");
+        }
+        let total: f64 = components.iter().map(|(weight, _)| weight).sum();
+        let (weights, components): (Vec<f64>, Vec<_>) = components
+            .into_iter()
+            .map(|(weight, component)| (weight / total, component))
+            .unzip();
+        Ok(Self {
+            index: WeightedIndex::new(&weights).unwrap(),
+            weights,
+            components,
+            rng,
+        })
+    }
+
+    /// Returns the normalized weight of each component, in the order they were given.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+}
+
+impl<F: Float, const N: usize, R: Rng> SamplingDistribution<F, N> for MixtureSampler<F, N, R> {
+    fn sample(&mut self) -> Point<F, N> {
+        let component = self.index.sample(&mut self.rng);
+        self.components[component].sample()
+    }
+
+    fn set_goal(&mut self, goal: Point<F, N>) {
+        for component in self.components.iter_mut() {
+            component.set_goal(goal);
+        }
+    }
+
+    fn feedback(&mut self, feedback: SampleFeedback<F>) {
+        for component in self.components.iter_mut() {
+            component.feedback(feedback);
+        }
+    }
+
+    fn extension_failed(&mut self, from: &Point<F, N>, to: &Point<F, N>) {
+        for component in self.components.iter_mut() {
+            component.extension_failed(from, to);
+        }
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        let mut expanded = false;
+        for component in self.components.iter_mut() {
+            expanded |= component.expand_ranges(factor);
+        }
+        expanded
+    }
+}
+
+/// A sampling distribution that samples uniformly inside an N-dimensional ball,
+/// or on its surface in `on_surface` mode.
+///
+/// The direction is drawn from a standard gaussian in each dimension, which is uniform after normalizing,
+/// and the distance from the center is scaled by the N-th root of a uniform sample.
+/// This avoids rejection sampling, which becomes very inefficient in high dimensions.
+pub struct BallSampler<F: Float, const N: usize, R: Rng = ThreadRng>
+where
+    StandardNormal: Distribution<F>,
+{
+    center: Point<F, N>, // The center of the ball.
+    radius: F,           // The radius of the ball.
+    on_surface: bool,    // Whether to sample only the surface of the ball.
+    rng: R,              // The random number generator.
+}
+
+impl<F: Float, const N: usize> BallSampler<F, N>
+where
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new ball sampler.
+    ///
+    /// Parameters:
+    /// - `center`: The center of the ball.
+    /// - `radius`: The radius of the ball.
+    /// - `on_surface`: Whether to sample only the surface of the ball instead of its interior.
+    ///
+    /// Returns:
+    /// The ball sampler, or an error if the radius is negative or not finite.
+    pub fn new(center: Point<F, N>, radius: F, on_surface: bool) -> Result<Self, &'static str> {
+        Self::new_with_rng(center, radius, on_surface, rand::thread_rng())
+    }
+}
+
+impl<F: Float, const N: usize, R: Rng> BallSampler<F, N, R>
+where
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new ball sampler with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `center`: The center of the ball.
+    /// - `radius`: The radius of the ball.
+    /// - `on_surface`: Whether to sample only the surface of the ball instead of its interior.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The ball sampler, or an error if the radius is negative or not finite.
+    pub fn new_with_rng(
+        center: Point<F, N>,
+        radius: F,
+        on_surface: bool,
+        rng: R,
+    ) -> Result<Self, &'static str> {
+        if !(radius >= F::zero() && radius.is_finite()) {
+            return Err("radius must be non-negative and finite");
+        }
+        Ok(Self {
+            center,
+            radius,
+            on_surface,
+            rng,
+        })
+    }
+
+    pub fn center(&self) -> &Point<F, N> {
+        &self.center
+    }
+
+    pub fn radius(&self) -> F {
+        self.radius
+    }
+
+    pub fn on_surface(&self) -> bool {
+        self.on_surface
+    }
+
+    /// Moves the ball to a new center.
+    pub fn set_center(&mut self, center: Point<F, N>) {
+        self.center = center;
+    }
+
+    /// Changes the radius of the ball.
+    ///
+    /// Returns:
+    /// An error if the radius is negative or not finite, in which case the radius is unchanged.
+    pub fn set_radius(&mut self, radius: F) -> Result<(), &'static str> {
+        if !(radius >= F::zero() && radius.is_finite()) {
+            return Err("radius must be non-negative and finite");
+        }
+        self.radius = radius;
+        Ok(())
+    }
+}
+
+/// Samples a point uniformly inside a ball, or on its surface.
+///
+/// Parameters:
+/// - `rng`: The random number generator.
+/// - `center`: The center of the ball.
+/// - `radius`: The radius of the ball.
+/// - `on_surface`: Whether to sample only the surface of the ball instead of its interior.
+fn sample_in_ball<F: Float, const N: usize, R: Rng>(
+    rng: &mut R,
+    center: Point<F, N>,
+    radius: F,
+    on_surface: bool,
+) -> Point<F, N>
+where
+    StandardNormal: Distribution<F>,
+{
+    // A standard gaussian in each dimension has a uniformly distributed direction.
+    let direction = loop {
+        let direction: Point<F, N> =
+            Point::new(std::array::from_fn(|_| StandardNormal.sample(rng)));
+        let norm = direction.norm();
+        // The norm is zero with negligible probability, but the direction would then be undefined.
+        if norm > F::zero() {
+            break direction / norm;
+        }
+    };
+    let distance = if on_surface {
+        radius
+    } else {
+        let u: f64 = rng.gen();
+        radius * F::from(u.powf(1.0 / N as f64)).unwrap()
+    };
+    center + direction * distance
+}
+
+impl<F: Float, const N: usize, R: Rng> SamplingDistribution<F, N> for BallSampler<F, N, R>
+where
+    StandardNormal: Distribution<F>,
+{
+    fn sample(&mut self) -> Point<F, N> {
+        sample_in_ball(&mut self.rng, self.center, self.radius, self.on_surface)
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        let radius = self.radius * factor;
+        if !(factor > F::zero() && factor.is_finite() && radius.is_finite()) {
+            return false;
+        }
+        self.radius = radius;
+        true
+    }
+}
+
+/// The maximum number of cells a `StratifiedSampler` may have, since the cell order is stored in memory.
+pub const MAX_STRATIFIED_CELLS: usize = 1 << 24;
+
+/// A sampling distribution that splits each dimension's range into `strata` equal intervals,
+/// giving `strata^N` cells, and samples one uniformly jittered point inside each cell per pass.
+///
+/// The cells are visited in a shuffled order, which is reshuffled when every cell has been sampled.
+/// This avoids the clumps and holes that independent uniform samples leave early in planning.
+pub struct StratifiedSampler<F: Float, const N: usize, R: Rng = ThreadRng> {
+    ranges: [(F, F); N], // The ranges for each dimension.
+    strata: usize,       // The number of intervals each dimension is divided into.
+    order: Vec<usize>,   // The order in which the cells are visited in the current pass.
+    position: usize,     // The position of the next cell in the order.
+    rng: R,              // The random number generator.
+}
+
+impl<F: Float, const N: usize> StratifiedSampler<F, N> {
+    /// Constructs a new stratified sampler.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `strata`: The number of intervals each dimension is divided into.
+    ///
+    /// Returns:
+    /// The stratified sampler, or an error if `strata` is 0, a range is not finite or is inverted,
+    /// or there would be more than `MAX_STRATIFIED_CELLS` cells.
+    pub fn new(ranges: [(F, F); N], strata: usize) -> Result<Self, SamplingError> {
+        Self::new_with_rng(ranges, strata, rand::thread_rng())
+    }
+}
+
+impl<F: Float, const N: usize, R: Rng> StratifiedSampler<F, N, R> {
+    /// Constructs a new stratified sampler with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `strata`: The number of intervals each dimension is divided into.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The stratified sampler, or an error if `strata` is 0, a range is not finite or is inverted,
+    /// or there would be more than `MAX_STRATIFIED_CELLS` cells.
+    pub fn new_with_rng(
+        ranges: [(F, F); N],
+        strata: usize,
+        mut rng: R,
+    ) -> Result<Self, SamplingError> {
+        if strata == 0 {
+            return Err(SamplingError::ZeroStrata);
+        }
+        validate_ranges(&ranges)?;
+        let cells = u32::try_from(N)
+            .ok()
+            .and_then(|n| strata.checked_pow(n))
+            .filter(|&cells| cells <= MAX_STRATIFIED_CELLS)
+            .ok_or(SamplingError::TooManyCells {
+                max: MAX_STRATIFIED_CELLS,
+            })?;
+        let mut order: Vec<usize> = (0..cells).collect();
+        order.shuffle(&mut rng);
+        Ok(Self {
+            ranges,
+            strata,
+            order,
+            position: 0,
+            rng,
+        })
+    }
+
+    /// Returns the number of intervals each dimension is divided into.
+    pub fn strata(&self) -> usize {
+        self.strata
+    }
+
+    /// Returns the total number of cells, which is `strata^N`.
+    pub fn cell_count(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns the ranges for each dimension.
+    pub fn ranges(&self) -> &[(F, F); N] {
+        &self.ranges
+    }
+
+    /// Finds the index of the cell containing a point.
+    /// Dimension 0 is the least significant digit of the index, in base `strata`.
+    ///
+    /// Parameters:
+    /// - `point`: The point to locate.
+    ///
+    /// Returns:
+    /// The cell index, or None if the point is outside the ranges.
+    pub fn cell_index(&self, point: &Point<F, N>) -> Option<usize> {
+        let mut index = 0;
+        for i in (0..N).rev() {
+            let (min, max) = self.ranges[i];
+            if point[i] < min || point[i] > max {
+                return None;
+            }
+            let t = if max > min {
+                ((point[i] - min) / (max - min)).to_f64().unwrap()
+            } else {
+                0.0
+            };
+            let stratum = ((t * self.strata as f64) as usize).min(self.strata - 1);
+            index = index * self.strata + stratum;
+        }
+        Some(index)
+    }
+
+    /// Finds the stratum of each dimension from a cell index.
+    fn decode_cell(&self, mut index: usize) -> [usize; N] {
+        std::array::from_fn(|_| {
+            let stratum = index % self.strata;
+            index /= self.strata;
+            stratum
+        })
+    }
+}
+
+impl<F: Float, const N: usize, R: Rng> SamplingDistribution<F, N> for StratifiedSampler<F, N, R> {
+    fn sample(&mut self) -> Point<F, N> {
+        if self.position == self.order.len() {
+            self.order.shuffle(&mut self.rng);
+            self.position = 0;
+        }
+        let cell = self.decode_cell(self.order[self.position]);
+        self.position += 1;
+
+        let strata = F::from(self.strata).unwrap();
+        let values: [F; N] = std::array::from_fn(|i| {
+            let (min, max) = self.ranges[i];
+            let jitter = F::from(self.rng.gen::<f64>()).unwrap();
+            let t = (F::from(cell[i]).unwrap() + jitter) / strata;
+            min + (max - min) * t
+        });
+        Point::new(values)
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        let Some(ranges) = expanded_ranges(&self.ranges, factor) else {
+            return false;
+        };
+        self.ranges = ranges;
+        true
+    }
+}
+
+/// A sampling distribution that reuses valid points from previous planning runs on the same map.
+///
+/// Points are recorded with `record`, or from the nodes of a previous tree with `record_tree`.
+/// With the given probability, a recorded point is picked uniformly and perturbed with zero-mean
+/// gaussian noise. Otherwise, or if nothing has been recorded, the base distribution is sampled.
+///
+/// The history holds at most `capacity` points. Once it is full, reservoir sampling keeps
+/// a uniform random subset of every point recorded so far.
+/// The history can be saved with `to_vec` and restored with `from_vec`.
+pub struct HistorySampler<F: Float, const N: usize, SD, R: Rng = ThreadRng>
+where
+    SD: SamplingDistribution<F, N>,
+    StandardNormal: Distribution<F>,
+{
+    base: SD,                  // The distribution sampled when the history is not sampled.
+    history: Vec<Point<F, N>>, // The recorded valid points.
+    capacity: usize,           // The maximum number of points in the history.
+    recorded: u64,             // The number of points recorded so far, for reservoir sampling.
+    noise: Normal<F>,          // The zero-mean gaussian noise added to recorded points.
+    bernoulli: Bernoulli,      // Bernoulli distribution for sampling the history.
+    rng: R,                    // The random number generator.
+}
+
+impl<F: Float, const N: usize, SD> HistorySampler<F, N, SD>
+where
+    SD: SamplingDistribution<F, N>,
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new history sampler with an empty history.
+    ///
+    /// Parameters:
+    /// - `base`: The distribution sampled when the history is not sampled.
+    /// - `capacity`: The maximum number of points in the history.
+    /// - `sigma`: The standard deviation of the noise added to recorded points.
+    /// - `probability`: The probability of sampling the history.
+    ///
+    /// Returns:
+    /// The history sampler, or an error if the capacity is 0, sigma is negative or not finite,
+    /// or the probability is not valid.
+    pub fn new(
+        base: SD,
+        capacity: usize,
+        sigma: F,
+        probability: f64,
+    ) -> Result<Self, &'static str> {
+        Self::new_with_rng(base, capacity, sigma, probability, rand::thread_rng())
+    }
+
+    /// Constructs a new history sampler from a history saved with `to_vec`.
+    ///
+    /// Parameters:
+    /// - `base`: The distribution sampled when the history is not sampled.
+    /// - `history`: The saved points. If there are more than `capacity`, a random subset is kept.
+    /// - `capacity`: The maximum number of points in the history.
+    /// - `sigma`: The standard deviation of the noise added to recorded points.
+    /// - `probability`: The probability of sampling the history.
+    ///
+    /// Returns:
+    /// The history sampler, or an error if the capacity is 0, sigma is negative or not finite,
+    /// or the probability is not valid.
+    pub fn from_vec(
+        base: SD,
+        history: Vec<Point<F, N>>,
+        capacity: usize,
+        sigma: F,
+        probability: f64,
+    ) -> Result<Self, &'static str> {
+        let mut sampler = Self::new(base, capacity, sigma, probability)?;
+        sampler.record_all(history);
+        Ok(sampler)
+    }
+}
+
+impl<F: Float, const N: usize, SD, R: Rng> HistorySampler<F, N, SD, R>
+where
+    SD: SamplingDistribution<F, N>,
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new history sampler with an empty history and the given random number generator.
+    ///
+    /// Parameters:
+    /// - `base`: The distribution sampled when the history is not sampled.
+    /// - `capacity`: The maximum number of points in the history.
+    /// - `sigma`: The standard deviation of the noise added to recorded points.
+    /// - `probability`: The probability of sampling the history.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The history sampler, or an error if the capacity is 0, sigma is negative or not finite,
+    /// or the probability is not valid.
+    pub fn new_with_rng(
+        base: SD,
+        capacity: usize,
+        sigma: F,
+        probability: f64,
+        rng: R,
+    ) -> Result<Self, &'static str> {
+        if capacity == 0 {
+            return Err("capacity must be at least 1");
+        }
+        if !(sigma >= F::zero() && sigma.is_finite()) {
+            return Err("sigma must be non-negative and finite");
+        }
+        if !(0.0..=1.0).contains(&probability) {
+            return Err("probability must be in the range [0, 1]");
+        }
+        Ok(Self {
+            base,
+            history: Vec::new(),
+            capacity,
+            recorded: 0,
+            noise: Normal::new(F::zero(), sigma).unwrap(),
+            bernoulli: Bernoulli::new(probability).unwrap(),
+            rng,
+        })
+    }
+
+    /// Records a valid point in the history.
+    ///
+    /// Parameters:
+    /// - `point`: A point that passed validity checking.
+    pub fn record(&mut self, point: Point<F, N>) {
+        self.recorded += 1;
+        if self.history.len() < self.capacity {
+            self.history.push(point);
+        } else {
+            let index = self.rng.gen_range(0..self.recorded);
+            if index < self.capacity as u64 {
+                self.history[index as usize] = point;
+            }
+        }
+    }
+
+    /// Records several valid points in the history.
+    ///
+    /// Parameters:
+    /// - `points`: Points that passed validity checking.
+    pub fn record_all(&mut self, points: impl IntoIterator<Item = Point<F, N>>) {
+        for point in points {
+            self.record(point);
+        }
+    }
+
+    /// Records the points of every node of a tree, which all passed validity checking.
+    ///
+    /// Parameters:
+    /// - `nodes`: The nodes of a tree, for example from `RRT::get_tree`.
+    pub fn record_tree<T>(&mut self, nodes: &[Node<F, N, T>]) {
+        self.record_all(nodes.iter().map(|node| *node.point()));
+    }
+
+    /// Returns a copy of the history, so it can be saved and later restored with `from_vec`.
+    pub fn to_vec(&self) -> Vec<Point<F, N>> {
+        self.history.clone()
+    }
+
+    /// Returns the recorded points.
+    pub fn history(&self) -> &[Point<F, N>] {
+        &self.history
+    }
+
+    /// Removes every recorded point.
+    pub fn clear(&mut self) {
+        self.history.clear();
+        self.recorded = 0;
+    }
+
+    pub fn base(&self) -> &SD {
+        &self.base
+    }
+}
+
+impl<F: Float, const N: usize, SD, R: Rng> SamplingDistribution<F, N>
+    for HistorySampler<F, N, SD, R>
+where
+    SD: SamplingDistribution<F, N>,
+    StandardNormal: Distribution<F>,
+{
+    fn sample(&mut self) -> Point<F, N> {
+        if self.history.is_empty() || !self.bernoulli.sample(&mut self.rng) {
+            return self.base.sample();
+        }
+        let point = self.history[self.rng.gen_range(0..self.history.len())];
+        let values: [F; N] = std::array::from_fn(|i| point[i] + self.noise.sample(&mut self.rng));
+        Point::new(values)
+    }
+
+    fn set_goal(&mut self, goal: Point<F, N>) {
+        self.base.set_goal(goal);
+    }
+
+    fn feedback(&mut self, feedback: SampleFeedback<F>) {
+        self.base.feedback(feedback);
+    }
+
+    fn extension_failed(&mut self, from: &Point<F, N>, to: &Point<F, N>) {
+        self.base.extension_failed(from, to);
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        self.base.expand_ranges(factor)
+    }
+}
+
+/// A sampling distribution that samples points on the surface of the box defined by the ranges,
+/// which encourages exploration towards the edges of the workspace when mixed with other distributions.
+///
+/// A face is picked with probability proportional to its area. The coordinate of that face is fixed
+/// to the bound of its range and the other coordinates are sampled uniformly.
+pub struct BoundarySampler<F: Float, const N: usize, R: Rng = ThreadRng> {
+    ranges: [(F, F); N],               // The ranges for each dimension.
+    faces: Option<WeightedIndex<f64>>, // Distribution of the dimension of the face (None if every face has no area).
+    rng: R,                            // The random number generator.
+}
+
+impl<F: Float, const N: usize> BoundarySampler<F, N> {
+    /// Constructs a new boundary sampler.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    ///
+    /// Returns:
+    /// The boundary sampler, or an error if a range is not finite or is inverted.
+    pub fn new(ranges: [(F, F); N]) -> Result<Self, SamplingError> {
+        Self::new_with_rng(ranges, rand::thread_rng())
+    }
+}
+
+impl<F: Float, const N: usize, R: Rng> BoundarySampler<F, N, R> {
+    /// Constructs a new boundary sampler with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The boundary sampler, or an error if a range is not finite or is inverted.
+    pub fn new_with_rng(ranges: [(F, F); N], rng: R) -> Result<Self, SamplingError> {
+        validate_ranges(&ranges)?;
+        Ok(Self {
+            ranges,
+            faces: Self::face_distribution(&ranges),
+            rng,
+        })
+    }
+
+    /// Returns the ranges for each dimension.
+    pub fn ranges(&self) -> &[(F, F); N] {
+        &self.ranges
+    }
+
+    /// Computes the area of the faces perpendicular to each dimension.
+    /// Both faces of a dimension have the same area, so a face is picked by its dimension and then its side.
+    ///
+    /// Returns:
+    /// The distribution of the dimension of the face, or None if every face has no area.
+    fn face_distribution(ranges: &[(F, F); N]) -> Option<WeightedIndex<f64>> {
+        let widths: [f64; N] =
+            std::array::from_fn(|i| (ranges[i].1 - ranges[i].0).to_f64().unwrap());
+        let areas = (0..N).map(|i| {
+            (0..N)
+                .filter(|&j| j != i)
+                .map(|j| widths[j])
+                .product::<f64>()
+        });
+        WeightedIndex::new(areas).ok()
+    }
+}
+
+impl<F: Float, const N: usize, R: Rng> SamplingDistribution<F, N> for BoundarySampler<F, N, R> {
+    fn sample(&mut self) -> Point<F, N> {
+        let dimension = match &self.faces {
+            Some(faces) => faces.sample(&mut self.rng),
+            None => self.rng.gen_range(0..N),
+        };
+        let upper = self.rng.gen::<bool>();
+        let values: [F; N] = std::array::from_fn(|i| {
+            let (min, max) = self.ranges[i];
+            if i == dimension {
+                if upper {
+                    max
+                } else {
+                    min
+                }
+            } else {
+                min + (max - min) * F::from(self.rng.gen::<f64>()).unwrap()
+            }
+        });
+        Point::new(values)
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        let Some(ranges) = expanded_ranges(&self.ranges, factor) else {
+            return false;
+        };
+        self.ranges = ranges;
+        self.faces = Self::face_distribution(&self.ranges);
+        true
+    }
+}
+
+/// A deterministic sampler that sweeps the points of a lattice in row-major order and cycles when exhausted,
+/// which makes planners reproducible in tests without seeding a random number generator.
+///
+/// Dimension `i` has `counts[i]` evenly spaced values that include both bounds of its range
+/// (or only the center of the range if the count is 1). The last dimension varies fastest.
+pub struct LatticeSampler<F: Float, const N: usize> {
+    ranges: [(F, F); N], // The ranges for each dimension.
+    counts: [usize; N],  // The number of lattice values in each dimension.
+    total: usize,        // The number of points in the lattice.
+    position: usize,     // The position of the next point in the sweep.
+}
+
+impl<F: Float, const N: usize> LatticeSampler<F, N> {
+    /// Constructs a new lattice sampler.
+    ///
+    /// Parameters:
+    /// - `ranges`: The ranges for each dimension.
+    /// - `counts`: The number of lattice values in each dimension.
+    ///
+    /// Returns:
+    /// The lattice sampler, or an error if a count is 0, a range is not finite or is inverted,
+    /// or the number of points overflows.
+    pub fn new(ranges: [(F, F); N], counts: [usize; N]) -> Result<Self, SamplingError> {
+        if let Some(dimension) = counts.iter().position(|&count| count == 0) {
+            return Err(SamplingError::ZeroCount { dimension });
+        }
+        validate_ranges(&ranges)?;
+        let total = counts
+            .iter()
+            .try_fold(1usize, |total, &count| total.checked_mul(count))
+            .ok_or(SamplingError::TooManyPoints)?;
+        Ok(Self {
+            ranges,
+            counts,
+            total,
+            position: 0,
+        })
+    }
+
+    /// Restarts the sweep from the first point of the lattice.
+    pub fn reset(&mut self) {
+        self.position = 0;
+    }
+
+    /// Returns the position of the next point in the sweep.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the number of points in the lattice, after which the sweep repeats.
+    pub fn len(&self) -> usize {
+        self.total
+    }
+
+    /// Returns false, since a lattice always has at least one point.
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Returns the ranges for each dimension.
+    pub fn ranges(&self) -> &[(F, F); N] {
+        &self.ranges
+    }
+
+    /// Returns the number of lattice values in each dimension.
+    pub fn counts(&self) -> &[usize; N] {
+        &self.counts
+    }
+}
+
+impl<F: Float, const N: usize> SamplingDistribution<F, N> for LatticeSampler<F, N> {
+    fn sample(&mut self) -> Point<F, N> {
+        let mut remainder = self.position;
+        self.position = (self.position + 1) % self.total;
+
+        let mut values = [F::zero(); N];
+        for i in (0..N).rev() {
+            let (min, max) = self.ranges[i];
+            let count = self.counts[i];
+            let step = remainder % count;
+            remainder /= count;
+            values[i] = if count == 1 {
+                (min + max) / (F::one() + F::one())
+            } else {
+                min + (max - min) * F::from(step).unwrap() / F::from(count - 1).unwrap()
+            };
+        }
+        Point::new(values)
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        let Some(ranges) = expanded_ranges(&self.ranges, factor) else {
+            return false;
+        };
+        self.ranges = ranges;
+        true
+    }
+}
+
+/// Wraps any sampling distribution and transforms its samples into another frame, by scaling each dimension
+/// and then offsetting it: `sample[i] * scale[i] + offset[i]`.
+/// For example, a distribution built in meters can sample a map in millimeters with a scale of 1000.
+///
+/// Goal updates and failed extensions are transformed back into the frame of the wrapped distribution
+/// before they are forwarded. Feedback is forwarded unchanged, so its distances are in the outer frame.
+pub struct AffineSampler<F: Float, const N: usize, SD>
+where
+    SD: SamplingDistribution<F, N>,
+{
+    base: SD,       // The wrapped distribution.
+    scale: [F; N],  // The scale of each dimension.
+    offset: [F; N], // The offset of each dimension, applied after scaling.
+}
+
+impl<F: Float, const N: usize, SD> AffineSampler<F, N, SD>
+where
+    SD: SamplingDistribution<F, N>,
+{
+    /// Constructs a new affine sampler.
+    ///
+    /// Parameters:
+    /// - `base`: The wrapped distribution.
+    /// - `scale`: The scale of each dimension.
+    /// - `offset`: The offset of each dimension, applied after scaling.
+    ///
+    /// Returns:
+    /// The affine sampler, or an error if a scale is zero or a scale or offset is not finite.
+    pub fn new(base: SD, scale: [F; N], offset: [F; N]) -> Result<Self, &'static str> {
+        if scale
+            .iter()
+            .any(|&scale| scale == F::zero() || !scale.is_finite())
+        {
+            return Err("scales must be non-zero and finite");
+        }
+        if offset.iter().any(|offset| !offset.is_finite()) {
+            return Err("offsets must be finite");
+        }
+        Ok(Self {
+            base,
+            scale,
+            offset,
+        })
+    }
+
+    /// Constructs a new affine sampler that only scales the samples.
+    ///
+    /// Parameters:
+    /// - `base`: The wrapped distribution.
+    /// - `scale`: The scale of each dimension.
+    ///
+    /// Returns:
+    /// The affine sampler, or an error if a scale is zero or not finite.
+    pub fn scaled(base: SD, scale: [F; N]) -> Result<Self, &'static str> {
+        Self::new(base, scale, [F::zero(); N])
+    }
+
+    /// Constructs a new affine sampler that only offsets the samples.
+    ///
+    /// Parameters:
+    /// - `base`: The wrapped distribution.
+    /// - `offset`: The offset of each dimension.
+    ///
+    /// Returns:
+    /// The affine sampler, or an error if an offset is not finite.
+    pub fn translated(base: SD, offset: [F; N]) -> Result<Self, &'static str> {
+        Self::new(base, [F::one(); N], offset)
+    }
+
+    pub fn scale(&self) -> &[F; N] {
+        &self.scale
+    }
+
+    pub fn offset(&self) -> &[F; N] {
+        &self.offset
+    }
+
+    pub fn base(&self) -> &SD {
+        &self.base
+    }
+
+    /// Transforms a point back into the frame of the wrapped distribution.
+    fn to_base_frame(&self, point: &Point<F, N>) -> Point<F, N> {
+        Point::new(std::array::from_fn(|i| {
+            (point[i] - self.offset[i]) / self.scale[i]
+        }))
+    }
+}
+
+impl<F: Float, const N: usize, SD> SamplingDistribution<F, N> for AffineSampler<F, N, SD>
+where
+    SD: SamplingDistribution<F, N>,
+{
+    fn sample(&mut self) -> Point<F, N> {
+        let sample = self.base.sample();
+        Point::new(std::array::from_fn(|i| {
+            sample[i] * self.scale[i] + self.offset[i]
+        }))
+    }
+
+    fn set_goal(&mut self, goal: Point<F, N>) {
+        let goal = self.to_base_frame(&goal);
+        self.base.set_goal(goal);
+    }
+
+    fn feedback(&mut self, feedback: SampleFeedback<F>) {
+        self.base.feedback(feedback);
+    }
+
+    fn extension_failed(&mut self, from: &Point<F, N>, to: &Point<F, N>) {
+        let from = self.to_base_frame(from);
+        let to = self.to_base_frame(to);
+        self.base.extension_failed(&from, &to);
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        self.base.expand_ranges(factor)
+    }
+}
+
+/// A gaussian component of an `AdaptiveFocusSampler`, centered on a region where extensions failed.
+#[derive(Debug, Clone, Copy)]
+pub struct FocusComponent<F: Float, const N: usize> {
+    /// The mean of the midpoints of the failed edges assigned to this component.
+    pub mean: Point<F, N>,
+    /// The relative probability of sampling this component.
+    pub weight: f64,
+    /// The number of failed edges assigned to this component.
+    pub failures: u32,
+}
+
+/// The weight below which a component of an `AdaptiveFocusSampler` is removed.
+const MIN_FOCUS_WEIGHT: f64 = 0.1;
+
+/// A sampling distribution that focuses samples on regions where extensions of the tree keep failing,
+/// such as the entrance of a narrow passage.
+///
+/// The planner reports failed extensions through `SamplingDistribution::extension_failed`. The midpoint of
+/// each failed edge is assigned to the nearest gaussian component within `2 * sigma`, which moves towards it
+/// and gains weight, or else starts a new component (replacing the lightest one when there are `max_components`).
+/// With the given probability, a component is picked in proportion to its weight and sampled.
+/// Otherwise, or if there are no components, the base distribution is sampled.
+///
+/// Whenever a sample from a component adds a node to the tree, the weight of that component decays,
+/// and it is removed once its weight drops below 0.1. So components whose samples stop failing fade away.
+pub struct AdaptiveFocusSampler<F: Float, const N: usize, SD, R: Rng = ThreadRng>
+where
+    SD: SamplingDistribution<F, N>,
+    StandardNormal: Distribution<F>,
+{
+    base: SD,                              // The distribution sampled instead of the components.
+    components: Vec<FocusComponent<F, N>>, // The gaussian components around failure regions.
+    max_components: usize,                 // The maximum number of components.
+    noise: Normal<F>,                      // The noise added to the component means.
+    decay: f64,                            // The weight decay of a component whose sample succeeds.
+    bernoulli: Bernoulli,                  // Bernoulli distribution for sampling the components.
+    last_component: Option<usize>,         // The component of the last sample (None for the base).
+    rng: R,                                // The random number generator.
+}
+
+impl<F: Float, const N: usize, SD> AdaptiveFocusSampler<F, N, SD>
+where
+    SD: SamplingDistribution<F, N>,
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new adaptive focus sampler.
+    ///
+    /// Parameters:
+    /// - `base`: The distribution sampled when no component is sampled.
+    /// - `sigma`: The standard deviation of each component.
+    /// - `probability`: The probability of sampling the components.
+    /// - `max_components`: The maximum number of components.
+    ///
+    /// Returns:
+    /// The adaptive focus sampler, or an error if sigma is not positive and finite,
+    /// the probability is not valid or `max_components` is 0.
+    pub fn new(
+        base: SD,
+        sigma: F,
+        probability: f64,
+        max_components: usize,
+    ) -> Result<Self, &'static str> {
+        Self::new_with_rng(base, sigma, probability, max_components, rand::thread_rng())
+    }
+}
+
+impl<F: Float, const N: usize, SD, R: Rng> AdaptiveFocusSampler<F, N, SD, R>
+where
+    SD: SamplingDistribution<F, N>,
+    StandardNormal: Distribution<F>,
+{
+    /// Constructs a new adaptive focus sampler with the given random number generator.
+    ///
+    /// Parameters:
+    /// - `base`: The distribution sampled when no component is sampled.
+    /// - `sigma`: The standard deviation of each component.
+    /// - `probability`: The probability of sampling the components.
+    /// - `max_components`: The maximum number of components.
+    /// - `rng`: The random number generator.
+    ///
+    /// Returns:
+    /// The adaptive focus sampler, or an error if sigma is not positive and finite,
+    /// the probability is not valid or `max_components` is 0.
+    pub fn new_with_rng(
+        base: SD,
+        sigma: F,
+        probability: f64,
+        max_components: usize,
+        rng: R,
+    ) -> Result<Self, &'static str> {
+        if !(sigma > F::zero() && sigma.is_finite()) {
+            return Err("sigma must be positive and finite");
+        }
+        if !(0.0..=1.0).contains(&probability) {
+            return Err("probability must be in the range [0, 1]");
+        }
+        if max_components == 0 {
+            return Err("max_components must be at least 1");
+        }
+        Ok(Self {
+            base,
+            components: Vec::with_capacity(max_components),
+            max_components,
+            noise: Normal::new(F::zero(), sigma).unwrap(),
+            decay: 0.8,
+            bernoulli: Bernoulli::new(probability).unwrap(),
+            last_component: None,
+            rng,
+        })
+    }
+
+    /// Sets the factor applied to the weight of a component whenever its sample adds a node (0.8 by default).
+    ///
+    /// Returns:
+    /// An error if the decay is not in the range [0, 1), in which case it is unchanged.
+    pub fn set_decay(&mut self, decay: f64) -> Result<(), &'static str> {
+        if !(0.0..1.0).contains(&decay) {
+            return Err("decay must be in the range [0, 1)");
+        }
+        self.decay = decay;
+        Ok(())
+    }
+
+    pub fn decay(&self) -> f64 {
+        self.decay
+    }
+
+    /// Returns the gaussian components around failure regions.
+    pub fn components(&self) -> &[FocusComponent<F, N>] {
+        &self.components
+    }
+
+    /// Removes every component, for example when the environment changes.
+    pub fn clear_components(&mut self) {
+        self.components.clear();
+        self.last_component = None;
+    }
+
+    pub fn base(&self) -> &SD {
+        &self.base
+    }
+
+    /// Picks a component in proportion to its weight.
+    fn pick_component(&mut self) -> usize {
+        let total: f64 = self.components.iter().map(|c| c.weight).sum();
+        let mut target = self.rng.gen::<f64>() * total;
+        for (index, component) in self.components.iter().enumerate() {
+            if target < component.weight {
+                return index;
+            }
+            target -= component.weight;
+        }
+        self.components.len() - 1
+    }
+}
+
+impl<F: Float, const N: usize, SD, R: Rng> SamplingDistribution<F, N>
+    for AdaptiveFocusSampler<F, N, SD, R>
+where
+    SD: SamplingDistribution<F, N>,
+    StandardNormal: Distribution<F>,
+{
+    fn sample(&mut self) -> Point<F, N> {
+        if self.components.is_empty() || !self.bernoulli.sample(&mut self.rng) {
+            self.last_component = None;
+            return self.base.sample();
+        }
+        let index = self.pick_component();
+        self.last_component = Some(index);
+        let mean = self.components[index].mean;
+        let values: [F; N] = std::array::from_fn(|i| mean[i] + self.noise.sample(&mut self.rng));
+        Point::new(values)
+    }
+
+    fn set_goal(&mut self, goal: Point<F, N>) {
+        self.base.set_goal(goal);
+    }
+
+    fn feedback(&mut self, feedback: SampleFeedback<F>) {
+        if let Some(index) = self.last_component.take() {
+            if feedback.node_added {
+                self.components[index].weight *= self.decay;
+                if self.components[index].weight < MIN_FOCUS_WEIGHT {
+                    self.components.swap_remove(index);
+                }
+            }
+        }
+        self.base.feedback(feedback);
+    }
+
+    fn extension_failed(&mut self, from: &Point<F, N>, to: &Point<F, N>) {
+        let two = F::one() + F::one();
+        let midpoint = (*from + *to) / two;
+        let merge_distance = self.noise.std_dev() * two;
+
+        let nearest = self
+            .components
+            .iter()
+            .enumerate()
+            .map(|(index, component)| (index, (component.mean - midpoint).norm()))
+            .filter(|&(_, distance)| distance <= merge_distance)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        match nearest {
+            Some((index, _)) => {
+                // Move the mean towards the midpoint, so it stays the mean of every assigned midpoint.
+                let component = &mut self.components[index];
+                component.failures += 1;
+                let step = F::from(component.failures).unwrap();
+                component.mean = component.mean + (midpoint - component.mean) / step;
+                component.weight += 1.0;
+            }
+            None => {
+                let component = FocusComponent {
+                    mean: midpoint,
+                    weight: 1.0,
+                    failures: 1,
+                };
+                if self.components.len() < self.max_components {
+                    self.components.push(component);
+                } else {
+                    let lightest = (0..self.components.len())
+                        .min_by(|&a, &b| {
+                            self.components[a]
+                                .weight
+                                .partial_cmp(&self.components[b].weight)
+                                .unwrap()
+                        })
+                        .unwrap();
+                    self.components[lightest] = component;
+                    if self.last_component == Some(lightest) {
+                        self.last_component = None;
+                    }
+                }
+            }
+        }
+        self.base.extension_failed(from, to);
+    }
+
+    fn expand_ranges(&mut self, factor: F) -> bool {
+        self.base.expand_ranges(factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::{EuclideanSphericalObstacleSet, Sphere};
+    use crate::neighbors::KdTreeNearestNeighbors;
+    use crate::rrt::{IterationEvent, RRT};
+    use crate::steering::EuclideanSteering;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn seeded() -> StdRng {
+        StdRng::seed_from_u64(1)
+    }
+
+    #[test]
+    fn expand_ranges_rejects_invalid_factors() {
+        let mut uniform = UniformDistribution::new_with_rng([(0.0, 2.0); 2], seeded()).unwrap();
+        for factor in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(!uniform.expand_ranges(factor));
+            assert_eq!(uniform.ranges(), &[(0.0, 2.0); 2]);
+            uniform.sample();
+        }
+        assert!(uniform.expand_ranges(2.0));
+        assert_eq!(uniform.ranges(), &[(-1.0, 3.0); 2]);
+    }
+
+    #[test]
+    fn expand_ranges_stops_before_bounds_overflow() {
+        let mut uniform = UniformDistribution::new_with_rng([(0.0, 2.0); 2], seeded()).unwrap();
+        let mut expansions = 0;
+        while uniform.expand_ranges(2.0) {
+            expansions += 1;
+            assert!(expansions < 2000, "ranges never stopped expanding");
+        }
+        assert!(uniform
+            .ranges()
+            .iter()
+            .all(|(min, max)| min.is_finite() && max.is_finite()));
+        let sample = uniform.sample();
+        assert!(sample.coords().iter().all(|coord| coord.is_finite()));
+    }
+
+    #[test]
+    fn halton_rejects_invalid_ranges() {
+        assert_eq!(
+            HaltonSampler::new([(0.0, 1.0), (2.0, 1.0)], 0).err(),
+            Some(SamplingError::InvertedRange { dimension: 1 })
+        );
+        assert_eq!(
+            HaltonSampler::new([(f64::NAN, 1.0), (0.0, 1.0)], 0).err(),
+            Some(SamplingError::NonFiniteBound { dimension: 0 })
+        );
+        assert_eq!(
+            HaltonSampler::new([(0.0, 1.0), (0.0, f64::INFINITY)], 0).err(),
+            Some(SamplingError::NonFiniteBound { dimension: 1 })
+        );
+        assert!(HaltonSampler::new([(0.0, 1.0), (1.0, 1.0)], 0).is_ok());
+    }
+
+    #[test]
+    fn sobol_rejects_invalid_ranges() {
+        assert_eq!(
+            SobolSampler::new([(0.0, 1.0), (2.0, 1.0)], 0).err(),
+            Some(SamplingError::InvertedRange { dimension: 1 })
+        );
+        assert_eq!(
+            SobolSampler::new([(f64::NEG_INFINITY, 1.0), (0.0, 1.0)], 0).err(),
+            Some(SamplingError::NonFiniteBound { dimension: 0 })
+        );
+        assert_eq!(
+            SobolSampler::new([(0.0, 1.0); MAX_SOBOL_DIMENSIONS + 1], 0).err(),
+            Some(SamplingError::TooManyDimensions {
+                max: MAX_SOBOL_DIMENSIONS
+            })
+        );
+        assert!(SobolSampler::new([(0.0, 1.0); MAX_SOBOL_DIMENSIONS], 0).is_ok());
+    }
+
+    #[test]
+    fn stratified_rejects_invalid_ranges() {
+        assert_eq!(
+            StratifiedSampler::new_with_rng([(0.0, 1.0), (2.0, 1.0)], 4, seeded()).err(),
+            Some(SamplingError::InvertedRange { dimension: 1 })
+        );
+        assert_eq!(
+            StratifiedSampler::new_with_rng([(0.0, f64::NAN), (0.0, 1.0)], 4, seeded()).err(),
+            Some(SamplingError::NonFiniteBound { dimension: 0 })
+        );
+        assert_eq!(
+            StratifiedSampler::new_with_rng([(0.0, 1.0); 2], 0, seeded()).err(),
+            Some(SamplingError::ZeroStrata)
+        );
+        assert_eq!(
+            StratifiedSampler::new_with_rng([(0.0, 1.0); 2], usize::MAX, seeded()).err(),
+            Some(SamplingError::TooManyCells {
+                max: MAX_STRATIFIED_CELLS
+            })
+        );
+        assert!(StratifiedSampler::new_with_rng([(0.0, 1.0); 2], 4, seeded()).is_ok());
+    }
+
+    #[test]
+    fn lattice_rejects_invalid_ranges() {
+        assert_eq!(
+            LatticeSampler::new([(0.0, 1.0), (2.0, 1.0)], [3, 3]).err(),
+            Some(SamplingError::InvertedRange { dimension: 1 })
+        );
+        assert_eq!(
+            LatticeSampler::new([(0.0, 1.0), (f64::INFINITY, 1.0)], [3, 3]).err(),
+            Some(SamplingError::NonFiniteBound { dimension: 1 })
+        );
+        assert_eq!(
+            LatticeSampler::new([(0.0, 1.0); 2], [3, 0]).err(),
+            Some(SamplingError::ZeroCount { dimension: 1 })
+        );
+        assert_eq!(
+            LatticeSampler::new([(0.0, 1.0); 2], [usize::MAX, 2]).err(),
+            Some(SamplingError::TooManyPoints)
+        );
+        assert!(LatticeSampler::new([(0.0, 1.0); 2], [3, 3]).is_ok());
+    }
+
+    #[test]
+    fn masked_goal_bias_copies_only_masked_dimensions() {
+        let goal = Point::new([1.0, 2.0, 3.0]);
+        let mut distribution = MaskedGoalBiasedUniformDistribution::new_with_rng(
+            [(0.0, 10.0); 3],
+            goal,
+            [true, false, true],
+            1.0,
+            seeded(),
+        )
+        .unwrap();
+        let samples: Vec<_> = (0..100).map(|_| distribution.sample()).collect();
+        for sample in &samples {
+            assert_eq!(sample[0], 1.0);
+            assert_eq!(sample[2], 3.0);
+            assert!((0.0..=10.0).contains(&sample[1]));
+        }
+        // The unmasked dimension is still sampled uniformly.
+        assert!(samples.iter().any(|sample| sample[1] < 5.0));
+        assert!(samples.iter().any(|sample| sample[1] > 5.0));
+
+        // Without bias, no dimension is copied from the goal.
+        let mut unbiased = MaskedGoalBiasedUniformDistribution::new_with_rng(
+            [(0.0, 10.0); 3],
+            goal,
+            [true, false, true],
+            0.0,
+            seeded(),
+        )
+        .unwrap();
+        assert!((0..100).all(|_| unbiased.sample()[0] != 1.0));
+    }
+
+    #[test]
+    fn masked_goal_bias_rejects_invalid_dimensions() {
+        assert_eq!(
+            MaskedGoalBiasedUniformDistribution::from_dimensions(
+                [(0.0, 10.0); 3],
+                Point::new([1.0, 2.0, 3.0]),
+                &[0, 3],
+                0.5,
+            )
+            .err(),
+            Some(SamplingError::InvalidDimension { dimension: 3 })
+        );
+    }
+
+    /// The points of the tree grown in 500 iterations from (1, 1) around a sphere.
+    fn tree_points<SD: SamplingDistribution<f64, 2>>(sampling: SD) -> Vec<[f64; 2]> {
+        let mut rrt: RRT<_, 2, _, _, _, KdTreeNearestNeighbors<f64, 2>> = RRT::new(
+            Point::new([1.0, 1.0]),
+            Point::new([9.0, 9.0]),
+            0.5,
+            EuclideanSphericalObstacleSet::new(vec![Sphere {
+                center: Point::new([5.0, 5.0]),
+                radius: 2.0,
+            }]),
+            sampling,
+            EuclideanSteering::new(0.5),
+        );
+        rrt.run_iterations(500);
+        rrt.get_tree()
+            .iter()
+            .map(|node| *node.point().coords())
+            .collect()
+    }
+
+    #[test]
+    fn planners_with_the_same_seed_grow_the_same_tree() {
+        let uniform = |seed| {
+            UniformDistribution::new_with_rng([(0.0, 10.0); 2], StdRng::seed_from_u64(seed))
+                .unwrap()
+        };
+        let points = tree_points(uniform(42));
+        assert!(points.len() > 100);
+        assert_eq!(points, tree_points(uniform(42)));
+        assert_ne!(points, tree_points(uniform(43)));
+
+        let goal_biased = |seed| {
+            GoalBiasedUniformDistribution::new_with_rng(
+                [(0.0, 10.0); 2],
+                Point::new([9.0, 9.0]),
+                0.1,
+                StdRng::seed_from_u64(seed),
+            )
+            .unwrap()
+        };
+        assert_eq!(tree_points(goal_biased(42)), tree_points(goal_biased(42)));
+    }
+
+    /// Counts the samples drawn near the gap in a thick wall at x = 16..18 with a gap around y = 5,
+    /// in each window of `window` iterations of a planner that starts at the far end of the map.
+    fn samples_near_gap(seed: u64, iterations: u32, window: usize) -> Vec<usize> {
+        let mut spheres = Vec::new();
+        for column in 0..5 {
+            for row in 0..=20 {
+                let y = row as f64 * 0.5;
+                if (y - 5.0).abs() > 0.6 {
+                    spheres.push(Sphere {
+                        center: Point::new([16.0 + column as f64 * 0.5, y]),
+                        radius: 0.4,
+                    });
+                }
+            }
+        }
+        let base = UniformDistribution::new_with_rng(
+            [(0.0, 20.0), (0.0, 10.0)],
+            StdRng::seed_from_u64(seed),
+        )
+        .unwrap();
+        let sampler = AdaptiveFocusSampler::new_with_rng(
+            base,
+            0.5,
+            0.5,
+            5,
+            StdRng::seed_from_u64(seed + 100),
+        )
+        .unwrap();
+        // The goal is out of reach, so the planner keeps growing the tree.
+        let mut rrt: RRT<_, 2, _, _, _, KdTreeNearestNeighbors<f64, 2>> = RRT::new(
+            Point::new([0.5, 5.0]),
+            Point::new([40.0, 40.0]),
+            0.5,
+            EuclideanSphericalObstacleSet::new(spheres),
+            sampler,
+            EuclideanSteering::new(0.5),
+        );
+        let mut near = vec![0; iterations as usize / window];
+        let mut drawn = 0;
+        rrt.solve_with_callback(iterations, |_, event| {
+            let sample = match event {
+                IterationEvent::SampleRejected { sample }
+                | IterationEvent::NodeAdded { sample, .. } => sample,
+                _ => return,
+            };
+            if (sample[0] - 17.0).abs() < 1.5 && (sample[1] - 5.0).abs() < 1.0 {
+                near[drawn / window] += 1;
+            }
+            drawn += 1;
+        });
+        assert_eq!(drawn, iterations as usize);
+        near
+    }
+
+    #[test]
+    fn adaptive_focus_samples_more_near_the_gap_over_time() {
+        // The region near the gap covers 3% of the map. The wall is at least 31 steps from the start,
+        // so no extension fails at first and only the base distribution is sampled.
+        let mut early = 0;
+        let mut late = 0;
+        for seed in 0..8 {
+            let near = samples_near_gap(seed, 1000, 100);
+            early += near[0];
+            late += near[5..].iter().sum::<usize>();
+        }
+        let early = early as f64 / 800.0;
+        let late = late as f64 / 4000.0;
+        assert!(early < 0.06, "{early}");
+        assert!(
+            late > 2.5 * early,
+            "{early} near the gap at first, {late} later"
+        );
+    }
+
+    #[test]
+    fn gaussian_samples_have_the_given_mean_and_variance() {
+        const SAMPLES: usize = 100_000;
+        let mut gaussian =
+            GaussianDistribution::new_with_rng(Point::new([2.0, -3.0]), [0.5, 2.0], None, seeded())
+                .unwrap();
+        let samples: Vec<Point<f64, 2>> = (0..SAMPLES).map(|_| gaussian.sample()).collect();
+        for (dimension, (mean, std_dev)) in [(2.0, 0.5), (-3.0, 2.0)].into_iter().enumerate() {
+            let sample_mean = samples.iter().map(|s| s[dimension]).sum::<f64>() / SAMPLES as f64;
+            let variance = samples
+                .iter()
+                .map(|s| (s[dimension] - sample_mean).powi(2))
+                .sum::<f64>()
+                / (SAMPLES - 1) as f64;
+            assert!((sample_mean - mean).abs() < 0.02 * std_dev, "{sample_mean}");
+            assert!(
+                (variance / (std_dev * std_dev) - 1.0).abs() < 0.02,
+                "{variance}"
+            );
+        }
+
+        // With bounds, the samples are clamped to them.
+        let mut clamped = GaussianDistribution::new_with_rng(
+            Point::new([0.0, 0.0]),
+            [1.0, 1.0],
+            Some([(-0.5, 0.5), (0.0, 10.0)]),
+            seeded(),
+        )
+        .unwrap();
+        for _ in 0..1000 {
+            let sample = clamped.sample();
+            assert!((-0.5..=0.5).contains(&sample[0]));
+            assert!((0.0..=10.0).contains(&sample[1]));
+        }
+    }
+
+    #[test]
+    fn halton_starts_with_the_base_2_and_3_sequences() {
+        let mut halton = HaltonSampler::new([(0.0, 1.0); 2], 0).unwrap();
+        let expected = [
+            [1.0 / 2.0, 1.0 / 3.0],
+            [1.0 / 4.0, 2.0 / 3.0],
+            [3.0 / 4.0, 1.0 / 9.0],
+            [1.0 / 8.0, 4.0 / 9.0],
+            [5.0 / 8.0, 7.0 / 9.0],
+            [3.0 / 8.0, 2.0 / 9.0],
+        ];
+        for point in expected {
+            let sample = halton.sample();
+            assert!((sample[0] - point[0]).abs() < 1e-12);
+            assert!((sample[1] - point[1]).abs() < 1e-12);
+        }
+        assert_eq!(halton.index(), 6);
+
+        // Skipping points starts the sequence later.
+        let mut skipped = HaltonSampler::new([(0.0, 1.0); 2], 3).unwrap();
+        let sample = skipped.sample();
+        assert!((sample[0] - 1.0 / 8.0).abs() < 1e-12);
+        assert!((sample[1] - 4.0 / 9.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn halton_is_deterministic_and_stays_in_range() {
+        let ranges = [
+            (-1.0, 3.0),
+            (10.0, 20.0),
+            (0.0, 0.5),
+            (-5.0, -4.0),
+            (2.0, 2.0),
+        ];
+        let mut a = HaltonSampler::new(ranges, 10).unwrap();
+        let mut b = HaltonSampler::new(ranges, 10).unwrap();
+        for _ in 0..10_000 {
+            let sample = a.sample();
+            assert_eq!(sample.coords(), b.sample().coords());
+            for (value, (min, max)) in sample.coords().iter().zip(ranges) {
+                assert!((min..=max).contains(value));
+            }
+        }
+    }
+
+    /// The first 32 points of the unscrambled 16-dimensional Sobol sequence with the new-joe-kuo-6.21201
+    /// direction numbers, after the origin, in multiples of 1/64.
+    const SOBOL_FIRST_POINTS: [[u32; 16]; 32] = [
+        [
+            32, 32, 32, 32, 32, 32, 32, 32, 32, 32, 32, 32, 32, 32, 32, 32,
+        ],
+        [
+            48, 16, 16, 16, 48, 48, 16, 48, 48, 48, 48, 48, 16, 16, 48, 16,
+        ],
+        [
+            16, 48, 48, 48, 16, 16, 48, 16, 16, 16, 16, 16, 48, 48, 16, 48,
+        ],
+        [
+            24, 24, 40, 56, 24, 8, 24, 56, 56, 40, 56, 24, 24, 40, 24, 56,
+        ],
+        [56, 56, 8, 24, 56, 40, 56, 24, 24, 8, 24, 56, 56, 8, 56, 24],
+        [40, 8, 56, 40, 40, 56, 8, 8, 8, 24, 8, 40, 8, 56, 40, 40],
+        [8, 40, 24, 8, 8, 24, 40, 40, 40, 56, 40, 8, 40, 24, 8, 8],
+        [
+            12, 20, 60, 28, 36, 20, 28, 60, 60, 20, 44, 4, 60, 60, 52, 60,
+        ],
+        [
+            44, 52, 28, 60, 4, 52, 60, 28, 28, 52, 12, 36, 28, 28, 20, 28,
+        ],
+        [60, 4, 44, 12, 20, 36, 12, 12, 12, 36, 28, 52, 44, 44, 4, 44],
+        [28, 36, 12, 44, 52, 4, 44, 44, 44, 4, 60, 20, 12, 12, 36, 12],
+        [20, 12, 20, 36, 60, 28, 4, 4, 4, 60, 20, 28, 36, 20, 44, 4],
+        [52, 44, 52, 4, 28, 60, 36, 36, 36, 28, 52, 60, 4, 52, 12, 36],
+        [36, 28, 4, 52, 12, 44, 20, 52, 52, 12, 36, 44, 52, 4, 28, 20],
+        [4, 60, 36, 20, 44, 12, 52, 20, 20, 44, 4, 12, 20, 36, 60, 52],
+        [6, 30, 30, 42, 18, 62, 34, 54, 30, 10, 6, 26, 42, 42, 22, 2],
+        [
+            38, 62, 62, 10, 50, 30, 2, 22, 62, 42, 38, 58, 10, 10, 54, 34,
+        ],
+        [
+            54, 14, 14, 58, 34, 14, 50, 6, 46, 58, 54, 42, 58, 58, 38, 18,
+        ],
+        [22, 46, 46, 26, 2, 46, 18, 38, 14, 26, 22, 10, 26, 26, 6, 50],
+        [30, 6, 54, 18, 10, 54, 58, 14, 38, 34, 62, 2, 50, 2, 14, 58],
+        [62, 38, 22, 50, 42, 22, 26, 46, 6, 2, 30, 34, 18, 34, 46, 26],
+        [46, 22, 38, 2, 58, 6, 42, 62, 22, 18, 14, 50, 34, 18, 62, 42],
+        [14, 54, 6, 34, 26, 38, 10, 30, 54, 50, 46, 18, 2, 50, 30, 10],
+        [
+            10, 10, 34, 54, 54, 42, 62, 10, 34, 30, 42, 30, 22, 22, 34, 62,
+        ],
+        [42, 42, 2, 22, 22, 10, 30, 42, 2, 62, 10, 62, 54, 54, 2, 30],
+        [58, 26, 50, 38, 6, 26, 46, 58, 18, 46, 26, 46, 6, 6, 18, 46],
+        [
+            26, 58, 18, 6, 38, 58, 14, 26, 50, 14, 58, 14, 38, 38, 50, 14,
+        ],
+        [18, 18, 10, 14, 46, 34, 38, 50, 26, 54, 18, 6, 14, 62, 58, 6],
+        [50, 50, 42, 46, 14, 2, 6, 18, 58, 22, 50, 38, 46, 30, 26, 38],
+        [34, 2, 26, 30, 30, 18, 54, 2, 42, 6, 34, 54, 30, 46, 10, 22],
+        [2, 34, 58, 62, 62, 50, 22, 34, 10, 38, 2, 22, 62, 14, 42, 54],
+        [3, 17, 45, 35, 9, 59, 51, 43, 63, 3, 25, 61, 29, 63, 63, 7],
+    ];
+
+    #[test]
+    fn sobol_matches_the_published_first_points() {
+        let mut sobol = SobolSampler::new([(0.0, 1.0); 16], 0).unwrap();
+        for (index, expected) in SOBOL_FIRST_POINTS.iter().enumerate() {
+            let sample = sobol.sample();
+            for (dimension, (&value, &expected)) in sample.coords().iter().zip(expected).enumerate()
+            {
+                assert_eq!(
+                    value * 64.0,
+                    expected as f64,
+                    "point {} dimension {}",
+                    index + 1,
+                    dimension + 1
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sobol_set_index_repeats_points() {
+        let ranges = [(-1.0, 3.0), (10.0, 20.0), (0.0, 0.5)];
+        let mut sobol = SobolSampler::new(ranges, 0).unwrap();
+        let points: Vec<Point<f64, 3>> = (0..100).map(|_| sobol.sample()).collect();
+        assert_eq!(sobol.index(), 100);
+
+        sobol.set_index(40);
+        for point in &points[40..] {
+            assert_eq!(sobol.sample().coords(), point.coords());
+        }
+        sobol.set_index(0);
+        assert_eq!(sobol.sample().coords(), points[0].coords());
+
+        // Skipping points at construction is the same as setting the index.
+        let mut skipped = SobolSampler::new(ranges, 70).unwrap();
+        assert_eq!(skipped.sample().coords(), points[70].coords());
+    }
+
+    #[test]
+    fn stratified_visits_each_cell_once_per_pass() {
+        let mut stratified =
+            StratifiedSampler::new_with_rng([(0.0, 4.0), (-1.0, 1.0), (10.0, 13.0)], 3, seeded())
+                .unwrap();
+        let cells = stratified.cell_count();
+        assert_eq!(cells, 27);
+        let mut orders = Vec::new();
+        for _ in 0..4 {
+            let mut visits = vec![0; cells];
+            let mut order = Vec::new();
+            for _ in 0..cells {
+                let sample = stratified.sample();
+                let cell = stratified.cell_index(&sample).unwrap();
+                visits[cell] += 1;
+                order.push(cell);
+            }
+            assert!(visits.iter().all(|&count| count == 1), "{visits:?}");
+            orders.push(order);
+        }
+        // Each pass is shuffled again.
+        assert!(orders.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn lattice_sweeps_in_row_major_order_and_wraps() {
+        let mut lattice =
+            LatticeSampler::new([(0.0, 2.0), (10.0, 20.0), (5.0, 7.0)], [3, 2, 1]).unwrap();
+        assert_eq!(lattice.len(), 6);
+        // The last dimension varies fastest, and a count of 1 samples the center of its range.
+        let sweep = [
+            [0.0, 10.0, 6.0],
+            [0.0, 20.0, 6.0],
+            [1.0, 10.0, 6.0],
+            [1.0, 20.0, 6.0],
+            [2.0, 10.0, 6.0],
+            [2.0, 20.0, 6.0],
+        ];
+        for expected in sweep.iter().chain(&sweep) {
+            assert_eq!(lattice.sample().coords(), expected);
+        }
+        assert_eq!(lattice.position(), 0);
+
+        lattice.sample();
+        lattice.sample();
+        assert_eq!(lattice.position(), 2);
+        lattice.reset();
+        assert_eq!(lattice.sample().coords(), &sweep[0]);
+    }
+
+    fn two_spheres() -> EuclideanSphericalObstacleSet<f64, 2> {
+        EuclideanSphericalObstacleSet::new(vec![
+            Sphere {
+                center: Point::new([3.0, 3.0]),
+                radius: 1.0,
+            },
+            Sphere {
+                center: Point::new([7.0, 7.0]),
+                radius: 1.5,
+            },
+        ])
+    }
+
+    #[test]
+    fn obstacle_boundary_samples_land_in_the_offset_band() {
+        const SAMPLES: usize = 10_000;
+        let obstacles = two_spheres();
+        // The distance of a point from the nearest sphere surface, which is negative inside a sphere.
+        let surface_distance = |point: &Point<f64, 2>| {
+            obstacles
+                .spheres()
+                .iter()
+                .map(|sphere| (*point - sphere.center).norm() - sphere.radius)
+                .fold(f64::INFINITY, f64::min)
+        };
+        let in_band = |point: &Point<f64, 2>| (surface_distance(point) - 0.1).abs() < 1e-9;
+        let uniform = || UniformDistribution::new_with_rng([(0.0, 10.0); 2], seeded()).unwrap();
+
+        let mut sampler =
+            ObstacleBoundarySampler::new_with_rng(uniform(), &obstacles, 0.1, 0.5, seeded())
+                .unwrap();
+        let projected = (0..SAMPLES).filter(|_| in_band(&sampler.sample())).count();
+        let fraction = projected as f64 / SAMPLES as f64;
+        assert!((fraction - 0.5).abs() < 0.03, "{fraction}");
+
+        // Every sample is projected, including the ones drawn inside a sphere.
+        let mut sampler =
+            ObstacleBoundarySampler::new_with_rng(uniform(), &obstacles, 0.1, 1.0, seeded())
+                .unwrap();
+        assert!((0..SAMPLES).all(|_| in_band(&sampler.sample())));
+
+        // Without obstacles, the base samples pass through.
+        let empty = EuclideanSphericalObstacleSet::new(Vec::new());
+        let mut sampler =
+            ObstacleBoundarySampler::new_with_rng(uniform(), &empty, 0.1, 1.0, seeded()).unwrap();
+        let mut base = uniform();
+        for _ in 0..100 {
+            assert_eq!(sampler.sample().coords(), base.sample().coords());
+        }
+    }
+
+    /// The distance of a point from the polyline through the given points.
+    fn distance_to_path(point: &Point<f64, 2>, path: &[Point<f64, 2>]) -> f64 {
+        path.windows(2)
+            .map(|segment| {
+                let direction = segment[1] - segment[0];
+                let t = ((*point - segment[0]).dot(&direction) / direction.dot(&direction))
+                    .clamp(0.0, 1.0);
+                (*point - (segment[0] + direction * t)).norm()
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[test]
+    fn path_biased_samples_stay_in_a_tube_around_the_path() {
+        const SAMPLES: usize = 10_000;
+        const SIGMA: f64 = 0.1;
+        let path = vec![
+            Point::new([1.0, 1.0]),
+            Point::new([5.0, 5.0]),
+            Point::new([9.0, 1.0]),
+        ];
+        let base = UniformDistribution::new_with_rng([(0.0, 10.0); 2], seeded()).unwrap();
+        let mut sampler =
+            PathBiasedSampler::new_with_rng(base, path.clone(), SIGMA, 0.8, seeded()).unwrap();
+        let fraction_in_tube = |sampler: &mut PathBiasedSampler<f64, 2, _, _>,
+                                path: &[Point<f64, 2>]| {
+            let inside = (0..SAMPLES)
+                .filter(|_| distance_to_path(&sampler.sample(), path) < 3.0 * SIGMA)
+                .count();
+            inside as f64 / SAMPLES as f64
+        };
+
+        // Almost all of the path samples, and a few uniform ones, land within three sigma of the path.
+        let fraction = fraction_in_tube(&mut sampler, &path);
+        assert!(fraction > 0.77, "{fraction}");
+
+        // After the path is replaced, the samples follow the new path instead of the old one.
+        let new_path = vec![Point::new([1.0, 9.0]), Point::new([9.0, 9.0])];
+        sampler.set_path(new_path.clone());
+        let fraction = fraction_in_tube(&mut sampler, &new_path);
+        assert!(fraction > 0.77, "{fraction}");
+        let fraction = fraction_in_tube(&mut sampler, &path);
+        assert!(fraction < 0.05, "{fraction}");
+    }
+
+    #[test]
+    fn mixture_components_are_sampled_in_proportion_to_their_weights() {
+        const SAMPLES: usize = 20_000;
+        // Each component samples its own disjoint box, so a sample tells which component drew it.
+        let component = |low: f64| -> Box<dyn SamplingDistribution<f64, 2>> {
+            Box::new(UniformDistribution::new_with_rng([(low, low + 1.0); 2], seeded()).unwrap())
+        };
+        let mut sampler = MixtureSampler::new_with_rng(
+            vec![
+                (1.0, component(0.0)),
+                (3.0, component(10.0)),
+                (4.0, component(20.0)),
+            ],
+            seeded(),
+        )
+        .unwrap();
+        assert_eq!(sampler.weights(), &[0.125, 0.375, 0.5]);
+
+        let mut counts = [0usize; 3];
+        for _ in 0..SAMPLES {
+            counts[(sampler.sample()[0] / 10.0) as usize] += 1;
+        }
+        for (count, weight) in counts.iter().zip([0.125, 0.375, 0.5]) {
+            let frequency = *count as f64 / SAMPLES as f64;
+            assert!((frequency - weight).abs() < 0.015, "{counts:?}");
+        }
+    }
+
+    /// The mean distance of ball samples from the center of the ball.
+    fn mean_ball_radius<const N: usize>(radius: f64, on_surface: bool) -> f64 {
+        const SAMPLES: usize = 20_000;
+        let center = Point::new([1.0; N]);
+        let mut sampler = BallSampler::new_with_rng(center, radius, on_surface, seeded()).unwrap();
+        let total: f64 = (0..SAMPLES)
+            .map(|_| (sampler.sample() - center).norm())
+            .sum();
+        total / SAMPLES as f64
+    }
+
+    #[test]
+    fn ball_samples_have_the_uniform_mean_radius() {
+        // The distance of a uniform sample in an N-ball has density proportional to r^(N-1),
+        // so its mean is radius * N / (N + 1).
+        let expected = |n: f64| 2.0 * n / (n + 1.0);
+        for (n, mean) in [
+            (1.0, mean_ball_radius::<1>(2.0, false)),
+            (2.0, mean_ball_radius::<2>(2.0, false)),
+            (3.0, mean_ball_radius::<3>(2.0, false)),
+            (6.0, mean_ball_radius::<6>(2.0, false)),
+            (10.0, mean_ball_radius::<10>(2.0, false)),
+        ] {
+            assert!((mean - expected(n)).abs() < 0.01, "N = {n}: {mean}");
+        }
+    }
+
+    #[test]
+    fn ball_surface_samples_lie_at_the_radius() {
+        let center = Point::new([1.0, -2.0, 3.0]);
+        let mut sampler = BallSampler::new_with_rng(center, 2.5, true, seeded()).unwrap();
+        for _ in 0..1000 {
+            let distance = (sampler.sample() - center).norm();
+            assert!((distance - 2.5).abs() < 1e-12, "{distance}");
+        }
+        assert!((mean_ball_radius::<6>(2.5, true) - 2.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn goal_region_samples_fall_within_the_radius_at_the_bias_frequency() {
+        const SAMPLES: usize = 20_000;
+        // The goal lies outside the ranges, so a sample near the goal can only come from a biased draw.
+        let goal = Point::new([50.0, 50.0]);
+        let mut sampler = GoalRegionBiasedUniformDistribution::new_with_rng(
+            [(0.0, 10.0); 2],
+            goal,
+            1.5,
+            0.3,
+            seeded(),
+        )
+        .unwrap();
+        let mut biased = Vec::new();
+        for _ in 0..SAMPLES {
+            let sample = sampler.sample();
+            if (sample - goal).norm() <= 1.5 {
+                biased.push(sample);
+            } else {
+                assert!(sample.coords().iter().all(|x| (0.0..=10.0).contains(x)));
+            }
+        }
+        let frequency = biased.len() as f64 / SAMPLES as f64;
+        assert!((frequency - 0.3).abs() < 0.01, "{frequency}");
+
+        // The biased samples spread over the ball rather than repeating the goal.
+        let mean_distance = biased
+            .iter()
+            .map(|sample| (*sample - goal).norm())
+            .sum::<f64>()
+            / biased.len() as f64;
+        assert!((mean_distance - 1.0).abs() < 0.02, "{mean_distance}");
+    }
+
+    #[test]
+    fn boundary_samples_lie_on_faces_picked_by_area() {
+        const SAMPLES: usize = 28_000;
+        let ranges = [(0.0, 1.0), (-1.0, 1.0), (2.0, 6.0)];
+        let mut sampler = BoundarySampler::new_with_rng(ranges, seeded()).unwrap();
+        // Counts of the samples on the lower and upper face of each dimension.
+        let mut counts = [[0usize; 2]; 3];
+        for _ in 0..SAMPLES {
+            let sample = sampler.sample();
+            let mut faces = (0..3).filter_map(|i| {
+                let (min, max) = ranges[i];
+                assert!((min..=max).contains(&sample[i]));
+                if sample[i] == min {
+                    Some((i, 0))
+                } else if sample[i] == max {
+                    Some((i, 1))
+                } else {
+                    None
+                }
+            });
+            let (dimension, side) = faces.next().expect("the sample is not on a face");
+            assert!(faces.next().is_none());
+            counts[dimension][side] += 1;
+        }
+
+        // The faces perpendicular to each dimension have areas 2 * 4, 1 * 4 and 1 * 2.
+        for (dimension, area) in [8.0, 4.0, 2.0].into_iter().enumerate() {
+            for side in 0..2 {
+                let frequency = counts[dimension][side] as f64 / SAMPLES as f64;
+                let expected = area / 28.0;
+                assert!((frequency - expected).abs() < 0.01, "{counts:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn affine_sampler_maps_the_unit_square_into_the_outer_frame() {
+        let unit = || UniformDistribution::new_with_rng([(0.0, 1.0); 2], seeded()).unwrap();
+        assert!(AffineSampler::new(unit(), [10.0, 0.0], [5.0; 2]).is_err());
+
+        let mut sampler = AffineSampler::new(unit(), [10.0; 2], [5.0; 2]).unwrap();
+        let mut base = unit();
+        let (mut low, mut high) = ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]);
+        for _ in 0..10_000 {
+            let sample = sampler.sample();
+            let expected = base.sample();
+            for i in 0..2 {
+                assert!((5.0..=15.0).contains(&sample[i]));
+                assert!((sample[i] - (expected[i] * 10.0 + 5.0)).abs() < 1e-12);
+                low[i] = low[i].min(sample[i]);
+                high[i] = high[i].max(sample[i]);
+            }
+        }
+        // The samples cover the whole of [5, 15]^2.
+        for i in 0..2 {
+            assert!(low[i] < 5.01 && high[i] > 14.99, "{low:?} {high:?}");
+        }
+
+        // A goal in the outer frame reaches the wrapped distribution in its own frame.
+        let goal_biased = GoalBiasedUniformDistribution::new_with_rng(
+            [(0.0, 1.0); 2],
+            Point::new([0.0; 2]),
+            1.0,
+            seeded(),
+        )
+        .unwrap();
+        let mut sampler = AffineSampler::new(goal_biased, [10.0; 2], [5.0; 2]).unwrap();
+        sampler.set_goal(Point::new([15.0, 7.5]));
+        assert_eq!(sampler.base().goal().coords(), &[1.0, 0.25]);
+        assert_eq!(sampler.sample().coords(), &[15.0, 7.5]);
+    }
+
+    #[test]
+    fn goal_biased_draws_return_the_updated_goal() {
+        const SAMPLES: usize = 10_000;
+        let old_goal = Point::new([9.0, 9.0]);
+        let new_goal = Point::new([2.0, 3.0]);
+        let mut sampler =
+            GoalBiasedUniformDistribution::new_with_rng([(0.0, 10.0); 2], old_goal, 0.3, seeded())
+                .unwrap();
+        sampler.set_goal(new_goal);
+        assert_eq!(sampler.goal().coords(), new_goal.coords());
+        let mut samples = Vec::new();
+        for _ in 0..SAMPLES / 2 {
+            samples.push(sampler.sample());
+        }
+        sampler.sample_batch(SAMPLES / 2, &mut samples);
+        assert!(samples
+            .iter()
+            .all(|sample| sample.coords() != old_goal.coords()));
+        let goals = samples
+            .iter()
+            .filter(|sample| sample.coords() == new_goal.coords())
+            .count();
+        let frequency = goals as f64 / SAMPLES as f64;
+        assert!((frequency - 0.3).abs() < 0.015, "{frequency}");
+
+        // The planner changes the goal through the trait, and a full bias always draws it.
+        SamplingDistribution::set_goal(&mut sampler, old_goal);
+        assert_eq!(
+            sampler.set_goal_bias(1.5),
+            Err(SamplingError::InvalidGoalBias)
+        );
+        assert_eq!(sampler.goal_bias(), 0.3);
+        sampler.set_goal_bias(1.0).unwrap();
+        assert!((0..100).all(|_| sampler.sample().coords() == old_goal.coords()));
+    }
 }
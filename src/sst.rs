@@ -0,0 +1,548 @@
+use crate::collision::ValidityChecker;
+use crate::distance::path_length;
+use crate::goal::{GoalChecker, PointGoal};
+use crate::neighbors::NearestNeighbors;
+use crate::planner::Planner;
+use crate::point::Point;
+use crate::rrt::Node;
+use crate::sampling::SamplingDistribution;
+use crate::steering::{KinodynamicSteering, Trajectory};
+use num_traits::Float;
+
+/// The status of a node in the SST tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeStatus {
+    /// The node is the representative of its witness and can be selected for extension.
+    Active,
+    /// The node was dominated by a node with a lower cost, but is kept because it has descendants.
+    Inactive,
+    /// The node was pruned from the tree.
+    Removed,
+}
+
+/// A Stable Sparse RRT (SST) planner for kinodynamic problems.
+///
+/// The state space is covered by witness points that are at least `delta_s` apart.
+/// Each witness keeps only the lowest cost node within `delta_s` of it, so the tree stays sparse.
+/// Dominated nodes are pruned together with their ancestors that no longer lead anywhere.
+/// Instead of extending the nearest node, the lowest cost node within `delta_bn` of the sample
+/// is extended (best-near selection), which lets the solution cost improve over time.
+///
+/// Template Parameters:
+/// - `F`: The floating-point type.
+/// - `N`: The dimension of the state space.
+/// - `VC`: The validity checker.
+/// - `SD`: The sampling distribution.
+/// - `KS`: The kinodynamic steering function.
+/// - `NN`: The nearest neighbors data structure, used for both the nodes and the witnesses.
+pub struct SST<F: Float, const N: usize, VC, SD, KS, NN>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    KS: KinodynamicSteering<F, N>,
+    NN: NearestNeighbors<F, N>,
+{
+    goal: PointGoal<F, N>,
+    /// The radius for best-near selection.
+    delta_bn: F,
+    /// The radius of the witness regions.
+    delta_s: F,
+    /// The nodes in the tree, including removed nodes until the tree is compacted.
+    nodes: Vec<Node<F, N>>,
+    /// The trajectory from the parent of each node to the node (empty for the root).
+    trajectories: Vec<Trajectory<F, N, KS::Control>>,
+    status: Vec<NodeStatus>,
+    /// The number of children of each node that have not been removed.
+    live_children: Vec<usize>,
+    /// The number of removed nodes.
+    removed_count: usize,
+    /// The witness points.
+    witnesses: Vec<Point<F, N>>,
+    /// The node represented by each witness (None until a node is added near the witness).
+    representatives: Vec<Option<usize>>,
+    /// Index of the lowest cost goal node (None if no solution has been found).
+    solution: Option<usize>,
+    validity_checker: VC,
+    sampling_distribution: SD,
+    steering: KS,
+    nearest_neighbors: NN,
+    witness_neighbors: NN,
+}
+
+impl<F: Float, const N: usize, VC, SD, KS, NN> SST<F, N, VC, SD, KS, NN>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    KS: KinodynamicSteering<F, N>,
+    NN: NearestNeighbors<F, N>,
+{
+    /// Constructs a new SST planner.
+    ///
+    /// Parameters:
+    /// - `start`: The start state.
+    /// - `goal`: The goal state and tolerance.
+    /// - `validity_checker`: Checks if the states and edges between consecutive states are valid.
+    /// - `sampling_distribution`: The sampling distribution.
+    /// - `steering`: The kinodynamic steering function.
+    /// - `delta_bn`: The radius for best-near selection.
+    /// - `delta_s`: The radius of the witness regions. Larger values give sparser trees.
+    ///
+    /// Returns the SST planner.
+    pub fn new(
+        start: Point<F, N>,
+        goal: PointGoal<F, N>,
+        validity_checker: VC,
+        sampling_distribution: SD,
+        steering: KS,
+        delta_bn: F,
+        delta_s: F,
+    ) -> Self {
+        let mut sst = Self {
+            goal,
+            delta_bn,
+            delta_s,
+            nodes: Vec::new(),
+            trajectories: Vec::new(),
+            status: Vec::new(),
+            live_children: Vec::new(),
+            removed_count: 0,
+            witnesses: Vec::new(),
+            representatives: Vec::new(),
+            solution: None,
+            validity_checker,
+            sampling_distribution,
+            steering,
+            nearest_neighbors: NN::new(),
+            witness_neighbors: NN::new(),
+        };
+        let root_trajectory = Trajectory {
+            states: Vec::new(),
+            controls: Vec::new(),
+        };
+        let root = sst.add_node(Node::new(start, None), root_trajectory);
+        sst.add_witness(start, Some(root));
+        sst
+    }
+
+    /// Attempts to find a solution within a maximum number of iterations.
+    ///
+    /// Terminates and returns true when a solution is found. Otherwise, returns false.
+    ///
+    /// Parameters:
+    /// - `max_iterations`: The maximum number of iterations.
+    pub fn solve(&mut self, max_iterations: u32) -> bool {
+        for _ in 0..max_iterations {
+            self.iteration();
+            if self.solved() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Run a fixed number of iterations of the SST algorithm.
+    /// Does not terminate early, so the cost of the solution keeps improving.
+    ///
+    /// Returns true if the SST has found a solution.
+    ///
+    /// Parameters:
+    /// - `iterations`: The number of iterations to run.
+    pub fn run_iterations(&mut self, iterations: u32) -> bool {
+        for _ in 0..iterations {
+            self.iteration();
+        }
+        self.solved()
+    }
+
+    /// Returns true if the planner has found a solution.
+    pub fn solved(&self) -> bool {
+        self.solution.is_some()
+    }
+
+    /// Returns the states from the start to the goal, if a solution was found.
+    ///
+    /// This includes every intermediate state of the trajectories, not only the tree nodes.
+    pub fn get_path(&self) -> Option<Vec<Point<F, N>>> {
+        let indices = self.path_indices_to(self.solution?);
+        let mut path = vec![*self.nodes[0].point()];
+        for index in indices {
+            path.extend_from_slice(&self.trajectories[index].states);
+        }
+        Some(path)
+    }
+
+    /// Returns the controls that drive the robot from the start to the goal, if a solution was found.
+    pub fn get_controls(&self) -> Option<Vec<KS::Control>> {
+        let indices = self.path_indices_to(self.solution?);
+        let mut controls = Vec::new();
+        for index in indices {
+            controls.extend_from_slice(&self.trajectories[index].controls);
+        }
+        Some(controls)
+    }
+
+    /// Returns the length of the trajectory from the start to the goal, if a solution was found.
+    pub fn get_path_cost(&self) -> Option<F> {
+        self.solution.map(|solution| self.nodes[solution].cost())
+    }
+
+    /// Returns the number of nodes in the tree.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() - self.removed_count
+    }
+
+    /// Returns the number of witness points.
+    pub fn witness_count(&self) -> usize {
+        self.witnesses.len()
+    }
+
+    /// Returns the nodes in the tree with their indices.
+    /// Pruned nodes are skipped, so the indices are not necessarily contiguous.
+    pub fn nodes(&self) -> impl Iterator<Item = (usize, &Node<F, N>)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.status[*index] != NodeStatus::Removed)
+    }
+
+    pub fn get_sampling_distribution(&self) -> &SD {
+        &self.sampling_distribution
+    }
+
+    pub fn get_validity_checker(&self) -> &VC {
+        &self.validity_checker
+    }
+
+    /// Expands the tree by one iteration.
+    ///
+    /// Each iteration of the SST algorithm consists of the following steps:
+    /// 1. Sample a state from the sampling distribution.
+    /// 2. Select the lowest cost active node within `delta_bn` of the sample, or the nearest active node.
+    /// 3. Steer from the selected node towards the sample and check every state of the trajectory.
+    /// 4. Find the witness of the new state, creating one if no witness is within `delta_s`.
+    /// 5. If the new node has a lower cost than the representative of the witness, add it, make it the
+    ///    representative and prune the old representative.
+    /// 6. If the new node is in the goal and improves the solution, record it as the solution.
+    fn iteration(&mut self) {
+        let sample = self.sampling_distribution.sample();
+        let Some(selected) = self.best_near(&sample) else {
+            return;
+        };
+
+        let selected_point = *self.nodes[selected].point();
+        let trajectory = self.steering.steer(&selected_point, &sample);
+        let Some(&new_point) = trajectory.end() else {
+            return;
+        };
+        let mut previous = selected_point;
+        for state in &trajectory.states {
+            if !self.validity_checker.is_point_valid(state)
+                || !self.validity_checker.is_edge_valid(&previous, state)
+            {
+                return;
+            }
+            previous = *state;
+        }
+
+        let mut states = vec![selected_point];
+        states.extend_from_slice(&trajectory.states);
+        let new_cost = self.nodes[selected].cost() + path_length(&states);
+
+        let witness = match self.witness_neighbors.nearest_one_with_distance(&new_point) {
+            Some((witness, distance)) if distance <= self.delta_s => witness,
+            _ => self.add_witness(new_point, None),
+        };
+        let representative = self.representatives[witness];
+        if representative.is_some_and(|index| self.nodes[index].cost() <= new_cost) {
+            return;
+        }
+
+        let index = self.add_node(Node::new(new_point, Some(selected)), trajectory);
+        self.representatives[witness] = Some(index);
+        if self.goal.is_goal(&new_point)
+            && self
+                .get_path_cost()
+                .is_none_or(|solution_cost| new_cost < solution_cost)
+        {
+            self.solution = Some(index);
+        }
+        if let Some(dominated) = representative {
+            self.status[dominated] = NodeStatus::Inactive;
+            self.prune(dominated);
+        }
+
+        if self.removed_count > self.node_count() {
+            self.compact();
+        }
+    }
+
+    /// Selects the node to extend towards the sample.
+    ///
+    /// Returns:
+    /// The lowest cost active node within `delta_bn` of the sample,
+    /// or the nearest active node if there is none.
+    fn best_near(&self, sample: &Point<F, N>) -> Option<usize> {
+        let best = self
+            .nearest_neighbors
+            .within_radius(sample, self.delta_bn)
+            .into_iter()
+            .filter(|&index| self.status[index] == NodeStatus::Active)
+            .min_by(|&a, &b| {
+                self.nodes[a]
+                    .cost()
+                    .partial_cmp(&self.nodes[b].cost())
+                    .unwrap()
+            });
+        if best.is_some() {
+            return best;
+        }
+
+        // The nearest neighbors data structure also contains inactive and removed nodes,
+        // so keep widening the query until an active node is found.
+        let mut k = 1;
+        loop {
+            let nearest = self.nearest_neighbors.nearest_k(sample, k);
+            if let Some(&index) = nearest
+                .iter()
+                .find(|&&index| self.status[index] == NodeStatus::Active)
+            {
+                return Some(index);
+            }
+            if nearest.len() < k {
+                return None;
+            }
+            k *= 2;
+        }
+    }
+
+    /// Removes an inactive leaf and its inactive ancestors that become leaves.
+    /// Nodes on the solution path are never removed.
+    fn prune(&mut self, index: usize) {
+        let solution_path = self
+            .solution
+            .map(|solution| self.path_indices_to(solution))
+            .unwrap_or_default();
+        let mut current = index;
+        while self.status[current] == NodeStatus::Inactive
+            && self.live_children[current] == 0
+            && !solution_path.contains(&current)
+        {
+            self.status[current] = NodeStatus::Removed;
+            self.removed_count += 1;
+            let Some(parent) = self.nodes[current].parent() else {
+                break;
+            };
+            self.live_children[parent] -= 1;
+            current = parent;
+        }
+    }
+
+    /// Drops the removed nodes from storage and rebuilds the nearest neighbors data structure.
+    fn compact(&mut self) {
+        let mut new_indices = vec![None; self.nodes.len()];
+        let mut next_index = 0;
+        for (index, status) in self.status.iter().enumerate() {
+            if *status != NodeStatus::Removed {
+                new_indices[index] = Some(next_index);
+                next_index += 1;
+            }
+        }
+
+        let old_nodes = std::mem::take(&mut self.nodes);
+        let old_trajectories = std::mem::take(&mut self.trajectories);
+        let old_status = std::mem::take(&mut self.status);
+        self.live_children.clear();
+        self.removed_count = 0;
+        self.nearest_neighbors = NN::new();
+        for ((node, trajectory), status) in
+            old_nodes.into_iter().zip(old_trajectories).zip(old_status)
+        {
+            if status == NodeStatus::Removed {
+                continue;
+            }
+            let parent = node.parent().map(|parent| new_indices[parent].unwrap());
+            let index = self.add_node(Node::new(*node.point(), parent), trajectory);
+            self.status[index] = status;
+        }
+
+        for representative in self.representatives.iter_mut() {
+            *representative = representative.and_then(|index| new_indices[index]);
+        }
+        self.solution = self.solution.and_then(|index| new_indices[index]);
+    }
+
+    /// Returns the indices of the nodes on the path from the root to the node at `index`, excluding the root.
+    fn path_indices_to(&self, index: usize) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let mut current_index = index;
+        while let Some(parent_index) = self.nodes[current_index].parent() {
+            indices.push(current_index);
+            current_index = parent_index;
+        }
+        indices.reverse();
+        indices
+    }
+
+    /// Adds a witness point.
+    fn add_witness(&mut self, point: Point<F, N>, representative: Option<usize>) -> usize {
+        let index = self.witnesses.len();
+        self.witness_neighbors.add(point, index);
+        self.witnesses.push(point);
+        self.representatives.push(representative);
+        index
+    }
+
+    /// Adds an active node to the tree and the nearest neighbors data structure.
+    /// The cost of the node is the length of the trajectory from the root.
+    fn add_node(
+        &mut self,
+        mut node: Node<F, N>,
+        trajectory: Trajectory<F, N, KS::Control>,
+    ) -> usize {
+        let index = self.nodes.len();
+        self.nearest_neighbors.add(*node.point(), index);
+        if let Some(parent) = node.parent() {
+            let parent_node = &mut self.nodes[parent];
+            parent_node.add_child(index);
+            let mut states = vec![*parent_node.point()];
+            states.extend_from_slice(&trajectory.states);
+            node.set_cost(parent_node.cost() + path_length(&states));
+            self.live_children[parent] += 1;
+        }
+        self.nodes.push(node);
+        self.trajectories.push(trajectory);
+        self.status.push(NodeStatus::Active);
+        self.live_children.push(0);
+        index
+    }
+}
+
+impl<F: Float, const N: usize, VC, SD, KS, NN> Planner<F, N> for SST<F, N, VC, SD, KS, NN>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    KS: KinodynamicSteering<F, N>,
+    NN: NearestNeighbors<F, N>,
+{
+    fn solve(&mut self, max_iterations: u32) -> bool {
+        SST::solve(self, max_iterations)
+    }
+
+    fn solved(&self) -> bool {
+        SST::solved(self)
+    }
+
+    fn get_path(&self) -> Option<Vec<Point<F, N>>> {
+        SST::get_path(self)
+    }
+
+    fn get_tree_size(&self) -> usize {
+        self.node_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::EuclideanSphericalObstacleSet;
+    use crate::distance::euclidean_distance;
+    use crate::neighbors::KdTreeNearestNeighbors;
+    use crate::sampling::UniformDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// Moves in a straight line towards the target, by at most `range`.
+    struct StraightSteering {
+        range: f64,
+    }
+
+    impl KinodynamicSteering<f64, 2> for StraightSteering {
+        type Control = ();
+
+        fn steer(&self, from: &Point<f64, 2>, to: &Point<f64, 2>) -> Trajectory<f64, 2, ()> {
+            let distance = euclidean_distance(from, to);
+            let t = if distance > self.range {
+                self.range / distance
+            } else {
+                1.0
+            };
+            let end = Point::new([
+                from[0] + (to[0] - from[0]) * t,
+                from[1] + (to[1] - from[1]) * t,
+            ]);
+            Trajectory {
+                states: vec![end],
+                controls: vec![()],
+            }
+        }
+    }
+
+    type TestSST = SST<
+        f64,
+        2,
+        EuclideanSphericalObstacleSet<f64, 2>,
+        UniformDistribution<f64, 2, StdRng>,
+        StraightSteering,
+        KdTreeNearestNeighbors<f64, 2>,
+    >;
+
+    /// An SST from (1, 1) to (9, 9) in an empty 10 by 10 square.
+    fn sst(delta_s: f64, seed: u64) -> TestSST {
+        SST::new(
+            Point::new([1.0, 1.0]),
+            PointGoal::new(Point::new([9.0, 9.0]), 0.5),
+            EuclideanSphericalObstacleSet::new(Vec::new()),
+            UniformDistribution::new_with_rng([(0.0, 10.0); 2], StdRng::seed_from_u64(seed))
+                .unwrap(),
+            StraightSteering { range: 1.0 },
+            1.0,
+            delta_s,
+        )
+    }
+
+    #[test]
+    fn witnesses_keep_the_tree_sparse() {
+        let mut sst = sst(0.5, 1);
+        for _ in 0..10 {
+            sst.run_iterations(2_000);
+            // Only the representatives of witnesses are active, and the other nodes lead to them.
+            let active = sst
+                .nodes()
+                .filter(|(index, _)| sst.status[*index] == NodeStatus::Active)
+                .count();
+            assert!(active <= sst.witness_count());
+            assert!(sst.node_count() < 2 * sst.witness_count());
+        }
+
+        // Witnesses are more than `delta_s` apart, so at most this many disks of radius
+        // `delta_s / 2` around them fit in the square widened by `delta_s / 2`.
+        for (i, a) in sst.witnesses.iter().enumerate() {
+            for b in &sst.witnesses[i + 1..] {
+                assert!(euclidean_distance(a, b) > 0.5);
+            }
+        }
+        let packing_bound = 10.5 * 10.5 / (std::f64::consts::PI * 0.25 * 0.25);
+        assert!((sst.witness_count() as f64) < packing_bound);
+        assert!(sst.node_count() < 1_000);
+    }
+
+    #[test]
+    fn solution_cost_improves_over_iterations() {
+        let mut sst = sst(0.5, 2);
+        assert!(sst.solve(10_000));
+        let first_cost = sst.get_path_cost().unwrap();
+        let mut cost = first_cost;
+        for _ in 0..10 {
+            sst.run_iterations(2_000);
+            let new_cost = sst.get_path_cost().unwrap();
+            assert!(new_cost <= cost);
+            cost = new_cost;
+        }
+        assert!(cost < first_cost);
+
+        // The straight line from the start to the edge of the goal region is the shortest path.
+        let shortest = 8.0 * 2.0_f64.sqrt() - 0.5;
+        assert!(cost >= shortest);
+        assert!(cost < 1.1 * shortest);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::distance::euclidean_distance;
+use crate::collision::DistanceQuery;
+use crate::distance::{
+    angular_difference, euclidean_distance, normalize_angles, weighted_so2_distance, wrap_to_pi,
+};
 use crate::point::Point;
 use num_traits::Float;
 
@@ -6,6 +9,7 @@ use num_traits::Float;
 /// Allows considering the robot's kinematics and dynamics in the RRT.
 pub trait Steering<F: Float, const N: usize> {
     /// Steers the robot from one point towards another.
+    ///
     /// Parameters:
     /// - `from`: The start point.
     /// - `to`: The point to steer towards.
@@ -13,6 +17,54 @@ pub trait Steering<F: Float, const N: usize> {
     /// Returns:
     /// The point the robot should move to.
     fn steer(&self, from: &Point<F, N>, to: &Point<F, N>) -> Point<F, N>;
+
+    /// Steers the robot from one point towards another, and returns the states the robot passes through.
+    ///
+    /// The default moves along the straight line to the steered point. Steering functions whose robot
+    /// follows a curve should override it, so that the planner checks the curve instead of the straight edge.
+    ///
+    /// Parameters:
+    /// - `from`: The start point.
+    /// - `to`: The point to steer towards.
+    /// - `resolution`: The largest distance between consecutive waypoints.
+    ///
+    /// Returns:
+    /// The point the robot should move to, and the waypoints from `from` to that point, including both.
+    fn steer_with_waypoints(
+        &self,
+        from: &Point<F, N>,
+        to: &Point<F, N>,
+        resolution: F,
+    ) -> (Point<F, N>, Vec<Point<F, N>>) {
+        let new_point = self.steer(from, to);
+        let distance = euclidean_distance(from, &new_point);
+        let direction = &new_point - from;
+        let mut waypoints: Vec<Point<F, N>> = spaced_distances(distance, resolution)
+            .map(|travelled| {
+                if distance > F::zero() {
+                    from + &(direction * (travelled / distance))
+                } else {
+                    *from
+                }
+            })
+            .collect();
+        *waypoints.last_mut().unwrap() = new_point;
+        (new_point, waypoints)
+    }
+}
+
+/// Splits a distance into equal steps no longer than the resolution.
+///
+/// Returns:
+/// The distances from zero to `distance` at the ends of the steps, with at least one step.
+fn spaced_distances<F: Float>(distance: F, resolution: F) -> impl Iterator<Item = F> {
+    let steps = (distance / resolution)
+        .ceil()
+        .to_usize()
+        .unwrap_or(0)
+        .max(1);
+    let steps_float = F::from(steps).unwrap();
+    (0..=steps).map(move |step| distance * F::from(step).unwrap() / steps_float)
 }
 
 /// A steering strategy that moves the robot in a straight line towards the goal.
@@ -24,7 +76,7 @@ impl<F: Float, const N: usize> Steering<F, N> for EuclideanSteering<F, N> {
     fn steer(&self, from: &Point<F, N>, to: &Point<F, N>) -> Point<F, N> {
         let distance = euclidean_distance(from, to);
         if distance <= self.range {
-            to.clone()
+            *to
         } else {
             let direction = to - from;
             let steering_vector = direction / distance;
@@ -35,11 +87,1468 @@ impl<F: Float, const N: usize> Steering<F, N> for EuclideanSteering<F, N> {
 
 impl<F: Float, const N: usize> EuclideanSteering<F, N> {
     /// Constructs a new Euclidean steering function which moves the robot in a straight line.
+    ///
     /// Parameters:
     /// - `range`: The maximum distance the robot can move in one step.
+    ///
     /// Returns:
     /// The Euclidean steering strategy.
     pub fn new(range: F) -> Self {
         Self { range }
     }
 }
+
+/// Straight-line steering with a separate step limit for each dimension.
+///
+/// This suits states that mix units, such as positions and joint angles. The step towards the target
+/// is scaled uniformly, so the robot moves in a straight line and the most limited dimension saturates.
+pub struct BoxSteering<F: Float, const N: usize> {
+    max_deltas: [F; N],
+}
+
+impl<F: Float, const N: usize> BoxSteering<F, N> {
+    /// Constructs a new box steering function.
+    ///
+    /// Parameters:
+    /// - `max_deltas`: The maximum change of each coordinate in one step. Infinity leaves a dimension unlimited.
+    ///
+    /// Returns:
+    /// The box steering, or an error if a limit is not positive.
+    pub fn new(max_deltas: [F; N]) -> Result<Self, &'static str> {
+        if !max_deltas.iter().all(|&max_delta| max_delta > F::zero()) {
+            return Err("step limits must be positive");
+        }
+        Ok(Self { max_deltas })
+    }
+
+    pub fn max_deltas(&self) -> &[F; N] {
+        &self.max_deltas
+    }
+}
+
+impl<F: Float, const N: usize> Steering<F, N> for BoxSteering<F, N> {
+    fn steer(&self, from: &Point<F, N>, to: &Point<F, N>) -> Point<F, N> {
+        let direction = to - from;
+        // How many times the step limit the largest coordinate change is.
+        let scale = (0..N).fold(F::zero(), |scale, i| {
+            scale.max(direction[i].abs() / self.max_deltas[i])
+        });
+        if scale <= F::one() {
+            *to
+        } else {
+            from + &(direction / scale)
+        }
+    }
+}
+
+/// Straight-line steering for states with angular dimensions, which wrap at ±π.
+///
+/// Angular coordinates move by their shortest angular difference, so steering from 3 to -3 radians
+/// passes through π instead of 0. The step is measured with `weighted_so2_distance`, and the angular
+/// coordinates of the returned point are wrapped to (-π, π].
+pub struct TopologyAwareSteering<F: Float, const N: usize> {
+    range: F,
+    angular: [bool; N], // which dimensions are angles in radians
+    weights: [F; N],    // the weight of each dimension in the step length
+}
+
+impl<F: Float, const N: usize> TopologyAwareSteering<F, N> {
+    /// Constructs a new topology aware steering function, with all dimensions weighted equally.
+    ///
+    /// Parameters:
+    /// - `range`: The maximum distance the robot can move in one step.
+    /// - `angular`: Which dimensions are angles in radians.
+    ///
+    /// Returns:
+    /// The steering, or an error if the range is not positive and finite.
+    pub fn new(range: F, angular: [bool; N]) -> Result<Self, &'static str> {
+        if !(range > F::zero() && range.is_finite()) {
+            return Err("range must be positive and finite");
+        }
+        Ok(Self {
+            range,
+            angular,
+            weights: [F::one(); N],
+        })
+    }
+
+    /// Sets the weight of each dimension in the step length, for example to trade meters against radians.
+    ///
+    /// Returns:
+    /// The steering, or an error if a weight is negative, not finite, or all weights are zero.
+    pub fn with_weights(mut self, weights: [F; N]) -> Result<Self, &'static str> {
+        if !weights
+            .iter()
+            .all(|&weight| weight >= F::zero() && weight.is_finite())
+        {
+            return Err("weights must be non-negative and finite");
+        }
+        if weights.iter().all(|&weight| weight == F::zero()) {
+            return Err("at least one weight must be positive");
+        }
+        self.weights = weights;
+        Ok(self)
+    }
+
+    pub fn range(&self) -> F {
+        self.range
+    }
+
+    pub fn angular(&self) -> &[bool; N] {
+        &self.angular
+    }
+
+    pub fn weights(&self) -> &[F; N] {
+        &self.weights
+    }
+}
+
+impl<F: Float, const N: usize> Steering<F, N> for TopologyAwareSteering<F, N> {
+    fn steer(&self, from: &Point<F, N>, to: &Point<F, N>) -> Point<F, N> {
+        let distance = weighted_so2_distance(from, to, &self.angular, &self.weights);
+        if distance <= self.range {
+            return normalize_angles(to, &self.angular);
+        }
+        let scale = self.range / distance;
+        let mut coords = *from.coords();
+        for (i, coord) in coords.iter_mut().enumerate() {
+            let difference = if self.angular[i] {
+                angular_difference(from[i], to[i])
+            } else {
+                to[i] - from[i]
+            };
+            *coord = *coord + difference * scale;
+        }
+        normalize_angles(&Point::new(coords), &self.angular)
+    }
+}
+
+/// Straight-line steering whose step shrinks near obstacles.
+///
+/// The step from a point is `alpha` times its clearance, clamped between the minimum step and the maximum range.
+/// With `alpha` below one, a step longer than the minimum ends inside the free ball around its start,
+/// so it can not reach an obstacle. Far from obstacles, the robot moves the full range as `EuclideanSteering` does.
+pub struct ClearanceAdaptiveSteering<F: Float, const N: usize, C: DistanceQuery<F, N>> {
+    clearance: C,
+    max_range: F, // the step length far from obstacles
+    alpha: F,     // the fraction of the clearance to move
+    min_step: F,  // the step length at or inside obstacles
+}
+
+impl<F: Float, const N: usize, C: DistanceQuery<F, N>> ClearanceAdaptiveSteering<F, N, C> {
+    /// Constructs a new clearance adaptive steering function.
+    ///
+    /// Parameters:
+    /// - `clearance`: Measures the distance from a point to the nearest obstacle.
+    /// - `max_range`: The maximum distance the robot can move in one step.
+    /// - `alpha`: The fraction of the clearance of the start point to move in one step.
+    /// - `min_step`: The minimum distance the robot moves in one step, so the tree still grows near obstacles.
+    ///
+    /// Returns:
+    /// The steering, or an error if the maximum range or `alpha` is not positive and finite,
+    /// or the minimum step is not positive or exceeds the maximum range.
+    pub fn new(clearance: C, max_range: F, alpha: F, min_step: F) -> Result<Self, &'static str> {
+        if !(max_range > F::zero() && max_range.is_finite()) {
+            return Err("maximum range must be positive and finite");
+        }
+        if !(alpha > F::zero() && alpha.is_finite()) {
+            return Err("alpha must be positive and finite");
+        }
+        if !(min_step > F::zero() && min_step <= max_range) {
+            return Err("minimum step must be positive and at most the maximum range");
+        }
+        Ok(Self {
+            clearance,
+            max_range,
+            alpha,
+            min_step,
+        })
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.clearance
+    }
+
+    pub fn max_range(&self) -> F {
+        self.max_range
+    }
+
+    pub fn alpha(&self) -> F {
+        self.alpha
+    }
+
+    pub fn min_step(&self) -> F {
+        self.min_step
+    }
+
+    /// Returns the length of a step from a point.
+    pub fn step_length(&self, from: &Point<F, N>) -> F {
+        (self.alpha * self.clearance.clearance(from))
+            .max(self.min_step)
+            .min(self.max_range)
+    }
+}
+
+impl<F: Float, const N: usize, C: DistanceQuery<F, N>> Steering<F, N>
+    for ClearanceAdaptiveSteering<F, N, C>
+{
+    fn steer(&self, from: &Point<F, N>, to: &Point<F, N>) -> Point<F, N> {
+        let distance = euclidean_distance(from, to);
+        let step = self.step_length(from);
+        if distance <= step {
+            *to
+        } else {
+            let direction = to - from;
+            from + &(direction * (step / distance))
+        }
+    }
+}
+
+/// The trajectory produced by steering a robot with dynamics.
+///
+/// `controls[i]` is applied for one time step to reach `states[i]` (from `states[i - 1]`, or the start for `i = 0`).
+#[derive(Debug, Clone)]
+pub struct Trajectory<F: Float, const N: usize, C> {
+    /// The states reached after each control. The last state is the end of the trajectory.
+    pub states: Vec<Point<F, N>>,
+    /// The controls applied in each time step.
+    pub controls: Vec<C>,
+}
+
+impl<F: Float, const N: usize, C> Trajectory<F, N, C> {
+    /// Returns the final state of the trajectory, or None if the trajectory is empty.
+    pub fn end(&self) -> Option<&Point<F, N>> {
+        self.states.last()
+    }
+}
+
+/// A trait for steering a robot with dynamics by applying controls over time.
+/// Unlike `Steering`, the whole trajectory between the two states is returned, so it can be
+/// collision checked and executed.
+pub trait KinodynamicSteering<F: Float, const N: usize> {
+    /// The control input of the robot.
+    type Control: Clone;
+
+    /// Steers the robot from one state towards another.
+    ///
+    /// Parameters:
+    /// - `from`: The start state.
+    /// - `to`: The state to steer towards.
+    ///
+    /// Returns:
+    /// The trajectory of states and controls. It may be empty if the robot can not move towards `to`.
+    fn steer(&self, from: &Point<F, N>, to: &Point<F, N>) -> Trajectory<F, N, Self::Control>;
+}
+
+/// Steering for a double integrator, a robot whose controls are accelerations.
+///
+/// The state is `[positions..., velocities...]`, so `N` must be even and the control has `N / 2` values.
+/// Each call accelerates towards the target state with a critically damped feedback law
+/// (clamped to the maximum acceleration) and integrates for a fixed number of time steps.
+pub struct DoubleIntegratorSteering<F: Float, const N: usize> {
+    time_step: F,
+    num_steps: usize,
+    max_acceleration: F,
+}
+
+impl<F: Float, const N: usize> DoubleIntegratorSteering<F, N> {
+    /// Constructs a new double integrator steering function.
+    ///
+    /// Parameters:
+    /// - `time_step`: The duration of each control step.
+    /// - `num_steps`: The number of control steps in each call to `steer`.
+    /// - `max_acceleration`: The maximum absolute acceleration along each axis.
+    ///
+    /// Returns:
+    /// The double integrator steering, or an error if `N` is odd or a parameter is not positive.
+    pub fn new(time_step: F, num_steps: usize, max_acceleration: F) -> Result<Self, &'static str> {
+        if !N.is_multiple_of(2) {
+            return Err("the state dimension must be even");
+        }
+        if time_step <= F::zero() || num_steps == 0 || max_acceleration <= F::zero() {
+            return Err("time_step, num_steps and max_acceleration must be positive");
+        }
+        Ok(Self {
+            time_step,
+            num_steps,
+            max_acceleration,
+        })
+    }
+}
+
+impl<F: Float, const N: usize> KinodynamicSteering<F, N> for DoubleIntegratorSteering<F, N> {
+    type Control = Vec<F>;
+
+    fn steer(&self, from: &Point<F, N>, to: &Point<F, N>) -> Trajectory<F, N, Vec<F>> {
+        let axes = N / 2;
+        let horizon = self.time_step * F::from(self.num_steps).unwrap();
+        let position_gain = F::one() / (horizon * horizon);
+        let velocity_gain = F::from(2.0).unwrap() / horizon;
+
+        let mut state = *from.coords();
+        let mut trajectory = Trajectory {
+            states: Vec::with_capacity(self.num_steps),
+            controls: Vec::with_capacity(self.num_steps),
+        };
+        for _ in 0..self.num_steps {
+            let control: Vec<F> = (0..axes)
+                .map(|axis| {
+                    let acceleration = position_gain * (to[axis] - state[axis])
+                        + velocity_gain * (to[axis + axes] - state[axis + axes]);
+                    acceleration
+                        .max(-self.max_acceleration)
+                        .min(self.max_acceleration)
+                })
+                .collect();
+
+            // Semi-implicit Euler integration.
+            for (axis, &acceleration) in control.iter().enumerate() {
+                state[axis + axes] = state[axis + axes] + acceleration * self.time_step;
+                state[axis] = state[axis] + state[axis + axes] * self.time_step;
+            }
+            trajectory.states.push(Point::new(state));
+            trajectory.controls.push(control);
+        }
+        trajectory
+    }
+}
+
+/// Wraps an angle in radians to the range [0, 2π).
+fn mod_two_pi<F: Float>(angle: F) -> F {
+    let two_pi = F::from(2.0 * std::f64::consts::PI).unwrap();
+    let wrapped = angle - two_pi * (angle / two_pi).floor();
+    if wrapped >= two_pi {
+        F::zero()
+    } else {
+        wrapped
+    }
+}
+
+/// The direction of a segment of a Dubins path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DubinsSegment {
+    /// Turn left at the minimum turning radius.
+    Left,
+    /// Drive straight.
+    Straight,
+    /// Turn right at the minimum turning radius.
+    Right,
+}
+
+/// The six words a shortest Dubins path can follow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DubinsPathType {
+    LSL,
+    LSR,
+    RSL,
+    RSR,
+    RLR,
+    LRL,
+}
+
+impl DubinsPathType {
+    /// All the path types, in the order they are tried.
+    pub const ALL: [DubinsPathType; 6] = [
+        DubinsPathType::LSL,
+        DubinsPathType::LSR,
+        DubinsPathType::RSL,
+        DubinsPathType::RSR,
+        DubinsPathType::RLR,
+        DubinsPathType::LRL,
+    ];
+
+    /// Returns the directions of the three segments of the word.
+    pub fn segments(&self) -> [DubinsSegment; 3] {
+        use DubinsSegment::{Left, Right, Straight};
+        match self {
+            DubinsPathType::LSL => [Left, Straight, Left],
+            DubinsPathType::LSR => [Left, Straight, Right],
+            DubinsPathType::RSL => [Right, Straight, Left],
+            DubinsPathType::RSR => [Right, Straight, Right],
+            DubinsPathType::RLR => [Right, Left, Right],
+            DubinsPathType::LRL => [Left, Right, Left],
+        }
+    }
+}
+
+/// A Dubins path from a pose `(x, y, θ)`: three segments of forward driving that turn at the minimum
+/// turning radius or go straight.
+#[derive(Debug, Clone)]
+pub struct DubinsPath<F: Float> {
+    /// The start pose.
+    pub start: Point<F, 3>,
+    /// The word the path follows.
+    pub path_type: DubinsPathType,
+    /// The length of each segment, in the same units as the positions.
+    pub segment_lengths: [F; 3],
+    /// The minimum turning radius.
+    pub turning_radius: F,
+}
+
+impl<F: Float> DubinsPath<F> {
+    /// Returns the total length of the path.
+    pub fn length(&self) -> F {
+        self.segment_lengths[0] + self.segment_lengths[1] + self.segment_lengths[2]
+    }
+
+    /// Finds the pose after driving a distance along the path.
+    ///
+    /// Parameters:
+    /// - `distance`: The distance from the start, which is clamped to the length of the path.
+    ///
+    /// Returns:
+    /// The pose `(x, y, θ)`, with θ wrapped to (-π, π].
+    pub fn sample(&self, distance: F) -> Point<F, 3> {
+        let mut remaining = distance.max(F::zero()).min(self.length());
+        let mut pose = self.start;
+        for (segment, &length) in self.path_type.segments().iter().zip(&self.segment_lengths) {
+            let travelled = remaining.min(length);
+            pose = drive_segment(&pose, *segment, travelled, self.turning_radius);
+            remaining = remaining - travelled;
+        }
+        Point::new([pose[0], pose[1], wrap_to_pi(pose[2])])
+    }
+
+    /// Samples poses along the path, including both ends, for collision checking or execution.
+    ///
+    /// Parameters:
+    /// - `resolution`: The largest distance along the path between consecutive poses.
+    ///
+    /// Returns:
+    /// The poses from the start to the end of the path.
+    pub fn sample_many(&self, resolution: F) -> Vec<Point<F, 3>> {
+        spaced_distances(self.length(), resolution)
+            .map(|distance| self.sample(distance))
+            .collect()
+    }
+}
+
+/// Drives a pose along a single segment of a Dubins path.
+fn drive_segment<F: Float>(
+    pose: &Point<F, 3>,
+    segment: DubinsSegment,
+    length: F,
+    turning_radius: F,
+) -> Point<F, 3> {
+    let (x, y, theta) = (pose[0], pose[1], pose[2]);
+    match segment {
+        DubinsSegment::Straight => {
+            Point::new([x + theta.cos() * length, y + theta.sin() * length, theta])
+        }
+        DubinsSegment::Left => {
+            let turn = length / turning_radius;
+            Point::new([
+                x + turning_radius * ((theta + turn).sin() - theta.sin()),
+                y - turning_radius * ((theta + turn).cos() - theta.cos()),
+                theta + turn,
+            ])
+        }
+        DubinsSegment::Right => {
+            let turn = length / turning_radius;
+            Point::new([
+                x - turning_radius * ((theta - turn).sin() - theta.sin()),
+                y + turning_radius * ((theta - turn).cos() - theta.cos()),
+                theta - turn,
+            ])
+        }
+    }
+}
+
+/// Steering for a car-like robot that only drives forward and has a minimum turning radius.
+///
+/// The state is `(x, y, θ)`, with the heading θ in radians. Each call finds the shortest Dubins path from
+/// `from` to `to`, and drives along it for at most the range. The planner checks the straight edge between
+/// the two states unless a waypoint resolution is set (see `RRT::set_waypoint_resolution`), in which case
+/// it checks the curve the robot drives.
+pub struct DubinsSteering<F: Float> {
+    turning_radius: F,
+    range: F,
+}
+
+impl<F: Float> DubinsSteering<F> {
+    /// Constructs a new Dubins steering function.
+    ///
+    /// Parameters:
+    /// - `turning_radius`: The minimum turning radius of the robot.
+    /// - `range`: The maximum distance the robot drives in one step.
+    ///
+    /// Returns:
+    /// The Dubins steering, or an error if the turning radius or the range is not positive and finite.
+    pub fn new(turning_radius: F, range: F) -> Result<Self, &'static str> {
+        if !(turning_radius > F::zero() && turning_radius.is_finite()) {
+            return Err("turning radius must be positive and finite");
+        }
+        if !(range > F::zero() && range.is_finite()) {
+            return Err("range must be positive and finite");
+        }
+        Ok(Self {
+            turning_radius,
+            range,
+        })
+    }
+
+    pub fn turning_radius(&self) -> F {
+        self.turning_radius
+    }
+
+    pub fn range(&self) -> F {
+        self.range
+    }
+
+    /// Drives along a path for at most the range.
+    ///
+    /// Returns:
+    /// The distance driven and the pose reached, which is exactly `to` if the whole path fits in the range.
+    fn step_along(&self, path: &DubinsPath<F>, to: &Point<F, 3>) -> (F, Point<F, 3>) {
+        let length = path.length();
+        if length <= self.range {
+            (length, Point::new([to[0], to[1], wrap_to_pi(to[2])]))
+        } else {
+            (self.range, path.sample(self.range))
+        }
+    }
+
+    /// Finds the shortest Dubins path between two poses.
+    ///
+    /// Parameters:
+    /// - `from`: The start pose `(x, y, θ)`.
+    /// - `to`: The end pose `(x, y, θ)`.
+    ///
+    /// Returns:
+    /// The shortest path, or None if a coordinate is not finite.
+    pub fn shortest_path(&self, from: &Point<F, 3>, to: &Point<F, 3>) -> Option<DubinsPath<F>> {
+        DubinsPathType::ALL
+            .iter()
+            .filter_map(|&path_type| self.path(from, to, path_type))
+            .min_by(|a, b| a.length().partial_cmp(&b.length()).unwrap())
+    }
+
+    /// Finds the Dubins path between two poses that follows a given word.
+    ///
+    /// Returns:
+    /// The path, or None if no path follows the word or a coordinate is not finite.
+    pub fn path(
+        &self,
+        from: &Point<F, 3>,
+        to: &Point<F, 3>,
+        path_type: DubinsPathType,
+    ) -> Option<DubinsPath<F>> {
+        // Work in a frame where the start is at the origin, the goal is on the x axis
+        // and the turning radius is one.
+        let (dx, dy) = (to[0] - from[0], to[1] - from[1]);
+        let d = (dx * dx + dy * dy).sqrt() / self.turning_radius;
+        let theta = if d > F::zero() {
+            mod_two_pi(dy.atan2(dx))
+        } else {
+            F::zero()
+        };
+        let alpha = mod_two_pi(from[2] - theta);
+        let beta = mod_two_pi(to[2] - theta);
+        let lengths = dubins_word(alpha, beta, d, path_type)?;
+        if !lengths.iter().all(|length| length.is_finite()) {
+            return None;
+        }
+        Some(DubinsPath {
+            start: *from,
+            path_type,
+            segment_lengths: lengths.map(|length| length * self.turning_radius),
+            turning_radius: self.turning_radius,
+        })
+    }
+
+    /// Samples the poses along the shortest Dubins path between two poses, including both ends.
+    ///
+    /// Parameters:
+    /// - `from`: The start pose.
+    /// - `to`: The end pose.
+    /// - `resolution`: The largest distance along the path between consecutive poses.
+    ///
+    /// Returns:
+    /// The poses, or an empty list if a coordinate is not finite.
+    pub fn waypoints(
+        &self,
+        from: &Point<F, 3>,
+        to: &Point<F, 3>,
+        resolution: F,
+    ) -> Vec<Point<F, 3>> {
+        self.shortest_path(from, to)
+            .map(|path| path.sample_many(resolution))
+            .unwrap_or_default()
+    }
+}
+
+/// Computes the normalized segment lengths of a Dubins word, following Shkel and Lumelsky,
+/// "Classification of the Dubins set" (2001).
+///
+/// Parameters:
+/// - `alpha`: The start heading relative to the line from the start to the goal.
+/// - `beta`: The goal heading relative to the same line.
+/// - `d`: The distance from the start to the goal divided by the turning radius.
+///
+/// Returns:
+/// The lengths of the three segments divided by the turning radius, or None if no path follows the word.
+fn dubins_word<F: Float>(alpha: F, beta: F, d: F, path_type: DubinsPathType) -> Option<[F; 3]> {
+    let two = F::from(2.0).unwrap();
+    let two_pi = F::from(2.0 * std::f64::consts::PI).unwrap();
+    let (sa, sb, ca, cb) = (alpha.sin(), beta.sin(), alpha.cos(), beta.cos());
+    let c_ab = (alpha - beta).cos();
+    // Rounding makes words that barely exist, such as a single turn, fail their conditions by a few ulps.
+    let tolerance = F::epsilon().sqrt();
+    match path_type {
+        DubinsPathType::LSL => {
+            let p_squared = two + d * d - two * c_ab + two * d * (sa - sb);
+            let p_squared = checked_square(p_squared, tolerance)?;
+            let angle = (cb - ca).atan2(d + sa - sb);
+            Some([
+                mod_two_pi(angle - alpha),
+                p_squared.sqrt(),
+                mod_two_pi(beta - angle),
+            ])
+        }
+        DubinsPathType::RSR => {
+            let p_squared = two + d * d - two * c_ab + two * d * (sb - sa);
+            let p_squared = checked_square(p_squared, tolerance)?;
+            let angle = (ca - cb).atan2(d - sa + sb);
+            Some([
+                mod_two_pi(alpha - angle),
+                p_squared.sqrt(),
+                mod_two_pi(angle - beta),
+            ])
+        }
+        DubinsPathType::LSR => {
+            let p_squared = -two + d * d + two * c_ab + two * d * (sa + sb);
+            let p_squared = checked_square(p_squared, tolerance)?;
+            let p = p_squared.sqrt();
+            let angle = (-ca - cb).atan2(d + sa + sb) - (-two).atan2(p);
+            Some([mod_two_pi(angle - alpha), p, mod_two_pi(angle - beta)])
+        }
+        DubinsPathType::RSL => {
+            let p_squared = -two + d * d + two * c_ab - two * d * (sa + sb);
+            let p_squared = checked_square(p_squared, tolerance)?;
+            let p = p_squared.sqrt();
+            let angle = (ca + cb).atan2(d - sa - sb) - two.atan2(p);
+            Some([mod_two_pi(alpha - angle), p, mod_two_pi(beta - angle)])
+        }
+        DubinsPathType::RLR => {
+            let cos_p = (F::from(6.0).unwrap() - d * d + two * c_ab + two * d * (sa - sb))
+                / F::from(8.0).unwrap();
+            if cos_p.abs() > F::one() + tolerance {
+                return None;
+            }
+            let cos_p = cos_p.max(-F::one()).min(F::one());
+            let angle = (ca - cb).atan2(d - sa + sb);
+            let p = mod_two_pi(two_pi - cos_p.acos());
+            let t = mod_two_pi(alpha - angle + mod_two_pi(p / two));
+            Some([t, p, mod_two_pi(alpha - beta - t + mod_two_pi(p))])
+        }
+        DubinsPathType::LRL => {
+            let cos_p = (F::from(6.0).unwrap() - d * d + two * c_ab + two * d * (sb - sa))
+                / F::from(8.0).unwrap();
+            if cos_p.abs() > F::one() + tolerance {
+                return None;
+            }
+            let cos_p = cos_p.max(-F::one()).min(F::one());
+            let angle = (ca - cb).atan2(d + sa - sb);
+            let p = mod_two_pi(two_pi - cos_p.acos());
+            let t = mod_two_pi(-alpha - angle + p / two);
+            Some([t, p, mod_two_pi(beta - alpha - t + mod_two_pi(p))])
+        }
+    }
+}
+
+/// Returns a squared length clamped to zero, or None if it is negative by more than the tolerance.
+fn checked_square<F: Float>(squared: F, tolerance: F) -> Option<F> {
+    if squared < -tolerance {
+        None
+    } else {
+        Some(squared.max(F::zero()))
+    }
+}
+
+impl<F: Float> Steering<F, 3> for DubinsSteering<F> {
+    fn steer(&self, from: &Point<F, 3>, to: &Point<F, 3>) -> Point<F, 3> {
+        match self.shortest_path(from, to) {
+            Some(path) => self.step_along(&path, to).1,
+            None => *from,
+        }
+    }
+
+    fn steer_with_waypoints(
+        &self,
+        from: &Point<F, 3>,
+        to: &Point<F, 3>,
+        resolution: F,
+    ) -> (Point<F, 3>, Vec<Point<F, 3>>) {
+        let Some(path) = self.shortest_path(from, to) else {
+            return (*from, vec![*from]);
+        };
+        let (distance, new_point) = self.step_along(&path, to);
+        let mut waypoints: Vec<Point<F, 3>> = spaced_distances(distance, resolution)
+            .map(|travelled| path.sample(travelled))
+            .collect();
+        *waypoints.last_mut().unwrap() = new_point;
+        (new_point, waypoints)
+    }
+}
+
+/// The gear a segment of a Reeds-Shepp path is driven in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gear {
+    Forward,
+    Reverse,
+}
+
+/// A segment of a Reeds-Shepp path.
+#[derive(Debug, Clone, Copy)]
+pub struct ReedsSheppSegment<F: Float> {
+    pub direction: DubinsSegment, // which way the robot turns
+    pub gear: Gear,               // whether the robot drives forward or in reverse
+    pub length: F,                // the non-negative length of the segment
+}
+
+/// A Reeds-Shepp path from a pose `(x, y, θ)`: up to five segments that turn at the minimum turning radius
+/// or go straight, each driven forward or in reverse.
+#[derive(Debug, Clone)]
+pub struct ReedsSheppPath<F: Float> {
+    /// The start pose.
+    pub start: Point<F, 3>,
+    /// The segments, in the order they are driven.
+    pub segments: Vec<ReedsSheppSegment<F>>,
+    /// The minimum turning radius.
+    pub turning_radius: F,
+}
+
+impl<F: Float> ReedsSheppPath<F> {
+    /// Returns the total length of the path, counting reverse segments as positive.
+    pub fn length(&self) -> F {
+        self.segments
+            .iter()
+            .fold(F::zero(), |length, segment| length + segment.length)
+    }
+
+    /// Finds the pose after driving a distance along the path.
+    ///
+    /// Parameters:
+    /// - `distance`: The distance from the start, which is clamped to the length of the path.
+    ///
+    /// Returns:
+    /// The pose `(x, y, θ)`, with θ wrapped to (-π, π].
+    pub fn sample(&self, distance: F) -> Point<F, 3> {
+        let mut remaining = distance.max(F::zero()).min(self.length());
+        let mut pose = self.start;
+        for segment in &self.segments {
+            let travelled = remaining.min(segment.length);
+            let signed = match segment.gear {
+                Gear::Forward => travelled,
+                Gear::Reverse => -travelled,
+            };
+            pose = drive_segment(&pose, segment.direction, signed, self.turning_radius);
+            remaining = remaining - travelled;
+        }
+        Point::new([pose[0], pose[1], wrap_to_pi(pose[2])])
+    }
+
+    /// Returns the gear of the segment being driven at a distance along the path.
+    pub fn gear_at(&self, distance: F) -> Gear {
+        let mut end = F::zero();
+        for segment in &self.segments {
+            end = end + segment.length;
+            if distance < end {
+                return segment.gear;
+            }
+        }
+        self.segments
+            .last()
+            .map_or(Gear::Forward, |segment| segment.gear)
+    }
+
+    /// Samples poses along the path, including both ends, for collision checking or execution.
+    ///
+    /// Parameters:
+    /// - `resolution`: The largest distance along the path between consecutive poses.
+    ///
+    /// Returns:
+    /// The poses from the start to the end of the path, each with the gear the robot drives in from it.
+    pub fn sample_many(&self, resolution: F) -> Vec<(Point<F, 3>, Gear)> {
+        spaced_distances(self.length(), resolution)
+            .map(|distance| (self.sample(distance), self.gear_at(distance)))
+            .collect()
+    }
+}
+
+/// Steering for a car-like robot that drives forward and in reverse and has a minimum turning radius.
+///
+/// The state is `(x, y, θ)`, with the heading θ in radians. Each call finds the shortest Reeds-Shepp path from
+/// `from` to `to`, and drives along it for at most the range. As with `DubinsSteering`, set a waypoint
+/// resolution on the planner to check the curve the robot drives.
+pub struct ReedsSheppSteering<F: Float> {
+    turning_radius: F,
+    range: F,
+}
+
+impl<F: Float> ReedsSheppSteering<F> {
+    /// Constructs a new Reeds-Shepp steering function.
+    ///
+    /// Parameters:
+    /// - `turning_radius`: The minimum turning radius of the robot.
+    /// - `range`: The maximum distance the robot drives in one step, counting reverse driving as positive.
+    ///
+    /// Returns:
+    /// The Reeds-Shepp steering, or an error if the turning radius or the range is not positive and finite.
+    pub fn new(turning_radius: F, range: F) -> Result<Self, &'static str> {
+        if !(turning_radius > F::zero() && turning_radius.is_finite()) {
+            return Err("turning radius must be positive and finite");
+        }
+        if !(range > F::zero() && range.is_finite()) {
+            return Err("range must be positive and finite");
+        }
+        Ok(Self {
+            turning_radius,
+            range,
+        })
+    }
+
+    pub fn turning_radius(&self) -> F {
+        self.turning_radius
+    }
+
+    pub fn range(&self) -> F {
+        self.range
+    }
+
+    /// Drives along a path for at most the range.
+    ///
+    /// Returns:
+    /// The distance driven and the pose reached, which is exactly `to` if the whole path fits in the range.
+    fn step_along(&self, path: &ReedsSheppPath<F>, to: &Point<F, 3>) -> (F, Point<F, 3>) {
+        let length = path.length();
+        if length <= self.range {
+            (length, Point::new([to[0], to[1], wrap_to_pi(to[2])]))
+        } else {
+            (self.range, path.sample(self.range))
+        }
+    }
+
+    /// Finds the shortest Reeds-Shepp path between two poses.
+    ///
+    /// Parameters:
+    /// - `from`: The start pose `(x, y, θ)`.
+    /// - `to`: The end pose `(x, y, θ)`.
+    ///
+    /// Returns:
+    /// The shortest path, or None if a coordinate is not finite.
+    pub fn shortest_path(&self, from: &Point<F, 3>, to: &Point<F, 3>) -> Option<ReedsSheppPath<F>> {
+        // Work in a frame where the start is at the origin facing along the x axis
+        // and the turning radius is one.
+        let (dx, dy) = (to[0] - from[0], to[1] - from[1]);
+        let (sin, cos) = from[2].sin_cos();
+        let x = (cos * dx + sin * dy) / self.turning_radius;
+        let y = (cos * dy - sin * dx) / self.turning_radius;
+        let phi = to[2] - from[2];
+        let (word, lengths) = reeds_shepp_candidates(x, y, phi)
+            .into_iter()
+            .filter(|(_, lengths)| lengths.iter().all(|length| length.is_finite()))
+            .min_by(|a, b| {
+                let length = |lengths: &[F]| {
+                    lengths
+                        .iter()
+                        .fold(F::zero(), |total, length| total + length.abs())
+                };
+                length(&a.1).partial_cmp(&length(&b.1)).unwrap()
+            })?;
+        // Words with fewer turns are found as longer words with empty segments, which are dropped.
+        // Rounding can leave them slightly off zero.
+        let tolerance = F::epsilon().sqrt();
+        let segments = word
+            .into_iter()
+            .zip(lengths)
+            .filter(|(_, length)| length.abs() > tolerance)
+            .map(|(direction, length)| ReedsSheppSegment {
+                direction,
+                gear: if length < F::zero() {
+                    Gear::Reverse
+                } else {
+                    Gear::Forward
+                },
+                length: length.abs() * self.turning_radius,
+            })
+            .collect();
+        Some(ReedsSheppPath {
+            start: *from,
+            segments,
+            turning_radius: self.turning_radius,
+        })
+    }
+
+    /// Samples the poses along the shortest Reeds-Shepp path between two poses, including both ends.
+    ///
+    /// Parameters:
+    /// - `from`: The start pose.
+    /// - `to`: The end pose.
+    /// - `resolution`: The largest distance along the path between consecutive poses.
+    ///
+    /// Returns:
+    /// The poses, each with the gear the robot drives in from it, or an empty list if a coordinate is not finite.
+    pub fn waypoints(
+        &self,
+        from: &Point<F, 3>,
+        to: &Point<F, 3>,
+        resolution: F,
+    ) -> Vec<(Point<F, 3>, Gear)> {
+        self.shortest_path(from, to)
+            .map(|path| path.sample_many(resolution))
+            .unwrap_or_default()
+    }
+}
+
+impl<F: Float> Steering<F, 3> for ReedsSheppSteering<F> {
+    fn steer(&self, from: &Point<F, 3>, to: &Point<F, 3>) -> Point<F, 3> {
+        match self.shortest_path(from, to) {
+            Some(path) => self.step_along(&path, to).1,
+            None => *from,
+        }
+    }
+
+    fn steer_with_waypoints(
+        &self,
+        from: &Point<F, 3>,
+        to: &Point<F, 3>,
+        resolution: F,
+    ) -> (Point<F, 3>, Vec<Point<F, 3>>) {
+        let Some(path) = self.shortest_path(from, to) else {
+            return (*from, vec![*from]);
+        };
+        let (distance, new_point) = self.step_along(&path, to);
+        let mut waypoints: Vec<Point<F, 3>> = spaced_distances(distance, resolution)
+            .map(|travelled| path.sample(travelled))
+            .collect();
+        *waypoints.last_mut().unwrap() = new_point;
+        (new_point, waypoints)
+    }
+}
+
+// A candidate Reeds-Shepp word with its signed, normalized segment lengths. Negative lengths are driven in reverse.
+type ReedsSheppCandidate<F> = (Vec<DubinsSegment>, Vec<F>);
+
+/// Computes every Reeds-Shepp word that reaches the goal, following Reeds and Shepp, "Optimal paths for a car
+/// that goes both forwards and backwards" (1990), with the corrections made by OMPL.
+///
+/// Parameters:
+/// - `x`, `y`: The goal position in the frame of the start pose, divided by the turning radius.
+/// - `phi`: The goal heading relative to the start heading.
+fn reeds_shepp_candidates<F: Float>(x: F, y: F, phi: F) -> Vec<ReedsSheppCandidate<F>> {
+    use DubinsSegment::{Left as L, Right as R, Straight as S};
+    let half_pi = F::from(std::f64::consts::FRAC_PI_2).unwrap();
+    let (sin, cos) = phi.sin_cos();
+    // The goal as seen from the start when driving the path backwards.
+    let (xb, yb) = (x * cos + y * sin, x * sin - y * cos);
+    let mut candidates = Vec::new();
+
+    // CSC
+    add_variants(&mut candidates, x, y, phi, &[L, S, L], |x, y, phi| {
+        lp_sp_lp(x, y, phi).map(|(t, u, v)| vec![t, u, v])
+    });
+    add_variants(&mut candidates, x, y, phi, &[L, S, R], |x, y, phi| {
+        lp_sp_rp(x, y, phi).map(|(t, u, v)| vec![t, u, v])
+    });
+
+    // CCC
+    let ccc = |x, y, phi| lp_rm_l(x, y, phi).map(|(t, u, v)| vec![t, u, v]);
+    add_variants(&mut candidates, x, y, phi, &[L, R, L], ccc);
+    add_backward_variants(&mut candidates, xb, yb, phi, &[L, R, L], ccc);
+
+    // CCCC
+    add_variants(&mut candidates, x, y, phi, &[L, R, L, R], |x, y, phi| {
+        lp_rup_lum_rm(x, y, phi).map(|(t, u, v)| vec![t, u, -u, v])
+    });
+    add_variants(&mut candidates, x, y, phi, &[L, R, L, R], |x, y, phi| {
+        lp_rum_lum_rp(x, y, phi).map(|(t, u, v)| vec![t, u, u, v])
+    });
+
+    // CCSC
+    let ccsc_left = |x, y, phi| lp_rm_sm_lm(x, y, phi).map(|(t, u, v)| vec![t, -half_pi, u, v]);
+    let ccsc_right = |x, y, phi| lp_rm_sm_rm(x, y, phi).map(|(t, u, v)| vec![t, -half_pi, u, v]);
+    add_variants(&mut candidates, x, y, phi, &[L, R, S, L], ccsc_left);
+    add_variants(&mut candidates, x, y, phi, &[L, R, S, R], ccsc_right);
+    add_backward_variants(&mut candidates, xb, yb, phi, &[L, R, S, L], ccsc_left);
+    add_backward_variants(&mut candidates, xb, yb, phi, &[L, R, S, R], ccsc_right);
+
+    // CCSCC
+    add_variants(&mut candidates, x, y, phi, &[L, R, S, L, R], |x, y, phi| {
+        lp_rm_slm_rp(x, y, phi).map(|(t, u, v)| vec![t, -half_pi, u, -half_pi, v])
+    });
+
+    candidates
+}
+
+/// Adds a word and its time-flipped, reflected, and time-flipped and reflected variants.
+///
+/// Time flipping drives every segment in the opposite gear, and reflecting swaps left and right turns.
+fn add_variants<F: Float>(
+    candidates: &mut Vec<ReedsSheppCandidate<F>>,
+    x: F,
+    y: F,
+    phi: F,
+    word: &[DubinsSegment],
+    formula: impl Fn(F, F, F) -> Option<Vec<F>>,
+) {
+    let reflected: Vec<DubinsSegment> = word
+        .iter()
+        .map(|direction| match direction {
+            DubinsSegment::Left => DubinsSegment::Right,
+            DubinsSegment::Straight => DubinsSegment::Straight,
+            DubinsSegment::Right => DubinsSegment::Left,
+        })
+        .collect();
+    let negate = |lengths: Vec<F>| lengths.into_iter().map(|length| -length).collect();
+    if let Some(lengths) = formula(x, y, phi) {
+        candidates.push((word.to_vec(), lengths));
+    }
+    if let Some(lengths) = formula(-x, y, -phi) {
+        candidates.push((word.to_vec(), negate(lengths)));
+    }
+    if let Some(lengths) = formula(x, -y, -phi) {
+        candidates.push((reflected.clone(), lengths));
+    }
+    if let Some(lengths) = formula(-x, -y, phi) {
+        candidates.push((reflected, negate(lengths)));
+    }
+}
+
+/// Adds the variants of a word driven from the goal back to the start, which reverses the order of the segments.
+fn add_backward_variants<F: Float>(
+    candidates: &mut Vec<ReedsSheppCandidate<F>>,
+    xb: F,
+    yb: F,
+    phi: F,
+    word: &[DubinsSegment],
+    formula: impl Fn(F, F, F) -> Option<Vec<F>>,
+) {
+    let start = candidates.len();
+    add_variants(candidates, xb, yb, phi, word, formula);
+    for (word, lengths) in &mut candidates[start..] {
+        word.reverse();
+        lengths.reverse();
+    }
+}
+
+/// The tolerance on the signs of the segment lengths, which absorbs rounding at the boundaries between words.
+fn reeds_shepp_tolerance<F: Float>() -> F {
+    F::from(1e-5).unwrap()
+}
+
+/// Converts Cartesian coordinates to polar coordinates `(r, θ)`.
+fn polar<F: Float>(x: F, y: F) -> (F, F) {
+    ((x * x + y * y).sqrt(), y.atan2(x))
+}
+
+fn tau_omega<F: Float>(u: F, v: F, xi: F, eta: F, phi: F) -> (F, F) {
+    let pi = F::from(std::f64::consts::PI).unwrap();
+    let delta = wrap_to_pi(u - v);
+    let a = u.sin() - delta.sin();
+    let b = u.cos() - delta.cos() - F::one();
+    let t1 = (eta * a - xi * b).atan2(xi * a + eta * b);
+    let t2 = F::from(2.0).unwrap() * (delta.cos() - v.cos() - u.cos()) + F::from(3.0).unwrap();
+    let tau = if t2 < F::zero() {
+        wrap_to_pi(t1 + pi)
+    } else {
+        wrap_to_pi(t1)
+    };
+    (tau, wrap_to_pi(tau - u + v - phi))
+}
+
+// Formula 8.1 of Reeds and Shepp.
+fn lp_sp_lp<F: Float>(x: F, y: F, phi: F) -> Option<(F, F, F)> {
+    let zero = reeds_shepp_tolerance::<F>();
+    let (u, t) = polar(x - phi.sin(), y - F::one() + phi.cos());
+    let v = wrap_to_pi(phi - t);
+    (t >= -zero && v >= -zero).then_some((t, u, v))
+}
+
+// Formula 8.2 of Reeds and Shepp.
+fn lp_sp_rp<F: Float>(x: F, y: F, phi: F) -> Option<(F, F, F)> {
+    let zero = reeds_shepp_tolerance::<F>();
+    let four = F::from(4.0).unwrap();
+    let (u1, t1) = polar(x + phi.sin(), y - F::one() - phi.cos());
+    let u1_squared = u1 * u1;
+    if u1_squared < four {
+        return None;
+    }
+    let u = (u1_squared - four).sqrt();
+    let t = wrap_to_pi(t1 + F::from(2.0).unwrap().atan2(u));
+    let v = wrap_to_pi(t - phi);
+    (t >= -zero && v >= -zero).then_some((t, u, v))
+}
+
+// Formulas 8.3 and 8.4 of Reeds and Shepp, with the typo in the paper corrected.
+fn lp_rm_l<F: Float>(x: F, y: F, phi: F) -> Option<(F, F, F)> {
+    let zero = reeds_shepp_tolerance::<F>();
+    let pi = F::from(std::f64::consts::PI).unwrap();
+    let (u1, theta) = polar(x - phi.sin(), y - F::one() + phi.cos());
+    if u1 > F::from(4.0).unwrap() {
+        return None;
+    }
+    let u = -F::from(2.0).unwrap() * (u1 / F::from(4.0).unwrap()).asin();
+    let t = wrap_to_pi(theta + u / F::from(2.0).unwrap() + pi);
+    let v = wrap_to_pi(phi - t + u);
+    (t >= -zero && u <= zero).then_some((t, u, v))
+}
+
+// Formula 8.7 of Reeds and Shepp.
+fn lp_rup_lum_rm<F: Float>(x: F, y: F, phi: F) -> Option<(F, F, F)> {
+    let zero = reeds_shepp_tolerance::<F>();
+    let xi = x + phi.sin();
+    let eta = y - F::one() - phi.cos();
+    let rho = (F::from(2.0).unwrap() + (xi * xi + eta * eta).sqrt()) / F::from(4.0).unwrap();
+    if rho > F::one() {
+        return None;
+    }
+    let u = rho.acos();
+    let (t, v) = tau_omega(u, -u, xi, eta, phi);
+    (t >= -zero && v <= zero).then_some((t, u, v))
+}
+
+// Formula 8.8 of Reeds and Shepp.
+fn lp_rum_lum_rp<F: Float>(x: F, y: F, phi: F) -> Option<(F, F, F)> {
+    let zero = reeds_shepp_tolerance::<F>();
+    let xi = x + phi.sin();
+    let eta = y - F::one() - phi.cos();
+    let rho = (F::from(20.0).unwrap() - xi * xi - eta * eta) / F::from(16.0).unwrap();
+    if !(rho >= F::zero() && rho <= F::one()) {
+        return None;
+    }
+    let u = -rho.acos();
+    if u < -F::from(std::f64::consts::FRAC_PI_2).unwrap() {
+        return None;
+    }
+    let (t, v) = tau_omega(u, u, xi, eta, phi);
+    (t >= -zero && v >= -zero).then_some((t, u, v))
+}
+
+// Formula 8.9 of Reeds and Shepp.
+fn lp_rm_sm_lm<F: Float>(x: F, y: F, phi: F) -> Option<(F, F, F)> {
+    let zero = reeds_shepp_tolerance::<F>();
+    let two = F::from(2.0).unwrap();
+    let (rho, theta) = polar(x - phi.sin(), y - F::one() + phi.cos());
+    if rho < two {
+        return None;
+    }
+    let r = (rho * rho - two * two).sqrt();
+    let u = two - r;
+    let t = wrap_to_pi(theta + r.atan2(-two));
+    let v = wrap_to_pi(phi - F::from(std::f64::consts::FRAC_PI_2).unwrap() - t);
+    (t >= -zero && u <= zero && v <= zero).then_some((t, u, v))
+}
+
+// Formula 8.10 of Reeds and Shepp.
+fn lp_rm_sm_rm<F: Float>(x: F, y: F, phi: F) -> Option<(F, F, F)> {
+    let zero = reeds_shepp_tolerance::<F>();
+    let two = F::from(2.0).unwrap();
+    let xi = x + phi.sin();
+    let eta = y - F::one() - phi.cos();
+    let (rho, theta) = polar(-eta, xi);
+    if rho < two {
+        return None;
+    }
+    let t = theta;
+    let u = two - rho;
+    let v = wrap_to_pi(t + F::from(std::f64::consts::FRAC_PI_2).unwrap() - phi);
+    (t >= -zero && u <= zero && v <= zero).then_some((t, u, v))
+}
+
+// Formula 8.11 of Reeds and Shepp, with the typo in the paper corrected.
+fn lp_rm_slm_rp<F: Float>(x: F, y: F, phi: F) -> Option<(F, F, F)> {
+    let zero = reeds_shepp_tolerance::<F>();
+    let two = F::from(2.0).unwrap();
+    let four = F::from(4.0).unwrap();
+    let xi = x + phi.sin();
+    let eta = y - F::one() - phi.cos();
+    let (rho, _) = polar(xi, eta);
+    if rho < two {
+        return None;
+    }
+    let u = four - (rho * rho - four).sqrt();
+    if u > zero {
+        return None;
+    }
+    let t = wrap_to_pi(((four - u) * xi - two * eta).atan2(-two * xi + (u - four) * eta));
+    let v = wrap_to_pi(t - phi);
+    (t >= -zero && v >= -zero).then_some((t, u, v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn dubins_words_match_reference_lengths() {
+        let steering = DubinsSteering::new(1.0, 10.0).unwrap();
+        let start = Point::new([0.0, 0.0, 0.0]);
+        // The CSC goals are reached by turning a quarter circle, driving 2 and turning a quarter circle.
+        // The CCC goals are reached by turning a quarter circle, three quarters the other way and a quarter circle.
+        let cases = [
+            (
+                DubinsPathType::LSL,
+                [0.0, 4.0, PI],
+                [PI / 2.0, 2.0, PI / 2.0],
+            ),
+            (
+                DubinsPathType::RSR,
+                [0.0, -4.0, -PI],
+                [PI / 2.0, 2.0, PI / 2.0],
+            ),
+            (
+                DubinsPathType::LSR,
+                [2.0, 4.0, 0.0],
+                [PI / 2.0, 2.0, PI / 2.0],
+            ),
+            (
+                DubinsPathType::RSL,
+                [2.0, -4.0, 0.0],
+                [PI / 2.0, 2.0, PI / 2.0],
+            ),
+            (
+                DubinsPathType::RLR,
+                [1.0, 1.0, PI / 2.0],
+                [PI / 2.0, 1.5 * PI, PI / 2.0],
+            ),
+            (
+                DubinsPathType::LRL,
+                [1.0, -1.0, -PI / 2.0],
+                [PI / 2.0, 1.5 * PI, PI / 2.0],
+            ),
+        ];
+        for (path_type, goal, lengths) in cases {
+            let path = steering.path(&start, &Point::new(goal), path_type).unwrap();
+            for (actual, expected) in path.segment_lengths.iter().zip(lengths) {
+                assert_close(*actual, expected);
+            }
+            assert_close(path.length(), lengths.iter().sum());
+            let end = path.sample(path.length());
+            assert_close(end[0], goal[0]);
+            assert_close(end[1], goal[1]);
+        }
+    }
+
+    #[test]
+    fn dubins_lengths_scale_with_the_turning_radius() {
+        let steering = DubinsSteering::new(2.0, 10.0).unwrap();
+        let path = steering
+            .path(
+                &Point::new([0.0, 0.0, 0.0]),
+                &Point::new([0.0, 8.0, PI]),
+                DubinsPathType::LSL,
+            )
+            .unwrap();
+        assert_close(path.length(), 2.0 * PI + 4.0);
+
+        // Straight ahead, the shortest path does not turn.
+        let straight = steering
+            .shortest_path(&Point::new([0.0, 0.0, 0.0]), &Point::new([5.0, 0.0, 0.0]))
+            .unwrap();
+        assert_close(straight.length(), 5.0);
+    }
+
+    /// Asserts the directions, gears and lengths of the segments of the shortest Reeds-Shepp path.
+    fn assert_reeds_shepp(
+        steering: &ReedsSheppSteering<f64>,
+        goal: [f64; 3],
+        expected: &[(DubinsSegment, Gear, f64)],
+    ) {
+        let path = steering
+            .shortest_path(&Point::new([0.0, 0.0, 0.0]), &Point::new(goal))
+            .unwrap();
+        assert_eq!(path.segments.len(), expected.len());
+        for (segment, &(direction, gear, length)) in path.segments.iter().zip(expected) {
+            assert_eq!(segment.direction, direction);
+            assert_eq!(segment.gear, gear);
+            assert_close(segment.length, length);
+        }
+        let end = path.sample(path.length());
+        assert_close(end[0], goal[0]);
+        assert_close(end[1], goal[1]);
+        assert_close(angular_difference(end[2], goal[2]), 0.0);
+    }
+
+    #[test]
+    fn reeds_shepp_lengths_and_gears() {
+        use DubinsSegment::{Left, Right, Straight};
+        let steering = ReedsSheppSteering::new(1.0, 10.0).unwrap();
+        assert_reeds_shepp(
+            &steering,
+            [4.0, 0.0, 0.0],
+            &[(Straight, Gear::Forward, 4.0)],
+        );
+        assert_reeds_shepp(
+            &steering,
+            [-3.0, 0.0, 0.0],
+            &[(Straight, Gear::Reverse, 3.0)],
+        );
+        // A quarter turn is the shortest way to change the heading by a quarter turn.
+        assert_reeds_shepp(
+            &steering,
+            [1.0, 1.0, PI / 2.0],
+            &[(Left, Gear::Forward, PI / 2.0)],
+        );
+        assert_reeds_shepp(
+            &steering,
+            [-1.0, 1.0, -PI / 2.0],
+            &[(Left, Gear::Reverse, PI / 2.0)],
+        );
+        // Turning around with a cusp: a quarter turn forward, then a quarter turn in reverse.
+        assert_reeds_shepp(
+            &steering,
+            [2.0, 0.0, PI],
+            &[
+                (Left, Gear::Forward, PI / 2.0),
+                (Right, Gear::Reverse, PI / 2.0),
+            ],
+        );
+
+        let wide = ReedsSheppSteering::new(2.0, 10.0).unwrap();
+        assert_reeds_shepp(&wide, [2.0, 2.0, PI / 2.0], &[(Left, Gear::Forward, PI)]);
+    }
+
+    #[test]
+    fn reeds_shepp_gear_changes_at_the_cusp() {
+        let steering = ReedsSheppSteering::new(1.0, 10.0).unwrap();
+        let path = steering
+            .shortest_path(&Point::new([0.0, 0.0, 0.0]), &Point::new([2.0, 0.0, PI]))
+            .unwrap();
+        assert_eq!(path.gear_at(0.0), Gear::Forward);
+        assert_eq!(path.gear_at(PI / 2.0 - 0.1), Gear::Forward);
+        assert_eq!(path.gear_at(PI / 2.0 + 0.1), Gear::Reverse);
+        let gears: Vec<Gear> = path
+            .sample_many(0.1)
+            .into_iter()
+            .map(|(_, gear)| gear)
+            .collect();
+        assert_eq!(gears.first(), Some(&Gear::Forward));
+        assert_eq!(gears.last(), Some(&Gear::Reverse));
+        assert_eq!(
+            gears.windows(2).filter(|pair| pair[0] != pair[1]).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn clearance_adaptive_steps_shrink_near_obstacles_and_recover_in_open_space() {
+        use crate::collision::{EuclideanSphericalObstacleSet, Sphere, ValidityChecker};
+
+        let obstacles = EuclideanSphericalObstacleSet::new(vec![Sphere {
+            center: Point::new([0.0, 0.0]),
+            radius: 1.0,
+        }]);
+        let steering = ClearanceAdaptiveSteering::new(&obstacles, 2.0, 0.5, 0.05).unwrap();
+        let step_from = |x: f64, towards: f64| {
+            let from = Point::new([x, 0.0]);
+            let to = steering.steer(&from, &Point::new([towards, 0.0]));
+            assert!(obstacles.is_edge_valid(&from, &to));
+            euclidean_distance(&from, &to)
+        };
+        // Half the clearance, clamped between the minimum step and the maximum range.
+        assert_close(step_from(1.1, 20.0), 0.05);
+        assert_close(step_from(1.5, 20.0), 0.25);
+        assert_close(step_from(3.0, 20.0), 1.0);
+        assert_close(step_from(10.0, 20.0), 2.0);
+        // Steps towards the obstacle also shrink, and stop short of it.
+        assert_close(step_from(3.0, -20.0), 1.0);
+        assert_close(step_from(1.5, -20.0), 0.25);
+        // A target within the step is reached exactly.
+        assert_close(step_from(10.0, 11.0), 1.0);
+
+        // Moving away from the obstacle, the steps grow until they reach the full range.
+        let mut point = Point::new([1.1, 0.0]);
+        let mut steps = Vec::new();
+        for _ in 0..15 {
+            let next = steering.steer(&point, &Point::new([100.0, 0.0]));
+            steps.push(euclidean_distance(&point, &next));
+            point = next;
+        }
+        assert!(
+            steps.windows(2).all(|pair| pair[1] > pair[0] - 1e-9),
+            "{steps:?}"
+        );
+        assert_close(steps[0], 0.05);
+        assert_close(steps[14], 2.0);
+    }
+
+    #[test]
+    fn box_steering_saturates_the_most_limited_dimension() {
+        let steering = BoxSteering::new([1.0, 0.01]).unwrap();
+        let steer = |from: [f64; 2], to: [f64; 2]| {
+            *steering.steer(&Point::new(from), &Point::new(to)).coords()
+        };
+        let assert_step = |step: [f64; 2], expected: [f64; 2]| {
+            assert_close(step[0], expected[0]);
+            assert_close(step[1], expected[1]);
+        };
+        // The second dimension saturates, and the first moves in proportion along the straight line.
+        assert_step(steer([0.0, 0.0], [10.0, 10.0]), [0.01, 0.01]);
+        assert_step(steer([0.0, 0.0], [10.0, 0.1]), [1.0, 0.01]);
+        // The first dimension saturates.
+        assert_step(steer([0.0, 0.0], [5.0, 0.001]), [1.0, 0.0002]);
+        assert_step(steer([2.0, 1.0], [-3.0, 1.0]), [1.0, 1.0]);
+
+        // A target within the box of limits around the start is reached exactly.
+        assert_eq!(steer([0.0, 0.0], [0.5, 0.005]), [0.5, 0.005]);
+        assert_eq!(steer([0.3, 0.2], [1.2, 0.195]), [1.2, 0.195]);
+        assert!(BoxSteering::new([1.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn topology_aware_steering_crosses_pi() {
+        let steering = TopologyAwareSteering::new(0.1, [false, true]).unwrap();
+        let steer = |from: [f64; 2], to: [f64; 2]| {
+            *steering.steer(&Point::new(from), &Point::new(to)).coords()
+        };
+        // From 3 to -3 radians is 0.28 through π, against 6 through 0.
+        let step = steer([0.0, 3.0], [0.0, -3.0]);
+        assert_close(step[1], 3.1);
+        let step = steer([0.0, 3.1], [0.0, -3.0]);
+        assert_close(step[1], 3.2 - 2.0 * PI);
+        assert_close(steer([0.0, 3.1], [0.0, -3.1])[1], -3.1);
+
+        // Stepping repeatedly reaches the target after three steps through π.
+        let mut point = Point::new([0.0, 3.0]);
+        let mut angles = Vec::new();
+        for _ in 0..3 {
+            point = steering.steer(&point, &Point::new([0.0, -3.0]));
+            angles.push(point[1]);
+        }
+        assert_close(angles[0], 3.1);
+        assert_close(angles[1], 3.2 - 2.0 * PI);
+        assert_eq!(angles[2], -3.0);
+
+        // The other coordinates share the step, along the straight line through π.
+        let step = steer([0.0, 3.0], [0.16, 3.12 - 2.0 * PI]);
+        assert_close(step[0], 0.08);
+        assert_close(step[1], 3.06);
+    }
+
+    #[test]
+    fn headings_follow_the_shared_wrapping_convention() {
+        // Every wrapped angle is in (-π, π], so a heading of -π is reported as π.
+        assert_eq!(wrap_to_pi(-PI), PI);
+        assert_eq!(wrap_to_pi(PI), PI);
+        assert_close(wrap_to_pi(3.0 * PI), PI);
+        let turn = Point::new([0.0, 2.0, -PI]);
+        let start = Point::new([0.0, 0.0, 0.0]);
+        let dubins = DubinsSteering::new(1.0, 10.0).unwrap();
+        assert_eq!(dubins.steer(&start, &turn)[2], PI);
+        let reeds_shepp = ReedsSheppSteering::new(1.0, 10.0).unwrap();
+        assert_eq!(reeds_shepp.steer(&start, &turn)[2], PI);
+        let topology = TopologyAwareSteering::new(10.0, [false, false, true]).unwrap();
+        assert_eq!(topology.steer(&start, &turn)[2], PI);
+    }
+}
@@ -0,0 +1,487 @@
+use crate::collision::ValidityChecker;
+use crate::distance::euclidean_distance;
+use crate::goal::{GoalChecker, PointGoal};
+use crate::neighbors::NearestNeighbors;
+use crate::planner::Planner;
+use crate::point::Point;
+use crate::rrt::Node;
+use crate::sampling::SamplingDistribution;
+use crate::steering::Steering;
+use num_traits::Float;
+use rand::rngs::ThreadRng;
+use rand::Rng;
+
+/// A continuous cost over the state space, such as terrain slope.
+pub trait StateCost<F: Float, const N: usize> {
+    /// Computes the cost of a point.
+    ///
+    /// Parameters:
+    /// - `point`: The point.
+    ///
+    /// Returns:
+    /// The cost of the point.
+    fn cost(&self, point: &Point<F, N>) -> F;
+}
+
+/// Configuration of the transition test of the T-RRT planner.
+#[derive(Debug, Clone, Copy)]
+pub struct TRRTConfig<F: Float> {
+    /// The initial temperature. Higher temperatures accept more uphill transitions.
+    pub initial_temperature: F,
+    /// The factor (greater than 1) by which the temperature is raised after too many rejected
+    /// transitions. After an accepted uphill transition, the temperature is lowered by this factor
+    /// raised to the cost increase relative to a tenth of the range of costs in the tree.
+    pub temperature_adjustment_rate: F,
+    /// Points with a cost above this value are always rejected.
+    pub max_cost: F,
+    /// The number of consecutive rejected transitions after which the temperature is raised.
+    pub max_failed_transitions: u32,
+}
+
+impl<F: Float> Default for TRRTConfig<F> {
+    fn default() -> Self {
+        Self {
+            initial_temperature: F::one(),
+            temperature_adjustment_rate: F::from(2.0).unwrap(),
+            max_cost: F::infinity(),
+            max_failed_transitions: 10,
+        }
+    }
+}
+
+/// A Transition-based RRT (T-RRT) planner.
+///
+/// Grows a tree like the RRT, but each new node must also pass a transition test on the state cost.
+/// Transitions to a lower cost are always accepted. Transitions to a higher cost are accepted with
+/// probability `exp(-slope / temperature)`, where `slope` is the cost increase per unit distance.
+/// The temperature adapts so the tree prefers low cost regions, but can still climb when it is stuck.
+///
+/// Template Parameters:
+/// - `F`: The floating-point type.
+/// - `N`: The dimension of the space.
+/// - `VC`: The validity checker.
+/// - `SD`: The sampling distribution.
+/// - `ST`: The steering function.
+/// - `NN`: The nearest neighbors data structure.
+/// - `SC`: The state cost.
+/// - `R`: The random number generator for the transition test (defaults to `ThreadRng`).
+pub struct TRRT<F: Float, const N: usize, VC, SD, ST, NN, SC, R: Rng = ThreadRng>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    ST: Steering<F, N>,
+    NN: NearestNeighbors<F, N>,
+    SC: StateCost<F, N>,
+{
+    goal: PointGoal<F, N>,
+    /// The nodes in the tree.
+    nodes: Vec<Node<F, N>>,
+    /// The state cost of each node.
+    state_costs: Vec<F>,
+    /// Index of the goal node in the tree (None if no solution has been found).
+    solution: Option<usize>,
+    config: TRRTConfig<F>,
+    /// The current temperature of the transition test.
+    temperature: F,
+    /// The number of consecutive rejected transitions.
+    failed_transitions: u32,
+    /// The lowest and highest state cost of the nodes in the tree.
+    cost_range: (F, F),
+    rng: R,
+    validity_checker: VC,
+    sampling_distribution: SD,
+    steering: ST,
+    state_cost: SC,
+    nearest_neighbors: NN,
+}
+
+impl<F: Float, const N: usize, VC, SD, ST, NN, SC> TRRT<F, N, VC, SD, ST, NN, SC>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    ST: Steering<F, N>,
+    NN: NearestNeighbors<F, N>,
+    SC: StateCost<F, N>,
+{
+    /// Constructs a new T-RRT planner.
+    ///
+    /// Parameters:
+    /// - `start`: The start point.
+    /// - `goal`: The goal point and tolerance.
+    /// - `validity_checker`: Checks if the edges or nodes as valid.
+    /// - `sampling_distribution`: The sampling distribution.
+    /// - `steering`: The steering function.
+    /// - `state_cost`: The cost of each point in the space.
+    /// - `config`: The configuration of the transition test.
+    ///
+    /// Returns:
+    /// The T-RRT planner, or an error if the initial temperature is not positive and finite,
+    /// or the temperature adjustment rate is not finite and greater than 1.
+    pub fn new(
+        start: Point<F, N>,
+        goal: PointGoal<F, N>,
+        validity_checker: VC,
+        sampling_distribution: SD,
+        steering: ST,
+        state_cost: SC,
+        config: TRRTConfig<F>,
+    ) -> Result<Self, &'static str> {
+        if !(config.initial_temperature > F::zero() && config.initial_temperature.is_finite()) {
+            return Err("initial_temperature must be positive and finite");
+        }
+        if !(config.temperature_adjustment_rate > F::one()
+            && config.temperature_adjustment_rate.is_finite())
+        {
+            return Err("temperature_adjustment_rate must be finite and greater than 1");
+        }
+        let mut trrt = Self {
+            goal,
+            nodes: Vec::new(),
+            state_costs: Vec::new(),
+            solution: None,
+            config,
+            temperature: config.initial_temperature,
+            failed_transitions: 0,
+            cost_range: (F::infinity(), F::neg_infinity()),
+            rng: rand::thread_rng(),
+            validity_checker,
+            sampling_distribution,
+            steering,
+            state_cost,
+            nearest_neighbors: NN::new(),
+        };
+        let start_cost = trrt.state_cost.cost(&start);
+        trrt.add_node(Node::new(start, None), start_cost);
+        Ok(trrt)
+    }
+}
+
+impl<F: Float, const N: usize, VC, SD, ST, NN, SC, R: Rng> TRRT<F, N, VC, SD, ST, NN, SC, R>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    ST: Steering<F, N>,
+    NN: NearestNeighbors<F, N>,
+    SC: StateCost<F, N>,
+{
+    /// Replaces the random number generator used by the transition test.
+    /// Use a seeded generator for reproducible trees.
+    pub fn with_rng<R2: Rng>(self, rng: R2) -> TRRT<F, N, VC, SD, ST, NN, SC, R2> {
+        TRRT {
+            goal: self.goal,
+            nodes: self.nodes,
+            state_costs: self.state_costs,
+            solution: self.solution,
+            config: self.config,
+            temperature: self.temperature,
+            failed_transitions: self.failed_transitions,
+            cost_range: self.cost_range,
+            rng,
+            validity_checker: self.validity_checker,
+            sampling_distribution: self.sampling_distribution,
+            steering: self.steering,
+            state_cost: self.state_cost,
+            nearest_neighbors: self.nearest_neighbors,
+        }
+    }
+
+    /// Attempts to find a solution within a maximum number of iterations.
+    ///
+    /// Terminates and returns true when a solution is found. Otherwise, returns false.
+    ///
+    /// Parameters:
+    /// - `max_iterations`: The maximum number of iterations.
+    pub fn solve(&mut self, max_iterations: u32) -> bool {
+        for _ in 0..max_iterations {
+            self.iteration();
+            if self.solved() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Returns true if the T-RRT has found a solution.
+    pub fn solved(&self) -> bool {
+        self.solution.is_some()
+    }
+
+    /// Returns the path from the start to the goal, if a solution was found.
+    pub fn get_path(&self) -> Option<Vec<Point<F, N>>> {
+        let mut current_index = self.solution?;
+        let mut path = vec![*self.nodes[current_index].point()];
+        while let Some(parent_index) = self.nodes[current_index].parent() {
+            path.push(*self.nodes[parent_index].point());
+            current_index = parent_index;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Returns the current temperature of the transition test.
+    pub fn temperature(&self) -> F {
+        self.temperature
+    }
+
+    /// Returns the state cost of the node at `index`.
+    pub fn node_state_cost(&self, index: usize) -> F {
+        self.state_costs[index]
+    }
+
+    pub fn get_state_cost(&self) -> &SC {
+        &self.state_cost
+    }
+
+    pub fn get_sampling_distribution(&self) -> &SD {
+        &self.sampling_distribution
+    }
+
+    pub fn get_validity_checker(&self) -> &VC {
+        &self.validity_checker
+    }
+
+    /// Returns the vector of nodes in the tree.
+    pub fn get_tree(&self) -> &Vec<Node<F, N>> {
+        &self.nodes
+    }
+
+    /// Expands the tree by one iteration.
+    ///
+    /// Each iteration of the T-RRT algorithm consists of the following steps:
+    /// 1. Sample a point from the sampling distribution.
+    /// 2. Find the nearest node in the tree to the sample point and steer towards it.
+    /// 3. If the new point is valid, apply the transition test on its state cost.
+    /// 4. If the transition is accepted and the edge is valid, add the new node to the tree.
+    /// 5. If the new node is in the goal, record it as the solution.
+    fn iteration(&mut self) {
+        let sample = self.sampling_distribution.sample();
+        let Some(nearest_index) = self.nearest_neighbors.nearest_one(&sample) else {
+            return;
+        };
+        let nearest_point = *self.nodes[nearest_index].point();
+        let new_point = self.steering.steer(&nearest_point, &sample);
+        let distance = euclidean_distance(&nearest_point, &new_point);
+        if distance == F::zero() || !self.validity_checker.is_point_valid(&new_point) {
+            return;
+        }
+
+        let new_cost = self.state_cost.cost(&new_point);
+        if !self.transition_test(self.state_costs[nearest_index], new_cost, distance) {
+            return;
+        }
+        if !self
+            .validity_checker
+            .is_edge_valid(&nearest_point, &new_point)
+        {
+            return;
+        }
+
+        let index = self.add_node(Node::new(new_point, Some(nearest_index)), new_cost);
+        if self.goal.is_goal(&new_point) {
+            self.solution = Some(index);
+        }
+    }
+
+    /// Decides whether the transition from a node to a new point is accepted,
+    /// and adapts the temperature.
+    ///
+    /// Parameters:
+    /// - `from_cost`: The state cost of the existing node.
+    /// - `to_cost`: The state cost of the new point.
+    /// - `distance`: The distance between the node and the new point.
+    ///
+    /// Returns:
+    /// Whether the transition is accepted.
+    fn transition_test(&mut self, from_cost: F, to_cost: F, distance: F) -> bool {
+        if to_cost > self.config.max_cost {
+            return false;
+        }
+        if to_cost <= from_cost {
+            return true;
+        }
+
+        let slope = (to_cost - from_cost) / distance;
+        let probability = (-slope / self.temperature).exp().to_f64().unwrap_or(0.0);
+        if self.rng.gen::<f64>() < probability {
+            // Small cost increases relative to the costs seen so far barely cool the temperature.
+            let (min_cost, max_cost) = self.cost_range;
+            if max_cost > min_cost {
+                let exponent =
+                    (to_cost - from_cost) / (F::from(0.1).unwrap() * (max_cost - min_cost));
+                self.temperature =
+                    self.temperature / self.config.temperature_adjustment_rate.powf(exponent);
+            }
+            self.failed_transitions = 0;
+            true
+        } else {
+            self.failed_transitions += 1;
+            if self.failed_transitions > self.config.max_failed_transitions {
+                self.temperature = self.temperature * self.config.temperature_adjustment_rate;
+                self.failed_transitions = 0;
+            }
+            false
+        }
+    }
+
+    /// Adds a node to the tree and the nearest neighbors data structure.
+    fn add_node(&mut self, mut node: Node<F, N>, state_cost: F) -> usize {
+        let index = self.nodes.len();
+        self.nearest_neighbors.add(*node.point(), index);
+        if let Some(parent) = node.parent() {
+            let parent_node = &mut self.nodes[parent];
+            parent_node.add_child(index);
+            node.set_cost(
+                parent_node.cost() + euclidean_distance(parent_node.point(), node.point()),
+            );
+        }
+        self.nodes.push(node);
+        self.state_costs.push(state_cost);
+        self.cost_range = (
+            self.cost_range.0.min(state_cost),
+            self.cost_range.1.max(state_cost),
+        );
+        index
+    }
+}
+
+impl<F: Float, const N: usize, VC, SD, ST, NN, SC, R: Rng> Planner<F, N>
+    for TRRT<F, N, VC, SD, ST, NN, SC, R>
+where
+    VC: ValidityChecker<F, N>,
+    SD: SamplingDistribution<F, N>,
+    ST: Steering<F, N>,
+    NN: NearestNeighbors<F, N>,
+    SC: StateCost<F, N>,
+{
+    fn solve(&mut self, max_iterations: u32) -> bool {
+        TRRT::solve(self, max_iterations)
+    }
+
+    fn solved(&self) -> bool {
+        TRRT::solved(self)
+    }
+
+    fn get_path(&self) -> Option<Vec<Point<F, N>>> {
+        TRRT::get_path(self)
+    }
+
+    fn get_tree_size(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::EuclideanSphericalObstacleSet;
+    use crate::neighbors::KdTreeNearestNeighbors;
+    use crate::rrt::RRT;
+    use crate::sampling::GoalBiasedUniformDistribution;
+    use crate::steering::EuclideanSteering;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// A hill in the middle of the space, between the start and the goal.
+    struct Hill;
+
+    impl StateCost<f64, 2> for Hill {
+        fn cost(&self, point: &Point<f64, 2>) -> f64 {
+            let dx = point[0] - 50.0;
+            let dy = point[1] - 50.0;
+            10.0 * (-(dx * dx + dy * dy) / 200.0).exp()
+        }
+    }
+
+    /// Integrates the state cost along a path with the trapezoidal rule, subdividing each edge.
+    fn integrated_cost(path: &[Point<f64, 2>]) -> f64 {
+        const SUBDIVISIONS: usize = 10;
+        let mut total = 0.0;
+        for edge in path.windows(2) {
+            let length = euclidean_distance(&edge[0], &edge[1]);
+            for i in 0..SUBDIVISIONS {
+                let a = edge[0] + (edge[1] - edge[0]) * (i as f64 / SUBDIVISIONS as f64);
+                let b = edge[0] + (edge[1] - edge[0]) * ((i + 1) as f64 / SUBDIVISIONS as f64);
+                total += 0.5 * (Hill.cost(&a) + Hill.cost(&b)) * length / SUBDIVISIONS as f64;
+            }
+        }
+        total
+    }
+
+    fn sampling(seed: u64) -> GoalBiasedUniformDistribution<f64, 2, StdRng> {
+        GoalBiasedUniformDistribution::new_with_rng(
+            [(0.0, 100.0); 2],
+            Point::new([90.0, 50.0]),
+            0.05,
+            StdRng::seed_from_u64(seed),
+        )
+        .unwrap()
+    }
+
+    type TestTRRT = TRRT<
+        f64,
+        2,
+        EuclideanSphericalObstacleSet<f64, 2>,
+        GoalBiasedUniformDistribution<f64, 2, StdRng>,
+        EuclideanSteering<f64, 2>,
+        KdTreeNearestNeighbors<f64, 2>,
+        Hill,
+    >;
+
+    /// A T-RRT from one side of the hill to the other.
+    fn trrt(config: TRRTConfig<f64>, seed: u64) -> Result<TestTRRT, &'static str> {
+        TRRT::new(
+            Point::new([10.0, 50.0]),
+            PointGoal::new(Point::new([90.0, 50.0]), 2.0),
+            EuclideanSphericalObstacleSet::new(Vec::new()),
+            sampling(seed),
+            EuclideanSteering::new(2.0),
+            Hill,
+            config,
+        )
+    }
+
+    #[test]
+    fn paths_have_lower_integrated_cost_than_rrt() {
+        const TRIALS: u64 = 5;
+        let mut rrt_total = 0.0;
+        let mut trrt_total = 0.0;
+        for seed in 0..TRIALS {
+            let mut rrt: RRT<f64, 2, _, _, _, KdTreeNearestNeighbors<f64, 2>> = RRT::new(
+                Point::new([10.0, 50.0]),
+                Point::new([90.0, 50.0]),
+                2.0,
+                EuclideanSphericalObstacleSet::new(Vec::new()),
+                sampling(seed),
+                EuclideanSteering::new(2.0),
+            );
+            assert!(rrt.solve(100_000).is_solved());
+            rrt_total += integrated_cost(&rrt.get_path().unwrap());
+
+            let mut planner = trrt(TRRTConfig::default(), seed)
+                .unwrap()
+                .with_rng(StdRng::seed_from_u64(seed));
+            assert!(planner.solve(100_000));
+            trrt_total += integrated_cost(&planner.get_path().unwrap());
+        }
+        assert!(trrt_total < rrt_total);
+    }
+
+    #[test]
+    fn rejects_invalid_temperature_settings() {
+        for initial_temperature in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let config = TRRTConfig {
+                initial_temperature,
+                ..TRRTConfig::default()
+            };
+            assert!(trrt(config, 0).is_err());
+        }
+        for temperature_adjustment_rate in [0.0, -2.0, 0.5, 1.0, f64::NAN, f64::INFINITY] {
+            let config = TRRTConfig {
+                temperature_adjustment_rate,
+                ..TRRTConfig::default()
+            };
+            assert!(trrt(config, 0).is_err());
+        }
+        assert!(trrt(TRRTConfig::default(), 0).is_ok());
+    }
+}